@@ -24,6 +24,14 @@
 //! business. Auction types include:
 //!   - `collateral auction`: sell collateral assets for getting stable currency to eliminate the
 //!     system's bad debit by auction
+//!   - `Dutch collateral auction`: if it's enabled for the collateral type, the collateral auction
+//!     will be a descending-price auction instead, its price starts above the oracle price and
+//!     decays over blocks, anyone can take any portion of the collateral at the current price
+//!     instantly.
+//!   - `surplus auction`: sell excessive stable currency of the system for native currency, the
+//!     native currency paid by bidders is burned.
+//!   - `debit auction`: mint native currency to sell for fixed amount of stable currency to
+//!     recapitalize the system's bad debit, bidders bid for less native currency.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -45,14 +53,16 @@ use sp_runtime::{
 		storage_lock::{StorageLock, Time},
 		Duration,
 	},
-	traits::{CheckedDiv, Saturating, Zero},
+	traits::{CheckedDiv, One, Saturating, UniqueSaturatedInto, Zero},
 	transaction_validity::{
 		InvalidTransaction, TransactionPriority, TransactionSource, TransactionValidity, ValidTransaction,
 	},
 	DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug,
 };
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, PriceProvider, Rate, SwapLimit};
+use support::{
+	AuctionManager, CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, Price, PriceProvider, Rate, Ratio, SwapLimit,
+};
 
 mod mock;
 mod tests;
@@ -125,6 +135,123 @@ impl<AccountId, BlockNumber> CollateralAuctionItem<AccountId, BlockNumber> {
 	}
 }
 
+/// Information of a surplus auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct SurplusAuctionItem<BlockNumber> {
+	/// Fixed amount of surplus(stable currency) for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+}
+
+/// Information of a debit auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct DebitAuctionItem<BlockNumber> {
+	/// Initial amount of native currency for sale
+	#[codec(compact)]
+	initial_amount: Balance,
+	/// Current amount of native currency for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Fixed amount of debit(stable currency) which want to get by this
+	/// auction
+	#[codec(compact)]
+	fix: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+}
+
+impl<BlockNumber> DebitAuctionItem<BlockNumber> {
+	/// Return amount of native currency for sale at specific last bid price
+	/// and new bid price
+	fn amount_for_sale(&self, last_bid_price: Balance, new_bid_price: Balance) -> Balance {
+		if new_bid_price > last_bid_price && new_bid_price > self.fix {
+			Rate::checked_from_rational(sp_std::cmp::max(last_bid_price, self.fix), new_bid_price)
+				.and_then(|n| n.checked_mul_int(self.amount))
+				.unwrap_or(self.amount)
+		} else {
+			self.amount
+		}
+	}
+}
+
+/// Parameters of Dutch collateral auctions for a collateral type
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct DutchAuctionParams {
+	/// The ratio of the start price to the oracle price
+	pub start_price_ratio: Ratio,
+	/// The proportion of the start price that the price decays by per block
+	pub price_decay_per_block: Rate,
+	/// The ratio of the minimum price to the oracle price, the price stops decaying at it
+	pub minimum_price_ratio: Ratio,
+	/// The auction needs reset after this many blocks since its start time
+	pub reset_blocks: u32,
+	/// The auction needs reset when its price falls below this ratio of the start price
+	pub reset_price_ratio: Ratio,
+}
+
+/// Information of a Dutch collateral auction
+#[cfg_attr(feature = "std", derive(PartialEq, Eq))]
+#[derive(Encode, Decode, Clone, RuntimeDebug, TypeInfo)]
+pub struct DutchCollateralAuctionItem<AccountId, BlockNumber> {
+	/// Refund recipient for may receive refund
+	refund_recipient: AccountId,
+	/// Collateral type for sale
+	currency_id: CurrencyId,
+	/// Initial collateral amount for sale
+	#[codec(compact)]
+	initial_amount: Balance,
+	/// Current collateral amount for sale
+	#[codec(compact)]
+	amount: Balance,
+	/// Remain target sales amount of this auction
+	#[codec(compact)]
+	target: Balance,
+	/// Auction start time
+	start_time: BlockNumber,
+	/// The price at the start time
+	start_price: Price,
+	/// The minimum price, the price stops decaying at it
+	minimum_price: Price,
+	/// The Dutch auction parameters the prices are anchored with
+	params: DutchAuctionParams,
+}
+
+impl<AccountId, BlockNumber: Saturating + UniqueSaturatedInto<u128> + Copy>
+	DutchCollateralAuctionItem<AccountId, BlockNumber>
+{
+	/// Return the price of the Dutch collateral auction at specific block
+	fn price_at(&self, now: BlockNumber) -> Price {
+		let elapsed_blocks: u128 = now.saturating_sub(self.start_time).unique_saturated_into();
+		let decay = self
+			.params
+			.price_decay_per_block
+			.saturating_mul(Rate::saturating_from_integer(elapsed_blocks));
+		self.start_price
+			.saturating_sub(self.start_price.saturating_mul(decay))
+			.max(self.minimum_price)
+	}
+
+	/// Return true if the Dutch collateral auction has run too long or its price has dropped
+	/// too much at specific block, it must be reset to the oracle price before taken.
+	fn needs_reset(&self, now: BlockNumber) -> bool {
+		let elapsed_blocks: u128 = now.saturating_sub(self.start_time).unique_saturated_into();
+		elapsed_blocks >= u128::from(self.params.reset_blocks)
+			|| self.price_at(now) < self.params.reset_price_ratio.saturating_mul(self.start_price)
+	}
+
+	/// Anchor the prices of the Dutch collateral auction to the oracle price at specific block
+	fn anchor(&mut self, params: DutchAuctionParams, oracle_price: Price, now: BlockNumber) {
+		self.start_time = now;
+		self.start_price = params.start_price_ratio.saturating_mul(oracle_price);
+		self.minimum_price = params.minimum_price_ratio.saturating_mul(oracle_price);
+		self.params = params;
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -150,6 +277,10 @@ pub mod module {
 		#[pallet::constant]
 		type GetStableCurrencyId: Get<CurrencyId>;
 
+		/// The native currency id
+		#[pallet::constant]
+		type GetNativeCurrencyId: Get<CurrencyId>;
+
 		/// Currency to transfer assets
 		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
@@ -172,6 +303,10 @@ pub mod module {
 		/// Emergency shutdown.
 		type EmergencyShutdown: EmergencyShutdown;
 
+		/// The origin which may update Dutch auction parameters. Root can
+		/// always do this.
+		type UpdateOrigin: EnsureOrigin<Self::Origin>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -190,6 +325,16 @@ pub mod module {
 		InvalidBidPrice,
 		/// Invalid input amount
 		InvalidAmount,
+		/// The Dutch auction parameters are invalid
+		InvalidDutchAuctionParams,
+		/// The current price of the Dutch collateral auction is above the maximum price
+		PriceAboveLimit,
+		/// System has already been shutdown
+		AlreadyShutdown,
+		/// The Dutch collateral auction must be reset before taken
+		DutchAuctionNeedsReset,
+		/// The Dutch collateral auction doesn't need reset
+		DutchAuctionResetNotAllowed,
 	}
 
 	#[pallet::event]
@@ -228,6 +373,70 @@ pub mod module {
 			target_stable_amount: Balance,
 			refund_recipient: T::AccountId,
 		},
+		/// The Dutch auction parameters for specific collateral type updated.
+		DutchAuctionParamsUpdated {
+			collateral_type: CurrencyId,
+			new_params: Option<DutchAuctionParams>,
+		},
+		/// Dutch collateral auction created.
+		NewDutchCollateralAuction {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			collateral_amount: Balance,
+			target_stable_amount: Balance,
+			start_price: Price,
+		},
+		/// Dutch collateral auction taken.
+		DutchCollateralAuctionTaken {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			buyer: T::AccountId,
+			collateral_amount: Balance,
+			payment_amount: Balance,
+		},
+		/// Dutch collateral auction reset to the oracle price.
+		DutchCollateralAuctionReset { auction_id: AuctionId, start_price: Price },
+		/// Dutch collateral auction finished, the remain collateral is refunded.
+		DutchCollateralAuctionFinished {
+			auction_id: AuctionId,
+			collateral_type: CurrencyId,
+			refund_collateral_amount: Balance,
+		},
+		/// Surplus auction created.
+		NewSurplusAuction {
+			auction_id: AuctionId,
+			fixed_surplus_amount: Balance,
+		},
+		/// Debit auction created.
+		NewDebitAuction {
+			auction_id: AuctionId,
+			initial_supply_amount: Balance,
+			fixed_payment_amount: Balance,
+		},
+		/// Surplus auction dealt, the native currency paid by winner has been burned.
+		SurplusAuctionDealt {
+			auction_id: AuctionId,
+			surplus_amount: Balance,
+			winner: T::AccountId,
+			payment_amount: Balance,
+		},
+		/// Debit auction dealt, the native currency has been minted to winner.
+		DebitAuctionDealt {
+			auction_id: AuctionId,
+			debit_currency_amount: Balance,
+			winner: T::AccountId,
+			payment_amount: Balance,
+		},
+		/// Surplus auction aborted without bid, the surplus is returned to CDP treasury.
+		SurplusAuctionAborted {
+			auction_id: AuctionId,
+			surplus_amount: Balance,
+		},
+		/// Debit auction aborted without bid.
+		DebitAuctionAborted {
+			auction_id: AuctionId,
+			fixed_payment_amount: Balance,
+		},
 	}
 
 	/// Mapping from auction id to collateral auction info
@@ -253,6 +462,53 @@ pub mod module {
 	#[pallet::getter(fn total_target_in_auction)]
 	pub type TotalTargetInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// Mapping from collateral type to its Dutch auction parameters, the collateral auctions
+	/// are Dutch auctions if it's set
+	///
+	/// CollateralDutchAuctionParams: map CurrencyId => Option<DutchAuctionParams>
+	#[pallet::storage]
+	#[pallet::getter(fn collateral_dutch_auction_params)]
+	pub type CollateralDutchAuctionParams<T: Config> =
+		StorageMap<_, Twox64Concat, CurrencyId, DutchAuctionParams, OptionQuery>;
+
+	/// Mapping from auction id to Dutch collateral auction info
+	///
+	/// DutchCollateralAuctions: map AuctionId => Option<DutchCollateralAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn dutch_collateral_auctions)]
+	pub type DutchCollateralAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>, OptionQuery>;
+
+	/// Mapping from auction id to surplus auction info
+	///
+	/// SurplusAuctions: map AuctionId => Option<SurplusAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_auctions)]
+	pub type SurplusAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, SurplusAuctionItem<T::BlockNumber>, OptionQuery>;
+
+	/// Mapping from auction id to debit auction info
+	///
+	/// DebitAuctions: map AuctionId => Option<DebitAuctionItem>
+	#[pallet::storage]
+	#[pallet::getter(fn debit_auctions)]
+	pub type DebitAuctions<T: Config> =
+		StorageMap<_, Twox64Concat, AuctionId, DebitAuctionItem<T::BlockNumber>, OptionQuery>;
+
+	/// Record of the total surplus amount of all active surplus auctions
+	///
+	/// TotalSurplusInAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_surplus_in_auction)]
+	pub type TotalSurplusInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// Record of the total fixed debit amount of all active debit auctions
+	///
+	/// TotalDebitInAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn total_debit_in_auction)]
+	pub type TotalDebitInAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -294,6 +550,78 @@ pub mod module {
 			Self::deposit_event(Event::CancelAuction { auction_id: id });
 			Ok(())
 		}
+
+		/// Update the Dutch auction parameters of specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `params`: the Dutch auction parameters, `None` means the collateral auctions of this
+		///   collateral type are English auctions.
+		#[pallet::weight((T::WeightInfo::set_dutch_auction_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_dutch_auction_params(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			params: Option<DutchAuctionParams>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			if let Some(params) = params {
+				ensure!(
+					!params.minimum_price_ratio.is_zero()
+						&& params.minimum_price_ratio <= params.start_price_ratio
+						&& params.price_decay_per_block <= Rate::one()
+						&& !params.reset_blocks.is_zero()
+						&& params.reset_price_ratio <= Ratio::one(),
+					Error::<T>::InvalidDutchAuctionParams
+				);
+			}
+
+			CollateralDutchAuctionParams::<T>::set(currency_id, params);
+			Self::deposit_event(Event::DutchAuctionParamsUpdated {
+				collateral_type: currency_id,
+				new_params: params,
+			});
+			Ok(())
+		}
+
+		/// Take the collateral of Dutch collateral auction at current price.
+		///
+		/// The payment is capped by the remain target of the auction, the auction finishes
+		/// and refunds the remain collateral when the target is reached or all collateral is
+		/// sold.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: the Dutch collateral auction id.
+		/// - `max_collateral_amount`: the maximum collateral amount to take.
+		/// - `max_price`: the maximum acceptable price.
+		#[pallet::weight(T::WeightInfo::take_dutch_auction())]
+		#[transactional]
+		pub fn take_dutch_auction(
+			origin: OriginFor<T>,
+			id: AuctionId,
+			#[pallet::compact] max_collateral_amount: Balance,
+			max_price: Price,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			Self::do_take_dutch_auction(&who, id, max_collateral_amount, max_price)
+		}
+
+		/// Reset the Dutch collateral auction which has run too long or whose price has dropped
+		/// too much, its prices are anchored to the current oracle price again.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `id`: the Dutch collateral auction id.
+		#[pallet::weight(T::WeightInfo::reset_dutch_auction())]
+		#[transactional]
+		pub fn reset_dutch_auction(origin: OriginFor<T>, id: AuctionId) -> DispatchResult {
+			ensure_signed(origin)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			Self::do_reset_dutch_auction(id)
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -312,7 +640,10 @@ pub mod module {
 							return InvalidTransaction::Stale.into();
 						}
 					}
-				} else {
+				} else if !DutchCollateralAuctions::<T>::contains_key(auction_id)
+					&& !SurplusAuctions::<T>::contains_key(auction_id)
+					&& !DebitAuctions::<T>::contains_key(auction_id)
+				{
 					return InvalidTransaction::Stale.into();
 				}
 
@@ -407,6 +738,21 @@ impl<T: Config> Pallet<T> {
 
 		if finished {
 			to_be_continue.clear();
+
+			// cancel Dutch collateral, surplus and debit auctions after all collateral auctions
+			// have been iterated
+			for auction_id in <DutchCollateralAuctions<T>>::iter_keys()
+				.chain(<SurplusAuctions<T>>::iter_keys())
+				.chain(<DebitAuctions<T>>::iter_keys())
+			{
+				if iteration_count >= max_iterations {
+					break;
+				}
+				iteration_count += 1;
+
+				Self::submit_cancel_auction_tx(auction_id);
+				guard.extend_lock().map_err(|_| OffchainErr::OffchainLock)?;
+			}
 		} else {
 			to_be_continue.set(&iterator.last_raw_key());
 		}
@@ -473,6 +819,204 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	fn cancel_dutch_collateral_auction(
+		dutch_auction: DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>,
+	) -> DispatchResult {
+		// calculate how much collateral to offset the remain target in settle price
+		let settle_price = T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), dutch_auction.currency_id)
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		let confiscate_collateral_amount = sp_std::cmp::min(
+			settle_price.saturating_mul_int(dutch_auction.target),
+			dutch_auction.amount,
+		);
+		let refund_collateral_amount = dutch_auction.amount.saturating_sub(confiscate_collateral_amount);
+
+		// refund remain collateral to refund recipient from CDP treasury
+		T::CDPTreasury::withdraw_collateral(
+			&dutch_auction.refund_recipient,
+			dutch_auction.currency_id,
+			refund_collateral_amount,
+		)?;
+
+		// decrease account ref of refund recipient
+		frame_system::Pallet::<T>::dec_consumers(&dutch_auction.refund_recipient);
+
+		// decrease total collateral and target in auction
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(dutch_auction.amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+
+		Ok(())
+	}
+
+	fn cancel_surplus_auction(id: AuctionId, surplus_auction: SurplusAuctionItem<T::BlockNumber>) -> DispatchResult {
+		// refund the burned native currency to the last bidder
+		if let Some((bidder, bid_price)) = Self::get_last_bid(id) {
+			T::Currency::deposit(T::GetNativeCurrencyId::get(), &bidder, bid_price)?;
+
+			// decrease account ref of bidder
+			frame_system::Pallet::<T>::dec_consumers(&bidder);
+		}
+
+		// return the surplus to CDP treasury
+		T::CDPTreasury::on_system_surplus(surplus_auction.amount)?;
+
+		TotalSurplusInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(surplus_auction.amount));
+
+		Ok(())
+	}
+
+	fn cancel_debit_auction(id: AuctionId, debit_auction: DebitAuctionItem<T::BlockNumber>) -> DispatchResult {
+		// refund the fixed payment to the last bidder
+		if let Some((bidder, _)) = Self::get_last_bid(id) {
+			T::CDPTreasury::issue_debit(&bidder, debit_auction.fix, false)?;
+
+			// decrease account ref of bidder
+			frame_system::Pallet::<T>::dec_consumers(&bidder);
+		}
+
+		TotalDebitInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(debit_auction.fix));
+
+		Ok(())
+	}
+
+	/// Return the current price of the Dutch collateral auction.
+	pub fn get_dutch_auction_price(id: AuctionId) -> Option<Price> {
+		Self::dutch_collateral_auctions(id)
+			.map(|dutch_auction| dutch_auction.price_at(<frame_system::Pallet<T>>::block_number()))
+	}
+
+	/// Create the Dutch collateral auction item if Dutch auction is enabled for the collateral
+	/// type. The collateral auction with zero target is always English auction.
+	fn new_dutch_collateral_auction_item(
+		refund_recipient: &T::AccountId,
+		currency_id: CurrencyId,
+		amount: Balance,
+		target: Balance,
+		start_time: T::BlockNumber,
+	) -> Option<DutchCollateralAuctionItem<T::AccountId, T::BlockNumber>> {
+		if target.is_zero() {
+			return None;
+		}
+		let params = Self::collateral_dutch_auction_params(currency_id)?;
+		let oracle_price = T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())?;
+
+		let mut dutch_auction = DutchCollateralAuctionItem {
+			refund_recipient: refund_recipient.clone(),
+			currency_id,
+			initial_amount: amount,
+			amount,
+			target,
+			start_time,
+			start_price: Default::default(),
+			minimum_price: Default::default(),
+			params,
+		};
+		dutch_auction.anchor(params, oracle_price, start_time);
+		Some(dutch_auction)
+	}
+
+	fn do_reset_dutch_auction(id: AuctionId) -> DispatchResult {
+		let mut dutch_auction = Self::dutch_collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(dutch_auction.needs_reset(now), Error::<T>::DutchAuctionResetNotAllowed);
+
+		// anchor to the latest parameters, or the previous ones if Dutch auction has been disabled
+		let params = Self::collateral_dutch_auction_params(dutch_auction.currency_id).unwrap_or(dutch_auction.params);
+		let oracle_price = T::PriceSource::get_relative_price(dutch_auction.currency_id, T::GetStableCurrencyId::get())
+			.ok_or(Error::<T>::InvalidFeedPrice)?;
+		dutch_auction.anchor(params, oracle_price, now);
+
+		let start_price = dutch_auction.start_price;
+		DutchCollateralAuctions::<T>::insert(id, dutch_auction);
+		Self::deposit_event(Event::DutchCollateralAuctionReset {
+			auction_id: id,
+			start_price,
+		});
+		Ok(())
+	}
+
+	#[transactional]
+	fn do_take_dutch_auction(
+		who: &T::AccountId,
+		id: AuctionId,
+		max_collateral_amount: Balance,
+		max_price: Price,
+	) -> DispatchResult {
+		let mut dutch_auction = Self::dutch_collateral_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let now = <frame_system::Pallet<T>>::block_number();
+		ensure!(!dutch_auction.needs_reset(now), Error::<T>::DutchAuctionNeedsReset);
+		let price = dutch_auction.price_at(now);
+		ensure!(price <= max_price, Error::<T>::PriceAboveLimit);
+
+		let mut collateral_amount = max_collateral_amount.min(dutch_auction.amount);
+		let mut payment_amount = price.saturating_mul_int(collateral_amount);
+
+		// the payment is capped by the remain target
+		if payment_amount > dutch_auction.target {
+			payment_amount = dutch_auction.target;
+			if let Some(price_reciprocal) = price.reciprocal() {
+				collateral_amount = collateral_amount.min(price_reciprocal.saturating_mul_int(payment_amount));
+			}
+		}
+		ensure!(
+			!collateral_amount.is_zero() && !payment_amount.is_zero(),
+			Error::<T>::InvalidAmount
+		);
+
+		// transfer payment from buyer to CDP treasury and collateral from CDP treasury to buyer
+		T::CDPTreasury::deposit_surplus(who, payment_amount)?;
+		T::CDPTreasury::withdraw_collateral(who, dutch_auction.currency_id, collateral_amount)?;
+
+		dutch_auction.amount = dutch_auction.amount.saturating_sub(collateral_amount);
+		dutch_auction.target = dutch_auction.target.saturating_sub(payment_amount);
+		TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+			*balance = balance.saturating_sub(collateral_amount)
+		});
+		TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(payment_amount));
+
+		Self::deposit_event(Event::DutchCollateralAuctionTaken {
+			auction_id: id,
+			collateral_type: dutch_auction.currency_id,
+			buyer: who.clone(),
+			collateral_amount,
+			payment_amount,
+		});
+
+		if dutch_auction.target.is_zero() || dutch_auction.amount.is_zero() {
+			// refund remain collateral to refund recipient
+			let refund_collateral_amount = dutch_auction.amount;
+			if !refund_collateral_amount.is_zero() {
+				T::CDPTreasury::withdraw_collateral(
+					&dutch_auction.refund_recipient,
+					dutch_auction.currency_id,
+					refund_collateral_amount,
+				)?;
+			}
+
+			// decrease account ref of refund recipient
+			frame_system::Pallet::<T>::dec_consumers(&dutch_auction.refund_recipient);
+
+			// update auction records
+			TotalCollateralInAuction::<T>::mutate(dutch_auction.currency_id, |balance| {
+				*balance = balance.saturating_sub(refund_collateral_amount)
+			});
+			TotalTargetInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(dutch_auction.target));
+			DutchCollateralAuctions::<T>::remove(id);
+
+			Self::deposit_event(Event::DutchCollateralAuctionFinished {
+				auction_id: id,
+				collateral_type: dutch_auction.currency_id,
+				refund_collateral_amount,
+			});
+		} else {
+			DutchCollateralAuctions::<T>::insert(id, dutch_auction);
+		}
+
+		Ok(())
+	}
+
 	/// Return `true` if price increment rate is greater than or equal to
 	/// minimum.
 	///
@@ -592,6 +1136,192 @@ impl<T: Config> Pallet<T> {
 		)
 	}
 
+	/// Handles surplus auction new bid. Returns `Ok(new_auction_end_time)`
+	/// if bid accepted.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn surplus_auction_bid_handler(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+		let (new_bidder, new_bid_price) = new_bid;
+		ensure!(!new_bid_price.is_zero(), Error::<T>::InvalidBidPrice);
+
+		let surplus_auction = Self::surplus_auctions(id).ok_or(Error::<T>::AuctionNotExists)?;
+		let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price);
+
+		// ensure new bid price is valid
+		ensure!(
+			Self::check_minimum_increment(
+				new_bid_price,
+				last_bid_price,
+				Zero::zero(),
+				Self::get_minimum_increment_size(now, surplus_auction.start_time),
+			),
+			Error::<T>::InvalidBidPrice
+		);
+
+		let native_currency_id = T::GetNativeCurrencyId::get();
+		let last_bidder = last_bid.as_ref().map(|(who, _)| who);
+		let mut burn_amount = new_bid_price;
+
+		// if there's bid before, return native currency from new bidder to last bidder
+		if let Some(last_bidder) = last_bidder {
+			T::Currency::transfer(native_currency_id, &new_bidder, last_bidder, last_bid_price)?;
+			burn_amount = burn_amount.saturating_sub(last_bid_price);
+		}
+
+		// burn the remain native currency of new bidder
+		T::Currency::withdraw(native_currency_id, &new_bidder, burn_amount)?;
+
+		Self::swap_bidders(&new_bidder, last_bidder);
+
+		Ok(now + Self::get_auction_time_to_close(now, surplus_auction.start_time))
+	}
+
+	/// Handles debit auction new bid. Returns `Ok(new_auction_end_time)` if
+	/// bid accepted.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn debit_auction_bid_handler(
+		now: T::BlockNumber,
+		id: AuctionId,
+		new_bid: (T::AccountId, Balance),
+		last_bid: Option<(T::AccountId, Balance)>,
+	) -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+		let (new_bidder, new_bid_price) = new_bid;
+
+		<DebitAuctions<T>>::try_mutate_exists(
+			id,
+			|debit_auction| -> sp_std::result::Result<T::BlockNumber, DispatchError> {
+				let debit_auction = debit_auction.as_mut().ok_or(Error::<T>::AuctionNotExists)?;
+				let last_bid_price = last_bid.clone().map_or(Zero::zero(), |(_, price)| price);
+
+				// ensure new bid price is valid, the bid price must be no less than the fixed
+				// payment amount
+				ensure!(
+					new_bid_price >= debit_auction.fix
+						&& Self::check_minimum_increment(
+							new_bid_price,
+							last_bid_price,
+							debit_auction.fix,
+							Self::get_minimum_increment_size(now, debit_auction.start_time),
+						),
+					Error::<T>::InvalidBidPrice
+				);
+
+				let last_bidder = last_bid.as_ref().map(|(who, _)| who);
+
+				if let Some(last_bidder) = last_bidder {
+					// if there's bid before, transfer the fixed payment from new bidder to last bidder
+					T::Currency::transfer(
+						T::GetStableCurrencyId::get(),
+						&new_bidder,
+						last_bidder,
+						debit_auction.fix,
+					)?;
+				} else {
+					// otherwise transfer the fixed payment from new bidder to CDP treasury
+					T::CDPTreasury::deposit_surplus(&new_bidder, debit_auction.fix)?;
+				}
+
+				// the higher bid price, the less native currency for sale
+				debit_auction.amount = debit_auction.amount_for_sale(last_bid_price, new_bid_price);
+
+				Self::swap_bidders(&new_bidder, last_bidder);
+
+				Ok(now + Self::get_auction_time_to_close(now, debit_auction.start_time))
+			},
+		)
+	}
+
+	fn surplus_auction_end_handler(
+		auction_id: AuctionId,
+		surplus_auction: SurplusAuctionItem<T::BlockNumber>,
+		winner: Option<(T::AccountId, Balance)>,
+	) {
+		if let Some((bidder, bid_price)) = winner {
+			// issue the surplus to the winner, the surplus has been burned when the auction is created.
+			// If failed, just the winner did not get the surplus. It can be fixed by treasury council.
+			let res = T::CDPTreasury::issue_debit(&bidder, surplus_auction.amount, true);
+			if let Err(e) = res {
+				log::warn!(
+					target: "auction-manager",
+					"issue_debit: failed to issue stable {:?} to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					surplus_auction.amount, bidder, e
+				);
+				debug_assert!(false);
+			}
+
+			Self::deposit_event(Event::SurplusAuctionDealt {
+				auction_id,
+				surplus_amount: surplus_auction.amount,
+				winner: bidder,
+				payment_amount: bid_price,
+			});
+		} else {
+			// return the surplus to CDP treasury
+			let res = T::CDPTreasury::on_system_surplus(surplus_auction.amount);
+			if let Err(e) = res {
+				log::warn!(
+					target: "auction-manager",
+					"on_system_surplus: failed to return surplus {:?} to CDP treasury: {:?}. \
+					This is unexpected but should be safe",
+					surplus_auction.amount, e
+				);
+				debug_assert!(false);
+			}
+
+			Self::deposit_event(Event::SurplusAuctionAborted {
+				auction_id,
+				surplus_amount: surplus_auction.amount,
+			});
+		}
+
+		TotalSurplusInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(surplus_auction.amount));
+	}
+
+	fn debit_auction_end_handler(
+		auction_id: AuctionId,
+		debit_auction: DebitAuctionItem<T::BlockNumber>,
+		winner: Option<(T::AccountId, Balance)>,
+	) {
+		if let Some((bidder, _)) = winner {
+			// mint native currency to the winner.
+			// If failed, just the winner did not get the native currency. It can be fixed by treasury
+			// council.
+			let res = T::Currency::deposit(T::GetNativeCurrencyId::get(), &bidder, debit_auction.amount);
+			if let Err(e) = res {
+				log::warn!(
+					target: "auction-manager",
+					"deposit: failed to mint native currency {:?} to {:?}: {:?}. \
+					This is unexpected but should be safe",
+					debit_auction.amount, bidder, e
+				);
+				debug_assert!(false);
+			}
+
+			Self::deposit_event(Event::DebitAuctionDealt {
+				auction_id,
+				debit_currency_amount: debit_auction.amount,
+				winner: bidder,
+				payment_amount: debit_auction.fix,
+			});
+		} else {
+			Self::deposit_event(Event::DebitAuctionAborted {
+				auction_id,
+				fixed_payment_amount: debit_auction.fix,
+			});
+		}
+
+		TotalDebitInAuction::<T>::mutate(|balance| *balance = balance.saturating_sub(debit_auction.fix));
+	}
+
 	fn collateral_auction_end_handler(
 		auction_id: AuctionId,
 		collateral_auction: CollateralAuctionItem<T::AccountId, T::BlockNumber>,
@@ -730,7 +1460,15 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 		new_bid: (T::AccountId, Balance),
 		last_bid: Option<(T::AccountId, Balance)>,
 	) -> OnNewBidResult<T::BlockNumber> {
-		let bid_result = Self::collateral_auction_bid_handler(now, id, new_bid, last_bid);
+		let bid_result = if <CollateralAuctions<T>>::contains_key(id) {
+			Self::collateral_auction_bid_handler(now, id, new_bid, last_bid)
+		} else if <DebitAuctions<T>>::contains_key(id) {
+			Self::debit_auction_bid_handler(now, id, new_bid, last_bid)
+		} else if <SurplusAuctions<T>>::contains_key(id) {
+			Self::surplus_auction_bid_handler(now, id, new_bid, last_bid)
+		} else {
+			Err(Error::<T>::AuctionNotExists.into())
+		};
 
 		match bid_result {
 			Ok(new_auction_end_time) => OnNewBidResult {
@@ -747,6 +1485,10 @@ impl<T: Config> AuctionHandler<T::AccountId, Balance, T::BlockNumber, AuctionId>
 	fn on_auction_ended(id: AuctionId, winner: Option<(T::AccountId, Balance)>) {
 		if let Some(collateral_auction) = <CollateralAuctions<T>>::take(id) {
 			Self::collateral_auction_end_handler(id, collateral_auction, winner.clone());
+		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
+			Self::debit_auction_end_handler(id, debit_auction, winner.clone());
+		} else if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
+			Self::surplus_auction_end_handler(id, surplus_auction, winner.clone());
 		}
 
 		if let Some((bidder, _)) = &winner {
@@ -782,21 +1524,48 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 		}
 
 		let start_time = <frame_system::Pallet<T>>::block_number();
-		// use start_time + AuctionDurationSoftCap as the initial end-time of collateral auction.
-		let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
-		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+		if let Some(dutch_auction) =
+			Self::new_dutch_collateral_auction_item(refund_recipient, currency_id, amount, target, start_time)
+		{
+			// reserve an auction id from `T::Auction`, so that Dutch collateral auctions share
+			// the same id space with collateral auctions.
+			let auction_id = T::Auction::new_auction(start_time, None)?;
+			T::Auction::remove_auction(auction_id);
 
-		<CollateralAuctions<T>>::insert(
-			auction_id,
-			CollateralAuctionItem {
-				refund_recipient: refund_recipient.clone(),
-				currency_id,
-				initial_amount: amount,
-				amount,
-				target,
-				start_time,
-			},
-		);
+			let start_price = dutch_auction.start_price;
+			<DutchCollateralAuctions<T>>::insert(auction_id, dutch_auction);
+
+			Self::deposit_event(Event::NewDutchCollateralAuction {
+				auction_id,
+				collateral_type: currency_id,
+				collateral_amount: amount,
+				target_stable_amount: target,
+				start_price,
+			});
+		} else {
+			// use start_time + AuctionDurationSoftCap as the initial end-time of collateral auction.
+			let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
+			let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+
+			<CollateralAuctions<T>>::insert(
+				auction_id,
+				CollateralAuctionItem {
+					refund_recipient: refund_recipient.clone(),
+					currency_id,
+					initial_amount: amount,
+					amount,
+					target,
+					start_time,
+				},
+			);
+
+			Self::deposit_event(Event::NewCollateralAuction {
+				auction_id,
+				collateral_type: currency_id,
+				collateral_amount: amount,
+				target_bid_price: target,
+			});
+		}
 
 		// increment recipient account reference
 		if frame_system::Pallet::<T>::inc_consumers(refund_recipient).is_err() {
@@ -811,18 +1580,76 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 			);
 		}
 
-		Self::deposit_event(Event::NewCollateralAuction {
+		Ok(())
+	}
+
+	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::InvalidAmount);
+		TotalSurplusInAuction::<T>::try_mutate(|total| -> DispatchResult {
+			*total = total.checked_add(amount).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		// use start_time + AuctionDurationSoftCap as the initial end-time of surplus auction.
+		let start_time = <frame_system::Pallet<T>>::block_number();
+		let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
+		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+
+		<SurplusAuctions<T>>::insert(auction_id, SurplusAuctionItem { amount, start_time });
+
+		Self::deposit_event(Event::NewSurplusAuction {
+			auction_id,
+			fixed_surplus_amount: amount,
+		});
+		Ok(())
+	}
+
+	fn new_debit_auction(initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult {
+		ensure!(
+			!initial_amount.is_zero() && !fix_debit.is_zero(),
+			Error::<T>::InvalidAmount
+		);
+		TotalDebitInAuction::<T>::try_mutate(|total| -> DispatchResult {
+			*total = total.checked_add(fix_debit).ok_or(Error::<T>::InvalidAmount)?;
+			Ok(())
+		})?;
+
+		// use start_time + AuctionDurationSoftCap as the initial end-time of debit auction.
+		let start_time = <frame_system::Pallet<T>>::block_number();
+		let end_time = start_time.saturating_add(T::AuctionDurationSoftCap::get());
+		let auction_id = T::Auction::new_auction(start_time, Some(end_time))?;
+
+		<DebitAuctions<T>>::insert(
+			auction_id,
+			DebitAuctionItem {
+				initial_amount,
+				amount: initial_amount,
+				fix: fix_debit,
+				start_time,
+			},
+		);
+
+		Self::deposit_event(Event::NewDebitAuction {
 			auction_id,
-			collateral_type: currency_id,
-			collateral_amount: amount,
-			target_bid_price: target,
+			initial_supply_amount: initial_amount,
+			fixed_payment_amount: fix_debit,
 		});
 		Ok(())
 	}
 
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult {
-		let collateral_auction = <CollateralAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
-		Self::cancel_collateral_auction(id, collateral_auction)?;
+		if let Some(dutch_auction) = <DutchCollateralAuctions<T>>::take(id) {
+			return Self::cancel_dutch_collateral_auction(dutch_auction);
+		}
+
+		if let Some(surplus_auction) = <SurplusAuctions<T>>::take(id) {
+			Self::cancel_surplus_auction(id, surplus_auction)?;
+		} else if let Some(debit_auction) = <DebitAuctions<T>>::take(id) {
+			Self::cancel_debit_auction(id, debit_auction)?;
+		} else {
+			let collateral_auction = <CollateralAuctions<T>>::take(id).ok_or(Error::<T>::AuctionNotExists)?;
+			Self::cancel_collateral_auction(id, collateral_auction)?;
+		}
 		T::Auction::remove_auction(id);
 		Ok(())
 	}
@@ -834,4 +1661,12 @@ impl<T: Config> AuctionManager<T::AccountId> for Pallet<T> {
 	fn get_total_target_in_auction() -> Self::Balance {
 		Self::total_target_in_auction()
 	}
+
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Self::total_surplus_in_auction()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Self::total_debit_in_auction()
+	}
 }
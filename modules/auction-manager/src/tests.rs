@@ -25,7 +25,7 @@ use frame_support::{assert_noop, assert_ok};
 use mock::{Call as MockCall, Event, *};
 use sp_core::offchain::{testing, DbExternalities, OffchainDbExt, OffchainWorkerExt, StorageKind, TransactionPoolExt};
 use sp_io::offchain;
-use sp_runtime::traits::One;
+use sp_runtime::traits::{BadOrigin, One};
 use support::DEXManager;
 
 fn run_to_block_offchain(n: u64) {
@@ -569,6 +569,622 @@ fn cancel_collateral_auction_work() {
 	});
 }
 
+fn dutch_auction_params() -> DutchAuctionParams {
+	DutchAuctionParams {
+		start_price_ratio: Ratio::saturating_from_rational(12, 10),
+		price_decay_per_block: Rate::saturating_from_rational(1, 100),
+		minimum_price_ratio: Ratio::saturating_from_rational(8, 10),
+		reset_blocks: 100,
+		reset_price_ratio: Ratio::saturating_from_rational(85, 100),
+	}
+}
+
+#[test]
+fn set_dutch_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(Origin::signed(BOB), BTC, Some(dutch_auction_params())),
+			BadOrigin
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					minimum_price_ratio: Ratio::zero(),
+					..dutch_auction_params()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					minimum_price_ratio: Ratio::saturating_from_rational(13, 10),
+					..dutch_auction_params()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					price_decay_per_block: Rate::saturating_from_integer(2),
+					..dutch_auction_params()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					reset_blocks: 0,
+					..dutch_auction_params()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+		assert_noop!(
+			AuctionManagerModule::set_dutch_auction_params(
+				Origin::signed(ALICE),
+				BTC,
+				Some(DutchAuctionParams {
+					reset_price_ratio: Ratio::saturating_from_rational(11, 10),
+					..dutch_auction_params()
+				})
+			),
+			Error::<Runtime>::InvalidDutchAuctionParams
+		);
+
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params())
+		));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DutchAuctionParamsUpdated {
+			collateral_type: BTC,
+			new_params: Some(dutch_auction_params()),
+		}));
+		assert_eq!(
+			AuctionManagerModule::collateral_dutch_auction_params(BTC),
+			Some(dutch_auction_params())
+		);
+
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			None
+		));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DutchAuctionParamsUpdated {
+			collateral_type: BTC,
+			new_params: None,
+		}));
+		assert_eq!(AuctionManagerModule::collateral_dutch_auction_params(BTC), None);
+	});
+}
+
+#[test]
+fn new_dutch_collateral_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params())
+		));
+		let ref_count_0 = System::consumers(&ALICE);
+
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::NewDutchCollateralAuction {
+			auction_id: 0,
+			collateral_type: BTC,
+			collateral_amount: 100,
+			target_stable_amount: 60,
+			start_price: Price::saturating_from_rational(12, 10),
+		}));
+		assert!(AuctionManagerModule::dutch_collateral_auctions(0).is_some());
+		assert!(AuctionManagerModule::collateral_auctions(0).is_none());
+		assert!(AuctionModule::auctions(0).is_none());
+		assert_eq!(AuctionModule::auctions_index(), 1);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 100);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 60);
+		assert_eq!(System::consumers(&ALICE), ref_count_0 + 1);
+
+		// the price decays over blocks until the minimum price
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(0),
+			Some(Price::saturating_from_rational(12, 10))
+		);
+		System::set_block_number(11);
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(0),
+			Some(Price::saturating_from_rational(108, 100))
+		);
+		System::set_block_number(100);
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(0),
+			Some(Price::saturating_from_rational(8, 10))
+		);
+
+		// collateral auction with zero target is English auction
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 0));
+		assert!(AuctionManagerModule::collateral_auctions(1).is_some());
+
+		// collateral auction is English auction if the oracle price is unavailable
+		MockPriceSource::set_relative_price(None);
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 10));
+		assert!(AuctionManagerModule::collateral_auctions(2).is_some());
+	});
+}
+
+#[test]
+fn take_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params())
+		));
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(Origin::signed(BOB), 0, 20, Price::saturating_from_integer(2)),
+			Error::<Runtime>::AuctionNotExists
+		);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let alice_ref_count_0 = System::consumers(&ALICE);
+
+		System::set_block_number(11);
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(Origin::signed(BOB), 0, 20, Price::one()),
+			Error::<Runtime>::PriceAboveLimit
+		);
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(Origin::signed(BOB), 0, 0, Price::saturating_from_integer(2)),
+			Error::<Runtime>::InvalidAmount
+		);
+
+		// take part of the collateral at price 1.08
+		assert_ok!(AuctionManagerModule::take_dutch_auction(
+			Origin::signed(BOB),
+			0,
+			20,
+			Price::saturating_from_integer(2)
+		));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DutchCollateralAuctionTaken {
+			auction_id: 0,
+			collateral_type: BTC,
+			buyer: BOB,
+			collateral_amount: 20,
+			payment_amount: 21,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 979);
+		assert_eq!(Tokens::free_balance(BTC, &BOB), 1020);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 21);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 80);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 80);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 39);
+
+		// the payment is capped by the remain target, and the remain collateral is refunded
+		assert_ok!(AuctionManagerModule::take_dutch_auction(
+			Origin::signed(CAROL),
+			0,
+			100,
+			Price::saturating_from_integer(2)
+		));
+		System::assert_has_event(Event::AuctionManagerModule(crate::Event::DutchCollateralAuctionTaken {
+			auction_id: 0,
+			collateral_type: BTC,
+			buyer: CAROL,
+			collateral_amount: 36,
+			payment_amount: 39,
+		}));
+		System::assert_last_event(Event::AuctionManagerModule(
+			crate::Event::DutchCollateralAuctionFinished {
+				auction_id: 0,
+				collateral_type: BTC,
+				refund_collateral_amount: 44,
+			},
+		));
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 961);
+		assert_eq!(Tokens::free_balance(BTC, &CAROL), 936);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1044);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 60);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert!(AuctionManagerModule::dutch_collateral_auctions(0).is_none());
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 10));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 10, 60));
+		mock_shutdown();
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(Origin::signed(BOB), 1, 10, Price::saturating_from_integer(2)),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
+#[test]
+fn reset_dutch_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params())
+		));
+		assert_noop!(
+			AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::AuctionNotExists
+		);
+
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		System::set_block_number(16);
+		assert_noop!(
+			AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::DutchAuctionResetNotAllowed
+		);
+
+		// the price 1.008 falls below 85% of the start price 1.2, the auction must be reset
+		System::set_block_number(17);
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(Origin::signed(BOB), 0, 20, Price::saturating_from_integer(2)),
+			Error::<Runtime>::DutchAuctionNeedsReset
+		);
+		MockPriceSource::set_relative_price(None);
+		assert_noop!(
+			AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+
+		// reset with the latest parameters and anchor to the latest oracle price
+		MockPriceSource::set_relative_price(Some(Price::saturating_from_integer(2)));
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			Some(DutchAuctionParams {
+				reset_price_ratio: Ratio::zero(),
+				..dutch_auction_params()
+			})
+		));
+		assert_ok!(AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DutchCollateralAuctionReset {
+			auction_id: 0,
+			start_price: Price::saturating_from_rational(24, 10),
+		}));
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(0),
+			Some(Price::saturating_from_rational(24, 10))
+		);
+
+		// the auction must be reset after the reset blocks since the new start time
+		System::set_block_number(116);
+		assert_eq!(
+			AuctionManagerModule::get_dutch_auction_price(0),
+			Some(Price::saturating_from_rational(16, 10))
+		);
+		assert_noop!(
+			AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::DutchAuctionResetNotAllowed
+		);
+		System::set_block_number(117);
+		assert_noop!(
+			AuctionManagerModule::take_dutch_auction(Origin::signed(BOB), 0, 20, Price::saturating_from_integer(3)),
+			Error::<Runtime>::DutchAuctionNeedsReset
+		);
+		assert_ok!(AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0));
+
+		mock_shutdown();
+		System::set_block_number(217);
+		assert_noop!(
+			AuctionManagerModule::reset_dutch_auction(Origin::signed(BOB), 0),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
+#[test]
+fn cancel_dutch_collateral_auction_work() {
+	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
+	let (pool, pool_state) = testing::TestTransactionPoolExt::new();
+	let mut ext = ExtBuilder::default().build();
+	ext.register_extension(OffchainWorkerExt::new(offchain.clone()));
+	ext.register_extension(TransactionPoolExt::new(pool));
+	ext.register_extension(OffchainDbExt::new(offchain));
+
+	ext.execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::set_dutch_auction_params(
+			Origin::signed(ALICE),
+			BTC,
+			Some(dutch_auction_params())
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&CAROL, BTC, 100));
+		assert_ok!(AuctionManagerModule::new_collateral_auction(&ALICE, BTC, 100, 60));
+		let alice_ref_count_0 = System::consumers(&ALICE);
+
+		mock_shutdown();
+		// offchain worker will cancel Dutch collateral auction as shutdown is true
+		run_to_block_offchain(2);
+		let tx = pool_state.write().transactions.pop().unwrap();
+		let tx = Extrinsic::decode(&mut &*tx).unwrap();
+		assert_eq!(tx.call, MockCall::AuctionManagerModule(crate::Call::cancel { id: 0 }));
+		assert!(pool_state.write().transactions.pop().is_none());
+
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::CancelAuction {
+			auction_id: 0,
+		}));
+
+		// confiscate the collateral worth of target and refund the remain
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1040);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 60);
+		assert_eq!(AuctionManagerModule::total_collateral_in_auction(BTC), 0);
+		assert_eq!(AuctionManagerModule::total_target_in_auction(), 0);
+		assert!(AuctionManagerModule::dutch_collateral_auctions(0).is_none());
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn debit_auction_methods() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		let debit_auction = AuctionManagerModule::debit_auctions(0).unwrap();
+		assert_eq!(debit_auction.amount_for_sale(0, 100), 200);
+		assert_eq!(debit_auction.amount_for_sale(100, 200), 100);
+		assert_eq!(debit_auction.amount_for_sale(0, 400), 50);
+		assert_eq!(debit_auction.amount_for_sale(200, 100), 200);
+	});
+}
+
+#[test]
+fn new_surplus_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::new_surplus_auction(0),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::NewSurplusAuction {
+			auction_id: 0,
+			fixed_surplus_amount: 100,
+		}));
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 100);
+		assert_eq!(
+			AuctionModule::auctions(0),
+			Some(orml_traits::AuctionInfo {
+				bid: None,
+				start: 1,
+				end: Some(2001)
+			})
+		);
+
+		assert_noop!(
+			AuctionManagerModule::new_surplus_auction(Balance::max_value()),
+			Error::<Runtime>::InvalidAmount,
+		);
+	});
+}
+
+#[test]
+fn new_debit_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			AuctionManagerModule::new_debit_auction(0, 100),
+			Error::<Runtime>::InvalidAmount,
+		);
+		assert_noop!(
+			AuctionManagerModule::new_debit_auction(200, 0),
+			Error::<Runtime>::InvalidAmount,
+		);
+
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::NewDebitAuction {
+			auction_id: 0,
+			initial_supply_amount: 200,
+			fixed_payment_amount: 100,
+		}));
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 100);
+		assert_eq!(AuctionModule::auctions_index(), 1);
+	});
+}
+
+#[test]
+fn surplus_auction_bid_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionManagerModule::surplus_auction_bid_handler(1, 0, (BOB, 100), None),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_noop!(
+			AuctionManagerModule::surplus_auction_bid_handler(1, 0, (BOB, 0), None),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		let bob_ref_count_0 = System::consumers(&BOB);
+		assert_eq!(
+			AuctionManagerModule::surplus_auction_bid_handler(1, 0, (BOB, 100), None),
+			Ok(101)
+		);
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 900);
+		assert_eq!(Tokens::total_issuance(ACA), 1900);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 + 1);
+
+		assert_noop!(
+			AuctionManagerModule::surplus_auction_bid_handler(2, 0, (ALICE, 104), Some((BOB, 100))),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_eq!(
+			AuctionManagerModule::surplus_auction_bid_handler(2, 0, (ALICE, 105), Some((BOB, 100))),
+			Ok(102)
+		);
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(ACA, &ALICE), 895);
+		assert_eq!(Tokens::total_issuance(ACA), 1895);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0);
+	});
+}
+
+#[test]
+fn debit_auction_bid_handler_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 100), None),
+			Error::<Runtime>::AuctionNotExists,
+		);
+
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_noop!(
+			AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 99), None),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+
+		assert_eq!(
+			AuctionManagerModule::debit_auction_bid_handler(1, 0, (BOB, 100), None),
+			Ok(101)
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(AuctionManagerModule::debit_auctions(0).unwrap().amount, 200);
+
+		assert_noop!(
+			AuctionManagerModule::debit_auction_bid_handler(2, 0, (CAROL, 104), Some((BOB, 100))),
+			Error::<Runtime>::InvalidBidPrice,
+		);
+		assert_eq!(
+			AuctionManagerModule::debit_auction_bid_handler(2, 0, (CAROL, 200), Some((BOB, 100))),
+			Ok(102)
+		);
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 900);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(AuctionManagerModule::debit_auctions(0).unwrap().amount, 100);
+	});
+}
+
+#[test]
+fn surplus_auction_dealt_and_aborted() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionManagerModule::new_surplus_auction(50));
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 150);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 20));
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 980);
+
+		AuctionManagerModule::on_auction_ended(0, Some((BOB, 20)));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::SurplusAuctionDealt {
+			auction_id: 0,
+			surplus_amount: 100,
+			winner: BOB,
+			payment_amount: 20,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1100);
+		assert_eq!(Tokens::total_issuance(ACA), 1980);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 50);
+		assert!(AuctionManagerModule::surplus_auctions(0).is_none());
+
+		AuctionManagerModule::on_auction_ended(1, None);
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::SurplusAuctionAborted {
+			auction_id: 1,
+			surplus_amount: 50,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 50);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 0);
+	});
+}
+
+#[test]
+fn debit_auction_dealt_and_aborted() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 200);
+
+		assert_ok!(AuctionModule::bid(Origin::signed(CAROL), 0, 200));
+		assert_eq!(Tokens::free_balance(AUSD, &CAROL), 900);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+
+		AuctionManagerModule::on_auction_ended(0, Some((CAROL, 200)));
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DebitAuctionDealt {
+			auction_id: 0,
+			debit_currency_amount: 100,
+			winner: CAROL,
+			payment_amount: 100,
+		}));
+		assert_eq!(Tokens::free_balance(ACA, &CAROL), 100);
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 100);
+		assert!(AuctionManagerModule::debit_auctions(0).is_none());
+
+		AuctionManagerModule::on_auction_ended(1, None);
+		System::assert_last_event(Event::AuctionManagerModule(crate::Event::DebitAuctionAborted {
+			auction_id: 1,
+			fixed_payment_amount: 100,
+		}));
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 0);
+	});
+}
+
+#[test]
+fn cancel_surplus_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_surplus_auction(100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 20));
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 980);
+		let bob_ref_count_0 = System::consumers(&BOB);
+
+		mock_shutdown();
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		assert_eq!(Tokens::free_balance(ACA, &BOB), 1000);
+		assert_eq!(Tokens::total_issuance(ACA), 2000);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+		assert_eq!(AuctionManagerModule::total_surplus_in_auction(), 0);
+		assert!(AuctionManagerModule::surplus_auctions(0).is_none());
+		assert!(AuctionModule::auction_info(0).is_none());
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+	});
+}
+
+#[test]
+fn cancel_debit_auction_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(AuctionManagerModule::new_debit_auction(200, 100));
+		assert_ok!(AuctionModule::bid(Origin::signed(BOB), 0, 100));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 900);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+
+		mock_shutdown();
+		assert_ok!(AuctionManagerModule::cancel(Origin::none(), 0));
+		assert_eq!(Tokens::free_balance(AUSD, &BOB), 1000);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+		assert_eq!(AuctionManagerModule::total_debit_in_auction(), 0);
+		assert!(AuctionManagerModule::debit_auctions(0).is_none());
+		assert!(AuctionModule::auction_info(0).is_none());
+	});
+}
+
 #[test]
 fn offchain_worker_cancels_auction_in_shutdown() {
 	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
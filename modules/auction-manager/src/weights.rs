@@ -48,6 +48,9 @@ use sp_std::marker::PhantomData;
 /// Weight functions needed for module_auction_manager.
 pub trait WeightInfo {
 	fn cancel_collateral_auction() -> Weight;
+	fn set_dutch_auction_params() -> Weight;
+	fn take_dutch_auction() -> Weight;
+	fn reset_dutch_auction() -> Weight;
 }
 
 /// Weights for module_auction_manager using the Acala node and recommended hardware.
@@ -58,6 +61,20 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn set_dutch_auction_params() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn take_dutch_auction() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	fn reset_dutch_auction() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -67,4 +84,18 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(7 as Weight))
 	}
+	fn set_dutch_auction_params() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn take_dutch_auction() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(9 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+	}
+	fn reset_dutch_auction() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -0,0 +1,41 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for CDP engine module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use module_cdp_engine::RiskInfo;
+use primitives::CurrencyId;
+use sp_runtime::codec::Codec;
+
+sp_api::decl_runtime_apis! {
+	pub trait CdpEngineRuntimeApi<AccountId> where
+		AccountId: Codec,
+	{
+		/// Get the risk data of the CDP of `who` under the collateral type.
+		/// Returns `None` if `currency_id` isn't a collateral type.
+		fn get_position_risk(who: AccountId, currency_id: CurrencyId) -> Option<RiskInfo>;
+
+		/// Get the risk data of the total position of the collateral type.
+		/// Returns `None` if `currency_id` isn't a collateral type.
+		fn get_collateral_risk(currency_id: CurrencyId) -> Option<RiskInfo>;
+	}
+}
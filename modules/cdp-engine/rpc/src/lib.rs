@@ -0,0 +1,123 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the CDP engine module.
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use primitives::CurrencyId;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_runtime::{generic::BlockId, traits::Block as BlockT};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use module_cdp_engine_rpc_runtime_api::CdpEngineRuntimeApi;
+pub use types::{CDPStatus, RiskInfo};
+
+mod types;
+
+/// CDP engine rpc interface.
+#[rpc]
+pub trait CdpEngineApi<BlockHash, AccountId> {
+	/// Get the risk data of the CDP of the account under the collateral type.
+	#[rpc(name = "cdpEngine_getPositionRisk")]
+	fn get_position_risk(
+		&self,
+		who: AccountId,
+		currency_id: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Option<RiskInfo>>;
+
+	/// Get the risk data of the total position of the collateral type.
+	#[rpc(name = "cdpEngine_getCollateralRisk")]
+	fn get_collateral_risk(&self, currency_id: CurrencyId, at: Option<BlockHash>) -> Result<Option<RiskInfo>>;
+}
+
+/// Error type of this RPC api.
+pub enum CdpEngineError {
+	/// The call to runtime failed.
+	RuntimeError,
+}
+
+impl From<CdpEngineError> for i64 {
+	fn from(e: CdpEngineError) -> i64 {
+		match e {
+			CdpEngineError::RuntimeError => 1,
+		}
+	}
+}
+
+/// A struct that implements the [`CdpEngineApi`].
+pub struct CdpEngine<C, B> {
+	client: Arc<C>,
+	_marker: PhantomData<B>,
+}
+
+impl<C, B> CdpEngine<C, B> {
+	/// Create new `CdpEngine` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+fn runtime_error<T: std::fmt::Debug>(e: T) -> Error {
+	Error {
+		code: ErrorCode::ServerError(CdpEngineError::RuntimeError.into()),
+		message: "Unable to query cdp engine.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+impl<C, Block, AccountId> CdpEngineApi<<Block as BlockT>::Hash, AccountId> for CdpEngine<C, Block>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: CdpEngineRuntimeApi<Block, AccountId>,
+	AccountId: Codec,
+{
+	fn get_position_risk(
+		&self,
+		who: AccountId,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<RiskInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_position_risk(&at, who, currency_id)
+			.map(|info| info.map(Into::into))
+			.map_err(runtime_error)
+	}
+
+	fn get_collateral_risk(
+		&self,
+		currency_id: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<RiskInfo>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_collateral_risk(&at, currency_id)
+			.map(|info| info.map(Into::into))
+			.map_err(runtime_error)
+	}
+}
@@ -0,0 +1,94 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::FixedPointNumber;
+
+/// CDP status response
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum CDPStatus {
+	/// Safe
+	Safe,
+	/// Unsafe, can be liquidated
+	Unsafe,
+	/// Failed to check the status, with the error message
+	ChecksFailed(String),
+}
+
+/// Risk data response, the ratio and price are fixed-point numbers with 18
+/// decimals
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RiskInfo {
+	/// Collateral amount
+	pub collateral: NumberOrHex,
+	/// Debit balance
+	pub debit: NumberOrHex,
+	/// Debit value at the current debit exchange rate
+	pub debit_value: NumberOrHex,
+	/// Collateral ratio at the current feed price
+	pub collateral_ratio: Option<NumberOrHex>,
+	/// Price of collateral at which the collateral ratio drops to the
+	/// liquidation ratio
+	pub liquidation_price: Option<NumberOrHex>,
+	/// Interest accrued on the debit
+	pub accrued_interest: NumberOrHex,
+	/// Debit value can be additionally issued
+	pub max_additional_debit_value: NumberOrHex,
+	/// Status of the CDP
+	pub status: CDPStatus,
+}
+
+impl From<module_cdp_engine::RiskInfo> for RiskInfo {
+	fn from(info: module_cdp_engine::RiskInfo) -> Self {
+		Self {
+			collateral: info.collateral.into(),
+			debit: info.debit.into(),
+			debit_value: info.debit_value.into(),
+			collateral_ratio: info.collateral_ratio.map(|ratio| ratio.into_inner().into()),
+			liquidation_price: info.liquidation_price.map(|price| price.into_inner().into()),
+			accrued_interest: info.accrued_interest.into(),
+			max_additional_debit_value: info.max_additional_debit_value.into(),
+			status: match info.status {
+				module_cdp_engine::CDPStatus::Safe => CDPStatus::Safe,
+				module_cdp_engine::CDPStatus::Unsafe => CDPStatus::Unsafe,
+				module_cdp_engine::CDPStatus::ChecksFailed(e) => CDPStatus::ChecksFailed(format!("{:?}", e)),
+			},
+		}
+	}
+}
+
+#[test]
+fn risk_info_should_serialize() {
+	let info = RiskInfo {
+		collateral: NumberOrHex::Number(100),
+		debit: NumberOrHex::Number(500),
+		debit_value: NumberOrHex::Number(50),
+		collateral_ratio: Some(NumberOrHex::Number(2)),
+		liquidation_price: None,
+		accrued_interest: NumberOrHex::Number(0),
+		max_additional_debit_value: NumberOrHex::Number(5),
+		status: CDPStatus::Safe,
+	};
+	assert_eq!(
+		serde_json::to_string(&info).unwrap(),
+		r#"{"collateral":100,"debit":500,"debitValue":50,"collateralRatio":2,"liquidationPrice":null,"accruedInterest":0,"maxAdditionalDebitValue":5,"status":"safe"}"#
+	);
+}
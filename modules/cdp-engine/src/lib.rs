@@ -34,7 +34,7 @@ use frame_system::{
 	pallet_prelude::*,
 };
 use loans::Position;
-use orml_traits::Change;
+use orml_traits::{Change, MultiCurrency};
 use orml_utilities::OffchainErr;
 use primitives::{Amount, Balance, CurrencyId};
 use rand_chacha::{
@@ -56,8 +56,8 @@ use sp_runtime::{
 };
 use sp_std::prelude::*;
 use support::{
-	CDPTreasury, CDPTreasuryExtended, EmergencyShutdown, ExchangeRate, Price, PriceProvider, Rate, Ratio, RiskManager,
-	SwapLimit,
+	CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, ExchangeRate, Price, PriceProvider, Rate, Ratio,
+	RiskManager, SwapLimit,
 };
 
 mod debit_exchange_rate_convertor;
@@ -104,6 +104,47 @@ pub struct RiskManagementParams {
 	pub required_collateral_ratio: Option<Ratio>,
 }
 
+/// Kinked interest rate model over the debit utilization of a collateral
+/// type, which is the total debit value divided by its
+/// `maximum_total_debit_value`
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct InterestRateModel {
+	/// Interest rate per sec when the utilization is zero
+	pub base_rate: Rate,
+	/// Utilization at which the interest rate starts to increase with
+	/// `jump_multiplier`
+	pub kink: Ratio,
+	/// Increase of interest rate per sec per unit of utilization below the
+	/// kink
+	pub multiplier: Rate,
+	/// Increase of interest rate per sec per unit of utilization above the
+	/// kink
+	pub jump_multiplier: Rate,
+	/// Lower bound of the interest rate per sec
+	pub min_rate: Rate,
+	/// Upper bound of the interest rate per sec
+	pub max_rate: Rate,
+}
+
+impl InterestRateModel {
+	/// Calculate the interest rate per sec at `utilization`, bounded by
+	/// `min_rate` and `max_rate`.
+	pub fn interest_rate_at(&self, utilization: Ratio) -> Rate {
+		let rate = if utilization <= self.kink {
+			self.base_rate
+				.saturating_add(self.multiplier.saturating_mul(utilization))
+		} else {
+			self.base_rate
+				.saturating_add(self.multiplier.saturating_mul(self.kink))
+				.saturating_add(
+					self.jump_multiplier
+						.saturating_mul(utilization.saturating_sub(self.kink)),
+				)
+		};
+		rate.max(self.min_rate).min(self.max_rate)
+	}
+}
+
 // typedef to help polkadot.js disambiguate Change with different generic
 // parameters
 type ChangeOptionRate = Change<Option<Rate>>;
@@ -128,6 +169,31 @@ pub enum CDPStatus {
 	ChecksFailed(DispatchError),
 }
 
+/// Risk data of a CDP, or of the total position of a collateral type
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct RiskInfo {
+	/// Collateral amount
+	pub collateral: Balance,
+	/// Debit balance
+	pub debit: Balance,
+	/// Debit value at the current debit exchange rate
+	pub debit_value: Balance,
+	/// Collateral ratio at the current feed price, `None` if the price is
+	/// unavailable
+	pub collateral_ratio: Option<Ratio>,
+	/// Price of collateral in stable currency at which the collateral ratio
+	/// drops to the liquidation ratio, `None` if there is no collateral
+	pub liquidation_price: Option<Price>,
+	/// Interest accrued on the debit since the debit exchange rate of the
+	/// collateral type was at the default debit exchange rate
+	pub accrued_interest: Balance,
+	/// Debit value can be additionally issued without falling below the
+	/// required collateral ratio or exceeding the hard cap
+	pub max_additional_debit_value: Balance,
+	/// Status of the CDP
+	pub status: CDPStatus,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -174,6 +240,16 @@ pub mod module {
 		/// The price source of all types of currencies related to CDP
 		type PriceSource: PriceProvider<CurrencyId>;
 
+		/// Dex manager is used to swap between stable currency and collateral
+		/// when expand or shrink CDP
+		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
+
+		/// The alternative swap path joint list, which can be concated to
+		/// alternative swap path when swap between stable currency and
+		/// collateral.
+		#[pallet::constant]
+		type AlternativeSwapPathJointList: Get<Vec<Vec<CurrencyId>>>;
+
 		/// A configuration for base priority of unsigned transactions.
 		///
 		/// This is exposed so that it can be tuned for particular runtime, when
@@ -219,12 +295,27 @@ pub mod module {
 		AlreadyShutdown,
 		/// Must after system shutdown
 		MustAfterShutdown,
+		/// The close factor must be greater than zero and not greater than one
+		InvalidCloseFactor,
+		/// The keeper reward ratio must not be greater than one
+		InvalidKeeperRewardRatio,
+		/// Cannot find the swap path between stable currency and collateral
+		CannotSwap,
+		/// The lower bound of the interest rate model is greater than the upper bound
+		InvalidInterestRateModel,
+		/// The positions in cross-collateral mode must be liquidated as a whole
+		IsCrossCollateral,
+		/// The owner of the positions is not in cross-collateral mode
+		NotCrossCollateral,
+		/// The keeper cannot liquidate its own CDP
+		KeeperIsOwner,
 	}
 
 	#[pallet::event]
 	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
 	pub enum Event<T: Config> {
-		/// Liquidate the unsafe CDP.
+		/// Liquidate the unsafe CDP. The collateral amount and bad debt value are the liquidated
+		/// part of the CDP.
 		LiquidateUnsafeCDP {
 			collateral_type: CurrencyId,
 			owner: T::AccountId,
@@ -272,6 +363,51 @@ pub mod module {
 		},
 		/// The global interest rate per sec for all types of collateral updated.
 		GlobalInterestRatePerSecUpdated { new_global_interest_rate_per_sec: Rate },
+		/// The liquidation close factor for specific collateral type updated.
+		LiquidationCloseFactorUpdated {
+			collateral_type: CurrencyId,
+			new_liquidation_close_factor: Option<Ratio>,
+		},
+		/// Expand the collateral of CDP by issuing debit and swapping it to collateral with DEX.
+		ExpandPositionCollateral {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			increased_debit_value: Balance,
+			increased_collateral_amount: Balance,
+		},
+		/// Shrink the debit of CDP by swapping collateral to stable currency with DEX.
+		ShrinkPositionDebit {
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			decreased_collateral_amount: Balance,
+			decreased_debit_value: Balance,
+		},
+		/// The interest rate per sec output by the interest rate model for specific collateral
+		/// type updated.
+		ModelInterestRatePerSecUpdated {
+			collateral_type: CurrencyId,
+			new_interest_rate_per_sec: Rate,
+		},
+		/// The interest rate model for specific collateral type updated.
+		InterestRateModelUpdated {
+			collateral_type: CurrencyId,
+			new_interest_rate_model: Option<InterestRateModel>,
+		},
+		/// The proportion of liquidation penalty rewarded to keeper updated.
+		KeeperRewardRatioUpdated { new_keeper_reward_ratio: Ratio },
+		/// The keeper who liquidated the unsafe CDP is rewarded.
+		KeeperRewarded {
+			keeper: T::AccountId,
+			collateral_type: CurrencyId,
+			owner: T::AccountId,
+			reward_amount: Balance,
+		},
+		/// The keeper who liquidated the unsafe positions in cross-collateral mode is rewarded.
+		CrossCollateralKeeperRewarded {
+			keeper: T::AccountId,
+			owner: T::AccountId,
+			reward_amount: Balance,
+		},
 	}
 
 	/// Mapping from collateral type to its exchange rate of debit units and
@@ -296,6 +432,42 @@ pub mod module {
 	#[pallet::getter(fn collateral_params)]
 	pub type CollateralParams<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, RiskManagementParams, ValueQuery>;
 
+	/// Mapping from collateral type to its liquidation close factor, the maximum proportion
+	/// of the debit value of an unsafe CDP that can be liquidated at once. If it's set, only
+	/// the debit required to restore the CDP to the target collateral ratio will be
+	/// liquidated. `None` means the whole CDP will be liquidated.
+	///
+	/// LiquidationCloseFactors: CurrencyId => Option<Ratio>
+	#[pallet::storage]
+	#[pallet::getter(fn liquidation_close_factors)]
+	pub type LiquidationCloseFactors<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Ratio, OptionQuery>;
+
+	/// Mapping from collateral type to its interest rate model. If it's set,
+	/// the interest rate per sec is adjusted by the model according to the
+	/// debit utilization after accumulating interest.
+	///
+	/// InterestRateModels: CurrencyId => Option<InterestRateModel>
+	#[pallet::storage]
+	#[pallet::getter(fn interest_rate_models)]
+	pub type InterestRateModels<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, InterestRateModel, OptionQuery>;
+
+	/// Mapping from collateral type to the interest rate per sec output by its
+	/// interest rate model, which takes the place of the interest rate per sec
+	/// in the risk management params while the model is set.
+	///
+	/// ModelInterestRatePerSec: CurrencyId => Option<Rate>
+	#[pallet::storage]
+	#[pallet::getter(fn model_interest_rate_per_sec)]
+	pub type ModelInterestRatePerSec<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Rate, OptionQuery>;
+
+	/// The proportion of the liquidation penalty recovered by DEX rewarded to the
+	/// keeper who liquidates unsafe CDP by signed transaction.
+	///
+	/// KeeperRewardRatio: Ratio
+	#[pallet::storage]
+	#[pallet::getter(fn keeper_reward_ratio)]
+	pub type KeeperRewardRatio<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+
 	/// Timestamp in seconds of the last interest accumulation
 	///
 	/// LastAccumulationSecs: u64
@@ -406,12 +578,15 @@ pub mod module {
 		) -> DispatchResultWithPostInfo {
 			ensure_none(origin)?;
 			let who = T::Lookup::lookup(who)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
 			let consumed_weight: Weight = Self::liquidate_unsafe_cdp(who, currency_id)?;
 			Ok(Some(consumed_weight).into())
 		}
 
-		/// Settle CDP has debit after system shutdown
+		/// Settle CDP has debit after system or the collateral type shutdown
 		///
 		/// The dispatch origin of this call must be _None_.
 		///
@@ -426,7 +601,10 @@ pub mod module {
 		) -> DispatchResult {
 			ensure_none(origin)?;
 			let who = T::Lookup::lookup(who)?;
-			ensure!(T::EmergencyShutdown::is_shutdown(), Error::<T>::MustAfterShutdown);
+			ensure!(
+				T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::MustAfterShutdown
+			);
 			Self::settle_cdp_has_debit(who, currency_id)?;
 			Ok(())
 		}
@@ -518,6 +696,194 @@ pub mod module {
 			CollateralParams::<T>::insert(currency_id, collateral_params);
 			Ok(())
 		}
+
+		/// Update the liquidation close factor of specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `close_factor`: the maximum proportion of the debit value can be liquidated at once,
+		///   `None` means liquidate the whole CDP.
+		#[pallet::weight((<T as Config>::WeightInfo::set_liquidation_close_factor(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_liquidation_close_factor(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			close_factor: Option<Ratio>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			if let Some(close_factor) = close_factor {
+				ensure!(
+					!close_factor.is_zero() && close_factor <= Ratio::one(),
+					Error::<T>::InvalidCloseFactor
+				);
+			}
+
+			LiquidationCloseFactors::<T>::set(currency_id, close_factor);
+			Self::deposit_event(Event::LiquidationCloseFactorUpdated {
+				collateral_type: currency_id,
+				new_liquidation_close_factor: close_factor,
+			});
+			Ok(())
+		}
+
+		/// Liquidate unsafe CDP by keeper, the keeper is rewarded with a proportion
+		/// of the liquidation penalty recovered by DEX. The owner of the CDP cannot
+		/// be the keeper.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: CDP's collateral type.
+		/// - `who`: CDP's owner.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction())
+				.saturating_add(<T as Config>::WeightInfo::keeper_reward())
+		)]
+		#[transactional]
+		pub fn keeper_liquidate(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let keeper = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
+			let consumed_weight: Weight = Self::keeper_liquidate_unsafe_cdp(&keeper, who, currency_id)?;
+			Ok(Some(consumed_weight).into())
+		}
+
+		/// Liquidate the unsafe positions of all collateral types in cross-collateral mode as a
+		/// whole.
+		///
+		/// The dispatch origin of this call must be _None_.
+		///
+		/// - `who`: the owner of the positions.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction())
+				.saturating_mul(T::CollateralCurrencyIds::get().len() as Weight)
+		)]
+		#[transactional]
+		pub fn liquidate_cross_collateral(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			ensure_none(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			let consumed_weight: Weight = Self::liquidate_unsafe_cross_collateral_cdp(who)?;
+			Ok(Some(consumed_weight).into())
+		}
+
+		/// Liquidate the unsafe positions in cross-collateral mode by keeper, the keeper is
+		/// rewarded with a proportion of the liquidation penalty recovered by DEX. The owner of
+		/// the positions cannot be the keeper.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `who`: the owner of the positions.
+		#[pallet::weight(
+			<T as Config>::WeightInfo::liquidate_by_auction(<T as Config>::CDPTreasury::max_auction())
+				.saturating_mul(T::CollateralCurrencyIds::get().len() as Weight)
+				.saturating_add(<T as Config>::WeightInfo::keeper_reward())
+		)]
+		#[transactional]
+		pub fn keeper_liquidate_cross_collateral(
+			origin: OriginFor<T>,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResultWithPostInfo {
+			let keeper = ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			let consumed_weight: Weight = Self::keeper_liquidate_unsafe_cross_collateral_cdp(&keeper, who)?;
+			Ok(Some(consumed_weight).into())
+		}
+
+		/// Settle CDP has debit by keeper after system or the collateral type shutdown. There's
+		/// no penalty in settlement, so the keeper is not rewarded.
+		///
+		/// The dispatch origin of this call must be _Signed_.
+		///
+		/// - `currency_id`: CDP's collateral type.
+		/// - `who`: CDP's owner.
+		#[pallet::weight(<T as Config>::WeightInfo::settle())]
+		#[transactional]
+		pub fn keeper_settle(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			who: <T::Lookup as StaticLookup>::Source,
+		) -> DispatchResult {
+			ensure_signed(origin)?;
+			let who = T::Lookup::lookup(who)?;
+			ensure!(
+				T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::MustAfterShutdown
+			);
+			Self::settle_cdp_has_debit(who, currency_id)?;
+			Ok(())
+		}
+
+		/// Update the proportion of liquidation penalty rewarded to keeper
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `keeper_reward_ratio`: the proportion of liquidation penalty rewarded to keeper.
+		#[pallet::weight((<T as Config>::WeightInfo::set_keeper_reward_ratio(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_keeper_reward_ratio(origin: OriginFor<T>, keeper_reward_ratio: Ratio) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				keeper_reward_ratio <= Ratio::one(),
+				Error::<T>::InvalidKeeperRewardRatio
+			);
+
+			KeeperRewardRatio::<T>::put(keeper_reward_ratio);
+			Self::deposit_event(Event::KeeperRewardRatioUpdated {
+				new_keeper_reward_ratio: keeper_reward_ratio,
+			});
+			Ok(())
+		}
+
+		/// Update the interest rate model of specific collateral type
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `interest_rate_model`: the model adjusting the interest rate per sec by the debit
+		///   utilization, `None` means the interest rate per sec is only set by
+		///   `set_collateral_params`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_interest_rate_model(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_interest_rate_model(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			interest_rate_model: Option<InterestRateModel>,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			if let Some(model) = interest_rate_model {
+				ensure!(model.min_rate <= model.max_rate, Error::<T>::InvalidInterestRateModel);
+			}
+
+			InterestRateModels::<T>::set(currency_id, interest_rate_model);
+			if interest_rate_model.is_none() {
+				ModelInterestRatePerSec::<T>::remove(currency_id);
+			}
+			Self::deposit_event(Event::InterestRateModelUpdated {
+				collateral_type: currency_id,
+				new_interest_rate_model: interest_rate_model,
+			});
+			Ok(())
+		}
 	}
 
 	#[pallet::validate_unsigned]
@@ -528,11 +894,9 @@ pub mod module {
 			match call {
 				Call::liquidate { currency_id, who } => {
 					let account = T::Lookup::lookup(who.clone())?;
-					let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &account);
-					if !matches!(
-						Self::check_cdp_status(*currency_id, collateral, debit),
-						CDPStatus::Unsafe
-					) || T::EmergencyShutdown::is_shutdown()
+					if <LoansOf<T>>::is_cross_collateral(&account)
+						|| !matches!(Self::check_position_status(&account, *currency_id), CDPStatus::Unsafe)
+						|| T::EmergencyShutdown::is_collateral_shutdown(*currency_id)
 					{
 						return InvalidTransaction::Stale.into();
 					}
@@ -544,10 +908,28 @@ pub mod module {
 						.propagate(true)
 						.build()
 				}
+				Call::liquidate_cross_collateral { who } => {
+					let account = T::Lookup::lookup(who.clone())?;
+					if !<LoansOf<T>>::is_cross_collateral(&account)
+						|| !matches!(Self::check_cross_collateral_status(&account), CDPStatus::Unsafe)
+						|| T::EmergencyShutdown::is_shutdown()
+					{
+						return InvalidTransaction::Stale.into();
+					}
+
+					// the offchain worker may find the same positions under each collateral type,
+					// which are deduplicated by the owner
+					ValidTransaction::with_tag_prefix("CDPEngineOffchainWorker")
+						.priority(T::UnsignedPriority::get())
+						.and_provides(who)
+						.longevity(64_u64)
+						.propagate(true)
+						.build()
+				}
 				Call::settle { currency_id, who } => {
 					let account = T::Lookup::lookup(who.clone())?;
 					let Position { debit, .. } = <LoansOf<T>>::positions(currency_id, account);
-					if debit.is_zero() || !T::EmergencyShutdown::is_shutdown() {
+					if debit.is_zero() || !T::EmergencyShutdown::is_collateral_shutdown(*currency_id) {
 						return InvalidTransaction::Stale.into();
 					}
 
@@ -571,7 +953,11 @@ impl<T: Config> Pallet<T> {
 		if !T::EmergencyShutdown::is_shutdown() && !now_secs.is_zero() {
 			let interval_secs = now_secs.saturating_sub(last_accumulation_secs);
 
-			for currency_id in T::CollateralCurrencyIds::get() {
+			// the collateral type which has been shutdown alone stops accumulating interest
+			for currency_id in T::CollateralCurrencyIds::get()
+				.into_iter()
+				.filter(|currency_id| !T::EmergencyShutdown::is_collateral_shutdown(*currency_id))
+			{
 				let rate_to_accumulate =
 					Self::compound_interest_rate(Self::get_interest_rate_per_sec(currency_id), interval_secs);
 				let total_debits = <LoansOf<T>>::total_positions(currency_id).debit;
@@ -600,6 +986,10 @@ impl<T: Config> Pallet<T> {
 						}
 					}
 				}
+
+				// the interest of this interval is accumulated at the previous rate, then the
+				// rate is adjusted for the next interval
+				Self::adjust_interest_rate_by_model(currency_id);
 				count += 1;
 			}
 		}
@@ -609,6 +999,28 @@ impl<T: Config> Pallet<T> {
 		count
 	}
 
+	fn adjust_interest_rate_by_model(currency_id: CurrencyId) {
+		if let Some(model) = Self::interest_rate_models(currency_id) {
+			let total_debit_value =
+				Self::get_debit_value(currency_id, <LoansOf<T>>::total_positions(currency_id).debit);
+			let utilization = if total_debit_value.is_zero() {
+				Ratio::zero()
+			} else {
+				Ratio::checked_from_rational(total_debit_value, Self::maximum_total_debit_value(currency_id))
+					.unwrap_or_else(Ratio::max_value)
+			};
+			let new_interest_rate_per_sec = model.interest_rate_at(utilization);
+
+			if Self::model_interest_rate_per_sec(currency_id) != Some(new_interest_rate_per_sec) {
+				ModelInterestRatePerSec::<T>::insert(currency_id, new_interest_rate_per_sec);
+				Self::deposit_event(Event::ModelInterestRatePerSecUpdated {
+					collateral_type: currency_id,
+					new_interest_rate_per_sec,
+				});
+			}
+		}
+	}
+
 	fn submit_unsigned_liquidation_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let who = T::Lookup::unlookup(who);
 		let call = Call::<T>::liquidate {
@@ -624,6 +1036,18 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	fn submit_unsigned_cross_collateral_liquidation_tx(who: T::AccountId) {
+		let who = T::Lookup::unlookup(who);
+		let call = Call::<T>::liquidate_cross_collateral { who: who.clone() };
+		if SubmitTransaction::<T, Call<T>>::submit_unsigned_transaction(call.into()).is_err() {
+			log::info!(
+				target: "cdp-engine offchain worker",
+				"submit unsigned cross-collateral liquidation tx for \nAccountId {:?} \nfailed!",
+				who,
+			);
+		}
+	}
+
 	fn submit_unsigned_settlement_tx(currency_id: CurrencyId, who: T::AccountId) {
 		let who = T::Lookup::unlookup(who);
 		let call = Call::<T>::settle {
@@ -676,7 +1100,7 @@ impl<T: Config> Pallet<T> {
 			.unwrap_or(DEFAULT_MAX_ITERATIONS);
 
 		let currency_id = collateral_currency_ids[collateral_position as usize];
-		let is_shutdown = T::EmergencyShutdown::is_shutdown();
+		let is_shutdown = T::EmergencyShutdown::is_collateral_shutdown(currency_id);
 
 		// If start key is Some(value) continue iterating from that point in storage otherwise start
 		// iterating from the beginning of <loans::Positons<T>>
@@ -691,7 +1115,18 @@ impl<T: Config> Pallet<T> {
 
 		#[allow(clippy::while_let_on_iterator)]
 		while let Some((who, Position { collateral, debit })) = map_iterator.next() {
-			if !is_shutdown
+			if !is_shutdown && <LoansOf<T>>::is_cross_collateral(&who) {
+				// the positions in cross-collateral mode are liquidated as a whole, only submit
+				// it under the first collateral type of the positions to be liquidated
+				if matches!(Self::check_cross_collateral_status(&who), CDPStatus::Unsafe)
+					&& Self::get_cross_collateral_liquidation_amounts(&who)
+						.first()
+						.map(|(first_currency_id, _, _)| *first_currency_id)
+						== Some(currency_id)
+				{
+					Self::submit_unsigned_cross_collateral_liquidation_tx(who);
+				}
+			} else if !is_shutdown
 				&& matches!(
 					Self::check_cdp_status(currency_id, collateral, debit),
 					CDPStatus::Unsafe
@@ -759,6 +1194,79 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
+	/// Check the status of the CDP of `who` under `currency_id`, if `who` is in cross-collateral
+	/// mode, the positions of all collateral types are checked as a whole.
+	pub fn check_position_status(who: &T::AccountId, currency_id: CurrencyId) -> CDPStatus {
+		if <LoansOf<T>>::is_cross_collateral(who) {
+			Self::check_cross_collateral_status(who)
+		} else {
+			let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+			Self::check_cdp_status(currency_id, collateral, debit)
+		}
+	}
+
+	/// Check the status of the positions of all collateral types of `who` as a whole.
+	pub fn check_cross_collateral_status(who: &T::AccountId) -> CDPStatus {
+		match Self::calculate_cross_collateral_values(who, None) {
+			Ok((liquidation_collateral_value, _, total_debit_value)) => {
+				if liquidation_collateral_value < total_debit_value {
+					CDPStatus::Unsafe
+				} else {
+					CDPStatus::Safe
+				}
+			}
+			Err(e) => CDPStatus::ChecksFailed(e),
+		}
+	}
+
+	/// Calculate the collateral value of the positions of all collateral types of `who`, which
+	/// is discounted by the liquidation ratio and the required collateral ratio of each collateral
+	/// type as haircut, and the total debit value of them. The position under `currency_id` is
+	/// replaced by `maybe_position` if it's specified.
+	///
+	/// Returns `(liquidation_collateral_value, required_collateral_value, total_debit_value)`.
+	pub fn calculate_cross_collateral_values(
+		who: &T::AccountId,
+		maybe_position: Option<(CurrencyId, Position)>,
+	) -> Result<(Balance, Balance, Balance), DispatchError> {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let mut liquidation_collateral_value: Balance = Zero::zero();
+		let mut required_collateral_value: Balance = Zero::zero();
+		let mut total_debit_value: Balance = Zero::zero();
+
+		for currency_id in T::CollateralCurrencyIds::get() {
+			let Position { collateral, debit } = match maybe_position {
+				Some((position_currency_id, position)) if position_currency_id == currency_id => position,
+				_ => <LoansOf<T>>::positions(currency_id, who),
+			};
+			total_debit_value = total_debit_value.saturating_add(Self::get_debit_value(currency_id, debit));
+
+			if !collateral.is_zero() {
+				let feed_price = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+					.ok_or(Error::<T>::InvalidFeedPrice)?;
+				let collateral_value = feed_price.saturating_mul_int(collateral);
+				let liquidation_ratio = Self::get_liquidation_ratio(currency_id);
+				let required_ratio = Self::required_collateral_ratio(currency_id)
+					.unwrap_or_default()
+					.max(liquidation_ratio);
+				let haircut_value = |ratio: Ratio| {
+					ratio
+						.reciprocal()
+						.map_or_else(Balance::max_value, |r| r.saturating_mul_int(collateral_value))
+				};
+				liquidation_collateral_value =
+					liquidation_collateral_value.saturating_add(haircut_value(liquidation_ratio));
+				required_collateral_value = required_collateral_value.saturating_add(haircut_value(required_ratio));
+			}
+		}
+
+		Ok((
+			liquidation_collateral_value,
+			required_collateral_value,
+			total_debit_value,
+		))
+	}
+
 	pub fn maximum_total_debit_value(currency_id: CurrencyId) -> Balance {
 		Self::collateral_params(currency_id).maximum_total_debit_value
 	}
@@ -768,8 +1276,8 @@ impl<T: Config> Pallet<T> {
 	}
 
 	pub fn get_interest_rate_per_sec(currency_id: CurrencyId) -> Rate {
-		Self::collateral_params(currency_id)
-			.interest_rate_per_sec
+		Self::model_interest_rate_per_sec(currency_id)
+			.or_else(|| Self::collateral_params(currency_id).interest_rate_per_sec)
 			.unwrap_or_default()
 			.saturating_add(Self::global_interest_rate_per_sec())
 	}
@@ -813,6 +1321,66 @@ impl<T: Config> Pallet<T> {
 		Ratio::checked_from_rational(locked_collateral_value, debit_value).unwrap_or_else(Ratio::max_value)
 	}
 
+	/// Get the risk data of the CDP of `who` under `currency_id`. Returns
+	/// `None` if `currency_id` isn't a collateral type.
+	pub fn get_position_risk(who: &T::AccountId, currency_id: CurrencyId) -> Option<RiskInfo> {
+		if !T::CollateralCurrencyIds::get().contains(&currency_id) {
+			return None;
+		}
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+		let mut risk_info = Self::calculate_risk_info(currency_id, collateral, debit);
+		if <LoansOf<T>>::is_cross_collateral(who) {
+			// the position is liquidated with the positions of other collateral types as a whole
+			risk_info.status = Self::check_cross_collateral_status(who);
+		}
+		Some(risk_info)
+	}
+
+	/// Get the risk data of the total position of `currency_id`. Returns
+	/// `None` if `currency_id` isn't a collateral type.
+	pub fn get_collateral_risk(currency_id: CurrencyId) -> Option<RiskInfo> {
+		if !T::CollateralCurrencyIds::get().contains(&currency_id) {
+			return None;
+		}
+		let Position { collateral, debit } = <LoansOf<T>>::total_positions(currency_id);
+		Some(Self::calculate_risk_info(currency_id, collateral, debit))
+	}
+
+	fn calculate_risk_info(currency_id: CurrencyId, collateral: Balance, debit: Balance) -> RiskInfo {
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let liquidation_ratio = Self::get_liquidation_ratio(currency_id);
+		let feed_price = T::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get());
+
+		let liquidation_price =
+			Price::checked_from_rational(debit_value, collateral).map(|price| price.saturating_mul(liquidation_ratio));
+		let accrued_interest = debit_value.saturating_sub(T::DefaultDebitExchangeRate::get().saturating_mul_int(debit));
+
+		let max_additional_debit_value = feed_price.map_or(Zero::zero(), |price| {
+			let max_debit_value = Self::required_collateral_ratio(currency_id)
+				.unwrap_or(liquidation_ratio)
+				.reciprocal()
+				.map_or(Balance::max_value(), |ratio| {
+					ratio.saturating_mul_int(price.saturating_mul_int(collateral))
+				});
+			let total_debit_value =
+				Self::get_debit_value(currency_id, <LoansOf<T>>::total_positions(currency_id).debit);
+			let hard_cap_room = Self::maximum_total_debit_value(currency_id).saturating_sub(total_debit_value);
+			max_debit_value.saturating_sub(debit_value).min(hard_cap_room)
+		});
+
+		RiskInfo {
+			collateral,
+			debit,
+			debit_value,
+			collateral_ratio: feed_price
+				.map(|price| Self::calculate_collateral_ratio(currency_id, collateral, debit, price)),
+			liquidation_price,
+			accrued_interest,
+			max_additional_debit_value,
+			status: Self::check_cdp_status(currency_id, collateral, debit),
+		}
+	}
+
 	pub fn adjust_position(
 		who: &T::AccountId,
 		currency_id: CurrencyId,
@@ -842,6 +1410,7 @@ impl<T: Config> Pallet<T> {
 
 		// confiscate collateral and all debit
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, confiscate_collateral_amount, debit)?;
+		T::EmergencyShutdown::on_collateral_settled(currency_id, confiscate_collateral_amount);
 
 		Self::deposit_event(Event::SettleCDPInDebit {
 			collateral_type: currency_id,
@@ -860,13 +1429,18 @@ impl<T: Config> Pallet<T> {
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
 		ensure!(
-			matches!(Self::check_cdp_status(currency_id, collateral, debit), CDPStatus::Safe),
+			matches!(Self::check_position_status(&who, currency_id), CDPStatus::Safe),
 			Error::<T>::MustBeSafe
 		);
 
 		// confiscate all collateral and debit of unsafe cdp to cdp treasury
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
 
+		// the remain positions must be still safe if regarded as a whole
+		if <LoansOf<T>>::is_cross_collateral(&who) {
+			Self::check_cross_collateral_position_valid(&who, currency_id, Zero::zero(), Zero::zero(), false)?;
+		}
+
 		// swap exact stable with DEX in limit of price impact
 		let debit_value = Self::get_debit_value(currency_id, debit);
 		let collateral_supply = collateral.min(max_collateral_amount);
@@ -893,8 +1467,156 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Issue `increase_debit_value` of stable currency, swap it to collateral
+	/// with DEX and add them to the CDP of `who`.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn expand_position_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		increase_debit_value: Balance,
+		min_increase_collateral: Balance,
+	) -> DispatchResult {
+		ensure!(
+			T::CollateralCurrencyIds::get().contains(&currency_id),
+			Error::<T>::InvalidCollateralType,
+		);
+		let loans_module_account = <LoansOf<T>>::account_id();
+		let stable_currency_id = T::GetStableCurrencyId::get();
+
+		// issue stable currency with collateral backed to the loans module account in advance
+		<T as Config>::CDPTreasury::issue_debit(&loans_module_account, increase_debit_value, true)?;
+
+		// swap stable currency to collateral, the collateral is kept by the loans module account
+		let limit = SwapLimit::ExactSupply(increase_debit_value, min_increase_collateral);
+		let (_, increase_collateral) =
+			Self::swap_with_best_path(&loans_module_account, stable_currency_id, currency_id, limit)?;
+
+		// update CDP state
+		let increase_debit_balance = Self::convert_to_debit_balance(currency_id, increase_debit_value);
+		<LoansOf<T>>::update_loan(
+			who,
+			currency_id,
+			<LoansOf<T>>::amount_try_from_balance(increase_collateral)?,
+			<LoansOf<T>>::amount_try_from_balance(increase_debit_balance)?,
+		)?;
+
+		// check the debit cap and the risk of the CDP
+		Self::check_debit_cap(currency_id, <LoansOf<T>>::total_positions(currency_id).debit)?;
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+		Self::check_position_valid(who, currency_id, collateral, debit, true)?;
+
+		Self::deposit_event(Event::ExpandPositionCollateral {
+			collateral_type: currency_id,
+			owner: who.clone(),
+			increased_debit_value: increase_debit_value,
+			increased_collateral_amount: increase_collateral,
+		});
+		Ok(())
+	}
+
+	/// Swap `decrease_collateral` of collateral in the CDP of `who` to stable
+	/// currency with DEX, and use it to repay the debit of the CDP. The stable
+	/// currency exceeding the debit is refunded to `who`.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn shrink_position_debit(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		decrease_collateral: Balance,
+		min_decrease_debit_value: Balance,
+	) -> DispatchResult {
+		ensure!(
+			T::CollateralCurrencyIds::get().contains(&currency_id),
+			Error::<T>::InvalidCollateralType,
+		);
+		let loans_module_account = <LoansOf<T>>::account_id();
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let Position { debit, .. } = <LoansOf<T>>::positions(currency_id, who);
+		ensure!(!debit.is_zero(), Error::<T>::NoDebitValue);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+
+		// swap collateral kept by the loans module account to stable currency
+		let limit = SwapLimit::ExactSupply(decrease_collateral, min_decrease_debit_value);
+		let (_, stable_amount) =
+			Self::swap_with_best_path(&loans_module_account, currency_id, stable_currency_id, limit)?;
+
+		let (decrease_debit_value, decrease_debit_balance) = if stable_amount >= debit_value {
+			// refund the stable currency exceeding the debit to the CDP owner
+			<T as loans::Config>::Currency::transfer(
+				stable_currency_id,
+				&loans_module_account,
+				who,
+				stable_amount.saturating_sub(debit_value),
+			)?;
+			(debit_value, debit)
+		} else {
+			(
+				stable_amount,
+				Self::convert_to_debit_balance(currency_id, stable_amount),
+			)
+		};
+
+		// repay debit
+		<T as Config>::CDPTreasury::burn_debit(&loans_module_account, decrease_debit_value)?;
+
+		// update CDP state
+		<LoansOf<T>>::update_loan(
+			who,
+			currency_id,
+			<LoansOf<T>>::amount_try_from_balance(decrease_collateral)?.saturating_neg(),
+			<LoansOf<T>>::amount_try_from_balance(decrease_debit_balance)?.saturating_neg(),
+		)?;
+
+		// check the risk of the CDP
+		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+		Self::check_position_valid(who, currency_id, collateral, debit, false)?;
+
+		Self::deposit_event(Event::ShrinkPositionDebit {
+			collateral_type: currency_id,
+			owner: who.clone(),
+			decreased_collateral_amount: decrease_collateral,
+			decreased_debit_value: decrease_debit_value,
+		});
+		Ok(())
+	}
+
+	fn swap_with_best_path(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> sp_std::result::Result<(Balance, Balance), DispatchError> {
+		let swap_path = T::DEX::get_best_price_swap_path(
+			supply_currency_id,
+			target_currency_id,
+			limit,
+			T::AlternativeSwapPathJointList::get(),
+		)
+		.ok_or(Error::<T>::CannotSwap)?;
+		T::DEX::swap_with_specific_path(who, &swap_path, limit)
+	}
+
+	/// Convert debit value to debit balance at the current debit exchange rate
+	pub fn convert_to_debit_balance(currency_id: CurrencyId, debit_value: Balance) -> Balance {
+		Self::get_debit_exchange_rate(currency_id)
+			.reciprocal()
+			.unwrap_or_else(ExchangeRate::zero)
+			.saturating_mul_int(debit_value)
+	}
+
 	// liquidate unsafe cdp
 	pub fn liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<Weight, DispatchError> {
+		Self::do_liquidate_unsafe_cdp(who, currency_id).map(|(consumed_weight, _)| consumed_weight)
+	}
+
+	/// Liquidate unsafe cdp, returns the consumed weight and the liquidation penalty which has
+	/// been recovered by DEX. The penalty recovered by collateral auctions is unknown until they
+	/// are dealt, so it's not included.
+	fn do_liquidate_unsafe_cdp(who: T::AccountId, currency_id: CurrencyId) -> Result<(Weight, Balance), DispatchError> {
+		ensure!(!<LoansOf<T>>::is_cross_collateral(&who), Error::<T>::IsCrossCollateral);
 		let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, &who);
 
 		// ensure the cdp is unsafe
@@ -906,55 +1628,16 @@ impl<T: Config> Pallet<T> {
 			Error::<T>::MustBeUnsafe
 		);
 
-		// confiscate all collateral and debit of unsafe cdp to cdp treasury
+		// confiscate collateral and debit to be liquidated of unsafe cdp to cdp treasury
+		let (collateral, debit) =
+			Self::get_partial_liquidation_amounts(currency_id, collateral, debit).unwrap_or((collateral, debit));
 		<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
 
 		let bad_debt_value = Self::get_debit_value(currency_id, debit);
 		let target_stable_amount = Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value);
-		let liquidation_strategy = (|| -> Result<LiquidationStrategy, DispatchError> {
-			// calculate the supply limit by slippage limit for the price of oracle,
-			let max_supply_limit = Ratio::one()
-				.saturating_sub(T::MaxSwapSlippageCompareToOracle::get())
-				.reciprocal()
-				.unwrap_or_else(Ratio::max_value)
-				.saturating_mul_int(
-					T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
-						.expect("the oracle price should be avalible because liquidation are triggered by it.")
-						.saturating_mul_int(target_stable_amount),
-				);
-			let collateral_supply = collateral.min(max_supply_limit);
-
-			// try swap collateral to stable to settle debit swap succeed.
-			if let Ok((actual_supply_collateral, _)) = <T as Config>::CDPTreasury::swap_collateral_to_stable(
-				currency_id,
-				SwapLimit::ExactTarget(collateral_supply, target_stable_amount),
-				false,
-			) {
-				let refund_collateral_amount = collateral
-					.checked_sub(actual_supply_collateral)
-					.expect("swap succecced means collateral >= actual_supply_collateral; qed");
-
-				// refund remain collateral to CDP owner
-				if !refund_collateral_amount.is_zero() {
-					<T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_collateral_amount)?;
-				}
-
-				return Ok(LiquidationStrategy::Exchange);
-			}
-
-			// if cannot liquidate by swap, create collateral auctions by cdp treasury
-			let created_auctions = <T as Config>::CDPTreasury::create_collateral_auctions(
-				currency_id,
-				collateral,
-				target_stable_amount,
-				who.clone(),
-				true,
-			)?;
-
-			Ok(LiquidationStrategy::Auction {
-				auction_count: created_auctions,
-			})
-		})()?;
+		let liquidation_strategy = Self::liquidate_collateral(&who, currency_id, collateral, target_stable_amount)?;
+		let recovered_penalty =
+			Self::get_recovered_penalty(&liquidation_strategy, target_stable_amount, bad_debt_value);
 
 		Self::deposit_event(Event::LiquidateUnsafeCDP {
 			collateral_type: currency_id,
@@ -963,10 +1646,375 @@ impl<T: Config> Pallet<T> {
 			bad_debt_value,
 			liquidation_strategy: liquidation_strategy.clone(),
 		});
+		Ok((Self::liquidation_weight(&liquidation_strategy), recovered_penalty))
+	}
+
+	/// Liquidate the positions of all collateral types of `who` in cross-collateral mode as a
+	/// whole. The collaterals and debits returned by `get_cross_collateral_liquidation_amounts`
+	/// are confiscated, and the stable amount to be recovered, which is the debit value with
+	/// liquidation penalty, is allocated to each collateral type in proportion to its collateral
+	/// value.
+	pub fn liquidate_unsafe_cross_collateral_cdp(who: T::AccountId) -> Result<Weight, DispatchError> {
+		Self::do_liquidate_unsafe_cross_collateral_cdp(who).map(|(consumed_weight, _)| consumed_weight)
+	}
+
+	/// Liquidate the positions in cross-collateral mode like
+	/// `liquidate_unsafe_cross_collateral_cdp`, returns the consumed weight and the liquidation
+	/// penalty which has been recovered by DEX.
+	fn do_liquidate_unsafe_cross_collateral_cdp(who: T::AccountId) -> Result<(Weight, Balance), DispatchError> {
+		ensure!(<LoansOf<T>>::is_cross_collateral(&who), Error::<T>::NotCrossCollateral);
+		// ensure the cdp is unsafe
+		ensure!(
+			matches!(Self::check_cross_collateral_status(&who), CDPStatus::Unsafe),
+			Error::<T>::MustBeUnsafe
+		);
+
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let mut collaterals: Vec<(CurrencyId, Balance, Balance)> = Vec::new();
+		let mut total_collateral_value: Balance = Zero::zero();
+		let mut total_bad_debt_value: Balance = Zero::zero();
+		let mut total_target_stable_amount: Balance = Zero::zero();
+
+		// confiscate collaterals and debits to be liquidated to cdp treasury
+		for (currency_id, collateral, debit) in Self::get_cross_collateral_liquidation_amounts(&who) {
+			<LoansOf<T>>::confiscate_collateral_and_debit(&who, currency_id, collateral, debit)?;
+
+			let bad_debt_value = Self::get_debit_value(currency_id, debit);
+			total_bad_debt_value = total_bad_debt_value.saturating_add(bad_debt_value);
+			total_target_stable_amount = total_target_stable_amount
+				.saturating_add(Self::get_liquidation_penalty(currency_id).saturating_mul_acc_int(bad_debt_value));
+
+			if !collateral.is_zero() {
+				let collateral_value = T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+					.expect("the oracle price should be avalible because liquidation are triggered by it.")
+					.saturating_mul_int(collateral);
+				total_collateral_value = total_collateral_value.saturating_add(collateral_value);
+				collaterals.push((currency_id, collateral, collateral_value));
+			}
+		}
+
+		let mut consumed_weight: Weight = Zero::zero();
+		let mut total_recovered_penalty: Balance = Zero::zero();
+		for (currency_id, collateral, collateral_value) in collaterals {
+			let share = Ratio::checked_from_rational(collateral_value, total_collateral_value).unwrap_or_default();
+			let target_stable_amount = share.saturating_mul_int(total_target_stable_amount);
+			let bad_debt_value = share.saturating_mul_int(total_bad_debt_value);
+			let liquidation_strategy = Self::liquidate_collateral(&who, currency_id, collateral, target_stable_amount)?;
+			consumed_weight = consumed_weight.saturating_add(Self::liquidation_weight(&liquidation_strategy));
+			total_recovered_penalty = total_recovered_penalty.saturating_add(Self::get_recovered_penalty(
+				&liquidation_strategy,
+				target_stable_amount,
+				bad_debt_value,
+			));
+
+			Self::deposit_event(Event::LiquidateUnsafeCDP {
+				collateral_type: currency_id,
+				owner: who.clone(),
+				collateral_amount: collateral,
+				bad_debt_value,
+				liquidation_strategy,
+			});
+		}
+
+		Ok((consumed_weight, total_recovered_penalty))
+	}
+
+	/// Get the liquidation penalty recovered by the liquidation strategy. The DEX swap recovers
+	/// exactly the target stable amount, which is the bad debt value plus liquidation penalty.
+	fn get_recovered_penalty(
+		liquidation_strategy: &LiquidationStrategy,
+		target_stable_amount: Balance,
+		bad_debt_value: Balance,
+	) -> Balance {
+		match liquidation_strategy {
+			LiquidationStrategy::Exchange => target_stable_amount.saturating_sub(bad_debt_value),
+			LiquidationStrategy::Auction { .. } => Zero::zero(),
+		}
+	}
+
+	/// Sell the confiscated `collateral` of `who` to recover `target_stable_amount` of stable
+	/// currency, by DEX if possible, otherwise by collateral auctions.
+	fn liquidate_collateral(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral: Balance,
+		target_stable_amount: Balance,
+	) -> Result<LiquidationStrategy, DispatchError> {
+		// calculate the supply limit by slippage limit for the price of oracle,
+		let max_supply_limit = Ratio::one()
+			.saturating_sub(T::MaxSwapSlippageCompareToOracle::get())
+			.reciprocal()
+			.unwrap_or_else(Ratio::max_value)
+			.saturating_mul_int(
+				T::PriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
+					.expect("the oracle price should be avalible because liquidation are triggered by it.")
+					.saturating_mul_int(target_stable_amount),
+			);
+		let collateral_supply = collateral.min(max_supply_limit);
+
+		// try swap collateral to stable to settle debit swap succeed.
+		if let Ok((actual_supply_collateral, _)) = <T as Config>::CDPTreasury::swap_collateral_to_stable(
+			currency_id,
+			SwapLimit::ExactTarget(collateral_supply, target_stable_amount),
+			false,
+		) {
+			let refund_collateral_amount = collateral
+				.checked_sub(actual_supply_collateral)
+				.expect("swap succecced means collateral >= actual_supply_collateral; qed");
+
+			// refund remain collateral to CDP owner
+			if !refund_collateral_amount.is_zero() {
+				<T as Config>::CDPTreasury::withdraw_collateral(who, currency_id, refund_collateral_amount)?;
+			}
+
+			return Ok(LiquidationStrategy::Exchange);
+		}
+
+		// if cannot liquidate by swap, create collateral auctions by cdp treasury
+		let created_auctions = <T as Config>::CDPTreasury::create_collateral_auctions(
+			currency_id,
+			collateral,
+			target_stable_amount,
+			who.clone(),
+			true,
+		)?;
+
+		Ok(LiquidationStrategy::Auction {
+			auction_count: created_auctions,
+		})
+	}
+
+	fn liquidation_weight(liquidation_strategy: &LiquidationStrategy) -> Weight {
 		match liquidation_strategy {
-			LiquidationStrategy::Auction { auction_count } => Ok(T::WeightInfo::liquidate_by_auction(auction_count)),
-			LiquidationStrategy::Exchange => Ok(T::WeightInfo::liquidate_by_dex()),
+			LiquidationStrategy::Auction { auction_count } => T::WeightInfo::liquidate_by_auction(*auction_count),
+			LiquidationStrategy::Exchange => T::WeightInfo::liquidate_by_dex(),
+		}
+	}
+
+	/// Liquidate unsafe cdp by keeper, and reward the keeper with
+	/// `KeeperRewardRatio` of the liquidation penalty recovered. The reward is
+	/// paid from the surplus collected by the DEX swap of the liquidation, so
+	/// the keeper isn't rewarded if the collateral is sold by auctions.
+	pub fn keeper_liquidate_unsafe_cdp(
+		keeper: &T::AccountId,
+		who: T::AccountId,
+		currency_id: CurrencyId,
+	) -> Result<Weight, DispatchError> {
+		ensure!(*keeper != who, Error::<T>::KeeperIsOwner);
+		let (consumed_weight, recovered_penalty) = Self::do_liquidate_unsafe_cdp(who.clone(), currency_id)?;
+
+		let reward_amount = Self::keeper_reward_ratio().saturating_mul_int(recovered_penalty);
+		if !reward_amount.is_zero() {
+			<T as Config>::CDPTreasury::withdraw_surplus(keeper, reward_amount)?;
+			Self::deposit_event(Event::KeeperRewarded {
+				keeper: keeper.clone(),
+				collateral_type: currency_id,
+				owner: who,
+				reward_amount,
+			});
 		}
+
+		Ok(consumed_weight.saturating_add(<T as Config>::WeightInfo::keeper_reward()))
+	}
+
+	/// Liquidate the unsafe positions in cross-collateral mode by keeper, and reward the keeper
+	/// like `keeper_liquidate_unsafe_cdp`.
+	pub fn keeper_liquidate_unsafe_cross_collateral_cdp(
+		keeper: &T::AccountId,
+		who: T::AccountId,
+	) -> Result<Weight, DispatchError> {
+		ensure!(*keeper != who, Error::<T>::KeeperIsOwner);
+		let (consumed_weight, recovered_penalty) = Self::do_liquidate_unsafe_cross_collateral_cdp(who.clone())?;
+
+		let reward_amount = Self::keeper_reward_ratio().saturating_mul_int(recovered_penalty);
+		if !reward_amount.is_zero() {
+			<T as Config>::CDPTreasury::withdraw_surplus(keeper, reward_amount)?;
+			Self::deposit_event(Event::CrossCollateralKeeperRewarded {
+				keeper: keeper.clone(),
+				owner: who,
+				reward_amount,
+			});
+		}
+
+		Ok(consumed_weight.saturating_add(<T as Config>::WeightInfo::keeper_reward()))
+	}
+
+	/// Get the collateral and debit amounts of each collateral type to be liquidated for the
+	/// positions of `who` in cross-collateral mode. The collateral types which have been shutdown
+	/// are skipped, their positions are settled instead. The debit of each collateral type is
+	/// liquidated partially by its close factor, and the collateral worth of the liquidated debit
+	/// value plus liquidation penalty is confiscated from all collateral types in proportion.
+	///
+	/// All positions are liquidated if no close factor applies, the collateral value is not
+	/// enough, or the remain debit value would be below the dust amount.
+	pub fn get_cross_collateral_liquidation_amounts(who: &T::AccountId) -> Vec<(CurrencyId, Balance, Balance)> {
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let positions: Vec<(CurrencyId, Balance, Balance)> = T::CollateralCurrencyIds::get()
+			.into_iter()
+			.filter(|currency_id| !T::EmergencyShutdown::is_collateral_shutdown(*currency_id))
+			.filter_map(|currency_id| {
+				let Position { collateral, debit } = <LoansOf<T>>::positions(currency_id, who);
+				if collateral.is_zero() && debit.is_zero() {
+					None
+				} else {
+					Some((currency_id, collateral, debit))
+				}
+			})
+			.collect();
+
+		let mut is_partial = false;
+		let mut total_target_stable_amount: Balance = Zero::zero();
+		let mut total_collateral_value: Balance = Zero::zero();
+		let mut liquidate_debits: Vec<Balance> = Vec::with_capacity(positions.len());
+		for (currency_id, collateral, debit) in positions.iter().copied() {
+			let liquidate_debit = Self::liquidation_close_factors(currency_id)
+				.map_or(debit, |close_factor| close_factor.saturating_mul_int(debit));
+			if liquidate_debit < debit {
+				let remain_debit_value = Self::get_debit_value(currency_id, debit.saturating_sub(liquidate_debit));
+				if remain_debit_value < T::MinimumDebitValue::get() {
+					return positions;
+				}
+				is_partial = true;
+			}
+
+			total_target_stable_amount = total_target_stable_amount.saturating_add(
+				Self::get_liquidation_penalty(currency_id)
+					.saturating_mul_acc_int(Self::get_debit_value(currency_id, liquidate_debit)),
+			);
+			total_collateral_value = total_collateral_value.saturating_add(
+				T::PriceSource::get_relative_price(currency_id, stable_currency_id)
+					.map_or(Zero::zero(), |price| price.saturating_mul_int(collateral)),
+			);
+			liquidate_debits.push(liquidate_debit);
+		}
+
+		match Ratio::checked_from_rational(total_target_stable_amount, total_collateral_value) {
+			Some(liquidate_ratio) if is_partial && liquidate_ratio < Ratio::one() => positions
+				.into_iter()
+				.zip(liquidate_debits)
+				.map(|((currency_id, collateral, _), liquidate_debit)| {
+					(
+						currency_id,
+						liquidate_ratio.saturating_mul_int(collateral),
+						liquidate_debit,
+					)
+				})
+				.collect(),
+			_ => positions,
+		}
+	}
+
+	/// Get the collateral and debit amounts to be liquidated if the unsafe CDP can be
+	/// liquidated partially. Only the debit value required to restore the collateral ratio to
+	/// the target ratio is liquidated, always capped by the close factor, and the collateral
+	/// worth of it plus liquidation penalty is confiscated. The target ratio is the greater of
+	/// the required collateral ratio and the liquidation ratio, if it cannot be reached by
+	/// partial liquidation the debit value capped by the close factor is liquidated.
+	///
+	/// Returns `None` if the whole CDP should be liquidated, that is the close factor isn't
+	/// set, the remain debit value would be below the dust amount, or the collateral cannot
+	/// cover the liquidated debit value plus liquidation penalty.
+	pub fn get_partial_liquidation_amounts(
+		currency_id: CurrencyId,
+		collateral: Balance,
+		debit: Balance,
+	) -> Option<(Balance, Balance)> {
+		let close_factor = Self::liquidation_close_factors(currency_id)?;
+		let stable_currency_id = T::GetStableCurrencyId::get();
+		let collateral_price = T::PriceSource::get_relative_price(currency_id, stable_currency_id)?;
+		let stable_price = T::PriceSource::get_relative_price(stable_currency_id, currency_id)?;
+
+		let target_ratio = Self::required_collateral_ratio(currency_id)
+			.unwrap_or_default()
+			.max(Self::get_liquidation_ratio(currency_id));
+		let penalty_factor = Self::get_liquidation_penalty(currency_id).saturating_add(Rate::one());
+
+		let collateral_value = collateral_price.saturating_mul_int(collateral);
+		let debit_value = Self::get_debit_value(currency_id, debit);
+		let max_liquidate_value = close_factor.saturating_mul_int(debit_value);
+
+		// liquidating debit value `x` confiscates collateral value `x * penalty_factor`, so the
+		// collateral ratio after liquidation is `(collateral_value - x * penalty_factor) /
+		// (debit_value - x)`, which can reach the target ratio only if `target_ratio >
+		// penalty_factor`.
+		let liquidate_value = if target_ratio > penalty_factor {
+			target_ratio
+				.saturating_sub(penalty_factor)
+				.reciprocal()?
+				.saturating_mul_int(
+					target_ratio
+						.saturating_mul_int(debit_value)
+						.saturating_sub(collateral_value),
+				)
+				// round up to ensure the collateral ratio reaches the target ratio
+				.saturating_add(One::one())
+				.min(max_liquidate_value)
+		} else {
+			max_liquidate_value
+		};
+		let liquidate_debit = Self::get_debit_exchange_rate(currency_id)
+			.reciprocal()?
+			.saturating_mul_int(liquidate_value)
+			.min(debit);
+		if liquidate_debit.is_zero() {
+			return None;
+		}
+
+		let remain_debit_value = Self::get_debit_value(currency_id, debit.saturating_sub(liquidate_debit));
+		if remain_debit_value < T::MinimumDebitValue::get() {
+			return None;
+		}
+
+		let liquidate_collateral = stable_price
+			.saturating_mul_int(penalty_factor.saturating_mul_int(Self::get_debit_value(currency_id, liquidate_debit)));
+		if liquidate_collateral >= collateral {
+			return None;
+		}
+
+		Some((liquidate_collateral, liquidate_debit))
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Check the positions of all collateral types of `who` as a whole, with the position under
+	/// `currency_id` replaced by the specific collateral and debit balance.
+	fn check_cross_collateral_position_valid(
+		who: &T::AccountId,
+		currency_id: CurrencyId,
+		collateral_balance: Balance,
+		debit_balance: Balance,
+		check_required_ratio: bool,
+	) -> DispatchResult {
+		// check the minimum_debit_value of the position itself
+		if !debit_balance.is_zero() {
+			ensure!(
+				Self::get_debit_value(currency_id, debit_balance) >= T::MinimumDebitValue::get(),
+				Error::<T>::RemainDebitValueTooSmall,
+			);
+		}
+
+		let position = Position {
+			collateral: collateral_balance,
+			debit: debit_balance,
+		};
+		let (liquidation_collateral_value, required_collateral_value, total_debit_value) =
+			Self::calculate_cross_collateral_values(who, Some((currency_id, position)))?;
+
+		// check the required collateral ratio
+		if check_required_ratio {
+			ensure!(
+				required_collateral_value >= total_debit_value,
+				Error::<T>::BelowRequiredCollateralRatio
+			);
+		}
+
+		// check the liquidation ratio
+		ensure!(
+			liquidation_collateral_value >= total_debit_value,
+			Error::<T>::BelowLiquidationRatio
+		);
+
+		Ok(())
 	}
 }
 
@@ -976,11 +2024,22 @@ impl<T: Config> RiskManager<T::AccountId, CurrencyId, Balance, Balance> for Pall
 	}
 
 	fn check_position_valid(
+		who: &T::AccountId,
 		currency_id: CurrencyId,
 		collateral_balance: Balance,
 		debit_balance: Balance,
 		check_required_ratio: bool,
 	) -> DispatchResult {
+		if <LoansOf<T>>::is_cross_collateral(who) {
+			return Self::check_cross_collateral_position_valid(
+				who,
+				currency_id,
+				collateral_balance,
+				debit_balance,
+				check_required_ratio,
+			);
+		}
+
 		if !debit_balance.is_zero() {
 			let debit_value = Self::get_debit_value(currency_id, debit_balance);
 			let feed_price = <T as Config>::PriceSource::get_relative_price(currency_id, T::GetStableCurrencyId::get())
@@ -185,6 +185,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_surplus_auction(_amount: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, _fix_debit: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		Ok(())
 	}
@@ -193,6 +201,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		Default::default()
 	}
@@ -219,6 +235,7 @@ impl cdp_treasury::Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -260,17 +277,26 @@ impl pallet_timestamp::Config for Runtime {
 
 thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
+	static SHUTDOWN_COLLATERALS: RefCell<Vec<CurrencyId>> = RefCell::new(vec![]);
 }
 
 pub fn mock_shutdown() {
 	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
 }
 
+pub fn mock_collateral_shutdown(currency_id: CurrencyId) {
+	SHUTDOWN_COLLATERALS.with(|v| v.borrow_mut().push(currency_id))
+}
+
 pub struct MockEmergencyShutdown;
 impl EmergencyShutdown for MockEmergencyShutdown {
 	fn is_shutdown() -> bool {
 		IS_SHUTDOWN.with(|v| *v.borrow_mut())
 	}
+
+	fn is_collateral_shutdown(currency_id: CurrencyId) -> bool {
+		Self::is_shutdown() || SHUTDOWN_COLLATERALS.with(|v| v.borrow().contains(&currency_id))
+	}
 }
 
 ord_parameter_types! {
@@ -290,6 +316,8 @@ parameter_types! {
 impl Config for Runtime {
 	type Event = Event;
 	type PriceSource = MockPriceSource;
+	type DEX = DEXModule;
+	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
@@ -253,6 +253,66 @@ fn set_collateral_params_work() {
 	});
 }
 
+#[test]
+fn set_liquidation_close_factor_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_close_factor(
+				Origin::signed(5),
+				BTC,
+				Some(Ratio::saturating_from_rational(1, 2))
+			),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_close_factor(
+				Origin::signed(1),
+				LDOT,
+				Some(Ratio::saturating_from_rational(1, 2))
+			),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_close_factor(Origin::signed(1), BTC, Some(Ratio::zero())),
+			Error::<Runtime>::InvalidCloseFactor
+		);
+		assert_noop!(
+			CDPEngineModule::set_liquidation_close_factor(
+				Origin::signed(1),
+				BTC,
+				Some(Ratio::saturating_from_rational(3, 2))
+			),
+			Error::<Runtime>::InvalidCloseFactor
+		);
+
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidationCloseFactorUpdated {
+			collateral_type: BTC,
+			new_liquidation_close_factor: Some(Ratio::saturating_from_rational(1, 2)),
+		}));
+		assert_eq!(
+			CDPEngineModule::liquidation_close_factors(BTC),
+			Some(Ratio::saturating_from_rational(1, 2))
+		);
+
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
+			BTC,
+			None
+		));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidationCloseFactorUpdated {
+			collateral_type: BTC,
+			new_liquidation_close_factor: None,
+		}));
+		assert_eq!(CDPEngineModule::liquidation_close_factors(BTC), None);
+	});
+}
+
 #[test]
 fn calculate_collateral_ratio_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -272,6 +332,71 @@ fn calculate_collateral_ratio_work() {
 	});
 }
 
+#[test]
+fn get_position_risk_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_eq!(CDPEngineModule::get_position_risk(&ALICE, ACA), None);
+		assert_eq!(CDPEngineModule::get_collateral_risk(ACA), None);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		let risk_info = RiskInfo {
+			collateral: 100,
+			debit: 500,
+			debit_value: 50,
+			collateral_ratio: Some(Ratio::saturating_from_rational(2, 1)),
+			liquidation_price: Some(Price::saturating_from_rational(3, 4)),
+			accrued_interest: 0,
+			max_additional_debit_value: 5,
+			status: CDPStatus::Safe,
+		};
+		assert_eq!(CDPEngineModule::get_position_risk(&ALICE, BTC), Some(risk_info.clone()));
+		assert_eq!(CDPEngineModule::get_collateral_risk(BTC), Some(risk_info));
+
+		DebitExchangeRate::<Runtime>::insert(BTC, ExchangeRate::saturating_from_rational(1, 5));
+		assert_eq!(
+			CDPEngineModule::get_position_risk(&ALICE, BTC),
+			Some(RiskInfo {
+				collateral: 100,
+				debit: 500,
+				debit_value: 100,
+				collateral_ratio: Some(Ratio::one()),
+				liquidation_price: Some(Price::saturating_from_rational(3, 2)),
+				accrued_interest: 50,
+				max_additional_debit_value: 0,
+				status: CDPStatus::Unsafe,
+			})
+		);
+
+		MockPriceSource::set_relative_price(None);
+		assert_eq!(
+			CDPEngineModule::get_position_risk(&ALICE, BTC),
+			Some(RiskInfo {
+				collateral: 100,
+				debit: 500,
+				debit_value: 100,
+				collateral_ratio: None,
+				liquidation_price: Some(Price::saturating_from_rational(3, 2)),
+				accrued_interest: 50,
+				max_additional_debit_value: 0,
+				status: CDPStatus::ChecksFailed(Error::<Runtime>::InvalidFeedPrice.into()),
+			})
+		);
+		assert_eq!(
+			CDPEngineModule::get_position_risk(&BOB, BTC).map(|risk_info| risk_info.liquidation_price),
+			Some(None)
+		);
+	});
+}
+
 #[test]
 fn check_debit_cap_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -307,12 +432,12 @@ fn check_position_valid_failed_when_invalid_feed_price() {
 
 		MockPriceSource::set_relative_price(None);
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 100, 500, true),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 100, 500, true),
 			Error::<Runtime>::InvalidFeedPrice
 		);
 
 		MockPriceSource::set_relative_price(Some(Price::one()));
-		assert_ok!(CDPEngineModule::check_position_valid(BTC, 100, 500, true));
+		assert_ok!(CDPEngineModule::check_position_valid(&ALICE, BTC, 100, 500, true));
 	});
 }
 
@@ -329,7 +454,7 @@ fn check_position_valid_failed_when_remain_debit_value_too_small() {
 			Change::NewValue(10000),
 		));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 2, 10, true),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 2, 10, true),
 			Error::<Runtime>::RemainDebitValueTooSmall,
 		);
 	});
@@ -348,7 +473,7 @@ fn check_position_valid_ratio_below_liquidate_ratio() {
 			Change::NewValue(10000),
 		));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 91, 500, true),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 91, 500, true),
 			Error::<Runtime>::BelowLiquidationRatio,
 		);
 	});
@@ -366,9 +491,9 @@ fn check_position_valid_ratio_below_required_ratio() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_ok!(CDPEngineModule::check_position_valid(BTC, 89, 500, false));
+		assert_ok!(CDPEngineModule::check_position_valid(&ALICE, BTC, 89, 500, false));
 		assert_noop!(
-			CDPEngineModule::check_position_valid(BTC, 89, 500, true),
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 89, 500, true),
 			Error::<Runtime>::BelowRequiredCollateralRatio
 		);
 	});
@@ -480,6 +605,378 @@ fn liquidate_unsafe_cdp_by_collateral_auction() {
 	});
 }
 
+#[test]
+fn cross_collateral_position_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		for currency_id in [BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 0));
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, DOT, 0, 500),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+
+		// the DOT debit is backed by BTC collateral in cross-collateral mode
+		assert_ok!(LoansModule::set_cross_collateral(&ALICE, true));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 0, 500));
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 500);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(CDPEngineModule::check_cross_collateral_status(&ALICE), CDPStatus::Safe);
+
+		// collateral value 100 discounted by required collateral ratio 1.8 is below debit value 56
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, DOT, 0, 60),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_noop!(
+			CDPEngineModule::adjust_position(&ALICE, BTC, -20, 0),
+			Error::<Runtime>::BelowRequiredCollateralRatio
+		);
+		assert_ok!(CDPEngineModule::check_position_valid(&ALICE, BTC, 80, 0, false));
+		assert_noop!(
+			CDPEngineModule::check_position_valid(&ALICE, BTC, 70, 0, false),
+			Error::<Runtime>::BelowLiquidationRatio
+		);
+
+		// the DOT position isn't valid on its own
+		assert_noop!(
+			LoansModule::set_cross_collateral(&ALICE, false),
+			Error::<Runtime>::InvalidFeedPrice
+		);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cross_collateral_cdp_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		for currency_id in [BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(LoansModule::set_cross_collateral(&ALICE, true));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 0));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 0, 500));
+		assert_noop!(
+			CDPEngineModule::liquidate_cross_collateral(Origin::none(), ALICE),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(CDPEngineModule::check_cdp_status(BTC, 100, 0), CDPStatus::Safe);
+		assert_eq!(CDPEngineModule::check_position_status(&ALICE, BTC), CDPStatus::Unsafe);
+		assert_eq!(
+			CDPEngineModule::get_position_risk(&ALICE, BTC).map(|risk_info| risk_info.status),
+			Some(CDPStatus::Unsafe)
+		);
+
+		// the positions in cross-collateral mode can only be liquidated as a whole
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, DOT),
+			Error::<Runtime>::IsCrossCollateral,
+		);
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cross_collateral_cdp(BOB),
+			Error::<Runtime>::NotCrossCollateral,
+		);
+
+		// liquidate all positions of ALICE
+		assert_ok!(CDPEngineModule::liquidate_cross_collateral(Origin::none(), ALICE));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			liquidation_strategy: LiquidationStrategy::Auction { auction_count: 1 },
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE), Position::default());
+		assert_eq!(LoansModule::positions(DOT, ALICE), Position::default());
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cross_collateral_cdp_partially() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		for currency_id in [BTC, DOT] {
+			assert_ok!(CDPEngineModule::set_collateral_params(
+				Origin::signed(1),
+				currency_id,
+				Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+				Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+				Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+				Change::NewValue(10000),
+			));
+		}
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
+			DOT,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_ok!(LoansModule::set_cross_collateral(&ALICE, true));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 0));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, DOT, 0, 500));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		// half of the DOT debit is liquidated, the debit value 25 with penalty is 30
+		assert_eq!(
+			CDPEngineModule::get_cross_collateral_liquidation_amounts(&ALICE),
+			vec![(BTC, 30, 0), (DOT, 0, 250)]
+		);
+
+		assert_ok!(CDPEngineModule::liquidate_cross_collateral(Origin::none(), ALICE));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 30,
+			bad_debt_value: 25,
+			liquidation_strategy: LiquidationStrategy::Auction { auction_count: 1 },
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 25);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 70);
+		assert_eq!(LoansModule::positions(DOT, ALICE).debit, 250);
+
+		// the shutdown collateral type is skipped, and all the remain positions are liquidated
+		// because there's no collateral value to liquidate partially
+		mock_collateral_shutdown(BTC);
+		assert_eq!(
+			CDPEngineModule::get_cross_collateral_liquidation_amounts(&ALICE),
+			vec![(DOT, 0, 250)]
+		);
+	});
+}
+
+#[test]
+fn set_keeper_reward_ratio_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPEngineModule::set_keeper_reward_ratio(Origin::signed(5), Ratio::saturating_from_rational(1, 2)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_keeper_reward_ratio(Origin::signed(1), Ratio::saturating_from_rational(3, 2)),
+			Error::<Runtime>::InvalidKeeperRewardRatio
+		);
+
+		assert_ok!(CDPEngineModule::set_keeper_reward_ratio(
+			Origin::signed(1),
+			Ratio::saturating_from_rational(1, 2)
+		));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::KeeperRewardRatioUpdated {
+			new_keeper_reward_ratio: Ratio::saturating_from_rational(1, 2),
+		}));
+		assert_eq!(
+			CDPEngineModule::keeper_reward_ratio(),
+			Ratio::saturating_from_rational(1, 2)
+		);
+	});
+}
+
+#[test]
+fn set_interest_rate_model_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let model = InterestRateModel {
+			base_rate: Rate::zero(),
+			kink: Ratio::saturating_from_rational(1, 2),
+			multiplier: Rate::saturating_from_rational(1, 100),
+			jump_multiplier: Rate::saturating_from_rational(1, 10),
+			min_rate: Rate::saturating_from_rational(1, 10000),
+			max_rate: Rate::saturating_from_rational(1, 10),
+		};
+		assert_noop!(
+			CDPEngineModule::set_interest_rate_model(Origin::signed(5), BTC, Some(model)),
+			BadOrigin
+		);
+		assert_noop!(
+			CDPEngineModule::set_interest_rate_model(Origin::signed(1), ACA, Some(model)),
+			Error::<Runtime>::InvalidCollateralType
+		);
+		assert_noop!(
+			CDPEngineModule::set_interest_rate_model(
+				Origin::signed(1),
+				BTC,
+				Some(InterestRateModel {
+					min_rate: Rate::one(),
+					..model
+				})
+			),
+			Error::<Runtime>::InvalidInterestRateModel
+		);
+
+		assert_ok!(CDPEngineModule::set_interest_rate_model(
+			Origin::signed(1),
+			BTC,
+			Some(model)
+		));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::InterestRateModelUpdated {
+			collateral_type: BTC,
+			new_interest_rate_model: Some(model),
+		}));
+		assert_eq!(CDPEngineModule::interest_rate_models(BTC), Some(model));
+
+		assert_ok!(CDPEngineModule::set_interest_rate_model(Origin::signed(1), BTC, None));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::InterestRateModelUpdated {
+			collateral_type: BTC,
+			new_interest_rate_model: None,
+		}));
+		assert_eq!(CDPEngineModule::interest_rate_models(BTC), None);
+	});
+}
+
+#[test]
+fn keeper_liquidate_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_keeper_reward_ratio(
+			Origin::signed(1),
+			Ratio::saturating_from_rational(1, 2)
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			121,
+			0,
+			false
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_noop!(
+			CDPEngineModule::keeper_liquidate(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::MustBeUnsafe,
+		);
+		assert_noop!(CDPEngineModule::keeper_liquidate(Origin::none(), BTC, ALICE), BadOrigin,);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		// the owner cannot liquidate its own CDP for the reward
+		assert_noop!(
+			CDPEngineModule::keeper_liquidate(Origin::signed(ALICE), BTC, ALICE),
+			Error::<Runtime>::KeeperIsOwner,
+		);
+
+		let bob_stable_balance = Currencies::free_balance(AUSD, &BOB);
+		assert_ok!(CDPEngineModule::keeper_liquidate(Origin::signed(BOB), BTC, ALICE));
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			liquidation_strategy: LiquidationStrategy::Exchange,
+		}));
+		// DEX recovers 60 for the bad debt 50, the penalty 10 is recovered and the keeper is
+		// rewarded half of it from the surplus
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::KeeperRewarded {
+			keeper: BOB,
+			collateral_type: BTC,
+			owner: ALICE,
+			reward_amount: 5,
+		}));
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), bob_stable_balance + 5);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 55);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+
+		// the oracle value of the collateral covers the penalty, but the DEX sale recovers less
+		// than it, so the collateral is sold by auction and the keeper isn't rewarded
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(
+			DEXModule::get_swap_amount(&vec![BTC, AUSD], SwapLimit::ExactSupply(100, 0)),
+			Some((100, 20))
+		);
+		assert_ok!(CDPEngineModule::keeper_liquidate(Origin::signed(BOB), BTC, ALICE));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			liquidation_strategy: LiquidationStrategy::Auction { auction_count: 1 },
+		}));
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), bob_stable_balance + 5);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 55);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 100);
+
+		mock_shutdown();
+		assert_noop!(
+			CDPEngineModule::keeper_liquidate(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::AlreadyShutdown
+		);
+	});
+}
+
 #[test]
 fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -549,7 +1046,145 @@ fn liquidate_unsafe_cdp_by_collateral_auction_when_limited_by_slippage() {
 }
 
 #[test]
-fn liquidate_unsafe_cdp_by_swap() {
+fn liquidate_unsafe_cdp_by_swap() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			121,
+			0,
+			false
+		));
+		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (100, 121));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 500);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::max_value())),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+		));
+
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 100,
+			bad_debt_value: 50,
+			liquidation_strategy: LiquidationStrategy::Exchange,
+		}));
+
+		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (199, 61));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 901);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+	});
+}
+
+#[test]
+fn get_partial_liquidation_amounts_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NoChange,
+		));
+
+		// close factor is not set
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 500), None);
+
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::one())
+		));
+		// liquidate 28 debit value and confiscate 33 collateral to restore the collateral ratio to 3
+		assert_eq!(
+			CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 500),
+			Some((33, 280))
+		);
+		// the whole debit is liquidated to restore the collateral ratio, the remain is dust
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 55, 500), None);
+		// remain debit value is below the dust amount
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 7, 30), None);
+
+		// capped by close factor
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::saturating_from_rational(1, 2))
+		));
+		assert_eq!(
+			CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 500),
+			Some((30, 250))
+		);
+		// still capped by close factor even if the collateral ratio cannot be restored
+		assert_eq!(
+			CDPEngineModule::get_partial_liquidation_amounts(BTC, 55, 500),
+			Some((30, 250))
+		);
+		assert_eq!(
+			CDPEngineModule::get_partial_liquidation_amounts(BTC, 7, 30),
+			Some((1, 10))
+		);
+		// remain debit value is below the dust amount
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 1, 20), None);
+		// the collateral cannot cover the liquidated debit value plus penalty
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 1, 500), None);
+
+		// target ratio is not greater than the penalty factor, still capped by close factor
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(6, 5))),
+			Change::NoChange,
+			Change::NewValue(None),
+			Change::NoChange,
+		));
+		assert_eq!(
+			CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 900),
+			Some((54, 450))
+		);
+
+		// the whole debit is liquidated by the close factor, the remain is dust
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
+			BTC,
+			Some(Ratio::one())
+		));
+		assert_eq!(CDPEngineModule::get_partial_liquidation_amounts(BTC, 100, 900), None);
+	});
+}
+
+#[test]
+fn liquidate_unsafe_cdp_partially() {
 	ExtBuilder::default().build().execute_with(|| {
 		System::set_block_number(1);
 		assert_ok!(CDPEngineModule::set_collateral_params(
@@ -561,48 +1196,53 @@ fn liquidate_unsafe_cdp_by_swap() {
 			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
 			Change::NewValue(10000),
 		));
-		assert_ok!(DEXModule::add_liquidity(
-			Origin::signed(CAROL),
+		assert_ok!(CDPEngineModule::set_liquidation_close_factor(
+			Origin::signed(1),
 			BTC,
-			AUSD,
-			100,
-			121,
-			0,
-			false
+			Some(Ratio::saturating_from_rational(1, 2))
 		));
-		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (100, 121));
-
 		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 500);
-		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
-
 		assert_ok!(CDPEngineModule::set_collateral_params(
 			Origin::signed(1),
 			BTC,
 			Change::NoChange,
-			Change::NewValue(Some(Ratio::max_value())),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 1))),
 			Change::NoChange,
 			Change::NoChange,
 			Change::NoChange,
 		));
 
+		// liquidation is capped by the close factor
 		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
 		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
 			collateral_type: BTC,
 			owner: ALICE,
-			collateral_amount: 100,
-			bad_debt_value: 50,
-			liquidation_strategy: LiquidationStrategy::Exchange,
+			collateral_amount: 30,
+			bad_debt_value: 25,
+			liquidation_strategy: LiquidationStrategy::Auction { auction_count: 1 },
 		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 25);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 250);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 70);
 
-		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (199, 61));
-		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
-		assert_eq!(Currencies::free_balance(BTC, &ALICE), 901);
-		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
-		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
-		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		// still unsafe, liquidate the rest required to restore the collateral ratio
+		assert_ok!(CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::LiquidateUnsafeCDP {
+			collateral_type: BTC,
+			owner: ALICE,
+			collateral_amount: 3,
+			bad_debt_value: 3,
+			liquidation_strategy: LiquidationStrategy::Auction { auction_count: 1 },
+		}));
+		assert_eq!(CDPTreasuryModule::debit_pool(), 28);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 220);
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 67);
+
+		assert_noop!(
+			CDPEngineModule::liquidate_unsafe_cdp(ALICE, BTC),
+			Error::<Runtime>::MustBeUnsafe,
+		);
 	});
 }
 
@@ -753,6 +1393,114 @@ fn accumulate_interest_work() {
 	});
 }
 
+#[test]
+fn accumulate_interest_by_interest_rate_model_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::set_interest_rate_model(
+			Origin::signed(1),
+			BTC,
+			Some(InterestRateModel {
+				base_rate: Rate::zero(),
+				kink: Ratio::saturating_from_rational(1, 2),
+				multiplier: Rate::saturating_from_rational(1, 100),
+				jump_multiplier: Rate::saturating_from_rational(1, 10),
+				min_rate: Rate::saturating_from_rational(1, 10000),
+				max_rate: Rate::saturating_from_rational(1, 10),
+			})
+		));
+
+		// no debit, the interest rate is bounded by the min rate
+		CDPEngineModule::accumulate_interest(1, 0);
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::ModelInterestRatePerSecUpdated {
+			collateral_type: BTC,
+			new_interest_rate_per_sec: Rate::saturating_from_rational(1, 10000),
+		}));
+		assert_eq!(
+			CDPEngineModule::model_interest_rate_per_sec(BTC),
+			Some(Rate::saturating_from_rational(1, 10000))
+		);
+		assert_eq!(CDPEngineModule::collateral_params(BTC).interest_rate_per_sec, None);
+		assert_eq!(CDPEngineModule::debit_exchange_rate(BTC), None);
+
+		// the interest is accumulated at the previous rate, then the rate is adjusted by the
+		// utilization 5% which is below the kink
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 1000, 5000));
+		CDPEngineModule::accumulate_interest(2, 1);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(10001, 100000))
+		);
+		System::assert_has_event(Event::CDPEngineModule(crate::Event::ModelInterestRatePerSecUpdated {
+			collateral_type: BTC,
+			new_interest_rate_per_sec: Rate::saturating_from_rational(5, 10000),
+		}));
+		assert_eq!(
+			CDPEngineModule::get_interest_rate_per_sec(BTC),
+			Rate::saturating_from_rational(5, 10000)
+		);
+
+		// utilization is 125% which is above the kink
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(400),
+		));
+		CDPEngineModule::accumulate_interest(3, 2);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(100060005, 1000000000))
+		);
+		assert_eq!(
+			CDPEngineModule::model_interest_rate_per_sec(BTC),
+			Some(Rate::saturating_from_rational(8, 100))
+		);
+
+		// the interest rate is bounded by the max rate
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(100),
+		));
+		CDPEngineModule::accumulate_interest(4, 3);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(1080648054, 10000000000u128))
+		);
+		assert_eq!(
+			CDPEngineModule::model_interest_rate_per_sec(BTC),
+			Some(Rate::saturating_from_rational(1, 10))
+		);
+
+		// the interest rate in the risk management params is used again after the model is
+		// removed
+		assert_ok!(CDPEngineModule::set_interest_rate_model(Origin::signed(1), BTC, None));
+		assert_eq!(CDPEngineModule::model_interest_rate_per_sec(BTC), None);
+		CDPEngineModule::accumulate_interest(5, 4);
+		assert_eq!(
+			CDPEngineModule::debit_exchange_rate(BTC),
+			Some(ExchangeRate::saturating_from_rational(1080648054, 10000000000u128))
+		);
+	});
+}
+
 #[test]
 fn settle_cdp_has_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -794,6 +1542,78 @@ fn settle_cdp_has_debit_work() {
 	});
 }
 
+#[test]
+fn settle_after_collateral_shutdown_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		mock_collateral_shutdown(BTC);
+
+		// the shutdown collateral type stops accumulating interest
+		CDPEngineModule::accumulate_interest(2, 1);
+		assert_eq!(CDPEngineModule::debit_exchange_rate(BTC), None);
+
+		assert_noop!(
+			CDPEngineModule::liquidate(Origin::none(), BTC, ALICE),
+			Error::<Runtime>::AlreadyShutdown
+		);
+		assert_noop!(
+			CDPEngineModule::keeper_liquidate(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::AlreadyShutdown
+		);
+		assert_noop!(
+			CDPEngineModule::settle(Origin::none(), DOT, ALICE),
+			Error::<Runtime>::MustAfterShutdown
+		);
+
+		assert_ok!(CDPEngineModule::settle(Origin::none(), BTC, ALICE));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
+	});
+}
+
+#[test]
+fn keeper_settle_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 500));
+		assert_noop!(
+			CDPEngineModule::keeper_settle(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::MustAfterShutdown
+		);
+
+		mock_shutdown();
+		assert_noop!(CDPEngineModule::keeper_settle(Origin::none(), BTC, ALICE), BadOrigin);
+		assert_ok!(CDPEngineModule::keeper_settle(Origin::signed(BOB), BTC, ALICE));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::SettleCDPInDebit {
+			collateral_type: BTC,
+			owner: ALICE,
+		}));
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 50);
+		assert_eq!(CDPTreasuryModule::total_collaterals(BTC), 50);
+	});
+}
+
 #[test]
 fn close_cdp_has_debit_by_dex_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -956,6 +1776,128 @@ fn close_cdp_has_debit_by_swap_on_alternative_path() {
 	});
 }
 
+#[test]
+fn expand_position_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			0,
+			false
+		));
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 1000, 500));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 1000);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 500);
+
+		assert_noop!(
+			CDPEngineModule::expand_position_collateral(&ALICE, ACA, 50, 0),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+
+		// min increase collateral limit swap
+		assert_noop!(
+			CDPEngineModule::expand_position_collateral(&ALICE, BTC, 50, 5),
+			Error::<Runtime>::CannotSwap,
+		);
+
+		assert_ok!(CDPEngineModule::expand_position_collateral(&ALICE, BTC, 50, 4));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::ExpandPositionCollateral {
+			collateral_type: BTC,
+			owner: ALICE,
+			increased_debit_value: 50,
+			increased_collateral_amount: 4,
+		}));
+		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (96, 1050));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 1004);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 1000);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+
+		assert_noop!(
+			CDPEngineModule::expand_position_collateral(&ALICE, BTC, 1000, 0),
+			Error::<Runtime>::BelowRequiredCollateralRatio,
+		);
+	});
+}
+
+#[test]
+fn shrink_position_debit_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(DEXModule::add_liquidity(
+			Origin::signed(CAROL),
+			BTC,
+			AUSD,
+			100,
+			1000,
+			0,
+			false
+		));
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 100, 0));
+		assert_noop!(
+			CDPEngineModule::shrink_position_debit(&ALICE, BTC, 3, 0),
+			Error::<Runtime>::NoDebitValue,
+		);
+
+		assert_ok!(CDPEngineModule::adjust_position(&ALICE, BTC, 0, 500));
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+
+		// partially repay the debit
+		assert_ok!(CDPEngineModule::shrink_position_debit(&ALICE, BTC, 3, 0));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::ShrinkPositionDebit {
+			collateral_type: BTC,
+			owner: ALICE,
+			decreased_collateral_amount: 3,
+			decreased_debit_value: 29,
+		}));
+		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (103, 971));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 97);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 210);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 50);
+
+		// min decrease debit value limit swap
+		assert_noop!(
+			CDPEngineModule::shrink_position_debit(&ALICE, BTC, 1, 100),
+			Error::<Runtime>::CannotSwap,
+		);
+
+		// repay all the debit and refund the remainder
+		assert_ok!(CDPEngineModule::shrink_position_debit(&ALICE, BTC, 10, 0));
+		System::assert_last_event(Event::CDPEngineModule(crate::Event::ShrinkPositionDebit {
+			collateral_type: BTC,
+			owner: ALICE,
+			decreased_collateral_amount: 10,
+			decreased_debit_value: 21,
+		}));
+		assert_eq!(DEXModule::get_liquidity_pool(BTC, AUSD), (113, 886));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 87);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
+		assert_eq!(Currencies::free_balance(AUSD, &ALICE), 114);
+	});
+}
+
 #[test]
 fn offchain_worker_works_cdp() {
 	let (offchain, _offchain_state) = testing::TestOffchainExt::new();
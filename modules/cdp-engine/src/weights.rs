@@ -53,6 +53,10 @@ pub trait WeightInfo {
 	fn liquidate_by_auction(b: u32) -> Weight;
 	fn liquidate_by_dex() -> Weight;
 	fn settle() -> Weight;
+	fn set_liquidation_close_factor() -> Weight;
+	fn set_keeper_reward_ratio() -> Weight;
+	fn keeper_reward() -> Weight;
+	fn set_interest_rate_model() -> Weight;
 }
 
 /// Weights for module_cdp_engine using the Acala node and recommended hardware.
@@ -62,7 +66,8 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 		(33_360_000 as Weight)
 			.saturating_add((23_139_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 	fn set_collateral_params() -> Weight {
 		(37_000_000 as Weight)
@@ -85,8 +90,25 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 	}
 	fn settle() -> Weight {
 		(97_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(13 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	fn set_liquidation_close_factor() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_keeper_reward_ratio() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn keeper_reward() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn set_interest_rate_model() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 }
 
@@ -96,7 +118,8 @@ impl WeightInfo for () {
 		(33_360_000 as Weight)
 			.saturating_add((23_139_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((1 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(c as Weight)))
 	}
 	fn set_collateral_params() -> Weight {
 		(37_000_000 as Weight)
@@ -119,7 +142,24 @@ impl WeightInfo for () {
 	}
 	fn settle() -> Weight {
 		(97_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(8 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(13 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
+	fn set_liquidation_close_factor() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_keeper_reward_ratio() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn keeper_reward() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn set_interest_rate_model() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 	}
 }
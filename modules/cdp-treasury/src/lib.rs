@@ -24,20 +24,26 @@
 //! CDPs, and handle excessive surplus or debits timely in order to keep the
 //! system healthy with low risk. It's the only entry for issuing/burning stable
 //! coin for whole system.
+//!
+//! At the end of every block, surplus and debit are offset against each other
+//! first. Then the excessive surplus above the buffer is sold for native token
+//! by surplus auction, and the unoffset bad debit is recapitalized by debit
+//! auction which mints native token, the lot sizes of these auctions are set by
+//! governance.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
 
 use frame_support::{log, pallet_prelude::*, transactional, PalletId};
 use frame_system::pallet_prelude::*;
-use orml_traits::{MultiCurrency, MultiCurrencyExtended};
+use orml_traits::{Change, MultiCurrency, MultiCurrencyExtended};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{
 	traits::{AccountIdConversion, One, Zero},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber,
 };
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, Ratio, SwapLimit};
+use support::{AuctionManager, CDPTreasury, CDPTreasuryExtended, DEXManager, EmergencyShutdown, Ratio, SwapLimit};
 
 mod mock;
 mod tests;
@@ -46,6 +52,8 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+type ChangeBalance = Change<Balance>;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -91,6 +99,9 @@ pub mod module {
 		#[pallet::constant]
 		type AlternativeSwapPathJointList: Get<Vec<Vec<CurrencyId>>>;
 
+		/// Emergency shutdown, no surplus or debit auction is created after it.
+		type EmergencyShutdown: EmergencyShutdown;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -105,6 +116,10 @@ pub mod module {
 		DebitPoolNotEnough,
 		/// Cannot use collateral to swap stable
 		CannotSwap,
+		/// The surplus auction is disabled
+		SurplusAuctionDisabled,
+		/// The debit auction is disabled
+		DebitAuctionDisabled,
 	}
 
 	#[pallet::event]
@@ -116,6 +131,14 @@ pub mod module {
 			collateral_type: CurrencyId,
 			new_size: Balance,
 		},
+		/// The fixed amount of stable currency for per lot surplus auction updated.
+		SurplusAuctionFixedSizeUpdated { new_size: Balance },
+		/// The buffer size of surplus pool which will not be auctioned updated.
+		SurplusBufferSizeUpdated { new_size: Balance },
+		/// The initial amount of native currency for per lot debit auction updated.
+		InitialAmountPerDebitAuctionUpdated { new_amount: Balance },
+		/// The fixed amount of debit for per lot debit auction updated.
+		DebitAuctionFixedSizeUpdated { new_size: Balance },
 	}
 
 	/// The expected amount size for per lot collateral auction of specific
@@ -134,6 +157,38 @@ pub mod module {
 	#[pallet::getter(fn debit_pool)]
 	pub type DebitPool<T: Config> = StorageValue<_, Balance, ValueQuery>;
 
+	/// The fixed amount of stable currency for sale per surplus auction. If
+	/// zero, surplus auction is disabled.
+	///
+	/// SurplusAuctionFixedSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_auction_fixed_size)]
+	pub type SurplusAuctionFixedSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The buffer size of surplus pool, the surplus below it will not be
+	/// auctioned.
+	///
+	/// SurplusBufferSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn surplus_buffer_size)]
+	pub type SurplusBufferSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The initial amount of native currency for sale per debit auction. If
+	/// zero, debit auction is disabled.
+	///
+	/// InitialAmountPerDebitAuction: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn initial_amount_per_debit_auction)]
+	pub type InitialAmountPerDebitAuction<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
+	/// The fixed amount of debit to be recapitalized per debit auction. If
+	/// zero, debit auction is disabled.
+	///
+	/// DebitAuctionFixedSize: Balance
+	#[pallet::storage]
+	#[pallet::getter(fn debit_auction_fixed_size)]
+	pub type DebitAuctionFixedSize<T: Config> = StorageValue<_, Balance, ValueQuery>;
+
 	#[pallet::genesis_config]
 	#[cfg_attr(feature = "std", derive(Default))]
 	pub struct GenesisConfig {
@@ -157,10 +212,18 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Reserve the weight for handling surplus and debits on finalize
+		fn on_initialize(_now: T::BlockNumber) -> Weight {
+			T::WeightInfo::on_finalize()
+		}
+
 		/// Handle excessive surplus or debits of system when block end
 		fn on_finalize(_now: T::BlockNumber) {
 			// offset the same amount between debit pool and surplus pool
 			Self::offset_surplus_and_debit();
+
+			// auction the excessive surplus or the unoffset debit
+			Self::create_surplus_and_debit_auctions();
 		}
 	}
 
@@ -254,6 +317,47 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Update parameters related to surplus auction and debit auction
+		///
+		/// The dispatch origin of this call must be `UpdateOrigin`.
+		///
+		/// - `surplus_auction_fixed_size`: fixed amount of stable currency for sale per surplus
+		///   auction, zero means surplus auction is disabled.
+		/// - `surplus_buffer_size`: the surplus below this size will not be auctioned.
+		/// - `initial_amount_per_debit_auction`: initial amount of native currency for sale per
+		///   debit auction, zero means debit auction is disabled.
+		/// - `debit_auction_fixed_size`: fixed amount of debit to be recapitalized per debit
+		///   auction, zero means debit auction is disabled.
+		#[pallet::weight((T::WeightInfo::set_surplus_and_debit_auction_params(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_surplus_and_debit_auction_params(
+			origin: OriginFor<T>,
+			surplus_auction_fixed_size: ChangeBalance,
+			surplus_buffer_size: ChangeBalance,
+			initial_amount_per_debit_auction: ChangeBalance,
+			debit_auction_fixed_size: ChangeBalance,
+		) -> DispatchResult {
+			T::UpdateOrigin::ensure_origin(origin)?;
+
+			if let Change::NewValue(new_size) = surplus_auction_fixed_size {
+				SurplusAuctionFixedSize::<T>::put(new_size);
+				Self::deposit_event(Event::SurplusAuctionFixedSizeUpdated { new_size });
+			}
+			if let Change::NewValue(new_size) = surplus_buffer_size {
+				SurplusBufferSize::<T>::put(new_size);
+				Self::deposit_event(Event::SurplusBufferSizeUpdated { new_size });
+			}
+			if let Change::NewValue(new_amount) = initial_amount_per_debit_auction {
+				InitialAmountPerDebitAuction::<T>::put(new_amount);
+				Self::deposit_event(Event::InitialAmountPerDebitAuctionUpdated { new_amount });
+			}
+			if let Change::NewValue(new_size) = debit_auction_fixed_size {
+				DebitAuctionFixedSize::<T>::put(new_size);
+				Self::deposit_event(Event::DebitAuctionFixedSizeUpdated { new_size });
+			}
+			Ok(())
+		}
 	}
 }
 
@@ -303,6 +407,66 @@ impl<T: Config> Pallet<T> {
 			}
 		}
 	}
+
+	fn create_surplus_and_debit_auctions() {
+		// the surplus and debit are settled by the refund of emergency shutdown.
+		if T::EmergencyShutdown::is_shutdown() {
+			return;
+		}
+
+		// create surplus auction when there's no bad debit and the surplus exceeds the
+		// buffer.
+		let surplus_auction_fixed_size = Self::surplus_auction_fixed_size();
+		if !surplus_auction_fixed_size.is_zero()
+			&& Self::debit_pool().is_zero()
+			&& Self::surplus_pool() >= Self::surplus_buffer_size().saturating_add(surplus_auction_fixed_size)
+		{
+			if let Err(e) = Self::create_surplus_auction(surplus_auction_fixed_size) {
+				log::warn!(
+					target: "cdp-treasury",
+					"create_surplus_auction: failed to create surplus auction of {:?}: {:?}, this is unexpected but should be safe",
+					surplus_auction_fixed_size, e
+				);
+			}
+		}
+
+		// create debit auction when the debit which is not in auction exceeds the fixed size.
+		let debit_auction_fixed_size = Self::debit_auction_fixed_size();
+		if !debit_auction_fixed_size.is_zero()
+			&& Self::debit_pool().saturating_sub(T::AuctionManagerHandler::get_total_debit_in_auction())
+				>= debit_auction_fixed_size
+		{
+			if let Err(e) = Self::create_debit_auction(debit_auction_fixed_size) {
+				log::warn!(
+					target: "cdp-treasury",
+					"create_debit_auction: failed to create debit auction of {:?}: {:?}, this is unexpected but should be safe",
+					debit_auction_fixed_size, e
+				);
+			}
+		}
+	}
+
+	/// Burn the fixed amount of surplus and create surplus auction for it, the
+	/// stable currency will be issued to the winner when the auction is dealt.
+	#[transactional]
+	fn create_surplus_auction(amount: Balance) -> DispatchResult {
+		ensure!(!amount.is_zero(), Error::<T>::SurplusAuctionDisabled);
+		ensure!(Self::surplus_pool() >= amount, Error::<T>::SurplusPoolNotEnough);
+		T::Currency::withdraw(T::GetStableCurrencyId::get(), &Self::account_id(), amount)?;
+		T::AuctionManagerHandler::new_surplus_auction(amount)
+	}
+
+	/// Create debit auction to recapitalize the fixed amount of debit by
+	/// minting native currency.
+	#[transactional]
+	fn create_debit_auction(fix_debit: Balance) -> DispatchResult {
+		let initial_amount = Self::initial_amount_per_debit_auction();
+		ensure!(
+			!initial_amount.is_zero() && !fix_debit.is_zero(),
+			Error::<T>::DebitAuctionDisabled
+		);
+		T::AuctionManagerHandler::new_debit_auction(initial_amount, fix_debit)
+	}
 }
 
 impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
@@ -355,6 +519,11 @@ impl<T: Config> CDPTreasury<T::AccountId> for Pallet<T> {
 		T::Currency::transfer(T::GetStableCurrencyId::get(), from, &Self::account_id(), surplus)
 	}
 
+	fn withdraw_surplus(to: &T::AccountId, surplus: Self::Balance) -> DispatchResult {
+		ensure!(Self::surplus_pool() >= surplus, Error::<T>::SurplusPoolNotEnough);
+		T::Currency::transfer(T::GetStableCurrencyId::get(), &Self::account_id(), to, surplus)
+	}
+
 	fn deposit_collateral(from: &T::AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult {
 		T::Currency::transfer(currency_id, from, &Self::account_id(), amount)
 	}
@@ -31,6 +31,7 @@ use primitives::{TokenSymbol, TradingPair};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 use sp_std::cell::RefCell;
+use support::mocks::MockUnixTime;
 
 pub type AccountId = u128;
 pub type BlockNumber = u64;
@@ -131,6 +132,7 @@ parameter_types! {
 	pub const GetStableCurrencyId: CurrencyId = AUSD;
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 4;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2000;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::from_currency_ids(AUSD, BTC).unwrap(),
 		TradingPair::from_currency_ids(AUSD, DOT).unwrap(),
@@ -144,17 +146,24 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type MaxRouteTokens = frame_support::traits::ConstU32<3>;
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = EnsureSignedBy<One, AccountId>;
 	type OnLiquidityPoolUpdated = ();
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type TreasuryAccount = TreasuryAccount;
+	type FlashSwapCallback = ();
+	type UnixTime = MockUnixTime;
 }
 
 thread_local! {
 	pub static TOTAL_COLLATERAL_AUCTION: RefCell<u32> = RefCell::new(0);
 	pub static TOTAL_COLLATERAL_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	pub static TOTAL_SURPLUS_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	pub static TOTAL_DEBIT_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
 }
 
 pub struct MockAuctionManager;
@@ -174,6 +183,16 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult {
+		TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut() += amount);
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult {
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut() += fix_debit);
+		Ok(())
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		unimplemented!()
 	}
@@ -185,6 +204,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 	fn get_total_target_in_auction() -> Self::Balance {
 		unimplemented!()
 	}
+
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut())
+	}
 }
 
 ord_parameter_types! {
@@ -204,6 +231,17 @@ thread_local! {
 	static IS_SHUTDOWN: RefCell<bool> = RefCell::new(false);
 }
 
+pub fn mock_shutdown() {
+	IS_SHUTDOWN.with(|v| *v.borrow_mut() = true)
+}
+
+pub struct MockEmergencyShutdown;
+impl EmergencyShutdown for MockEmergencyShutdown {
+	fn is_shutdown() -> bool {
+		IS_SHUTDOWN.with(|v| *v.borrow_mut())
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -215,6 +253,7 @@ impl Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -139,6 +139,20 @@ fn deposit_surplus_work() {
 	});
 }
 
+#[test]
+fn withdraw_surplus_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::deposit_surplus(&ALICE, 300));
+		assert_noop!(
+			CDPTreasuryModule::withdraw_surplus(&BOB, 301),
+			Error::<Runtime>::SurplusPoolNotEnough
+		);
+		assert_ok!(CDPTreasuryModule::withdraw_surplus(&BOB, 200));
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1200);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 100);
+	});
+}
+
 #[test]
 fn deposit_collateral_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -321,6 +335,144 @@ fn set_expected_collateral_auction_size_work() {
 	});
 }
 
+#[test]
+fn set_surplus_and_debit_auction_params_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			CDPTreasuryModule::set_surplus_and_debit_auction_params(
+				Origin::signed(5),
+				Change::NewValue(100),
+				Change::NoChange,
+				Change::NoChange,
+				Change::NoChange,
+			),
+			BadOrigin
+		);
+		assert_ok!(CDPTreasuryModule::set_surplus_and_debit_auction_params(
+			Origin::signed(1),
+			Change::NewValue(100),
+			Change::NewValue(200),
+			Change::NewValue(300),
+			Change::NewValue(400),
+		));
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusAuctionFixedSizeUpdated {
+			new_size: 100,
+		}));
+		System::assert_has_event(Event::CDPTreasuryModule(crate::Event::SurplusBufferSizeUpdated {
+			new_size: 200,
+		}));
+		System::assert_has_event(Event::CDPTreasuryModule(
+			crate::Event::InitialAmountPerDebitAuctionUpdated { new_amount: 300 },
+		));
+		System::assert_last_event(Event::CDPTreasuryModule(crate::Event::DebitAuctionFixedSizeUpdated {
+			new_size: 400,
+		}));
+		assert_eq!(CDPTreasuryModule::surplus_auction_fixed_size(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 200);
+		assert_eq!(CDPTreasuryModule::initial_amount_per_debit_auction(), 300);
+		assert_eq!(CDPTreasuryModule::debit_auction_fixed_size(), 400);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_and_debit_auction_params(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NewValue(0),
+			Change::NoChange,
+			Change::NoChange,
+		));
+		assert_eq!(CDPTreasuryModule::surplus_auction_fixed_size(), 100);
+		assert_eq!(CDPTreasuryModule::surplus_buffer_size(), 0);
+	});
+}
+
+#[test]
+fn create_surplus_auction_on_finalize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_and_debit_auction_params(
+			Origin::signed(1),
+			Change::NewValue(300),
+			Change::NewValue(500),
+			Change::NoChange,
+			Change::NoChange,
+		));
+		CDPTreasuryModule::on_finalize(2);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 700);
+		assert_eq!(Currencies::total_issuance(AUSD), 2700);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 300);
+
+		// the remain surplus is not enough to exceed the buffer
+		CDPTreasuryModule::on_finalize(3);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 700);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 300);
+
+		// no surplus auction when there's bad debit
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		assert_ok!(CDPTreasuryModule::on_system_debit(1700));
+		CDPTreasuryModule::on_finalize(4);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 0);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 300);
+	});
+}
+
+#[test]
+fn create_debit_auction_on_finalize_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::on_system_debit(1000));
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1000);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		assert_ok!(CDPTreasuryModule::set_surplus_and_debit_auction_params(
+			Origin::signed(1),
+			Change::NoChange,
+			Change::NoChange,
+			Change::NewValue(2000),
+			Change::NewValue(400),
+		));
+		CDPTreasuryModule::on_finalize(2);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 400);
+		CDPTreasuryModule::on_finalize(3);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 800);
+
+		// the debit which is not in auction is less than the fixed size
+		CDPTreasuryModule::on_finalize(4);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 800);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1000);
+	});
+}
+
+#[test]
+fn no_surplus_and_debit_auction_after_emergency_shutdown() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPTreasuryModule::set_surplus_and_debit_auction_params(
+			Origin::signed(1),
+			Change::NewValue(300),
+			Change::NewValue(500),
+			Change::NewValue(2000),
+			Change::NewValue(400),
+		));
+		assert_ok!(CDPTreasuryModule::on_system_surplus(1000));
+		mock_shutdown();
+
+		CDPTreasuryModule::on_finalize(1);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 1000);
+		assert_eq!(TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut()), 0);
+
+		// surplus and debit are still offset
+		assert_ok!(CDPTreasuryModule::on_system_debit(2000));
+		CDPTreasuryModule::on_finalize(2);
+		assert_eq!(CDPTreasuryModule::surplus_pool(), 0);
+		assert_eq!(CDPTreasuryModule::debit_pool(), 1000);
+		assert_eq!(TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut()), 0);
+	});
+}
+
 #[test]
 fn extract_surplus_to_treasury_work() {
 	ExtBuilder::default().build().execute_with(|| {
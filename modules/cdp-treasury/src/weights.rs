@@ -52,6 +52,8 @@ pub trait WeightInfo {
 	fn auction_collateral(b: u32) -> Weight;
 	fn exchange_collateral_to_stable() -> Weight;
 	fn set_expected_collateral_auction_size() -> Weight;
+	fn set_surplus_and_debit_auction_params() -> Weight;
+	fn on_finalize() -> Weight;
 }
 
 /// Weights for module_cdp_treasury using the Acala node and recommended hardware.
@@ -79,6 +81,15 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	fn set_surplus_and_debit_auction_params() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn on_finalize() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -104,4 +115,13 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
+	fn set_surplus_and_debit_auction_params() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn on_finalize() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(9 as Weight))
+	}
 }
@@ -167,6 +167,7 @@ impl module_evm::Config for Runtime {
 	type ChainId = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
+	type EnableLondon = frame_support::traits::ConstBool<false>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
 
@@ -0,0 +1,366 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! # DEX Limit Order Module
+//!
+//! ## Overview
+//!
+//! Users place limit orders to sell an exact amount of supply currency for at
+//! least the minimum amount of target currency. The supply amount is escrowed
+//! by the module account until the order is cancelled or executed. Orders are
+//! settled against the DEX pool of the trading pair once the pool price
+//! crosses the limit, either by anyone calling `execute_order` or by the
+//! `on_idle` pass with the remaining weight of the block.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::unused_unit)]
+
+use frame_support::{log, pallet_prelude::*, transactional, PalletId};
+use frame_system::pallet_prelude::*;
+use orml_traits::MultiCurrency;
+use primitives::{Balance, CurrencyId, TradingPair};
+use sp_runtime::{
+	traits::{AccountIdConversion, Zero},
+	ArithmeticError, DispatchError, DispatchResult, RuntimeDebug,
+};
+use support::{DEXManager, SwapLimit};
+
+mod mock;
+mod tests;
+pub mod weights;
+
+pub use module::*;
+pub use weights::WeightInfo;
+
+pub type OrderId = u64;
+
+/// Limit order to sell exact `supply_amount` for at least `min_target_amount`.
+#[derive(Encode, Decode, Clone, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub struct LimitOrder<AccountId> {
+	/// The owner of the order.
+	pub maker: AccountId,
+	/// The currency to sell.
+	pub supply_currency_id: CurrencyId,
+	/// The currency to buy.
+	pub target_currency_id: CurrencyId,
+	/// The exact amount of supply currency to sell.
+	pub supply_amount: Balance,
+	/// The minimum amount of target currency to receive.
+	pub min_target_amount: Balance,
+}
+
+#[frame_support::pallet]
+pub mod module {
+	use super::*;
+
+	#[pallet::config]
+	pub trait Config: frame_system::Config {
+		type Event: From<Event<Self>> + IsType<<Self as frame_system::Config>::Event>;
+
+		/// Currency for escrowing and transferring.
+		type Currency: MultiCurrency<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
+
+		/// DEX to settle the orders.
+		type DEX: DEXManager<Self::AccountId, CurrencyId, Balance>;
+
+		/// The maximum number of open orders per account.
+		#[pallet::constant]
+		type MaxOrdersPerAccount: Get<u32>;
+
+		/// The maximum number of orders to check in `on_idle` per block.
+		#[pallet::constant]
+		type MaxCheckedOrdersPerBlock: Get<u32>;
+
+		/// The limit order module id, keep all escrowed assets.
+		#[pallet::constant]
+		type PalletId: Get<PalletId>;
+
+		/// Weight information for the extrinsics in this module.
+		type WeightInfo: WeightInfo;
+	}
+
+	#[pallet::error]
+	pub enum Error<T> {
+		/// The currency ids can't form a trading pair
+		InvalidCurrencyId,
+		/// The amount is zero
+		ZeroAmount,
+		/// Exceed the maximum number of open orders
+		TooManyOrders,
+		/// The order does not exist
+		OrderNotFound,
+		/// Only the maker can cancel the order
+		NoPermission,
+		/// The DEX pool price doesn't reach the limit of the order
+		OrderNotMatched,
+	}
+
+	#[pallet::event]
+	#[pallet::generate_deposit(pub(crate) fn deposit_event)]
+	pub enum Event<T: Config> {
+		/// Place a limit order.
+		OrderPlaced {
+			order_id: OrderId,
+			maker: T::AccountId,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+			min_target_amount: Balance,
+		},
+		/// Cancel a limit order and refund the escrowed supply amount.
+		OrderCancelled { order_id: OrderId, maker: T::AccountId },
+		/// A limit order has been settled against the DEX.
+		OrderExecuted {
+			order_id: OrderId,
+			maker: T::AccountId,
+			supply_amount: Balance,
+			target_amount: Balance,
+		},
+	}
+
+	/// The next order id.
+	///
+	/// NextOrderId: OrderId
+	#[pallet::storage]
+	#[pallet::getter(fn next_order_id)]
+	pub type NextOrderId<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	/// The open limit orders.
+	///
+	/// Orders: map OrderId => Option<LimitOrder>
+	#[pallet::storage]
+	#[pallet::getter(fn orders)]
+	pub type Orders<T: Config> = StorageMap<_, Twox64Concat, OrderId, LimitOrder<T::AccountId>, OptionQuery>;
+
+	/// The number of open orders of the account.
+	///
+	/// OrdersCount: map AccountId => u32
+	#[pallet::storage]
+	#[pallet::getter(fn orders_count)]
+	pub type OrdersCount<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, u32, ValueQuery>;
+
+	/// The order id from which `on_idle` continues to check.
+	///
+	/// IdleCursor: OrderId
+	#[pallet::storage]
+	#[pallet::getter(fn idle_cursor)]
+	pub type IdleCursor<T: Config> = StorageValue<_, OrderId, ValueQuery>;
+
+	#[pallet::pallet]
+	pub struct Pallet<T>(_);
+
+	#[pallet::hooks]
+	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		/// Check the open orders with the remaining weight and execute the matched ones.
+		fn on_idle(_n: T::BlockNumber, remaining_weight: Weight) -> Weight {
+			let check_weight = <T as Config>::WeightInfo::execute_order();
+			let next_order_id = Self::next_order_id();
+			let checks = remaining_weight
+				.checked_div(check_weight)
+				.unwrap_or_default()
+				.min(T::MaxCheckedOrdersPerBlock::get().into())
+				.min(next_order_id);
+			if checks.is_zero() {
+				return 0;
+			}
+
+			let mut order_id = Self::idle_cursor();
+			for _ in 0..checks {
+				if order_id >= next_order_id {
+					order_id = Zero::zero();
+				}
+				if let Some(order) = Self::orders(order_id) {
+					if Self::is_order_matched(&order) {
+						if let Err(e) = Self::do_execute_order(order_id) {
+							log::warn!(
+								target: "dex-limit-order",
+								"on_idle: failed to execute order {:?}: {:?}",
+								order_id, e
+							);
+						}
+					}
+				}
+				order_id = order_id.saturating_add(1);
+			}
+			IdleCursor::<T>::put(order_id);
+
+			check_weight.saturating_mul(checks)
+		}
+	}
+
+	#[pallet::call]
+	impl<T: Config> Pallet<T> {
+		/// Place a limit order, the supply amount is escrowed until the order is cancelled or
+		/// executed.
+		///
+		/// - `supply_currency_id`: the currency to sell.
+		/// - `target_currency_id`: the currency to buy.
+		/// - `supply_amount`: the exact amount of supply currency to sell.
+		/// - `min_target_amount`: the minimum amount of target currency to receive.
+		#[pallet::weight(<T as Config>::WeightInfo::place_order())]
+		#[transactional]
+		pub fn place_order(
+			origin: OriginFor<T>,
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			Self::do_place_order(
+				&who,
+				supply_currency_id,
+				target_currency_id,
+				supply_amount,
+				min_target_amount,
+			)?;
+			Ok(())
+		}
+
+		/// Cancel the limit order and refund the escrowed supply amount.
+		///
+		/// - `order_id`: the id of the order.
+		#[pallet::weight(<T as Config>::WeightInfo::cancel_order())]
+		#[transactional]
+		pub fn cancel_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+			ensure!(order.maker == who, Error::<T>::NoPermission);
+
+			T::Currency::transfer(order.supply_currency_id, &Self::account_id(), &who, order.supply_amount)?;
+			Self::remove_order(order_id, &who);
+
+			Self::deposit_event(Event::OrderCancelled { order_id, maker: who });
+			Ok(())
+		}
+
+		/// Execute the limit order against the DEX if the pool price reaches the limit. Anyone
+		/// can execute the order, the target amount is sent to the maker.
+		///
+		/// - `order_id`: the id of the order.
+		#[pallet::weight(<T as Config>::WeightInfo::execute_order())]
+		#[transactional]
+		pub fn execute_order(origin: OriginFor<T>, order_id: OrderId) -> DispatchResult {
+			ensure_signed(origin)?;
+			Self::do_execute_order(order_id)
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	pub fn account_id() -> T::AccountId {
+		T::PalletId::get().into_account()
+	}
+
+	/// Whether the DEX pool can fill the order with at least the minimum target amount.
+	pub fn is_order_matched(order: &LimitOrder<T::AccountId>) -> bool {
+		T::DEX::get_swap_amount(
+			&[order.supply_currency_id, order.target_currency_id],
+			SwapLimit::ExactSupply(order.supply_amount, order.min_target_amount),
+		)
+		.is_some()
+	}
+
+	pub fn do_place_order(
+		who: &T::AccountId,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> Result<OrderId, DispatchError> {
+		TradingPair::from_currency_ids(supply_currency_id, target_currency_id).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			!supply_amount.is_zero() && !min_target_amount.is_zero(),
+			Error::<T>::ZeroAmount
+		);
+
+		OrdersCount::<T>::try_mutate(who, |count| -> DispatchResult {
+			*count = count.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			ensure!(*count <= T::MaxOrdersPerAccount::get(), Error::<T>::TooManyOrders);
+			Ok(())
+		})?;
+		T::Currency::transfer(supply_currency_id, who, &Self::account_id(), supply_amount)?;
+
+		let order_id = NextOrderId::<T>::try_mutate(|id| -> Result<OrderId, DispatchError> {
+			let current_id = *id;
+			*id = id.checked_add(1).ok_or(ArithmeticError::Overflow)?;
+			Ok(current_id)
+		})?;
+		Orders::<T>::insert(
+			order_id,
+			LimitOrder {
+				maker: who.clone(),
+				supply_currency_id,
+				target_currency_id,
+				supply_amount,
+				min_target_amount,
+			},
+		);
+
+		Self::deposit_event(Event::OrderPlaced {
+			order_id,
+			maker: who.clone(),
+			supply_currency_id,
+			target_currency_id,
+			supply_amount,
+			min_target_amount,
+		});
+		Ok(order_id)
+	}
+
+	/// Swap the escrowed supply amount of the order through the DEX and send the target amount
+	/// to the maker.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn do_execute_order(order_id: OrderId) -> DispatchResult {
+		let order = Self::orders(order_id).ok_or(Error::<T>::OrderNotFound)?;
+		ensure!(Self::is_order_matched(&order), Error::<T>::OrderNotMatched);
+
+		let module_account_id = Self::account_id();
+		let (supply_amount, target_amount) = T::DEX::swap_with_specific_path(
+			&module_account_id,
+			&[order.supply_currency_id, order.target_currency_id],
+			SwapLimit::ExactSupply(order.supply_amount, order.min_target_amount),
+		)?;
+		T::Currency::transfer(
+			order.target_currency_id,
+			&module_account_id,
+			&order.maker,
+			target_amount,
+		)?;
+		Self::remove_order(order_id, &order.maker);
+
+		Self::deposit_event(Event::OrderExecuted {
+			order_id,
+			maker: order.maker,
+			supply_amount,
+			target_amount,
+		});
+		Ok(())
+	}
+
+	fn remove_order(order_id: OrderId, maker: &T::AccountId) {
+		Orders::<T>::remove(order_id);
+		OrdersCount::<T>::mutate_exists(maker, |maybe_count| {
+			*maybe_count = maybe_count
+				.and_then(|count| count.checked_sub(1))
+				.filter(|count| !count.is_zero());
+		});
+	}
+}
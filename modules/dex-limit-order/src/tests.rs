@@ -0,0 +1,244 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Unit tests for the dex limit order module.
+
+#![cfg(test)]
+
+use super::*;
+use frame_support::{assert_noop, assert_ok};
+use mock::{Event, *};
+use orml_traits::MultiCurrency;
+
+fn inject_liquidity() {
+	assert_ok!(DEXModule::add_liquidity(
+		Origin::signed(ALICE),
+		AUSD,
+		DOT,
+		100_000,
+		100_000,
+		0,
+		false
+	));
+}
+
+#[test]
+fn place_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			DexLimitOrderModule::place_order(Origin::signed(BOB), DOT, DOT, 1_000, 1_000),
+			Error::<Runtime>::InvalidCurrencyId
+		);
+		assert_noop!(
+			DexLimitOrderModule::place_order(Origin::signed(BOB), DOT, AUSD, 0, 1_000),
+			Error::<Runtime>::ZeroAmount
+		);
+		assert_noop!(
+			DexLimitOrderModule::place_order(Origin::signed(BOB), DOT, AUSD, 1_000, 0),
+			Error::<Runtime>::ZeroAmount
+		);
+
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1_000,
+			2_000
+		));
+		System::assert_last_event(Event::DexLimitOrderModule(crate::Event::OrderPlaced {
+			order_id: 0,
+			maker: BOB,
+			supply_currency_id: DOT,
+			target_currency_id: AUSD,
+			supply_amount: 1_000,
+			min_target_amount: 2_000,
+		}));
+		assert_eq!(
+			DexLimitOrderModule::orders(0),
+			Some(LimitOrder {
+				maker: BOB,
+				supply_currency_id: DOT,
+				target_currency_id: AUSD,
+				supply_amount: 1_000,
+				min_target_amount: 2_000,
+			})
+		);
+		assert_eq!(DexLimitOrderModule::orders_count(BOB), 1);
+		assert_eq!(DexLimitOrderModule::next_order_id(), 1);
+		assert_eq!(Currencies::free_balance(DOT, &BOB), 999_000);
+		assert_eq!(Currencies::free_balance(DOT, &DexLimitOrderModule::account_id()), 1_000);
+
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			1_000,
+			2_000
+		));
+		assert_noop!(
+			DexLimitOrderModule::place_order(Origin::signed(BOB), DOT, AUSD, 1_000, 2_000),
+			Error::<Runtime>::TooManyOrders
+		);
+	});
+}
+
+#[test]
+fn cancel_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_noop!(
+			DexLimitOrderModule::cancel_order(Origin::signed(BOB), 0),
+			Error::<Runtime>::OrderNotFound
+		);
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1_000,
+			2_000
+		));
+		assert_noop!(
+			DexLimitOrderModule::cancel_order(Origin::signed(ALICE), 0),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(DexLimitOrderModule::cancel_order(Origin::signed(BOB), 0));
+		System::assert_last_event(Event::DexLimitOrderModule(crate::Event::OrderCancelled {
+			order_id: 0,
+			maker: BOB,
+		}));
+		assert_eq!(DexLimitOrderModule::orders(0), None);
+		assert_eq!(DexLimitOrderModule::orders_count(BOB), 0);
+		assert_eq!(Currencies::free_balance(DOT, &BOB), 1_000_000);
+		assert_eq!(Currencies::free_balance(DOT, &DexLimitOrderModule::account_id()), 0);
+	});
+}
+
+#[test]
+fn execute_order_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		inject_liquidity();
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1_000,
+			1_000
+		));
+		assert_noop!(
+			DexLimitOrderModule::execute_order(Origin::signed(CHARLIE), 0),
+			Error::<Runtime>::OrderNotMatched
+		);
+		assert_noop!(
+			DexLimitOrderModule::execute_order(Origin::signed(CHARLIE), 1),
+			Error::<Runtime>::OrderNotFound
+		);
+
+		// raise the price of DOT
+		assert_ok!(DEXModule::swap_with_exact_supply(
+			Origin::signed(ALICE),
+			vec![AUSD, DOT],
+			20_000,
+			0
+		));
+		let order = DexLimitOrderModule::orders(0).unwrap();
+		assert!(DexLimitOrderModule::is_order_matched(&order));
+
+		assert_ok!(DexLimitOrderModule::execute_order(Origin::signed(CHARLIE), 0));
+		System::assert_last_event(Event::DexLimitOrderModule(crate::Event::OrderExecuted {
+			order_id: 0,
+			maker: BOB,
+			supply_amount: 1_000,
+			target_amount: 1_422,
+		}));
+		assert_eq!(DexLimitOrderModule::orders(0), None);
+		assert_eq!(DexLimitOrderModule::orders_count(BOB), 0);
+		assert_eq!(Currencies::free_balance(DOT, &BOB), 999_000);
+		assert_eq!(Currencies::free_balance(AUSD, &BOB), 1_001_422);
+		assert_eq!(Currencies::free_balance(DOT, &DexLimitOrderModule::account_id()), 0);
+		assert_eq!(Currencies::free_balance(AUSD, &DexLimitOrderModule::account_id()), 0);
+	});
+}
+
+#[test]
+fn on_idle_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let check_weight = <() as WeightInfo>::execute_order();
+		inject_liquidity();
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1_000,
+			1_000
+		));
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(BOB),
+			DOT,
+			AUSD,
+			1_000,
+			5_000
+		));
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(CHARLIE),
+			DOT,
+			AUSD,
+			1_000,
+			1_000
+		));
+		assert_ok!(DexLimitOrderModule::place_order(
+			Origin::signed(CHARLIE),
+			DOT,
+			AUSD,
+			1_000,
+			1_000
+		));
+
+		// check orders 0, 1, 2 and no order is matched
+		assert_eq!(DexLimitOrderModule::on_idle(1, 1_000_000_000_000), check_weight * 3);
+		assert_eq!(DexLimitOrderModule::idle_cursor(), 3);
+		assert_eq!(DexLimitOrderModule::orders_count(BOB), 2);
+
+		// not enough weight
+		assert_eq!(DexLimitOrderModule::on_idle(1, check_weight - 1), 0);
+		assert_eq!(DexLimitOrderModule::idle_cursor(), 3);
+
+		assert_ok!(DEXModule::swap_with_exact_supply(
+			Origin::signed(ALICE),
+			vec![AUSD, DOT],
+			20_000,
+			0
+		));
+
+		// check orders 3, 0, 1
+		assert_eq!(DexLimitOrderModule::on_idle(2, 1_000_000_000_000), check_weight * 3);
+		assert_eq!(DexLimitOrderModule::idle_cursor(), 2);
+		assert_eq!(DexLimitOrderModule::orders(0), None);
+		assert!(DexLimitOrderModule::orders(1).is_some());
+		assert!(DexLimitOrderModule::orders(2).is_some());
+		assert_eq!(DexLimitOrderModule::orders(3), None);
+		assert_eq!(DexLimitOrderModule::orders_count(BOB), 1);
+		assert_eq!(DexLimitOrderModule::orders_count(CHARLIE), 1);
+
+		// check orders 2, 3, 0
+		assert_eq!(DexLimitOrderModule::on_idle(3, 1_000_000_000_000), check_weight * 3);
+		assert_eq!(DexLimitOrderModule::idle_cursor(), 1);
+		assert_eq!(DexLimitOrderModule::orders(2), None);
+		assert_eq!(DexLimitOrderModule::orders_count(CHARLIE), 0);
+		assert!(DexLimitOrderModule::orders(1).is_some());
+	});
+}
@@ -0,0 +1,40 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+//! Runtime API definition for DEX oracle module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use primitives::CurrencyId;
+use sp_runtime::codec::Codec;
+use support::ExchangeRate;
+
+sp_api::decl_runtime_apis! {
+	pub trait DexOracleRuntimeApi<Moment> where
+		Moment: Codec,
+	{
+		/// Get the real-time price of `base` in `quote` from DEX.
+		fn get_current_price(base: CurrencyId, quote: CurrencyId) -> Option<ExchangeRate>;
+
+		/// Get the time-weighted average price of `base` in `quote` over the `window` until the
+		/// latest observation. Returns `None` if the trading pair isn't enabled average price or
+		/// the observations can't cover the window.
+		fn get_twap(base: CurrencyId, quote: CurrencyId, window: Moment) -> Option<ExchangeRate>;
+	}
+}
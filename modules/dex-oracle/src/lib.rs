@@ -17,6 +17,13 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 //! # DEX Oracle Module
+//!
+//! ## Overview
+//!
+//! DEX oracle tracks the price cumulatives of the enabled trading pairs, updates the average
+//! prices at the interval of each trading pair, and keeps a ring buffer of the cumulative
+//! observations, by which the time-weighted average price (TWAP) over an arbitrary window up to
+//! `ObservationPeriod * (MaxObservations - 1)` can be requested.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -26,10 +33,11 @@ use frame_support::{pallet_prelude::*, traits::Time, transactional};
 use frame_system::pallet_prelude::*;
 use orml_traits::Happened;
 use primitives::{Balance, CurrencyId, TradingPair};
+use scale_info::TypeInfo;
 use sp_core::U256;
 use sp_runtime::{
-	traits::{Saturating, Zero},
-	FixedPointNumber, SaturatedConversion,
+	traits::{CheckedSub, Saturating, Zero},
+	FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
 use sp_std::marker::PhantomData;
 use support::{DEXManager, DEXPriceProvider, ExchangeRate};
@@ -41,6 +49,17 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// Observation of the price cumulatives of TradingPair.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct Observation<Moment> {
+	/// The timestamp of the observation.
+	pub timestamp: Moment,
+	/// The price cumulative of the first currency of TradingPair.
+	pub cumulative_0: U256,
+	/// The price cumulative of the second currency of TradingPair.
+	pub cumulative_1: U256,
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -58,6 +77,15 @@ pub mod module {
 		/// The origin which may manage dex oracle.
 		type UpdateOrigin: EnsureOrigin<Self::Origin>;
 
+		/// The minimum time between two observations of the price cumulatives.
+		#[pallet::constant]
+		type ObservationPeriod: Get<MomentOf<Self>>;
+
+		/// The maximum number of observations kept for a trading pair, the TWAP over the window up
+		/// to `ObservationPeriod * (MaxObservations - 1)` can be requested.
+		#[pallet::constant]
+		type MaxObservations: Get<u32>;
+
 		/// Weight information for the extrinsics in this module.
 		type WeightInfo: WeightInfo;
 	}
@@ -97,6 +125,21 @@ pub mod module {
 		OptionQuery,
 	>;
 
+	/// The ring buffer of observations for TradingPair.
+	///
+	/// Observations: double_map TradingPair, ObservationIndex => Option<Observation>
+	#[pallet::storage]
+	#[pallet::getter(fn observations)]
+	pub type Observations<T: Config> =
+		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, u32, Observation<MomentOf<T>>, OptionQuery>;
+
+	/// The state of the observation ring buffer for TradingPair.
+	///
+	/// ObservationRings: map TradingPair => (LatestObservationIndex, ObservationCount)
+	#[pallet::storage]
+	#[pallet::getter(fn observation_rings)]
+	pub type ObservationRings<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (u32, u32), ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -148,6 +191,12 @@ pub mod module {
 						),
 					);
 
+					update_count += 1;
+				} else if Self::is_observation_due(&trading_pair, now) {
+					// record the observation for TWAP even if it's not time to update average price.
+					let (pool_0, pool_1) = T::DEX::get_liquidity_pool(trading_pair.first(), trading_pair.second());
+					Self::try_update_cumulative(&trading_pair, pool_0, pool_1);
+
 					update_count += 1;
 				}
 			}
@@ -201,6 +250,7 @@ pub mod module {
 				),
 			);
 			Cumulatives::<T>::insert(&trading_pair, (initial_cumulative_0, initial_cumulative_1, now));
+			Self::try_record_observation(&trading_pair, initial_cumulative_0, initial_cumulative_1, now);
 
 			Ok(())
 		}
@@ -224,6 +274,8 @@ pub mod module {
 				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
 			AveragePrices::<T>::take(&trading_pair).ok_or(Error::<T>::AveragePriceMustBeEnabled)?;
 			Cumulatives::<T>::remove(&trading_pair);
+			ObservationRings::<T>::remove(&trading_pair);
+			let _ = Observations::<T>::remove_prefix(&trading_pair, None);
 
 			Ok(())
 		}
@@ -270,32 +322,135 @@ impl<T: Config> Pallet<T> {
 					let now = T::Time::now();
 					// update cumulative only occurs once in one block
 					if *last_cumulative_timestamp != now {
-						let elapsed_time: U256 = now
-							.saturating_sub(*last_cumulative_timestamp)
-							.saturated_into::<u128>()
-							.into();
-						let increased_cumulative_0: U256 = U256::from(
-							ExchangeRate::checked_from_rational(pool_1, pool_0)
-								.unwrap_or_default()
-								.into_inner(),
-						)
-						.saturating_mul(elapsed_time);
-						let increased_cumulative_1: U256 = U256::from(
-							ExchangeRate::checked_from_rational(pool_0, pool_1)
-								.unwrap_or_default()
-								.into_inner(),
-						)
-						.saturating_mul(elapsed_time);
+						let (increased_cumulative_0, increased_cumulative_1) = Self::get_increased_cumulatives(
+							pool_0,
+							pool_1,
+							now.saturating_sub(*last_cumulative_timestamp),
+						);
 
 						*cumulative_0 = cumulative_0.saturating_add(increased_cumulative_0);
 						*cumulative_1 = cumulative_1.saturating_add(increased_cumulative_1);
 						*last_cumulative_timestamp = now;
+
+						Self::try_record_observation(trading_pair, *cumulative_0, *cumulative_1, now);
 					}
 				},
 			);
 		}
 	}
 
+	fn get_increased_cumulatives(pool_0: Balance, pool_1: Balance, elapsed_time: MomentOf<T>) -> (U256, U256) {
+		let elapsed_time: U256 = elapsed_time.saturated_into::<u128>().into();
+		let increased_cumulative_0: U256 = U256::from(
+			ExchangeRate::checked_from_rational(pool_1, pool_0)
+				.unwrap_or_default()
+				.into_inner(),
+		)
+		.saturating_mul(elapsed_time);
+		let increased_cumulative_1: U256 = U256::from(
+			ExchangeRate::checked_from_rational(pool_0, pool_1)
+				.unwrap_or_default()
+				.into_inner(),
+		)
+		.saturating_mul(elapsed_time);
+		(increased_cumulative_0, increased_cumulative_1)
+	}
+
+	/// Whether `ObservationPeriod` has passed since the latest observation of the trading pair.
+	fn is_observation_due(trading_pair: &TradingPair, now: MomentOf<T>) -> bool {
+		let (latest_index, count) = Self::observation_rings(trading_pair);
+		if count.is_zero() {
+			return true;
+		}
+		Self::observations(trading_pair, latest_index).map_or(true, |observation| {
+			now.saturating_sub(observation.timestamp) >= T::ObservationPeriod::get()
+		})
+	}
+
+	/// Record the observation of cumulatives into the ring buffer if it's due, the oldest
+	/// observation will be overwritten if the ring buffer is full.
+	fn try_record_observation(trading_pair: &TradingPair, cumulative_0: U256, cumulative_1: U256, now: MomentOf<T>) {
+		let max_observations = T::MaxObservations::get();
+		if max_observations.is_zero() || !Self::is_observation_due(trading_pair, now) {
+			return;
+		}
+
+		ObservationRings::<T>::mutate(trading_pair, |(latest_index, count)| {
+			let index = if count.is_zero() {
+				0
+			} else {
+				latest_index.saturating_add(1) % max_observations
+			};
+			Observations::<T>::insert(
+				trading_pair,
+				index,
+				Observation {
+					timestamp: now,
+					cumulative_0,
+					cumulative_1,
+				},
+			);
+			*latest_index = index;
+			*count = count.saturating_add(1).min(max_observations);
+		});
+	}
+
+	/// Get the time-weighted average price of `base` in `quote` over the `window` until the latest
+	/// observation. The price after the latest observation isn't extrapolated, and the latest
+	/// observation not later than `latest - window` is used as the start, so the actual window
+	/// may be longer than `window` by up to `ObservationPeriod`. Returns `None` if the trading
+	/// pair isn't enabled average price or the observations can't cover the window.
+	pub fn get_twap(base: CurrencyId, quote: CurrencyId, window: MomentOf<T>) -> Option<ExchangeRate> {
+		let trading_pair = TradingPair::from_currency_ids(base, quote)?;
+		if window.is_zero() || !AveragePrices::<T>::contains_key(&trading_pair) {
+			return None;
+		}
+
+		let (latest_index, count) = Self::observation_rings(&trading_pair);
+		if count.is_zero() {
+			return None;
+		}
+		let latest_observation = Self::observations(&trading_pair, latest_index)?;
+		let start = latest_observation.timestamp.checked_sub(&window)?;
+
+		// the observations are in chronological order starting from the oldest one.
+		let oldest_index = latest_index.saturating_add(1) % count;
+		let observation_at = |i: u32| Self::observations(&trading_pair, oldest_index.saturating_add(i) % count);
+
+		// binary search the number of observations not later than start
+		let (mut low, mut high) = (0u32, count);
+		while low < high {
+			let mid = low + (high - low) / 2;
+			if observation_at(mid)?.timestamp <= start {
+				low = mid + 1;
+			} else {
+				high = mid;
+			}
+		}
+		let observation = observation_at(low.checked_sub(1)?)?;
+
+		let (end_cumulative, start_cumulative) = if base == trading_pair.first() {
+			(latest_observation.cumulative_0, observation.cumulative_0)
+		} else {
+			(latest_observation.cumulative_1, observation.cumulative_1)
+		};
+		let elapsed_time: U256 = latest_observation
+			.timestamp
+			.saturating_sub(observation.timestamp)
+			.saturated_into::<u128>()
+			.into();
+
+		end_cumulative
+			.saturating_sub(start_cumulative)
+			.checked_div(elapsed_time)
+			.map(|price| ExchangeRate::from_inner(price.saturated_into::<u128>()))
+	}
+
+	/// Get the real-time price of `base` in `quote` from dex.
+	pub fn get_relative_current_price(base: CurrencyId, quote: CurrencyId) -> Option<ExchangeRate> {
+		CurrentDEXPriceProvider::<T>::get_relative_price(base, quote)
+	}
+
 	fn get_current_price(trading_pair: &TradingPair) -> Option<(ExchangeRate, ExchangeRate)> {
 		let (pool_0, pool_1) = T::DEX::get_liquidity_pool(trading_pair.first(), trading_pair.second());
 		ExchangeRate::checked_from_rational(pool_1, pool_0).zip(ExchangeRate::checked_from_rational(pool_0, pool_1))
@@ -173,10 +173,17 @@ ord_parameter_types! {
 	pub const One: AccountId = 1;
 }
 
+parameter_types! {
+	pub static ObservationPeriod: Moment = 10_000;
+	pub const MaxObservations: u32 = 3;
+}
+
 impl Config for Runtime {
 	type DEX = MockDEX;
 	type Time = Timestamp;
 	type UpdateOrigin = EnsureSignedBy<One, AccountId>;
+	type ObservationPeriod = ObservationPeriod;
+	type MaxObservations = MaxObservations;
 	type WeightInfo = ();
 }
 
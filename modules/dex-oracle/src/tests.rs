@@ -522,6 +522,134 @@ fn on_initialize_work() {
 	});
 }
 
+#[test]
+fn record_observation_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		ObservationPeriod::set(100);
+		set_pool(&AUSDDOTPair::get(), 1000, 100);
+		assert_ok!(DexOracle::enable_average_price(Origin::signed(1), AUSD, DOT, 10000));
+		assert_eq!(DexOracle::observation_rings(AUSDDOTPair::get()), (0, 1));
+		assert_eq!(
+			DexOracle::observations(AUSDDOTPair::get(), 0),
+			Some(Observation {
+				timestamp: 0,
+				cumulative_0: U256::from(0),
+				cumulative_1: U256::from(0),
+			})
+		);
+
+		// observation is not due
+		Timestamp::set_timestamp(50);
+		DexOracle::on_initialize(1);
+		assert_eq!(
+			DexOracle::cumulatives(AUSDDOTPair::get()),
+			(U256::from(0), U256::from(0), 0)
+		);
+		assert_eq!(DexOracle::observation_rings(AUSDDOTPair::get()), (0, 1));
+
+		// record observation even if it's not time to update average price
+		Timestamp::set_timestamp(100);
+		DexOracle::on_initialize(2);
+		assert_eq!(DexOracle::observation_rings(AUSDDOTPair::get()), (1, 2));
+		assert_eq!(
+			DexOracle::observations(AUSDDOTPair::get(), 1),
+			Some(Observation {
+				timestamp: 100,
+				cumulative_0: U256::from(10_000_000_000_000_000_000u128),
+				cumulative_1: U256::from(1_000_000_000_000_000_000_000u128),
+			})
+		);
+
+		set_pool(&AUSDDOTPair::get(), 1000, 200);
+		Timestamp::set_timestamp(200);
+		DexOracle::on_initialize(3);
+		assert_eq!(DexOracle::observation_rings(AUSDDOTPair::get()), (2, 3));
+
+		// overwrite the oldest observation if the ring buffer is full
+		Timestamp::set_timestamp(300);
+		DexOracle::on_initialize(4);
+		assert_eq!(DexOracle::observation_rings(AUSDDOTPair::get()), (0, 3));
+		assert_eq!(
+			DexOracle::observations(AUSDDOTPair::get(), 0),
+			Some(Observation {
+				timestamp: 300,
+				cumulative_0: U256::from(50_000_000_000_000_000_000u128),
+				cumulative_1: U256::from(2_000_000_000_000_000_000_000u128),
+			})
+		);
+
+		assert_ok!(DexOracle::disable_average_price(Origin::signed(1), AUSD, DOT));
+		assert_eq!(DexOracle::observation_rings(AUSDDOTPair::get()), (0, 0));
+		assert_eq!(DexOracle::observations(AUSDDOTPair::get(), 0), None);
+		assert_eq!(DexOracle::observations(AUSDDOTPair::get(), 1), None);
+	});
+}
+
+#[test]
+fn get_twap_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		ObservationPeriod::set(100);
+		set_pool(&AUSDDOTPair::get(), 1000, 100);
+		assert_eq!(DexOracle::get_twap(AUSD, DOT, 100), None);
+		assert_ok!(DexOracle::enable_average_price(Origin::signed(1), AUSD, DOT, 10000));
+
+		Timestamp::set_timestamp(100);
+		DexOracle::on_initialize(1);
+		set_pool(&AUSDDOTPair::get(), 1000, 200);
+		Timestamp::set_timestamp(200);
+		DexOracle::on_initialize(2);
+		Timestamp::set_timestamp(300);
+		DexOracle::on_initialize(3);
+		set_pool(&AUSDDOTPair::get(), 1000, 400);
+		Timestamp::set_timestamp(350);
+
+		// the observations of 100, 200 and 300 are kept, the current price after the latest
+		// observation isn't extrapolated
+		assert_eq!(
+			DexOracle::get_twap(AUSD, DOT, 50),
+			Some(ExchangeRate::saturating_from_rational(2, 10))
+		);
+		assert_eq!(
+			DexOracle::get_twap(AUSD, DOT, 200),
+			Some(ExchangeRate::saturating_from_rational(2, 10))
+		);
+		assert_eq!(DexOracle::get_twap(AUSD, DOT, 250), None);
+
+		Timestamp::set_timestamp(400);
+		DexOracle::on_initialize(4);
+
+		// the observations of 200, 300 and 400 are kept
+		assert_eq!(
+			DexOracle::get_twap(AUSD, DOT, 50),
+			Some(ExchangeRate::saturating_from_rational(4, 10))
+		);
+		assert_eq!(
+			DexOracle::get_twap(DOT, AUSD, 50),
+			Some(ExchangeRate::saturating_from_rational(25, 10))
+		);
+		assert_eq!(
+			DexOracle::get_twap(AUSD, DOT, 100),
+			Some(ExchangeRate::saturating_from_rational(4, 10))
+		);
+		assert_eq!(
+			DexOracle::get_twap(AUSD, DOT, 150),
+			Some(ExchangeRate::saturating_from_rational(3, 10))
+		);
+		assert_eq!(
+			DexOracle::get_twap(AUSD, DOT, 200),
+			Some(ExchangeRate::saturating_from_rational(3, 10))
+		);
+
+		// the observations can't cover the window
+		assert_eq!(DexOracle::get_twap(AUSD, DOT, 250), None);
+		assert_eq!(DexOracle::get_twap(AUSD, DOT, 300), None);
+		assert_eq!(DexOracle::get_twap(AUSD, DOT, 400), None);
+		assert_eq!(DexOracle::get_twap(AUSD, DOT, 0), None);
+		assert_eq!(DexOracle::get_twap(ACA, DOT, 50), None);
+		assert_eq!(DexOracle::get_twap(AUSD, LP_AUSD_DOT, 50), None);
+	});
+}
+
 #[test]
 fn dex_price_providers_work() {
 	ExtBuilder::default().build().execute_with(|| {
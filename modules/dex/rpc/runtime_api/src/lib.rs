@@ -0,0 +1,88 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Runtime API definition for DEX module.
+
+#![cfg_attr(not(feature = "std"), no_std)]
+#![allow(clippy::too_many_arguments)]
+#![allow(clippy::unnecessary_mut_passed)]
+
+use module_dex::TradingPairStatus;
+use primitives::CurrencyId;
+use sp_runtime::{
+	codec::Codec,
+	traits::{MaybeDisplay, MaybeFromStr},
+};
+use sp_std::vec::Vec;
+use support::SwapLimit;
+
+sp_api::decl_runtime_apis! {
+	pub trait DexRuntimeApi<Balance, BlockNumber> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+		BlockNumber: Codec,
+	{
+		/// Get the liquidity pool of the trading pair, in the order of
+		/// (currency_id_a, currency_id_b).
+		fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance);
+
+		/// Get the (supply_amount, target_amount) of swap along the specific path with limit.
+		/// Returns `None` if the path is invalid or the limit can't be satisfied.
+		fn get_swap_amount(path: Vec<CurrencyId>, limit: SwapLimit<Balance>) -> Option<(Balance, Balance)>;
+
+		/// Get the status of the trading pair, the amounts of Provisioning parameters are in the
+		/// order of (currency_id_a, currency_id_b).
+		fn get_trading_pair_status(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> Option<TradingPairStatus<Balance, BlockNumber>>;
+
+		/// Get the amounts of currencies that `share_amount` dex share of the trading pair are
+		/// worth, in the order of (currency_id_a, currency_id_b).
+		fn get_liquidity_share_value(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			share_amount: Balance,
+		) -> Option<(Balance, Balance)>;
+
+		/// Get the accumulated protocol fee charged from the trading pair, in the order of
+		/// (currency_id_a, currency_id_b).
+		fn get_collected_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance);
+
+		/// Get the protocol fee charged from the trading pair which hasn't been withdrawn to
+		/// treasury, in the order of (currency_id_a, currency_id_b).
+		fn get_pending_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance);
+
+		/// Search the Enabled trading pairs through the route tokens for the swap path which gets
+		/// the most target amount with the exact supply amount. Returns the path and the target
+		/// amount.
+		fn get_best_swap_path_with_exact_supply(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, Balance)>;
+
+		/// Search the Enabled trading pairs through the route tokens for the swap path which pays
+		/// the least supply amount for the exact target amount. Returns the path and the supply
+		/// amount.
+		fn get_best_swap_path_with_exact_target(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			target_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, Balance)>;
+	}
+}
@@ -0,0 +1,341 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! RPC interface for the DEX module.
+
+use codec::Codec;
+use jsonrpc_core::{Error, ErrorCode, Result};
+use jsonrpc_derive::rpc;
+use primitives::CurrencyId;
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_rpc::number::NumberOrHex;
+use sp_runtime::{
+	generic::BlockId,
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
+};
+use std::{marker::PhantomData, sync::Arc};
+
+pub use module_dex_rpc_runtime_api::DexRuntimeApi;
+pub use types::{SwapAmount, SwapLimit, SwapPath, TradingPairStatus};
+
+mod types;
+
+/// DEX rpc interface.
+#[rpc]
+pub trait DexApi<BlockHash, BlockNumber> {
+	/// Get the liquidity pool of the trading pair, in the order of (currency_id_a, currency_id_b).
+	#[rpc(name = "dex_getLiquidityPool")]
+	fn get_liquidity_pool(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<(NumberOrHex, NumberOrHex)>;
+
+	/// Get the supply amount and target amount of swap along the path with limit.
+	#[rpc(name = "dex_getSwapAmount")]
+	fn get_swap_amount(
+		&self,
+		path: Vec<CurrencyId>,
+		limit: SwapLimit,
+		at: Option<BlockHash>,
+	) -> Result<Option<SwapAmount>>;
+
+	/// Get the best swap path and the target amount with exact supply amount.
+	#[rpc(name = "dex_getBestSwapPathWithExactSupply")]
+	fn get_best_swap_path_with_exact_supply(
+		&self,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Option<SwapPath<CurrencyId>>>;
+
+	/// Get the best swap path and the supply amount with exact target amount.
+	#[rpc(name = "dex_getBestSwapPathWithExactTarget")]
+	fn get_best_swap_path_with_exact_target(
+		&self,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Option<SwapPath<CurrencyId>>>;
+
+	/// Get the status and Provisioning parameters of the trading pair.
+	#[rpc(name = "dex_getTradingPairStatus")]
+	fn get_trading_pair_status(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<Option<TradingPairStatus<BlockNumber>>>;
+
+	/// Get the amounts of currencies that the dex share of the trading pair are worth, in the
+	/// order of (currency_id_a, currency_id_b).
+	#[rpc(name = "dex_getLiquidityShareValue")]
+	fn get_liquidity_share_value(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		share_amount: NumberOrHex,
+		at: Option<BlockHash>,
+	) -> Result<Option<(NumberOrHex, NumberOrHex)>>;
+
+	/// Get the protocol fee collected from the trading pair, in the order of
+	/// (currency_id_a, currency_id_b).
+	#[rpc(name = "dex_getCollectedProtocolFees")]
+	fn get_collected_protocol_fees(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<(NumberOrHex, NumberOrHex)>;
+
+	/// Get the protocol fee of the trading pair which hasn't been withdrawn, in the order of
+	/// (currency_id_a, currency_id_b).
+	#[rpc(name = "dex_getPendingProtocolFees")]
+	fn get_pending_protocol_fees(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<BlockHash>,
+	) -> Result<(NumberOrHex, NumberOrHex)>;
+}
+
+/// Error type of this RPC api.
+pub enum DexError {
+	/// The call to runtime failed.
+	RuntimeError,
+	/// The amount is invalid.
+	InvalidAmount,
+}
+
+impl From<DexError> for i64 {
+	fn from(e: DexError) -> i64 {
+		match e {
+			DexError::RuntimeError => 1,
+			DexError::InvalidAmount => 2,
+		}
+	}
+}
+
+/// A struct that implements the [`DexApi`].
+pub struct Dex<C, B, Balance> {
+	client: Arc<C>,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<C, B, Balance> Dex<C, B, Balance> {
+	/// Create new `Dex` with the given reference to the client.
+	pub fn new(client: Arc<C>) -> Self {
+		Self {
+			client,
+			_marker: Default::default(),
+		}
+	}
+}
+
+fn runtime_error<T: std::fmt::Debug>(e: T) -> Error {
+	Error {
+		code: ErrorCode::ServerError(DexError::RuntimeError.into()),
+		message: "Unable to query dex.".into(),
+		data: Some(format!("{:?}", e).into()),
+	}
+}
+
+fn to_balance<Balance: TryFrom<NumberOrHex>>(amount: NumberOrHex) -> Result<Balance> {
+	Balance::try_from(amount).map_err(|_| Error {
+		code: ErrorCode::ServerError(DexError::InvalidAmount.into()),
+		message: "Invalid amount.".into(),
+		data: Some(format!("{:?}", amount).into()),
+	})
+}
+
+fn to_pair<Balance: Into<NumberOrHex>>((amount_a, amount_b): (Balance, Balance)) -> (NumberOrHex, NumberOrHex) {
+	(amount_a.into(), amount_b.into())
+}
+
+impl<C, Block, Balance, BlockNumber> DexApi<<Block as BlockT>::Hash, BlockNumber> for Dex<C, Block, Balance>
+where
+	Block: BlockT,
+	C: Send + Sync + 'static + ProvideRuntimeApi<Block> + HeaderBackend<Block>,
+	C::Api: DexRuntimeApi<Block, Balance, BlockNumber>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Send + Sync + 'static + Into<NumberOrHex> + TryFrom<NumberOrHex>,
+	BlockNumber: Codec + Send + Sync + 'static,
+{
+	fn get_liquidity_pool(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<(NumberOrHex, NumberOrHex)> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_liquidity_pool(&at, currency_id_a, currency_id_b)
+			.map(to_pair)
+			.map_err(runtime_error)
+	}
+
+	fn get_swap_amount(
+		&self,
+		path: Vec<CurrencyId>,
+		limit: SwapLimit,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SwapAmount>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		let limit = match limit {
+			SwapLimit::ExactSupply(supply_amount, min_target_amount) => {
+				module_support::SwapLimit::ExactSupply(to_balance(supply_amount)?, to_balance(min_target_amount)?)
+			}
+			SwapLimit::ExactTarget(max_supply_amount, target_amount) => {
+				module_support::SwapLimit::ExactTarget(to_balance(max_supply_amount)?, to_balance(target_amount)?)
+			}
+		};
+
+		api.get_swap_amount(&at, path, limit)
+			.map(|amounts| {
+				amounts.map(|(supply_amount, target_amount)| SwapAmount {
+					supply_amount: supply_amount.into(),
+					target_amount: target_amount.into(),
+				})
+			})
+			.map_err(runtime_error)
+	}
+
+	fn get_best_swap_path_with_exact_supply(
+		&self,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		supply_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SwapPath<CurrencyId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_best_swap_path_with_exact_supply(
+			&at,
+			supply_currency_id,
+			target_currency_id,
+			to_balance(supply_amount)?,
+		)
+		.map(|result| {
+			result.map(|(path, amount)| SwapPath {
+				path,
+				amount: amount.into(),
+			})
+		})
+		.map_err(runtime_error)
+	}
+
+	fn get_best_swap_path_with_exact_target(
+		&self,
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		target_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<SwapPath<CurrencyId>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_best_swap_path_with_exact_target(
+			&at,
+			supply_currency_id,
+			target_currency_id,
+			to_balance(target_amount)?,
+		)
+		.map(|result| {
+			result.map(|(path, amount)| SwapPath {
+				path,
+				amount: amount.into(),
+			})
+		})
+		.map_err(runtime_error)
+	}
+
+	fn get_trading_pair_status(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<TradingPairStatus<BlockNumber>>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_trading_pair_status(&at, currency_id_a, currency_id_b)
+			.map(|status| {
+				status.map(|status| match status {
+					module_dex::TradingPairStatus::Disabled => TradingPairStatus::Disabled,
+					module_dex::TradingPairStatus::Provisioning(parameters) => TradingPairStatus::Provisioning {
+						min_contribution: to_pair(parameters.min_contribution),
+						target_provision: to_pair(parameters.target_provision),
+						accumulated_provision: to_pair(parameters.accumulated_provision),
+						not_before: parameters.not_before,
+					},
+					module_dex::TradingPairStatus::Enabled => TradingPairStatus::Enabled,
+				})
+			})
+			.map_err(runtime_error)
+	}
+
+	fn get_liquidity_share_value(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		share_amount: NumberOrHex,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<Option<(NumberOrHex, NumberOrHex)>> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_liquidity_share_value(&at, currency_id_a, currency_id_b, to_balance(share_amount)?)
+			.map(|value| value.map(to_pair))
+			.map_err(runtime_error)
+	}
+
+	fn get_collected_protocol_fees(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<(NumberOrHex, NumberOrHex)> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_collected_protocol_fees(&at, currency_id_a, currency_id_b)
+			.map(to_pair)
+			.map_err(runtime_error)
+	}
+
+	fn get_pending_protocol_fees(
+		&self,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		at: Option<<Block as BlockT>::Hash>,
+	) -> Result<(NumberOrHex, NumberOrHex)> {
+		let api = self.client.runtime_api();
+		let at = BlockId::hash(at.unwrap_or_else(|| self.client.info().best_hash));
+
+		api.get_pending_protocol_fees(&at, currency_id_a, currency_id_b)
+			.map(to_pair)
+			.map_err(runtime_error)
+	}
+}
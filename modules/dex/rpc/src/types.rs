@@ -0,0 +1,84 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use serde::{Deserialize, Serialize};
+use sp_rpc::number::NumberOrHex;
+
+/// Swap limit
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum SwapLimit {
+	/// (exact_supply_amount, minimum_target_amount)
+	ExactSupply(NumberOrHex, NumberOrHex),
+	/// (maximum_supply_amount, exact_target_amount)
+	ExactTarget(NumberOrHex, NumberOrHex),
+}
+
+/// Swap amount response
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapAmount {
+	/// Supply amount
+	pub supply_amount: NumberOrHex,
+	/// Target amount
+	pub target_amount: NumberOrHex,
+}
+
+/// Swap path response
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct SwapPath<CurrencyId> {
+	/// Trading path
+	pub path: Vec<CurrencyId>,
+	/// Supply amount for exact target, or target amount for exact supply
+	pub amount: NumberOrHex,
+}
+
+/// Trading pair status response
+#[derive(Debug, Eq, PartialEq, Serialize, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub enum TradingPairStatus<BlockNumber> {
+	/// Disabled
+	Disabled,
+	/// Provisioning, the amounts are in the order of (currency_id_a, currency_id_b)
+	#[serde(rename_all = "camelCase")]
+	Provisioning {
+		/// Minimum contribution
+		min_contribution: (NumberOrHex, NumberOrHex),
+		/// Target provision
+		target_provision: (NumberOrHex, NumberOrHex),
+		/// Accumulated provision
+		accumulated_provision: (NumberOrHex, NumberOrHex),
+		/// The block number that the trading pair can be enabled
+		not_before: BlockNumber,
+	},
+	/// Enabled
+	Enabled,
+}
+
+#[test]
+fn swap_limit_should_deserialize() {
+	assert_eq!(
+		serde_json::from_str::<SwapLimit>(r#"{"exactSupply":[100,"0x10"]}"#).unwrap(),
+		SwapLimit::ExactSupply(NumberOrHex::Number(100), NumberOrHex::Hex(16.into()))
+	);
+	assert_eq!(
+		serde_json::from_str::<SwapLimit>(r#"{"exactTarget":["0xff",1]}"#).unwrap(),
+		SwapLimit::ExactTarget(NumberOrHex::Hex(255.into()), NumberOrHex::Number(1))
+	);
+}
@@ -34,18 +34,18 @@
 #![allow(clippy::collapsible_if)]
 
 use codec::MaxEncodedLen;
-use frame_support::{log, pallet_prelude::*, transactional, PalletId};
+use frame_support::{log, pallet_prelude::*, traits::UnixTime, transactional, PalletId};
 use frame_system::pallet_prelude::*;
 use orml_traits::{Happened, MultiCurrency, MultiCurrencyExtended};
-use primitives::{Balance, CurrencyId, TradingPair};
+use primitives::{Balance, CurrencyId, Moment, TradingPair};
 use scale_info::TypeInfo;
 use sp_core::{H160, U256};
 use sp_runtime::{
-	traits::{AccountIdConversion, One, Zero},
+	traits::{AccountIdConversion, One, Saturating, StaticLookup, Zero},
 	ArithmeticError, DispatchError, DispatchResult, FixedPointNumber, RuntimeDebug, SaturatedConversion,
 };
-use sp_std::{prelude::*, vec};
-use support::{DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, Ratio, SwapLimit};
+use sp_std::{collections::btree_map::BTreeMap, prelude::*, vec};
+use support::{DEXFlashSwapCallback, DEXIncentives, DEXManager, Erc20InfoMapping, ExchangeRate, Ratio, SwapLimit};
 
 mod mock;
 mod tests;
@@ -58,13 +58,22 @@ pub use weights::WeightInfo;
 #[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
 pub struct ProvisioningParameters<Balance, BlockNumber> {
 	/// limit contribution per time.
-	min_contribution: (Balance, Balance),
+	pub min_contribution: (Balance, Balance),
 	/// target provision that trading pair could to be Enabled.
-	target_provision: (Balance, Balance),
+	pub target_provision: (Balance, Balance),
 	/// accumulated provision amount for this Provisioning trading pair.
-	accumulated_provision: (Balance, Balance),
+	pub accumulated_provision: (Balance, Balance),
 	/// The number of block that status can be converted to Enabled.
-	not_before: BlockNumber,
+	pub not_before: BlockNumber,
+}
+
+/// Deadline of swap, the swap fails if it's executed after the deadline.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub enum SwapDeadline<BlockNumber> {
+	/// The last block number at which the swap can be executed.
+	BlockNumber(BlockNumber),
+	/// The last unix timestamp in milliseconds at which the swap can be executed.
+	Timestamp(Moment),
 }
 
 /// Status for TradingPair
@@ -87,6 +96,67 @@ impl<Balance, BlockNumber> Default for TradingPairStatus<Balance, BlockNumber> {
 	}
 }
 
+/// Invariant curve of the liquidity pool of TradingPair.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, MaxEncodedLen, TypeInfo)]
+pub enum Curve {
+	/// Constant product invariant (k = x * y) of Uniswap V2.
+	ConstantProduct,
+	/// StableSwap invariant of Curve with the amplification coefficient, which gives lower
+	/// slippage for pegged currencies. Like Curve, the pools are normalized by
+	/// `pool * precision_multiplier * rate_multiplier / STABLE_SWAP_RATE_PRECISION` before
+	/// computing the invariant, the multipliers are in the order of the trading pair.
+	StableSwap {
+		amplification: u32,
+		/// Scale the pools of currencies with different decimals to the same precision.
+		precision_multipliers: (Balance, Balance),
+		/// The value of the currencies in the pegged asset, `STABLE_SWAP_RATE_PRECISION` means
+		/// one.
+		rate_multipliers: (Balance, Balance),
+	},
+}
+
+impl Default for Curve {
+	fn default() -> Self {
+		Self::ConstantProduct
+	}
+}
+
+impl Curve {
+	/// StableSwap curve of the currencies which have the same precision and value.
+	pub fn stable_swap(amplification: u32) -> Self {
+		Self::StableSwap {
+			amplification,
+			precision_multipliers: (1, 1),
+			rate_multipliers: (STABLE_SWAP_RATE_PRECISION, STABLE_SWAP_RATE_PRECISION),
+		}
+	}
+
+	/// The curve with the multipliers in the reversed order.
+	fn reversed(self) -> Self {
+		match self {
+			Self::ConstantProduct => Self::ConstantProduct,
+			Self::StableSwap {
+				amplification,
+				precision_multipliers: (precision_multiplier_0, precision_multiplier_1),
+				rate_multipliers: (rate_multiplier_0, rate_multiplier_1),
+			} => Self::StableSwap {
+				amplification,
+				precision_multipliers: (precision_multiplier_1, precision_multiplier_0),
+				rate_multipliers: (rate_multiplier_1, rate_multiplier_0),
+			},
+		}
+	}
+}
+
+/// The maximum amplification coefficient of StableSwap curve.
+pub const MAX_AMPLIFICATION: u32 = 1_000_000;
+
+/// The precision of the rate multipliers of StableSwap curve.
+pub const STABLE_SWAP_RATE_PRECISION: Balance = 1_000_000_000_000_000_000;
+
+/// The maximum iterations to compute the StableSwap invariant by Newton's method.
+const MAX_STABLE_SWAP_ITERATIONS: u32 = 255;
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -98,7 +168,8 @@ pub mod module {
 		/// Currency for transfer currencies
 		type Currency: MultiCurrencyExtended<Self::AccountId, CurrencyId = CurrencyId, Balance = Balance>;
 
-		/// Trading fee rate
+		/// Default trading fee rate, used if the trading pair has no fee rate set in
+		/// `ExchangeFees`.
 		/// The first item of the tuple is the numerator of the fee rate, second
 		/// item is the denominator, fee_rate = numerator / denominator,
 		/// use (u32, u32) over `Rate` type to minimize internal division
@@ -110,6 +181,11 @@ pub mod module {
 		#[pallet::constant]
 		type TradingPathLimit: Get<u32>;
 
+		/// The maximum number of route tokens which can be the intermediate currencies of the
+		/// best route search.
+		#[pallet::constant]
+		type MaxRouteTokens: Get<u32>;
+
 		/// The DEX's module id, keep all assets in DEX.
 		#[pallet::constant]
 		type PalletId: Get<PalletId>;
@@ -129,6 +205,22 @@ pub mod module {
 
 		/// Event handler which calls when update liquidity pool.
 		type OnLiquidityPoolUpdated: Happened<(TradingPair, Balance, Balance)>;
+
+		/// The extended provisioning blocks since the `not_before` of provisioning, after which
+		/// anyone can abort a provisioning that has not been ended.
+		#[pallet::constant]
+		type ExtendedProvisioningBlocks: Get<Self::BlockNumber>;
+
+		/// Treasury account which receives the protocol fee of swaps.
+		#[pallet::constant]
+		type TreasuryAccount: Get<Self::AccountId>;
+
+		/// Callback of flash swap, called after the borrowed currencies have been sent to the
+		/// borrower and before the repayment is taken back.
+		type FlashSwapCallback: DEXFlashSwapCallback<Self::AccountId, CurrencyId, Balance>;
+
+		/// Time used for checking the timestamp deadline of swap.
+		type UnixTime: UnixTime;
 	}
 
 	#[pallet::error]
@@ -175,6 +267,29 @@ pub mod module {
 		AssetUnregistered,
 		/// The trading path is invalid
 		InvalidTradingPath,
+		/// The provisioning is not allowed to be aborted yet
+		NotAllowedAbort,
+		/// The provision is not allowed to be refunded
+		NotAllowedRefund,
+		/// The provision of aborted provisioning is not allowed to be claimed as dex share
+		NotAllowedClaim,
+		/// The exchange fee rate is invalid
+		InvalidExchangeFee,
+		/// The share of protocol fee is invalid
+		InvalidProtocolFeeShare,
+		/// The borrowed amount of flash swap is invalid
+		InvalidFlashSwapAmount,
+		/// The trading pair is locked by an ongoing flash swap
+		FlashSwapLocked,
+		/// The deadline of swap has passed
+		SwapExpired,
+		/// The curve of liquidity pool is invalid
+		InvalidCurve,
+		/// The curve can only be set when the liquidity pool is empty or the trading pair is
+		/// Disabled
+		NotAllowedSetCurve,
+		/// The number of route tokens exceeds `MaxRouteTokens`
+		TooManyRouteTokens,
 	}
 
 	#[pallet::event]
@@ -225,6 +340,53 @@ pub mod module {
 			pool_1: Balance,
 			share_amount: Balance,
 		},
+		/// Provisioning of trading pair is aborted, provisions can be refunded.
+		ProvisioningAborted {
+			trading_pair: TradingPair,
+			accumulated_provision_0: Balance,
+			accumulated_provision_1: Balance,
+		},
+		/// Refund provision success.
+		RefundProvision {
+			who: T::AccountId,
+			currency_0: CurrencyId,
+			contribution_0: Balance,
+			currency_1: CurrencyId,
+			contribution_1: Balance,
+		},
+		/// The exchange fee rate of trading pair is updated, `None` means use the default rate.
+		ExchangeFeeUpdated {
+			trading_pair: TradingPair,
+			exchange_fee: Option<(u32, u32)>,
+		},
+		/// The share of exchange fee charged as protocol fee is updated.
+		ProtocolFeeShareUpdated { share: Ratio },
+		/// Protocol fee is charged from the swap of trading pair.
+		ProtocolFeeCharged {
+			trading_pair: TradingPair,
+			currency_id: CurrencyId,
+			amount: Balance,
+		},
+		/// Charged protocol fee of trading pair is withdrawn to treasury account.
+		ProtocolFeeWithdrawn {
+			trading_pair: TradingPair,
+			amount_0: Balance,
+			amount_1: Balance,
+		},
+		/// Flash swap from the liquidity pool success.
+		FlashSwap {
+			who: T::AccountId,
+			currency_0: CurrencyId,
+			borrowed_0: Balance,
+			repaid_0: Balance,
+			currency_1: CurrencyId,
+			borrowed_1: Balance,
+			repaid_1: Balance,
+		},
+		/// The curve of trading pair is updated.
+		CurveUpdated { trading_pair: TradingPair, curve: Curve },
+		/// The route tokens of the best route search are updated.
+		RouteTokensUpdated { route_tokens: Vec<CurrencyId> },
 	}
 
 	/// Liquidity pool for TradingPair.
@@ -251,7 +413,16 @@ pub mod module {
 	pub type ProvisioningPool<T: Config> =
 		StorageDoubleMap<_, Twox64Concat, TradingPair, Twox64Concat, T::AccountId, (Balance, Balance), ValueQuery>;
 
-	/// Initial exchange rate, used to calculate the dex share amount for founders of provisioning
+	/// The provisions of the aborted provisioning of TradingPair which haven't been refunded yet.
+	/// It exists from aborting the provisioning until all the provisions are refunded.
+	///
+	/// AbortedProvisioning: map TradingPair => Option<(Balance, Balance)>
+	#[pallet::storage]
+	#[pallet::getter(fn aborted_provisioning)]
+	pub type AbortedProvisioning<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (Balance, Balance), OptionQuery>;
+
+	/// Initial exchange rate, used to calculate the dex share amount for founders of provisioning.
+	/// It only exists for trading pairs which have been converted from Provisioning to Enabled.
 	///
 	/// InitialShareExchangeRates: map TradingPair => (ExchangeRate, ExchangeRate)
 	#[pallet::storage]
@@ -259,6 +430,61 @@ pub mod module {
 	pub type InitialShareExchangeRates<T: Config> =
 		StorageMap<_, Twox64Concat, TradingPair, (ExchangeRate, ExchangeRate), ValueQuery>;
 
+	/// Exchange fee rate for TradingPair, `GetExchangeFee` is used if it isn't set.
+	///
+	/// ExchangeFees: map TradingPair => Option<(u32, u32)>
+	#[pallet::storage]
+	#[pallet::getter(fn exchange_fees)]
+	pub type ExchangeFees<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (u32, u32), OptionQuery>;
+
+	/// The share of exchange fee charged as protocol fee, which will be withdrawn to
+	/// `TreasuryAccount` rather than kept in liquidity pool. Protocol fee is switched off if it's
+	/// zero.
+	///
+	/// ProtocolFeeShare: Ratio
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_share)]
+	pub type ProtocolFeeShare<T: Config> = StorageValue<_, Ratio, ValueQuery>;
+
+	/// Protocol fee of TradingPair which has been charged but not yet withdrawn to
+	/// `TreasuryAccount`.
+	///
+	/// ProtocolFeePool: map TradingPair => (Balance, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn protocol_fee_pool)]
+	pub type ProtocolFeePool<T: Config> = StorageMap<_, Twox64Concat, TradingPair, (Balance, Balance), ValueQuery>;
+
+	/// Accumulated protocol fee charged from TradingPair.
+	///
+	/// CollectedProtocolFees: map TradingPair => (Balance, Balance)
+	#[pallet::storage]
+	#[pallet::getter(fn collected_protocol_fees)]
+	pub type CollectedProtocolFees<T: Config> =
+		StorageMap<_, Twox64Concat, TradingPair, (Balance, Balance), ValueQuery>;
+
+	/// The TradingPair which has an ongoing flash swap, it can't be swapped and liquidity of it
+	/// can't be added or removed until the flash swap is finished.
+	///
+	/// FlashSwapLocks: map TradingPair => bool
+	#[pallet::storage]
+	#[pallet::getter(fn flash_swap_locks)]
+	pub type FlashSwapLocks<T: Config> = StorageMap<_, Twox64Concat, TradingPair, bool, ValueQuery>;
+
+	/// The invariant curve of TradingPair, `Curve::ConstantProduct` is used if it isn't set.
+	///
+	/// Curves: map TradingPair => Curve
+	#[pallet::storage]
+	#[pallet::getter(fn curves)]
+	pub type Curves<T: Config> = StorageMap<_, Twox64Concat, TradingPair, Curve, ValueQuery>;
+
+	/// The currencies which can be the intermediate currencies of the best route search, only
+	/// the trading pairs between them and the supply/target currencies are searched.
+	///
+	/// RouteTokens: Vec<CurrencyId>
+	#[pallet::storage]
+	#[pallet::getter(fn route_tokens)]
+	pub type RouteTokens<T: Config> = StorageValue<_, BoundedVec<CurrencyId, T::MaxRouteTokens>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub initial_listing_trading_pairs: Vec<(TradingPair, (Balance, Balance), (Balance, Balance), T::BlockNumber)>,
@@ -368,6 +594,56 @@ pub mod module {
 			Ok(())
 		}
 
+		/// Trading with DEX, swap with exact supply amount before the deadline, and send the
+		/// target currency to `dest`.
+		///
+		/// - `path`: trading path.
+		/// - `supply_amount`: exact supply amount.
+		/// - `min_target_amount`: acceptable minimum target amount.
+		/// - `dest`: the account to receive the target currency.
+		/// - `deadline`: the swap fails if it's executed after the deadline.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_supply_to(path.len() as u32))]
+		#[transactional]
+		pub fn swap_with_exact_supply_to(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] supply_amount: Balance,
+			#[pallet::compact] min_target_amount: Balance,
+			dest: <T::Lookup as StaticLookup>::Source,
+			deadline: SwapDeadline<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_before_deadline(deadline)?;
+			Self::do_swap_with_exact_supply_to(&who, &dest, &path, supply_amount, min_target_amount)?;
+			Ok(())
+		}
+
+		/// Trading with DEX, swap with exact target amount before the deadline, and send the
+		/// target currency to `dest`.
+		///
+		/// - `path`: trading path.
+		/// - `target_amount`: exact target amount.
+		/// - `max_supply_amount`: acceptable maximum supply amount.
+		/// - `dest`: the account to receive the target currency.
+		/// - `deadline`: the swap fails if it's executed after the deadline.
+		#[pallet::weight(<T as Config>::WeightInfo::swap_with_exact_target_to(path.len() as u32))]
+		#[transactional]
+		pub fn swap_with_exact_target_to(
+			origin: OriginFor<T>,
+			path: Vec<CurrencyId>,
+			#[pallet::compact] target_amount: Balance,
+			#[pallet::compact] max_supply_amount: Balance,
+			dest: <T::Lookup as StaticLookup>::Source,
+			deadline: SwapDeadline<T::BlockNumber>,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let dest = T::Lookup::lookup(dest)?;
+			Self::ensure_before_deadline(deadline)?;
+			Self::do_swap_with_exact_target_to(&who, &dest, &path, target_amount, max_supply_amount)?;
+			Ok(())
+		}
+
 		/// Add liquidity to Enabled trading pair.
 		/// - Add provision success will record the provision, issue shares to caller in the initial
 		///   exchange rate when trading pair convert to Enabled.
@@ -513,7 +789,8 @@ pub mod module {
 			);
 			ensure!(
 				T::Currency::total_issuance(trading_pair.dex_share_currency_id()).is_zero()
-					&& ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none(),
+					&& ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none()
+					&& !AbortedProvisioning::<T>::contains_key(trading_pair),
 				Error::<T>::NotAllowedList
 			);
 
@@ -692,7 +969,13 @@ pub mod module {
 			let trading_pair =
 				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
 			match Self::trading_pair_statuses(trading_pair) {
-				TradingPairStatus::<_, _>::Disabled => {}
+				TradingPairStatus::<_, _>::Disabled => {
+					// the provisions of aborted provisioning must be refunded before enable it.
+					ensure!(
+						!AbortedProvisioning::<T>::contains_key(trading_pair),
+						Error::<T>::NotAllowedList
+					);
+				}
 				TradingPairStatus::<_, _>::Provisioning(provisioning_parameters) => {
 					ensure!(
 						provisioning_parameters.accumulated_provision.0.is_zero()
@@ -731,6 +1014,262 @@ pub mod module {
 			Self::deposit_event(Event::DisableTradingPair { trading_pair });
 			Ok(())
 		}
+
+		/// Abort a Provisioning trading pair, the trading pair will become Disabled status and
+		/// the founders can refund their provisions.
+		/// `ListingOrigin` can abort it at any time, other signed origins can only abort it when it
+		/// is still unqualified after `not_before` + `ExtendedProvisioningBlocks`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[pallet::weight(<T as Config>::WeightInfo::abort_provisioning())]
+		#[transactional]
+		pub fn abort_provisioning(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResult {
+			let is_listing_origin = match T::ListingOrigin::try_origin(origin) {
+				Ok(_) => true,
+				Err(origin) => {
+					let _ = ensure_signed(origin)?;
+					false
+				}
+			};
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			match Self::trading_pair_statuses(trading_pair) {
+				TradingPairStatus::<_, _>::Provisioning(provisioning_parameters) => {
+					let (total_provision_0, total_provision_1) = provisioning_parameters.accumulated_provision;
+					if !is_listing_origin {
+						let expired = frame_system::Pallet::<T>::block_number()
+							> provisioning_parameters
+								.not_before
+								.saturating_add(T::ExtendedProvisioningBlocks::get());
+						let qualified = !total_provision_0.is_zero()
+							&& !total_provision_1.is_zero()
+							&& (total_provision_0 >= provisioning_parameters.target_provision.0
+								|| total_provision_1 >= provisioning_parameters.target_provision.1);
+						ensure!(expired && !qualified, Error::<T>::NotAllowedAbort);
+					}
+
+					TradingPairStatuses::<T>::insert(trading_pair, TradingPairStatus::Disabled);
+					if ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_some() {
+						AbortedProvisioning::<T>::insert(trading_pair, (total_provision_0, total_provision_1));
+					}
+					Self::deposit_event(Event::ProvisioningAborted {
+						trading_pair,
+						accumulated_provision_0: total_provision_0,
+						accumulated_provision_1: total_provision_1,
+					});
+				}
+				_ => return Err(Error::<T>::MustBeProvisioning.into()),
+			}
+
+			Ok(())
+		}
+
+		/// Set the exchange fee rate of a trading pair.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `exchange_fee`: the (numerator, denominator) of fee rate, `None` means use the default
+		///   `GetExchangeFee`.
+		#[pallet::weight((<T as Config>::WeightInfo::set_exchange_fee(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_exchange_fee(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			exchange_fee: Option<(u32, u32)>,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			if let Some((fee_numerator, fee_denominator)) = exchange_fee {
+				ensure!(
+					!fee_denominator.is_zero() && fee_numerator < fee_denominator,
+					Error::<T>::InvalidExchangeFee
+				);
+			}
+
+			ExchangeFees::<T>::set(trading_pair, exchange_fee);
+			Self::deposit_event(Event::ExchangeFeeUpdated {
+				trading_pair,
+				exchange_fee,
+			});
+			Ok(())
+		}
+
+		/// Set the invariant curve of a trading pair.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `curve`: the curve used to calculate swap amounts and dex share of the liquidity pool,
+		///   the amplification of `Curve::StableSwap` must be in the range of 1 to
+		///   `MAX_AMPLIFICATION` and the multipliers must not be zero, which are in the order of
+		///   the trading pair.
+		///
+		/// The curve can only be set when the liquidity pool is empty or the trading pair is
+		/// Disabled, otherwise the dex share minted by the old curve would be mispriced.
+		#[pallet::weight((<T as Config>::WeightInfo::set_curve(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_curve(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			curve: Curve,
+		) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+			ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
+			let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+			ensure!(
+				(pool_0.is_zero() && pool_1.is_zero())
+					|| matches!(
+						Self::trading_pair_statuses(trading_pair),
+						TradingPairStatus::<_, _>::Disabled
+					),
+				Error::<T>::NotAllowedSetCurve
+			);
+
+			if let Curve::StableSwap {
+				amplification,
+				precision_multipliers,
+				rate_multipliers,
+			} = curve
+			{
+				ensure!(
+					!amplification.is_zero()
+						&& amplification <= MAX_AMPLIFICATION
+						&& !precision_multipliers.0.is_zero()
+						&& !precision_multipliers.1.is_zero()
+						&& !rate_multipliers.0.is_zero()
+						&& !rate_multipliers.1.is_zero(),
+					Error::<T>::InvalidCurve
+				);
+			}
+
+			Curves::<T>::insert(trading_pair, curve);
+			Self::deposit_event(Event::CurveUpdated { trading_pair, curve });
+			Ok(())
+		}
+
+		/// Set the share of exchange fee charged as protocol fee, zero means switch off protocol
+		/// fee.
+		///
+		/// - `share`: the share of exchange fee, must not be greater than one.
+		#[pallet::weight((<T as Config>::WeightInfo::set_protocol_fee_share(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_protocol_fee_share(origin: OriginFor<T>, share: Ratio) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+			ensure!(share <= Ratio::one(), Error::<T>::InvalidProtocolFeeShare);
+
+			ProtocolFeeShare::<T>::put(share);
+			Self::deposit_event(Event::ProtocolFeeShareUpdated { share });
+			Ok(())
+		}
+
+		/// Withdraw the charged protocol fee of trading pair to `TreasuryAccount`.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[pallet::weight(<T as Config>::WeightInfo::withdraw_protocol_fee())]
+		#[transactional]
+		pub fn withdraw_protocol_fee(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			let trading_pair =
+				TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+
+			let (amount_0, amount_1) = ProtocolFeePool::<T>::take(trading_pair);
+			if !amount_0.is_zero() || !amount_1.is_zero() {
+				let module_account_id = Self::account_id();
+				let treasury_account = T::TreasuryAccount::get();
+				T::Currency::transfer(trading_pair.first(), &module_account_id, &treasury_account, amount_0)?;
+				T::Currency::transfer(trading_pair.second(), &module_account_id, &treasury_account, amount_1)?;
+
+				Self::deposit_event(Event::ProtocolFeeWithdrawn {
+					trading_pair,
+					amount_0,
+					amount_1,
+				});
+			}
+			Ok(())
+		}
+
+		/// Refund provision for founders who have participated in an aborted provisioning.
+		///
+		/// - `owner`: founder account.
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		#[pallet::weight(<T as Config>::WeightInfo::refund_provision())]
+		#[transactional]
+		pub fn refund_provision(
+			origin: OriginFor<T>,
+			owner: T::AccountId,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> DispatchResult {
+			let _ = ensure_signed(origin)?;
+			Self::do_refund_provision(&owner, currency_id_a, currency_id_b)?;
+			Ok(())
+		}
+
+		/// Flash swap with DEX, borrow currencies from the liquidity pool, call `callee` with
+		/// `data`, then repay the borrowed amount plus exchange fee in the same call.
+		///
+		/// - `currency_id_a`: currency id A.
+		/// - `currency_id_b`: currency id B.
+		/// - `amount_a`: amount of currency_id_a to borrow.
+		/// - `amount_b`: amount of currency_id_b to borrow.
+		/// - `callee`: the EVM contract to call back after the borrowed currencies are received.
+		/// - `data`: the data passed to the callback.
+		#[pallet::weight(<T as Config>::WeightInfo::flash_swap().saturating_add(
+			T::FlashSwapCallback::gas_to_weight(T::FlashSwapCallback::gas_limit())
+		))]
+		#[transactional]
+		pub fn flash_swap(
+			origin: OriginFor<T>,
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			#[pallet::compact] amount_a: Balance,
+			#[pallet::compact] amount_b: Balance,
+			callee: H160,
+			data: Vec<u8>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let (_, _, used_gas) =
+				Self::do_flash_swap(&who, currency_id_a, currency_id_b, amount_a, amount_b, callee, data)?;
+			Ok(Some(
+				<T as Config>::WeightInfo::flash_swap().saturating_add(T::FlashSwapCallback::gas_to_weight(used_gas)),
+			)
+			.into())
+		}
+
+		/// Set the route tokens of the best route search.
+		///
+		/// - `route_tokens`: the currencies which can be the intermediate currencies of the best
+		///   route.
+		#[pallet::weight((<T as Config>::WeightInfo::set_route_tokens(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn set_route_tokens(origin: OriginFor<T>, route_tokens: Vec<CurrencyId>) -> DispatchResult {
+			T::ListingOrigin::ensure_origin(origin)?;
+
+			let bounded_route_tokens: BoundedVec<CurrencyId, T::MaxRouteTokens> = route_tokens
+				.clone()
+				.try_into()
+				.map_err(|_| Error::<T>::TooManyRouteTokens)?;
+			RouteTokens::<T>::put(bounded_route_tokens);
+			Self::deposit_event(Event::RouteTokensUpdated { route_tokens });
+			Ok(())
+		}
 	}
 }
 
@@ -766,6 +1305,10 @@ impl<T: Config> Pallet<T> {
 			),
 			Error::<T>::StillProvisioning
 		);
+		ensure!(
+			!AbortedProvisioning::<T>::contains_key(trading_pair),
+			Error::<T>::NotAllowedClaim
+		);
 
 		ProvisioningPool::<T>::try_mutate_exists(trading_pair, who, |maybe_contribution| -> DispatchResult {
 			if let Some((contribution_0, contribution_1)) = maybe_contribution.take() {
@@ -801,30 +1344,81 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	fn do_add_provision(
-		who: &T::AccountId,
-		currency_id_a: CurrencyId,
-		currency_id_b: CurrencyId,
-		contribution_a: Balance,
-		contribution_b: Balance,
-	) -> DispatchResult {
+	fn do_refund_provision(who: &T::AccountId, currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> DispatchResult {
 		let trading_pair =
 			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
-		let mut provision_parameters = match Self::trading_pair_statuses(trading_pair) {
-			TradingPairStatus::<_, _>::Provisioning(provision_parameters) => provision_parameters,
-			_ => return Err(Error::<T>::MustBeProvisioning.into()),
-		};
-		let (contribution_0, contribution_1) = if currency_id_a == trading_pair.first() {
-			(contribution_a, contribution_b)
-		} else {
-			(contribution_b, contribution_a)
-		};
-
 		ensure!(
-			contribution_0 >= provision_parameters.min_contribution.0
-				|| contribution_1 >= provision_parameters.min_contribution.1,
-			Error::<T>::InvalidContributionIncrement
-		);
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Disabled
+			),
+			Error::<T>::MustBeDisabled
+		);
+		// only the provisions of aborted provisioning can be refunded, the provisions of trading pair
+		// which has been converted to Enabled should be claimed as dex share.
+		let (remaining_0, remaining_1) =
+			Self::aborted_provisioning(trading_pair).ok_or(Error::<T>::NotAllowedRefund)?;
+
+		ProvisioningPool::<T>::try_mutate_exists(trading_pair, who, |maybe_contribution| -> DispatchResult {
+			if let Some((contribution_0, contribution_1)) = maybe_contribution.take() {
+				AbortedProvisioning::<T>::insert(
+					trading_pair,
+					(
+						remaining_0.saturating_sub(contribution_0),
+						remaining_1.saturating_sub(contribution_1),
+					),
+				);
+
+				let module_account_id = Self::account_id();
+				T::Currency::transfer(trading_pair.first(), &module_account_id, who, contribution_0)?;
+				T::Currency::transfer(trading_pair.second(), &module_account_id, who, contribution_1)?;
+
+				// decrease ref count
+				frame_system::Pallet::<T>::dec_consumers(who);
+
+				Self::deposit_event(Event::RefundProvision {
+					who: who.clone(),
+					currency_0: trading_pair.first(),
+					contribution_0,
+					currency_1: trading_pair.second(),
+					contribution_1,
+				});
+			}
+			Ok(())
+		})?;
+
+		// clear AbortedProvisioning once it is all refunded
+		if ProvisioningPool::<T>::iter_prefix(trading_pair).next().is_none() {
+			AbortedProvisioning::<T>::remove(trading_pair);
+		}
+
+		Ok(())
+	}
+
+	fn do_add_provision(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		contribution_a: Balance,
+		contribution_b: Balance,
+	) -> DispatchResult {
+		let trading_pair =
+			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		let mut provision_parameters = match Self::trading_pair_statuses(trading_pair) {
+			TradingPairStatus::<_, _>::Provisioning(provision_parameters) => provision_parameters,
+			_ => return Err(Error::<T>::MustBeProvisioning.into()),
+		};
+		let (contribution_0, contribution_1) = if currency_id_a == trading_pair.first() {
+			(contribution_a, contribution_b)
+		} else {
+			(contribution_b, contribution_a)
+		};
+
+		ensure!(
+			contribution_0 >= provision_parameters.min_contribution.0
+				|| contribution_1 >= provision_parameters.min_contribution.1,
+			Error::<T>::InvalidContributionIncrement
+		);
 
 		ProvisioningPool::<T>::try_mutate_exists(trading_pair, &who, |maybe_pool| -> DispatchResult {
 			let existed = maybe_pool.is_some();
@@ -895,6 +1489,7 @@ impl<T: Config> Pallet<T> {
 			),
 			Error::<T>::MustBeEnabled,
 		);
+		ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
 
 		ensure!(
 			!max_amount_a.is_zero() && !max_amount_b.is_zero(),
@@ -909,54 +1504,69 @@ impl<T: Config> Pallet<T> {
 			} else {
 				(max_amount_b, max_amount_a)
 			};
-			let (pool_0_increment, pool_1_increment, share_increment): (Balance, Balance, Balance) = if total_shares
-				.is_zero()
-			{
-				// directly use token_0 as base to calculate initial dex share amount.
-				let (exchange_rate_0, exchange_rate_1) = (
-					ExchangeRate::one(),
-					ExchangeRate::checked_from_rational(max_amount_0, max_amount_1).ok_or(ArithmeticError::Overflow)?,
-				);
-
-				let shares_from_token_0 = exchange_rate_0
-					.checked_mul_int(max_amount_0)
-					.ok_or(ArithmeticError::Overflow)?;
-				let shares_from_token_1 = exchange_rate_1
-					.checked_mul_int(max_amount_1)
-					.ok_or(ArithmeticError::Overflow)?;
-				let initial_shares = shares_from_token_0
-					.checked_add(shares_from_token_1)
-					.ok_or(ArithmeticError::Overflow)?;
+			let (pool_0_increment, pool_1_increment, share_increment): (Balance, Balance, Balance) =
+				if let Curve::StableSwap {
+					amplification,
+					precision_multipliers,
+					rate_multipliers,
+				} = Self::curves(trading_pair)
+				{
+					Self::get_stable_swap_liquidity_increment(
+						amplification,
+						precision_multipliers,
+						rate_multipliers,
+						Self::get_exchange_fee(&trading_pair),
+						(*pool_0, *pool_1),
+						(max_amount_0, max_amount_1),
+						total_shares,
+					)?
+				} else if total_shares.is_zero() {
+					// directly use token_0 as base to calculate initial dex share amount.
+					let (exchange_rate_0, exchange_rate_1) = (
+						ExchangeRate::one(),
+						ExchangeRate::checked_from_rational(max_amount_0, max_amount_1)
+							.ok_or(ArithmeticError::Overflow)?,
+					);
 
-				(max_amount_0, max_amount_1, initial_shares)
-			} else {
-				let exchange_rate_0_1 =
-					ExchangeRate::checked_from_rational(*pool_1, *pool_0).ok_or(ArithmeticError::Overflow)?;
-				let input_exchange_rate_0_1 =
-					ExchangeRate::checked_from_rational(max_amount_1, max_amount_0).ok_or(ArithmeticError::Overflow)?;
-
-				if input_exchange_rate_0_1 <= exchange_rate_0_1 {
-					// max_amount_0 may be too much, calculate the actual amount_0
-					let exchange_rate_1_0 =
-						ExchangeRate::checked_from_rational(*pool_0, *pool_1).ok_or(ArithmeticError::Overflow)?;
-					let amount_0 = exchange_rate_1_0
+					let shares_from_token_0 = exchange_rate_0
+						.checked_mul_int(max_amount_0)
+						.ok_or(ArithmeticError::Overflow)?;
+					let shares_from_token_1 = exchange_rate_1
 						.checked_mul_int(max_amount_1)
 						.ok_or(ArithmeticError::Overflow)?;
-					let share_increment = Ratio::checked_from_rational(amount_0, *pool_0)
-						.and_then(|n| n.checked_mul_int(total_shares))
+					let initial_shares = shares_from_token_0
+						.checked_add(shares_from_token_1)
 						.ok_or(ArithmeticError::Overflow)?;
-					(amount_0, max_amount_1, share_increment)
+
+					(max_amount_0, max_amount_1, initial_shares)
 				} else {
-					// max_amount_1 is too much, calculate the actual amount_1
-					let amount_1 = exchange_rate_0_1
-						.checked_mul_int(max_amount_0)
+					let exchange_rate_0_1 =
+						ExchangeRate::checked_from_rational(*pool_1, *pool_0).ok_or(ArithmeticError::Overflow)?;
+					let input_exchange_rate_0_1 = ExchangeRate::checked_from_rational(max_amount_1, max_amount_0)
 						.ok_or(ArithmeticError::Overflow)?;
-					let share_increment = Ratio::checked_from_rational(amount_1, *pool_1)
-						.and_then(|n| n.checked_mul_int(total_shares))
-						.ok_or(ArithmeticError::Overflow)?;
-					(max_amount_0, amount_1, share_increment)
-				}
-			};
+
+					if input_exchange_rate_0_1 <= exchange_rate_0_1 {
+						// max_amount_0 may be too much, calculate the actual amount_0
+						let exchange_rate_1_0 =
+							ExchangeRate::checked_from_rational(*pool_0, *pool_1).ok_or(ArithmeticError::Overflow)?;
+						let amount_0 = exchange_rate_1_0
+							.checked_mul_int(max_amount_1)
+							.ok_or(ArithmeticError::Overflow)?;
+						let share_increment = Ratio::checked_from_rational(amount_0, *pool_0)
+							.and_then(|n| n.checked_mul_int(total_shares))
+							.ok_or(ArithmeticError::Overflow)?;
+						(amount_0, max_amount_1, share_increment)
+					} else {
+						// max_amount_1 is too much, calculate the actual amount_1
+						let amount_1 = exchange_rate_0_1
+							.checked_mul_int(max_amount_0)
+							.ok_or(ArithmeticError::Overflow)?;
+						let share_increment = Ratio::checked_from_rational(amount_1, *pool_1)
+							.and_then(|n| n.checked_mul_int(total_shares))
+							.ok_or(ArithmeticError::Overflow)?;
+						(max_amount_0, amount_1, share_increment)
+					}
+				};
 
 			ensure!(
 				!share_increment.is_zero() && !pool_0_increment.is_zero() && !pool_1_increment.is_zero(),
@@ -1006,6 +1616,7 @@ impl<T: Config> Pallet<T> {
 		}
 		let trading_pair =
 			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
 		let dex_share_currency_id = trading_pair.dex_share_currency_id();
 
 		Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
@@ -1061,14 +1672,148 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Get how much target amount will be got for specific supply amount.
-	fn get_target_amount(supply_pool: Balance, target_pool: Balance, supply_amount: Balance) -> Balance {
+	/// Get the curve of trading pair, the multipliers of `Curve::StableSwap` are in the order of
+	/// (currency_id_a, currency_id_b).
+	fn get_curve(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> Curve {
+		if let Some(trading_pair) = TradingPair::from_currency_ids(currency_id_a, currency_id_b) {
+			let curve = Self::curves(trading_pair);
+			if currency_id_a == trading_pair.first() {
+				curve
+			} else {
+				curve.reversed()
+			}
+		} else {
+			Curve::ConstantProduct
+		}
+	}
+
+	/// Get the exchange fee rate of the trading pair.
+	pub fn get_exchange_fee(trading_pair: &TradingPair) -> (u32, u32) {
+		Self::exchange_fees(trading_pair).unwrap_or_else(T::GetExchangeFee::get)
+	}
+
+	/// Get how much protocol fee will be charged from the supply amount of swap.
+	fn get_protocol_fee(trading_pair: &TradingPair, supply_amount: Balance) -> Balance {
+		let protocol_fee_share = Self::protocol_fee_share();
+		if protocol_fee_share.is_zero() || supply_amount.is_zero() {
+			return Zero::zero();
+		}
+
+		let (fee_numerator, fee_denominator) = Self::get_exchange_fee(trading_pair);
+		let exchange_fee = U256::from(supply_amount)
+			.saturating_mul(U256::from(fee_numerator))
+			.checked_div(U256::from(fee_denominator))
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.unwrap_or_else(Zero::zero);
+		protocol_fee_share.saturating_mul_int(exchange_fee)
+	}
+
+	/// Get the accumulated protocol fee charged from trading pair, in the order of
+	/// (currency_id_a, currency_id_b).
+	pub fn get_collected_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+		if let Some(trading_pair) = TradingPair::from_currency_ids(currency_id_a, currency_id_b) {
+			let (amount_0, amount_1) = Self::collected_protocol_fees(trading_pair);
+			if currency_id_a == trading_pair.first() {
+				(amount_0, amount_1)
+			} else {
+				(amount_1, amount_0)
+			}
+		} else {
+			(Zero::zero(), Zero::zero())
+		}
+	}
+
+	/// Get the protocol fee which has been charged from trading pair but not withdrawn, in the
+	/// order of (currency_id_a, currency_id_b).
+	pub fn get_pending_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+		if let Some(trading_pair) = TradingPair::from_currency_ids(currency_id_a, currency_id_b) {
+			let (amount_0, amount_1) = Self::protocol_fee_pool(trading_pair);
+			if currency_id_a == trading_pair.first() {
+				(amount_0, amount_1)
+			} else {
+				(amount_1, amount_0)
+			}
+		} else {
+			(Zero::zero(), Zero::zero())
+		}
+	}
+
+	/// Get the status of trading pair, the amounts of Provisioning parameters are in the order of
+	/// (currency_id_a, currency_id_b). Returns `None` if the currency ids can't form a trading
+	/// pair.
+	pub fn get_trading_pair_status(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+	) -> Option<TradingPairStatus<Balance, T::BlockNumber>> {
+		let trading_pair = TradingPair::from_currency_ids(currency_id_a, currency_id_b)?;
+		let status = match Self::trading_pair_statuses(trading_pair) {
+			TradingPairStatus::Provisioning(parameters) if currency_id_a != trading_pair.first() => {
+				let swap = |(amount_0, amount_1): (Balance, Balance)| (amount_1, amount_0);
+				TradingPairStatus::Provisioning(ProvisioningParameters {
+					min_contribution: swap(parameters.min_contribution),
+					target_provision: swap(parameters.target_provision),
+					accumulated_provision: swap(parameters.accumulated_provision),
+					not_before: parameters.not_before,
+				})
+			}
+			status => status,
+		};
+		Some(status)
+	}
+
+	/// Get the amounts of currencies that `share_amount` dex share of the trading pair are worth,
+	/// in the order of (currency_id_a, currency_id_b).
+	pub fn get_liquidity_share_value(
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		share_amount: Balance,
+	) -> Option<(Balance, Balance)> {
+		let trading_pair = TradingPair::from_currency_ids(currency_id_a, currency_id_b)?;
+		let total_shares = T::Currency::total_issuance(trading_pair.dex_share_currency_id());
+		if share_amount > total_shares {
+			return None;
+		}
+		let proportion = Ratio::checked_from_rational(share_amount, total_shares)?;
+		let (pool_a, pool_b) = Self::get_liquidity(currency_id_a, currency_id_b);
+		Some((proportion.checked_mul_int(pool_a)?, proportion.checked_mul_int(pool_b)?))
+	}
+
+	/// Get how much target amount will be got for specific supply amount, the multipliers of
+	/// `curve` are in the order of (supply, target).
+	fn get_target_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_amount: Balance,
+		exchange_fee: (u32, u32),
+		curve: Curve,
+	) -> Balance {
 		if supply_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = exchange_fee;
 			let supply_amount_with_fee: U256 =
 				U256::from(supply_amount).saturating_mul(U256::from(fee_denominator.saturating_sub(fee_numerator)));
+			if let Curve::StableSwap {
+				amplification,
+				precision_multipliers,
+				rate_multipliers,
+			} = curve
+			{
+				return supply_amount_with_fee
+					.checked_div(U256::from(fee_denominator))
+					.and_then(|supply_increment| {
+						Self::get_stable_swap_target_amount(
+							amplification,
+							precision_multipliers,
+							rate_multipliers,
+							supply_pool,
+							target_pool,
+							supply_increment,
+						)
+					})
+					.unwrap_or_else(Zero::zero);
+			}
+
 			let numerator: U256 = supply_amount_with_fee.saturating_mul(U256::from(target_pool));
 			let denominator: U256 = U256::from(supply_pool)
 				.saturating_mul(U256::from(fee_denominator))
@@ -1081,12 +1826,43 @@ impl<T: Config> Pallet<T> {
 		}
 	}
 
-	/// Get how much supply amount will be paid for specific target amount.
-	fn get_supply_amount(supply_pool: Balance, target_pool: Balance, target_amount: Balance) -> Balance {
+	/// Get how much supply amount will be paid for specific target amount, the multipliers of
+	/// `curve` are in the order of (supply, target).
+	fn get_supply_amount(
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+		exchange_fee: (u32, u32),
+		curve: Curve,
+	) -> Balance {
 		if target_amount.is_zero() || supply_pool.is_zero() || target_pool.is_zero() {
 			Zero::zero()
 		} else {
-			let (fee_numerator, fee_denominator) = T::GetExchangeFee::get();
+			let (fee_numerator, fee_denominator) = exchange_fee;
+			if let Curve::StableSwap {
+				amplification,
+				precision_multipliers,
+				rate_multipliers,
+			} = curve
+			{
+				return Self::get_stable_swap_supply_increment(
+					amplification,
+					precision_multipliers,
+					rate_multipliers,
+					supply_pool,
+					target_pool,
+					target_amount,
+				)
+				.and_then(|supply_increment| {
+					supply_increment
+						.checked_mul(U256::from(fee_denominator))?
+						.checked_div(U256::from(fee_denominator.saturating_sub(fee_numerator)))?
+						.checked_add(U256::one())
+				})
+				.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+				.unwrap_or_else(Zero::zero);
+			}
+
 			let numerator: U256 = U256::from(supply_pool)
 				.saturating_mul(U256::from(target_amount))
 				.saturating_mul(U256::from(fee_denominator));
@@ -1128,7 +1904,13 @@ impl<T: Config> Pallet<T> {
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let target_amount = Self::get_target_amount(supply_pool, target_pool, target_amounts[i]);
+			let target_amount = Self::get_target_amount(
+				supply_pool,
+				target_pool,
+				target_amounts[i],
+				Self::get_exchange_fee(&trading_pair),
+				Self::get_curve(path[i], path[i + 1]),
+			);
 			ensure!(!target_amount.is_zero(), Error::<T>::ZeroTargetAmount);
 
 			target_amounts[i + 1] = target_amount;
@@ -1164,7 +1946,13 @@ impl<T: Config> Pallet<T> {
 				!supply_pool.is_zero() && !target_pool.is_zero(),
 				Error::<T>::InsufficientLiquidity
 			);
-			let supply_amount = Self::get_supply_amount(supply_pool, target_pool, supply_amounts[i]);
+			let supply_amount = Self::get_supply_amount(
+				supply_pool,
+				target_pool,
+				supply_amounts[i],
+				Self::get_exchange_fee(&trading_pair),
+				Self::get_curve(path[i - 1], path[i]),
+			);
 			ensure!(!supply_amount.is_zero(), Error::<T>::ZeroSupplyAmount);
 
 			supply_amounts[i - 1] = supply_amount;
@@ -1174,6 +1962,464 @@ impl<T: Config> Pallet<T> {
 		Ok(supply_amounts)
 	}
 
+	/// Get the currencies which are connected by the Enabled trading pairs with liquidity, only
+	/// the trading pairs between `supply_currency_id`, `target_currency_id` and `RouteTokens` are
+	/// included. Returns the graph and the number of trading pairs read.
+	fn get_trading_graph(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+	) -> (BTreeMap<CurrencyId, Vec<CurrencyId>>, u32) {
+		let mut currency_ids = vec![supply_currency_id, target_currency_id];
+		for currency_id in Self::route_tokens() {
+			if !currency_ids.contains(&currency_id) {
+				currency_ids.push(currency_id);
+			}
+		}
+
+		let mut graph: BTreeMap<CurrencyId, Vec<CurrencyId>> = BTreeMap::new();
+		let mut visited: u32 = 0;
+		for (i, currency_id_a) in currency_ids.iter().enumerate() {
+			for currency_id_b in currency_ids.iter().skip(i + 1) {
+				if let Some(trading_pair) = TradingPair::from_currency_ids(*currency_id_a, *currency_id_b) {
+					visited = visited.saturating_add(1);
+					if matches!(
+						Self::trading_pair_statuses(trading_pair),
+						TradingPairStatus::<_, _>::Enabled
+					) {
+						let (pool_0, pool_1) = Self::liquidity_pool(trading_pair);
+						if !pool_0.is_zero() && !pool_1.is_zero() {
+							graph.entry(*currency_id_a).or_default().push(*currency_id_b);
+							graph.entry(*currency_id_b).or_default().push(*currency_id_a);
+						}
+					}
+				}
+			}
+		}
+		(graph, visited)
+	}
+
+	/// Search the Enabled trading pairs for the swap path with the best price, the intermediate
+	/// currencies of path are limited to `RouteTokens` and the length of path is limited by
+	/// `TradingPathLimit`. Returns the path and its (supply_amount, target_amount), and the
+	/// number of trading pairs and paths visited by the search, which can be used to charge the
+	/// cost of it.
+	///
+	/// Searching all the Enabled trading pairs is unbounded, so the governance set `RouteTokens`
+	/// (at most `MaxRouteTokens`) bounds the search, the trading pairs which don't connect them
+	/// are never routed through.
+	pub fn get_best_route(
+		supply_currency_id: CurrencyId,
+		target_currency_id: CurrencyId,
+		limit: SwapLimit<Balance>,
+	) -> (Option<(Vec<CurrencyId>, Balance, Balance)>, u32) {
+		if supply_currency_id == target_currency_id {
+			return (None, 0);
+		}
+
+		let (graph, mut visited) = Self::get_trading_graph(supply_currency_id, target_currency_id);
+		let max_path_length: usize = T::TradingPathLimit::get().saturated_into();
+		let mut best: Option<(Vec<CurrencyId>, Balance)> = None;
+
+		let route = match limit {
+			SwapLimit::ExactSupply(exact_supply_amount, minimum_target_amount) => {
+				let mut path = vec![supply_currency_id];
+				Self::search_best_target_amount(
+					&graph,
+					target_currency_id,
+					max_path_length,
+					&mut path,
+					exact_supply_amount,
+					&mut best,
+					&mut visited,
+				);
+
+				best.filter(|(_, target_amount)| *target_amount >= minimum_target_amount)
+					.map(|(path, target_amount)| (path, exact_supply_amount, target_amount))
+			}
+			SwapLimit::ExactTarget(maximum_supply_amount, exact_target_amount) => {
+				// search from the target currency, the path is reversed.
+				let mut path = vec![target_currency_id];
+				Self::search_best_supply_amount(
+					&graph,
+					supply_currency_id,
+					max_path_length,
+					&mut path,
+					exact_target_amount,
+					&mut best,
+					&mut visited,
+				);
+
+				best.filter(|(_, supply_amount)| *supply_amount <= maximum_supply_amount)
+					.map(|(mut path, supply_amount)| {
+						path.reverse();
+						(path, supply_amount, exact_target_amount)
+					})
+			}
+		};
+		(route, visited)
+	}
+
+	/// Depth-first search the path from the last currency of `path` to `target_currency_id`
+	/// which gets the most target amount. `amount` is the amount of the last currency of `path`.
+	fn search_best_target_amount(
+		graph: &BTreeMap<CurrencyId, Vec<CurrencyId>>,
+		target_currency_id: CurrencyId,
+		max_path_length: usize,
+		path: &mut Vec<CurrencyId>,
+		amount: Balance,
+		best: &mut Option<(Vec<CurrencyId>, Balance)>,
+		visited: &mut u32,
+	) {
+		let current_currency_id = path[path.len() - 1];
+		if current_currency_id == target_currency_id {
+			// prefer the shorter path if the price is the same
+			let is_better = best.as_ref().map_or(true, |(best_path, best_amount)| {
+				amount > *best_amount || (amount == *best_amount && path.len() < best_path.len())
+			});
+			if is_better {
+				*best = Some((path.clone(), amount));
+			}
+			return;
+		}
+
+		if path.len() >= max_path_length {
+			return;
+		}
+
+		for next_currency_id in graph.get(&current_currency_id).into_iter().flatten() {
+			if path.contains(next_currency_id) {
+				continue;
+			}
+			if let Some(trading_pair) = TradingPair::from_currency_ids(current_currency_id, *next_currency_id) {
+				*visited = visited.saturating_add(1);
+				let (supply_pool, target_pool) = Self::get_liquidity(current_currency_id, *next_currency_id);
+				let target_amount = Self::get_target_amount(
+					supply_pool,
+					target_pool,
+					amount,
+					Self::get_exchange_fee(&trading_pair),
+					Self::get_curve(current_currency_id, *next_currency_id),
+				);
+				if !target_amount.is_zero() {
+					path.push(*next_currency_id);
+					Self::search_best_target_amount(
+						graph,
+						target_currency_id,
+						max_path_length,
+						path,
+						target_amount,
+						best,
+						visited,
+					);
+					path.pop();
+				}
+			}
+		}
+	}
+
+	/// Depth-first search the path from `supply_currency_id` to the last currency of the
+	/// reversed `path` which pays the least supply amount. `amount` is the amount of the last
+	/// currency of the reversed `path`.
+	fn search_best_supply_amount(
+		graph: &BTreeMap<CurrencyId, Vec<CurrencyId>>,
+		supply_currency_id: CurrencyId,
+		max_path_length: usize,
+		path: &mut Vec<CurrencyId>,
+		amount: Balance,
+		best: &mut Option<(Vec<CurrencyId>, Balance)>,
+		visited: &mut u32,
+	) {
+		let current_currency_id = path[path.len() - 1];
+		if current_currency_id == supply_currency_id {
+			// prefer the shorter path if the price is the same
+			let is_better = best.as_ref().map_or(true, |(best_path, best_amount)| {
+				amount < *best_amount || (amount == *best_amount && path.len() < best_path.len())
+			});
+			if is_better {
+				*best = Some((path.clone(), amount));
+			}
+			return;
+		}
+
+		if path.len() >= max_path_length {
+			return;
+		}
+
+		for previous_currency_id in graph.get(&current_currency_id).into_iter().flatten() {
+			if path.contains(previous_currency_id) {
+				continue;
+			}
+			if let Some(trading_pair) = TradingPair::from_currency_ids(*previous_currency_id, current_currency_id) {
+				*visited = visited.saturating_add(1);
+				let (supply_pool, target_pool) = Self::get_liquidity(*previous_currency_id, current_currency_id);
+				let supply_amount = Self::get_supply_amount(
+					supply_pool,
+					target_pool,
+					amount,
+					Self::get_exchange_fee(&trading_pair),
+					Self::get_curve(*previous_currency_id, current_currency_id),
+				);
+				if !supply_amount.is_zero() {
+					path.push(*previous_currency_id);
+					Self::search_best_supply_amount(
+						graph,
+						supply_currency_id,
+						max_path_length,
+						path,
+						supply_amount,
+						best,
+						visited,
+					);
+					path.pop();
+				}
+			}
+		}
+	}
+
+	/// Check whether the liquidity pool after swap keeps the invariant of the curve, the
+	/// multipliers of `curve` are in the order of (supply, target). For StableSwap the target
+	/// pool must not be less than the one on the curve of the pool before swap with the supply
+	/// pool after swap.
+	fn check_invariant(
+		curve: Curve,
+		(supply_pool_before, target_pool_before): (Balance, Balance),
+		(supply_pool_after, target_pool_after): (Balance, Balance),
+	) -> bool {
+		match curve {
+			Curve::ConstantProduct => {
+				U256::from(supply_pool_after).saturating_mul(U256::from(target_pool_after))
+					>= U256::from(supply_pool_before).saturating_mul(U256::from(target_pool_before))
+			}
+			Curve::StableSwap {
+				amplification,
+				precision_multipliers: (supply_precision_multiplier, target_precision_multiplier),
+				rate_multipliers: (supply_rate_multiplier, target_rate_multiplier),
+			} => Self::get_invariant(curve, supply_pool_before, target_pool_before)
+				.zip(Self::get_stable_swap_xp(
+					supply_pool_after,
+					supply_precision_multiplier,
+					supply_rate_multiplier,
+				))
+				.and_then(|(d, supply_xp)| Self::get_stable_swap_y(amplification, supply_xp, d))
+				.zip(Self::get_stable_swap_xp(
+					target_pool_after,
+					target_precision_multiplier,
+					target_rate_multiplier,
+				))
+				.map_or(false, |(min_target_xp, target_xp)| target_xp >= min_target_xp),
+		}
+	}
+
+	/// Get the invariant of the liquidity pool on the curve, the multipliers of `curve` are in
+	/// the order of (pool_a, pool_b).
+	fn get_invariant(curve: Curve, pool_a: Balance, pool_b: Balance) -> Option<U256> {
+		match curve {
+			Curve::ConstantProduct => U256::from(pool_a).checked_mul(U256::from(pool_b)),
+			Curve::StableSwap {
+				amplification,
+				precision_multipliers: (precision_multiplier_a, precision_multiplier_b),
+				rate_multipliers: (rate_multiplier_a, rate_multiplier_b),
+			} => Self::get_stable_swap_invariant(
+				amplification,
+				Self::get_stable_swap_xp(pool_a, precision_multiplier_a, rate_multiplier_a)?,
+				Self::get_stable_swap_xp(pool_b, precision_multiplier_b, rate_multiplier_b)?,
+			),
+		}
+	}
+
+	/// Normalize the pool of StableSwap curve by the precision and rate multipliers.
+	fn get_stable_swap_xp(pool: Balance, precision_multiplier: Balance, rate_multiplier: Balance) -> Option<U256> {
+		U256::from(pool)
+			.checked_mul(U256::from(precision_multiplier))?
+			.checked_mul(U256::from(rate_multiplier))?
+			.checked_div(U256::from(STABLE_SWAP_RATE_PRECISION))
+	}
+
+	/// Convert the normalized amount of StableSwap curve back to the amount of currency, the
+	/// remainder is discarded.
+	fn get_stable_swap_amount(xp: U256, precision_multiplier: Balance, rate_multiplier: Balance) -> Option<U256> {
+		xp.checked_mul(U256::from(STABLE_SWAP_RATE_PRECISION))?
+			.checked_div(U256::from(precision_multiplier).checked_mul(U256::from(rate_multiplier))?)
+	}
+
+	/// Compute the StableSwap invariant D of the two normalized pools by Newton's method:
+	/// A * n^n * (x + y) + D = A * n^n * D + D^(n + 1) / (n^n * x * y), where n = 2.
+	fn get_stable_swap_invariant(amplification: u32, xp_0: U256, xp_1: U256) -> Option<U256> {
+		let sum = xp_0.checked_add(xp_1)?;
+		if sum.is_zero() {
+			return Some(U256::zero());
+		}
+		if xp_0.is_zero() || xp_1.is_zero() {
+			return None;
+		}
+
+		let ann = U256::from(amplification).checked_mul(U256::from(4u8))?;
+		let mut d = sum;
+		for _ in 0..MAX_STABLE_SWAP_ITERATIONS {
+			let d_p = d
+				.checked_mul(d)?
+				.checked_div(xp_0.checked_mul(U256::from(2u8))?)?
+				.checked_mul(d)?
+				.checked_div(xp_1.checked_mul(U256::from(2u8))?)?;
+			let d_prev = d;
+			let numerator = ann
+				.checked_mul(sum)?
+				.checked_add(d_p.checked_mul(U256::from(2u8))?)?
+				.checked_mul(d)?;
+			let denominator = ann
+				.checked_sub(U256::one())?
+				.checked_mul(d)?
+				.checked_add(d_p.checked_mul(U256::from(3u8))?)?;
+			d = numerator.checked_div(denominator)?;
+
+			let diff = if d > d_prev { d - d_prev } else { d_prev - d };
+			if diff <= U256::one() {
+				return Some(d);
+			}
+		}
+		None
+	}
+
+	/// Compute the normalized pool y on the StableSwap curve of invariant `d` with the other
+	/// normalized pool `x` by Newton's method.
+	fn get_stable_swap_y(amplification: u32, x: U256, d: U256) -> Option<U256> {
+		if x.is_zero() {
+			return None;
+		}
+
+		let ann = U256::from(amplification).checked_mul(U256::from(4u8))?;
+		let c = d
+			.checked_mul(d)?
+			.checked_div(x.checked_mul(U256::from(2u8))?)?
+			.checked_mul(d)?
+			.checked_div(ann.checked_mul(U256::from(2u8))?)?;
+		let b = x.checked_add(d.checked_div(ann)?)?;
+		let mut y = d;
+		for _ in 0..MAX_STABLE_SWAP_ITERATIONS {
+			let y_prev = y;
+			let numerator = y.checked_mul(y)?.checked_add(c)?;
+			let denominator = y.checked_mul(U256::from(2u8))?.checked_add(b)?.checked_sub(d)?;
+			y = numerator.checked_div(denominator)?;
+
+			let diff = if y > y_prev { y - y_prev } else { y_prev - y };
+			if diff <= U256::one() {
+				return Some(y);
+			}
+		}
+		None
+	}
+
+	/// Get how much target amount will be got for specific supply increment of pool (exchange fee
+	/// excluded) on the StableSwap curve, the multipliers are in the order of (supply, target).
+	fn get_stable_swap_target_amount(
+		amplification: u32,
+		(supply_precision_multiplier, target_precision_multiplier): (Balance, Balance),
+		(supply_rate_multiplier, target_rate_multiplier): (Balance, Balance),
+		supply_pool: Balance,
+		target_pool: Balance,
+		supply_increment: U256,
+	) -> Option<Balance> {
+		let supply_xp = Self::get_stable_swap_xp(supply_pool, supply_precision_multiplier, supply_rate_multiplier)?;
+		let target_xp = Self::get_stable_swap_xp(target_pool, target_precision_multiplier, target_rate_multiplier)?;
+		let d = Self::get_stable_swap_invariant(amplification, supply_xp, target_xp)?;
+		let new_supply_xp = Self::get_stable_swap_xp(
+			supply_pool.checked_add(supply_increment.try_into().ok()?)?,
+			supply_precision_multiplier,
+			supply_rate_multiplier,
+		)?;
+		let new_target_xp = Self::get_stable_swap_y(amplification, new_supply_xp, d)?;
+		// sub 1 from result to correct the possible losses caused by rounding of Newton's method
+		let target_amount_xp = target_xp.checked_sub(new_target_xp)?.checked_sub(U256::one())?;
+		Self::get_stable_swap_amount(target_amount_xp, target_precision_multiplier, target_rate_multiplier)
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+	}
+
+	/// Get how much supply increment of pool (exchange fee excluded) will be paid for specific
+	/// target amount on the StableSwap curve, the multipliers are in the order of (supply,
+	/// target).
+	fn get_stable_swap_supply_increment(
+		amplification: u32,
+		(supply_precision_multiplier, target_precision_multiplier): (Balance, Balance),
+		(supply_rate_multiplier, target_rate_multiplier): (Balance, Balance),
+		supply_pool: Balance,
+		target_pool: Balance,
+		target_amount: Balance,
+	) -> Option<U256> {
+		let new_target_pool = target_pool.checked_sub(target_amount).filter(|n| !n.is_zero())?;
+		let supply_xp = Self::get_stable_swap_xp(supply_pool, supply_precision_multiplier, supply_rate_multiplier)?;
+		let target_xp = Self::get_stable_swap_xp(target_pool, target_precision_multiplier, target_rate_multiplier)?;
+		let d = Self::get_stable_swap_invariant(amplification, supply_xp, target_xp)?;
+		let new_target_xp =
+			Self::get_stable_swap_xp(new_target_pool, target_precision_multiplier, target_rate_multiplier)?;
+		let new_supply_xp = Self::get_stable_swap_y(amplification, new_target_xp, d)?;
+		// add 1 to result to correct the possible losses caused by rounding of Newton's method
+		// and the remainder discarding of normalization
+		Self::get_stable_swap_amount(
+			new_supply_xp.checked_sub(supply_xp)?,
+			supply_precision_multiplier,
+			supply_rate_multiplier,
+		)?
+		.checked_add(U256::one())
+	}
+
+	/// Get the (pool_0_increment, pool_1_increment, share_increment) of adding liquidity to the
+	/// StableSwap pool. All the max amounts are added, the dex share is minted by the increment
+	/// of invariant, and the imbalanced part of the amounts is charged half of the exchange fee
+	/// which is kept in the pool.
+	fn get_stable_swap_liquidity_increment(
+		amplification: u32,
+		(precision_multiplier_0, precision_multiplier_1): (Balance, Balance),
+		(rate_multiplier_0, rate_multiplier_1): (Balance, Balance),
+		(fee_numerator, fee_denominator): (u32, u32),
+		(pool_0, pool_1): (Balance, Balance),
+		(max_amount_0, max_amount_1): (Balance, Balance),
+		total_shares: Balance,
+	) -> sp_std::result::Result<(Balance, Balance, Balance), DispatchError> {
+		let get_invariant = |pool_0: Balance, pool_1: Balance| -> Option<U256> {
+			Self::get_stable_swap_invariant(
+				amplification,
+				Self::get_stable_swap_xp(pool_0, precision_multiplier_0, rate_multiplier_0)?,
+				Self::get_stable_swap_xp(pool_1, precision_multiplier_1, rate_multiplier_1)?,
+			)
+		};
+		let new_pool_0 = pool_0.checked_add(max_amount_0).ok_or(ArithmeticError::Overflow)?;
+		let new_pool_1 = pool_1.checked_add(max_amount_1).ok_or(ArithmeticError::Overflow)?;
+		let new_invariant = get_invariant(new_pool_0, new_pool_1).ok_or(ArithmeticError::Overflow)?;
+
+		let share_increment: U256 = if total_shares.is_zero() {
+			new_invariant
+		} else {
+			let invariant = get_invariant(pool_0, pool_1).ok_or(ArithmeticError::Overflow)?;
+			ensure!(!invariant.is_zero(), Error::<T>::InsufficientLiquidity);
+
+			let balanced_pool = |pool: Balance, new_pool: Balance| -> Option<Balance> {
+				let ideal_pool = new_invariant.checked_mul(U256::from(pool))?.checked_div(invariant)?;
+				let new_pool = U256::from(new_pool);
+				let difference = if ideal_pool > new_pool {
+					ideal_pool - new_pool
+				} else {
+					new_pool - ideal_pool
+				};
+				let fee = difference
+					.checked_mul(U256::from(fee_numerator))?
+					.checked_div(U256::from(fee_denominator).checked_mul(U256::from(2u8))?)?;
+				new_pool.checked_sub(fee)?.try_into().ok()
+			};
+			let balanced_invariant = balanced_pool(pool_0, new_pool_0)
+				.zip(balanced_pool(pool_1, new_pool_1))
+				.and_then(|(balanced_pool_0, balanced_pool_1)| get_invariant(balanced_pool_0, balanced_pool_1))
+				.ok_or(ArithmeticError::Overflow)?;
+
+			U256::from(total_shares)
+				.checked_mul(balanced_invariant.saturating_sub(invariant))
+				.and_then(|n| n.checked_div(invariant))
+				.ok_or(ArithmeticError::Overflow)?
+		};
+
+		let share_increment: Balance = share_increment.try_into().map_err(|_| ArithmeticError::Overflow)?;
+		Ok((max_amount_0, max_amount_1, share_increment))
+	}
+
 	fn validate_path(path: &[CurrencyId]) -> DispatchResult {
 		let path_length = path.len();
 		ensure!(
@@ -1192,26 +2438,77 @@ impl<T: Config> Pallet<T> {
 		target_decrement: Balance,
 	) -> DispatchResult {
 		if let Some(trading_pair) = TradingPair::from_currency_ids(supply_currency_id, target_currency_id) {
-			Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
-				let invariant_before_swap: U256 = U256::from(*pool_0).saturating_mul(U256::from(*pool_1));
+			// the drained liquidity pool of an ongoing flash swap can't be swapped with.
+			ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
 
-				if supply_currency_id == trading_pair.first() {
-					*pool_0 = pool_0.checked_add(supply_increment).ok_or(ArithmeticError::Overflow)?;
-					*pool_1 = pool_1.checked_sub(target_decrement).ok_or(ArithmeticError::Underflow)?;
+			// protocol fee is part of the exchange fee, it's kept out of liquidity pool so that the
+			// invariant check still works.
+			let protocol_fee = Self::get_protocol_fee(&trading_pair, supply_increment);
+			let pool_increment = supply_increment
+				.checked_sub(protocol_fee)
+				.ok_or(ArithmeticError::Underflow)?;
+
+			let curve = Self::get_curve(supply_currency_id, target_currency_id);
+
+			Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+				let (supply_pool, target_pool) = if supply_currency_id == trading_pair.first() {
+					(pool_0, pool_1)
 				} else {
-					*pool_0 = pool_0.checked_sub(target_decrement).ok_or(ArithmeticError::Underflow)?;
-					*pool_1 = pool_1.checked_add(supply_increment).ok_or(ArithmeticError::Overflow)?;
-				}
+					(pool_1, pool_0)
+				};
+				let (supply_pool_before, target_pool_before) = (*supply_pool, *target_pool);
+				*supply_pool = supply_pool
+					.checked_add(pool_increment)
+					.ok_or(ArithmeticError::Overflow)?;
+				*target_pool = target_pool
+					.checked_sub(target_decrement)
+					.ok_or(ArithmeticError::Underflow)?;
 
-				// invariant check to ensure the constant product formulas (k = x * y)
-				let invariant_after_swap: U256 = U256::from(*pool_0).saturating_mul(U256::from(*pool_1));
+				// invariant check to ensure the formulas of the curve
 				ensure!(
-					invariant_after_swap >= invariant_before_swap,
+					Self::check_invariant(
+						curve,
+						(supply_pool_before, target_pool_before),
+						(*supply_pool, *target_pool)
+					),
 					Error::<T>::InvariantCheckFailed,
 				);
 				Ok(())
 			})?;
+
+			Self::charge_protocol_fee(trading_pair, supply_currency_id, protocol_fee)?;
+		}
+		Ok(())
+	}
+
+	/// Record the protocol fee of `currency_id` charged from the trading pair, the fee must have
+	/// been kept out of the liquidity pool.
+	fn charge_protocol_fee(
+		trading_pair: TradingPair,
+		currency_id: CurrencyId,
+		protocol_fee: Balance,
+	) -> DispatchResult {
+		if protocol_fee.is_zero() {
+			return Ok(());
 		}
+
+		let is_first = currency_id == trading_pair.first();
+		let charge = |(amount_0, amount_1): &mut (Balance, Balance)| -> DispatchResult {
+			if is_first {
+				*amount_0 = amount_0.checked_add(protocol_fee).ok_or(ArithmeticError::Overflow)?;
+			} else {
+				*amount_1 = amount_1.checked_add(protocol_fee).ok_or(ArithmeticError::Overflow)?;
+			}
+			Ok(())
+		};
+		ProtocolFeePool::<T>::try_mutate(trading_pair, charge)?;
+		CollectedProtocolFees::<T>::try_mutate(trading_pair, charge)?;
+
+		Self::deposit_event(Event::ProtocolFeeCharged {
+			trading_pair,
+			currency_id,
+			amount: protocol_fee,
+		});
 		Ok(())
 	}
 
@@ -1231,10 +2528,32 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
+	/// Check whether the deadline of swap has passed.
+	pub fn ensure_before_deadline(deadline: SwapDeadline<T::BlockNumber>) -> DispatchResult {
+		let expired = match deadline {
+			SwapDeadline::BlockNumber(block_number) => frame_system::Pallet::<T>::block_number() > block_number,
+			SwapDeadline::Timestamp(timestamp) => T::UnixTime::now().as_millis() > u128::from(timestamp),
+		};
+		ensure!(!expired, Error::<T>::SwapExpired);
+		Ok(())
+	}
+
+	fn do_swap_with_exact_supply(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		supply_amount: Balance,
+		min_target_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Self::do_swap_with_exact_supply_to(who, who, path, supply_amount, min_target_amount)
+	}
+
+	/// Swap with exact supply amount of `who`, and send the target amount to `dest`.
+	///
 	/// Ensured atomic.
 	#[transactional]
-	fn do_swap_with_exact_supply(
+	pub fn do_swap_with_exact_supply_to(
 		who: &T::AccountId,
+		dest: &T::AccountId,
 		path: &[CurrencyId],
 		supply_amount: Balance,
 		min_target_amount: Balance,
@@ -1249,7 +2568,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Currency::transfer(path[0], who, &module_account_id, supply_amount)?;
 		Self::_swap_by_path(path, &amounts)?;
-		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, actual_target_amount)?;
+		T::Currency::transfer(path[path.len() - 1], &module_account_id, dest, actual_target_amount)?;
 
 		Self::deposit_event(Event::Swap {
 			trader: who.clone(),
@@ -1259,10 +2578,22 @@ impl<T: Config> Pallet<T> {
 		Ok(actual_target_amount)
 	}
 
+	fn do_swap_with_exact_target(
+		who: &T::AccountId,
+		path: &[CurrencyId],
+		target_amount: Balance,
+		max_supply_amount: Balance,
+	) -> sp_std::result::Result<Balance, DispatchError> {
+		Self::do_swap_with_exact_target_to(who, who, path, target_amount, max_supply_amount)
+	}
+
+	/// Swap with exact target amount from `who`, and send the target amount to `dest`.
+	///
 	/// Ensured atomic.
 	#[transactional]
-	fn do_swap_with_exact_target(
+	pub fn do_swap_with_exact_target_to(
 		who: &T::AccountId,
+		dest: &T::AccountId,
 		path: &[CurrencyId],
 		target_amount: Balance,
 		max_supply_amount: Balance,
@@ -1274,7 +2605,7 @@ impl<T: Config> Pallet<T> {
 
 		T::Currency::transfer(path[0], who, &module_account_id, actual_supply_amount)?;
 		Self::_swap_by_path(path, &amounts)?;
-		T::Currency::transfer(path[path.len() - 1], &module_account_id, who, target_amount)?;
+		T::Currency::transfer(path[path.len() - 1], &module_account_id, dest, target_amount)?;
 
 		Self::deposit_event(Event::Swap {
 			trader: who.clone(),
@@ -1283,6 +2614,141 @@ impl<T: Config> Pallet<T> {
 		});
 		Ok(actual_supply_amount)
 	}
+
+	/// Get how much should be repaid for the borrowed amount of flash swap, the exchange fee is
+	/// charged on the repayment so that it's the same as swapping the borrowed amount back.
+	fn get_flash_swap_repayment(borrowed_amount: Balance, exchange_fee: (u32, u32)) -> Result<Balance, DispatchError> {
+		if borrowed_amount.is_zero() {
+			return Ok(Zero::zero());
+		}
+
+		let (fee_numerator, fee_denominator) = exchange_fee;
+		let rest_rate = U256::from(fee_denominator.saturating_sub(fee_numerator));
+		ensure!(!rest_rate.is_zero(), Error::<T>::InvalidExchangeFee);
+
+		// round up to make sure the repayment covers the exchange fee
+		U256::from(borrowed_amount)
+			.saturating_mul(U256::from(fee_denominator))
+			.saturating_add(rest_rate.saturating_sub(U256::one()))
+			.checked_div(rest_rate)
+			.and_then(|n| TryInto::<Balance>::try_into(n).ok())
+			.ok_or_else(|| ArithmeticError::Overflow.into())
+	}
+
+	/// Flash swap from the liquidity pool of trading pair, the borrowed amounts are sent to
+	/// `who` before calling `FlashSwapCallback`, and the repayment is taken from `who` after it.
+	/// Returns the repayment in the order of (currency_id_a, currency_id_b), and the gas used by
+	/// the callback.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn do_flash_swap(
+		who: &T::AccountId,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+		callee: H160,
+		data: Vec<u8>,
+	) -> sp_std::result::Result<(Balance, Balance, u64), DispatchError> {
+		let trading_pair =
+			TradingPair::from_currency_ids(currency_id_a, currency_id_b).ok_or(Error::<T>::InvalidCurrencyId)?;
+		ensure!(
+			matches!(
+				Self::trading_pair_statuses(trading_pair),
+				TradingPairStatus::<_, _>::Enabled
+			),
+			Error::<T>::MustBeEnabled,
+		);
+		ensure!(!Self::flash_swap_locks(trading_pair), Error::<T>::FlashSwapLocked);
+		ensure!(
+			!amount_a.is_zero() || !amount_b.is_zero(),
+			Error::<T>::InvalidFlashSwapAmount
+		);
+
+		let (pool_a, pool_b) = Self::get_liquidity(currency_id_a, currency_id_b);
+		ensure!(
+			amount_a < pool_a && amount_b < pool_b,
+			Error::<T>::InsufficientLiquidity
+		);
+
+		let exchange_fee = Self::get_exchange_fee(&trading_pair);
+		let repay_a = Self::get_flash_swap_repayment(amount_a, exchange_fee)?;
+		let repay_b = Self::get_flash_swap_repayment(amount_b, exchange_fee)?;
+		let (amount_0, amount_1, repay_0, repay_1) = if currency_id_a == trading_pair.first() {
+			(amount_a, amount_b, repay_a, repay_b)
+		} else {
+			(amount_b, amount_a, repay_b, repay_a)
+		};
+		let curve = Self::get_curve(currency_id_a, currency_id_b);
+		let invariant_before_swap = Self::get_invariant(curve, pool_a, pool_b).ok_or(ArithmeticError::Overflow)?;
+		let module_account_id = Self::account_id();
+
+		// lock the trading pair to prevent swapping and adding/removing liquidity by the callback
+		// on the drained liquidity pool.
+		FlashSwapLocks::<T>::insert(trading_pair, true);
+
+		Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+			*pool_0 = pool_0.checked_sub(amount_0).ok_or(ArithmeticError::Underflow)?;
+			*pool_1 = pool_1.checked_sub(amount_1).ok_or(ArithmeticError::Underflow)?;
+			Ok(())
+		})?;
+		T::Currency::transfer(currency_id_a, &module_account_id, who, amount_a)?;
+		T::Currency::transfer(currency_id_b, &module_account_id, who, amount_b)?;
+
+		let used_gas = T::FlashSwapCallback::on_flash_swap(
+			who,
+			callee,
+			currency_id_a,
+			currency_id_b,
+			amount_a,
+			amount_b,
+			repay_a,
+			repay_b,
+			data,
+		)?;
+
+		T::Currency::transfer(currency_id_a, who, &module_account_id, repay_a)?;
+		T::Currency::transfer(currency_id_b, who, &module_account_id, repay_b)?;
+
+		// the flash swap fee is charged as the exchange fee, the protocol fee share of it is kept
+		// out of liquidity pool.
+		let protocol_fee_share = Self::protocol_fee_share();
+		let protocol_fee_0 = protocol_fee_share.saturating_mul_int(repay_0.saturating_sub(amount_0));
+		let protocol_fee_1 = protocol_fee_share.saturating_mul_int(repay_1.saturating_sub(amount_1));
+		Self::try_mutate_liquidity_pool(&trading_pair, |(pool_0, pool_1)| -> DispatchResult {
+			*pool_0 = pool_0
+				.checked_add(repay_0.saturating_sub(protocol_fee_0))
+				.ok_or(ArithmeticError::Overflow)?;
+			*pool_1 = pool_1
+				.checked_add(repay_1.saturating_sub(protocol_fee_1))
+				.ok_or(ArithmeticError::Overflow)?;
+			Ok(())
+		})?;
+		Self::charge_protocol_fee(trading_pair, trading_pair.first(), protocol_fee_0)?;
+		Self::charge_protocol_fee(trading_pair, trading_pair.second(), protocol_fee_1)?;
+
+		FlashSwapLocks::<T>::remove(trading_pair);
+
+		// invariant check to ensure the formulas of the curve
+		let (pool_a, pool_b) = Self::get_liquidity(currency_id_a, currency_id_b);
+		let invariant_after_swap = Self::get_invariant(curve, pool_a, pool_b).ok_or(ArithmeticError::Overflow)?;
+		ensure!(
+			invariant_after_swap >= invariant_before_swap,
+			Error::<T>::InvariantCheckFailed
+		);
+
+		Self::deposit_event(Event::FlashSwap {
+			who: who.clone(),
+			currency_0: trading_pair.first(),
+			borrowed_0: amount_0,
+			repaid_0: repay_0,
+			currency_1: trading_pair.second(),
+			borrowed_1: amount_1,
+			repaid_1: repay_1,
+		});
+		Ok((repay_a, repay_b, used_gas))
+	}
 }
 
 impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
@@ -1363,6 +2829,20 @@ impl<T: Config> DEXManager<T::AccountId, CurrencyId, Balance> for Pallet<T> {
 			}
 		}
 
+		// also compare with the best route searched in the trading pairs of `RouteTokens`
+		if let (Some((swap_path, supply_amount, target_amount)), _) =
+			Self::get_best_route(supply_currency_id, target_currency_id, limit)
+		{
+			let is_better = maybe_best
+				.as_ref()
+				.map_or(true, |(_, previous_supply, previous_target)| {
+					supply_amount < *previous_supply || target_amount > *previous_target
+				});
+			if is_better {
+				maybe_best = Some((swap_path, supply_amount, target_amount));
+			}
+		}
+
 		maybe_best.map(|(path, _, _)| path)
 	}
 
@@ -27,7 +27,7 @@ use frame_support::{
 };
 use frame_system::EnsureSignedBy;
 use orml_traits::{parameter_type_with_key, MultiReservableCurrency};
-use primitives::{Amount, TokenSymbol};
+use primitives::{Amount, Moment, TokenSymbol};
 use sp_core::H256;
 use sp_runtime::{testing::Header, traits::IdentityLookup};
 use sp_std::cell::RefCell;
@@ -38,6 +38,7 @@ pub type AccountId = u128;
 
 pub const ALICE: AccountId = 1;
 pub const BOB: AccountId = 2;
+pub const TREASURY: AccountId = 10;
 pub const AUSD: CurrencyId = CurrencyId::Token(TokenSymbol::AUSD);
 pub const BTC: CurrencyId = CurrencyId::Token(TokenSymbol::RENBTC);
 pub const DOT: CurrencyId = CurrencyId::Token(TokenSymbol::DOT);
@@ -121,7 +122,10 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 3;
+	pub const MaxRouteTokens: u32 = 3;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2000;
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub const TreasuryAccount: AccountId = TREASURY;
 }
 
 thread_local! {
@@ -138,17 +142,63 @@ impl Happened<(TradingPair, Balance, Balance)> for MockOnLiquidityPoolUpdated {
 	}
 }
 
+parameter_types! {
+	pub const MinimumPeriod: Moment = 1000;
+}
+
+impl pallet_timestamp::Config for Runtime {
+	type Moment = Moment;
+	type OnTimestampSet = ();
+	type MinimumPeriod = MinimumPeriod;
+	type WeightInfo = ();
+}
+
+pub struct MockFlashSwapCallback;
+impl DEXFlashSwapCallback<AccountId, CurrencyId, Balance> for MockFlashSwapCallback {
+	fn gas_limit() -> u64 {
+		1_000
+	}
+
+	fn gas_to_weight(gas: u64) -> Weight {
+		gas
+	}
+
+	fn on_flash_swap(
+		who: &AccountId,
+		_callee: H160,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		_amount_a: Balance,
+		_amount_b: Balance,
+		_repay_a: Balance,
+		_repay_b: Balance,
+		data: Vec<u8>,
+	) -> Result<u64, DispatchError> {
+		match &data[..] {
+			b"add_liquidity" => DexModule::do_add_liquidity(who, currency_id_a, currency_id_b, 1_000, 1_000, 0, false),
+			b"swap" => DexModule::do_swap_with_exact_supply(who, &[currency_id_a, currency_id_b], 1_000, 0).map(|_| ()),
+			_ => Ok(()),
+		}
+		.map(|_| 100)
+	}
+}
+
 impl Config for Runtime {
 	type Event = Event;
 	type Currency = Tokens;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type MaxRouteTokens = MaxRouteTokens;
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = MockErc20InfoMapping;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
 	type OnLiquidityPoolUpdated = MockOnLiquidityPoolUpdated;
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type TreasuryAccount = TreasuryAccount;
+	type FlashSwapCallback = MockFlashSwapCallback;
+	type UnixTime = Timestamp;
 }
 
 type UncheckedExtrinsic = frame_system::mocking::MockUncheckedExtrinsic<Runtime>;
@@ -163,6 +213,7 @@ construct_runtime!(
 		System: frame_system::{Pallet, Call, Storage, Config, Event<T>},
 		DexModule: dex::{Pallet, Storage, Call, Event<T>, Config<T>},
 		Tokens: orml_tokens::{Pallet, Storage, Event<T>, Config<T>},
+		Timestamp: pallet_timestamp::{Pallet, Call, Storage, Inherent},
 	}
 );
 
@@ -23,8 +23,8 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{
-	AUSDBTCPair, AUSDDOTPair, DOTBTCPair, DexModule, Event, ExtBuilder, ListingOrigin, Origin, Runtime, System, Tokens,
-	ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, DOT,
+	AUSDBTCPair, AUSDDOTPair, DOTBTCPair, DexModule, Event, ExtBuilder, GetExchangeFee, ListingOrigin, Origin, Runtime,
+	System, Timestamp, Tokens, ACA, ALICE, AUSD, AUSD_DOT_POOL_RECORD, BOB, BTC, DOT, TREASURY,
 };
 use orml_traits::MultiReservableCurrency;
 use sp_core::H160;
@@ -680,6 +680,298 @@ fn claim_dex_share_work() {
 	});
 }
 
+#[test]
+fn abort_provisioning_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(
+			DexModule::abort_provisioning(Origin::signed(ALICE), AUSD, DOT),
+			Error::<Runtime>::MustBeProvisioning
+		);
+
+		assert_ok!(DexModule::list_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000u128,
+			2_000_000_000_000u128,
+			10,
+		));
+		assert_ok!(DexModule::add_provision(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128
+		));
+
+		// not expired
+		System::set_block_number(2010);
+		assert_noop!(
+			DexModule::abort_provisioning(Origin::signed(ALICE), AUSD, DOT),
+			Error::<Runtime>::NotAllowedAbort
+		);
+
+		// qualified provisioning can not be aborted by signed origin
+		assert_ok!(DexModule::add_provision(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128
+		));
+		System::set_block_number(2011);
+		assert_noop!(
+			DexModule::abort_provisioning(Origin::signed(ALICE), AUSD, DOT),
+			Error::<Runtime>::NotAllowedAbort
+		);
+
+		// ListingOrigin can abort it at any time
+		assert_ok!(DexModule::abort_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSDDOTPair::get()),
+			TradingPairStatus::<_, _>::Disabled
+		);
+		System::assert_last_event(Event::DexModule(crate::Event::ProvisioningAborted {
+			trading_pair: AUSDDOTPair::get(),
+			accumulated_provision_0: 2_000_000_000_000u128,
+			accumulated_provision_1: 2_000_000_000_000u128,
+		}));
+		assert_eq!(
+			DexModule::aborted_provisioning(AUSDDOTPair::get()),
+			Some((2_000_000_000_000u128, 2_000_000_000_000u128))
+		);
+
+		// aborted provisioning can not be enabled, listed or claimed before all provisions are refunded
+		assert_noop!(
+			DexModule::enable_trading_pair(Origin::signed(ListingOrigin::get()), AUSD, DOT),
+			Error::<Runtime>::NotAllowedList
+		);
+		assert_noop!(
+			DexModule::list_provisioning(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				1_000_000_000_000u128,
+				1_000_000_000_000u128,
+				5_000_000_000_000u128,
+				2_000_000_000_000u128,
+				10,
+			),
+			Error::<Runtime>::NotAllowedList
+		);
+		assert_noop!(
+			DexModule::claim_dex_share(Origin::signed(ALICE), ALICE, AUSD, DOT),
+			Error::<Runtime>::NotAllowedClaim
+		);
+
+		assert_ok!(DexModule::list_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			BTC,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000u128,
+			2_000_000_000_000u128,
+			2020,
+		));
+		assert_ok!(DexModule::add_provision(
+			Origin::signed(ALICE),
+			AUSD,
+			BTC,
+			1_000_000_000_000u128,
+			0
+		));
+		System::set_block_number(4020);
+		assert_noop!(
+			DexModule::abort_provisioning(Origin::signed(BOB), AUSD, BTC),
+			Error::<Runtime>::NotAllowedAbort
+		);
+
+		// unqualified and expired provisioning can be aborted by anyone
+		System::set_block_number(4021);
+		assert_ok!(DexModule::abort_provisioning(Origin::signed(BOB), AUSD, BTC));
+		assert_eq!(
+			DexModule::trading_pair_statuses(AUSDBTCPair::get()),
+			TradingPairStatus::<_, _>::Disabled
+		);
+		System::assert_last_event(Event::DexModule(crate::Event::ProvisioningAborted {
+			trading_pair: AUSDBTCPair::get(),
+			accumulated_provision_0: 1_000_000_000_000u128,
+			accumulated_provision_1: 0,
+		}));
+		assert_eq!(
+			DexModule::aborted_provisioning(AUSDBTCPair::get()),
+			Some((1_000_000_000_000u128, 0))
+		);
+
+		// nothing to refund if there is no provision
+		assert_ok!(DexModule::list_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			DOT,
+			BTC,
+			1_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000u128,
+			2_000_000_000_000u128,
+			10,
+		));
+		assert_ok!(DexModule::abort_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			DOT,
+			BTC
+		));
+		assert_eq!(DexModule::aborted_provisioning(DOTBTCPair::get()), None);
+	});
+}
+
+#[test]
+fn refund_provision_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_ok!(DexModule::list_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			5_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000_000u128,
+			1_000_000_000_000_000u128,
+			0,
+		));
+		assert_ok!(DexModule::add_provision(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			1_000_000_000_000_000u128,
+			0,
+		));
+		assert_ok!(DexModule::add_provision(
+			Origin::signed(BOB),
+			AUSD,
+			DOT,
+			0,
+			600_000_000_000_000u128,
+		));
+
+		assert_noop!(
+			DexModule::refund_provision(Origin::signed(ALICE), ALICE, AUSD, DOT),
+			Error::<Runtime>::MustBeDisabled
+		);
+
+		assert_ok!(DexModule::abort_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_eq!(
+			Tokens::free_balance(AUSD, &DexModule::account_id()),
+			1_000_000_000_000_000u128
+		);
+		assert_eq!(
+			Tokens::free_balance(DOT, &DexModule::account_id()),
+			600_000_000_000_000u128
+		);
+		let alice_ref_count_0 = System::consumers(&ALICE);
+		let bob_ref_count_0 = System::consumers(&BOB);
+		assert_eq!(
+			DexModule::aborted_provisioning(AUSDDOTPair::get()),
+			Some((1_000_000_000_000_000u128, 600_000_000_000_000u128))
+		);
+
+		// the pair can't be re-listed or enabled while there are provisions to refund, so the
+		// refunds still work after trying
+		assert_noop!(
+			DexModule::list_provisioning(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				5_000_000_000_000u128,
+				1_000_000_000_000u128,
+				5_000_000_000_000_000u128,
+				1_000_000_000_000_000u128,
+				0,
+			),
+			Error::<Runtime>::NotAllowedList
+		);
+		assert_noop!(
+			DexModule::enable_trading_pair(Origin::signed(ListingOrigin::get()), AUSD, DOT),
+			Error::<Runtime>::NotAllowedList
+		);
+
+		assert_ok!(DexModule::refund_provision(Origin::signed(BOB), ALICE, AUSD, DOT));
+		System::assert_last_event(Event::DexModule(crate::Event::RefundProvision {
+			who: ALICE,
+			currency_0: AUSD,
+			contribution_0: 1_000_000_000_000_000u128,
+			currency_1: DOT,
+			contribution_1: 0,
+		}));
+		assert_eq!(DexModule::provisioning_pool(AUSDDOTPair::get(), ALICE), (0, 0));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000u128);
+		assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
+		assert_eq!(System::consumers(&ALICE), alice_ref_count_0 - 1);
+		assert_eq!(
+			DexModule::aborted_provisioning(AUSDDOTPair::get()),
+			Some((0, 600_000_000_000_000u128))
+		);
+
+		// refund again will do nothing
+		assert_ok!(DexModule::refund_provision(Origin::signed(BOB), ALICE, AUSD, DOT));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000u128);
+
+		assert_ok!(DexModule::refund_provision(Origin::signed(BOB), BOB, AUSD, DOT));
+		assert_eq!(DexModule::provisioning_pool(AUSDDOTPair::get(), BOB), (0, 0));
+		assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000u128);
+		assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 0);
+		assert_eq!(System::consumers(&BOB), bob_ref_count_0 - 1);
+		assert_eq!(DexModule::aborted_provisioning(AUSDDOTPair::get()), None);
+
+		// it can be listed again after all provisions are refunded
+		assert_ok!(DexModule::list_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			5_000_000_000_000u128,
+			1_000_000_000_000u128,
+			5_000_000_000_000_000u128,
+			1_000_000_000_000_000u128,
+			0,
+		));
+
+		// the provision of provisioning which has been converted to Enabled is not allowed to be refunded
+		assert_ok!(DexModule::add_provision(
+			Origin::signed(ALICE),
+			AUSD,
+			DOT,
+			5_000_000_000_000_000u128,
+			1_000_000_000_000_000u128,
+		));
+		assert_ok!(DexModule::end_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_ok!(DexModule::disable_trading_pair(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT
+		));
+		assert_noop!(
+			DexModule::refund_provision(Origin::signed(ALICE), ALICE, AUSD, DOT),
+			Error::<Runtime>::NotAllowedRefund
+		);
+	});
+}
+
 #[test]
 fn get_liquidity_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -691,42 +983,155 @@ fn get_liquidity_work() {
 }
 
 #[test]
-fn get_target_amount_work() {
+fn get_trading_pair_status_work() {
 	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_target_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 1, 1000000), 0);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 10000), 9949);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
-	});
-}
+		assert_eq!(DexModule::get_trading_pair_status(AUSD, AUSD), None);
+		assert_eq!(
+			DexModule::get_trading_pair_status(AUSD, DOT),
+			Some(TradingPairStatus::<_, _>::Disabled)
+		);
 
-#[test]
-fn get_supply_amount_work() {
-	ExtBuilder::default().build().execute_with(|| {
-		assert_eq!(DexModule::get_supply_amount(10000, 0, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(0, 20000, 1000), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 0), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 1, 1), 0);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 9949), 9999);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 9999), 9949);
-		assert_eq!(DexModule::get_supply_amount(10000, 20000, 1801), 1000);
-		assert_eq!(DexModule::get_target_amount(10000, 20000, 1000), 1801);
+		assert_ok!(DexModule::list_provisioning(
+			Origin::signed(ListingOrigin::get()),
+			AUSD,
+			DOT,
+			1_000_000_000_000u128,
+			2_000_000_000_000u128,
+			5_000_000_000_000u128,
+			6_000_000_000_000u128,
+			10,
+		));
+		assert_eq!(
+			DexModule::get_trading_pair_status(AUSD, DOT),
+			Some(TradingPairStatus::<_, _>::Provisioning(ProvisioningParameters {
+				min_contribution: (1_000_000_000_000u128, 2_000_000_000_000u128),
+				target_provision: (5_000_000_000_000u128, 6_000_000_000_000u128),
+				accumulated_provision: (0, 0),
+				not_before: 10,
+			}))
+		);
+		assert_eq!(
+			DexModule::get_trading_pair_status(DOT, AUSD),
+			Some(TradingPairStatus::<_, _>::Provisioning(ProvisioningParameters {
+				min_contribution: (2_000_000_000_000u128, 1_000_000_000_000u128),
+				target_provision: (6_000_000_000_000u128, 5_000_000_000_000u128),
+				accumulated_provision: (0, 0),
+				not_before: 10,
+			}))
+		);
 	});
 }
 
 #[test]
-fn get_target_amounts_work() {
+fn get_liquidity_share_value_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.build()
 		.execute_with(|| {
-			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
-			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (100000, 10));
-			assert_noop!(
-				DexModule::get_target_amounts(&[DOT], 10000),
-				Error::<Runtime>::InvalidTradingPathLength,
+			assert_eq!(DexModule::get_liquidity_share_value(AUSD, AUSD, 100), None);
+			assert_eq!(DexModule::get_liquidity_share_value(AUSD, DOT, 100), None);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false,
+			));
+			let total_shares = Tokens::total_issuance(AUSDDOTPair::get().dex_share_currency_id());
+			assert_eq!(
+				DexModule::get_liquidity_share_value(AUSD, DOT, total_shares),
+				Some((5_000_000_000_000, 1_000_000_000_000))
+			);
+			assert_eq!(
+				DexModule::get_liquidity_share_value(DOT, AUSD, total_shares / 2),
+				Some((500_000_000_000, 2_500_000_000_000))
+			);
+			assert_eq!(DexModule::get_liquidity_share_value(AUSD, DOT, total_shares + 1), None);
+		});
+}
+
+#[test]
+fn get_target_amount_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			DexModule::get_target_amount(10000, 0, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(0, 20000, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 0, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 1, 1000000, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 10000, GetExchangeFee::get(), Curve::ConstantProduct),
+			9949
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			1801
+		);
+	});
+}
+
+#[test]
+fn get_supply_amount_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 0, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(0, 20000, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 0, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 1, 1, GetExchangeFee::get(), Curve::ConstantProduct),
+			0
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 9949, GetExchangeFee::get(), Curve::ConstantProduct),
+			9999
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 9999, GetExchangeFee::get(), Curve::ConstantProduct),
+			9949
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(10000, 20000, 1801, GetExchangeFee::get(), Curve::ConstantProduct),
+			1000
+		);
+		assert_eq!(
+			DexModule::get_target_amount(10000, 20000, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			1801
+		);
+	});
+}
+
+#[test]
+fn get_target_amounts_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (100000, 10));
+			assert_noop!(
+				DexModule::get_target_amounts(&[DOT], 10000),
+				Error::<Runtime>::InvalidTradingPathLength,
 			);
 			assert_noop!(
 				DexModule::get_target_amounts(&[DOT, AUSD, BTC, DOT], 10000),
@@ -745,717 +1150,1355 @@ fn get_target_amounts_work() {
 				Error::<Runtime>::MustBeEnabled,
 			);
 			assert_eq!(
-				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
-				Ok(vec![10000, 24874])
+				DexModule::get_target_amounts(&[DOT, AUSD], 10000),
+				Ok(vec![10000, 24874])
+			);
+			assert_eq!(
+				DexModule::get_target_amounts(&[DOT, AUSD, BTC], 10000),
+				Ok(vec![10000, 24874, 1])
+			);
+			assert_noop!(
+				DexModule::get_target_amounts(&[DOT, AUSD, BTC], 100),
+				Error::<Runtime>::ZeroTargetAmount,
+			);
+			assert_noop!(
+				DexModule::get_target_amounts(&[DOT, BTC], 100),
+				Error::<Runtime>::InsufficientLiquidity,
+			);
+		});
+}
+
+#[test]
+fn calculate_amount_for_big_number_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		LiquidityPool::<Runtime>::insert(
+			AUSDDOTPair::get(),
+			(171_000_000_000_000_000_000_000, 56_000_000_000_000_000_000_000),
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(
+				171_000_000_000_000_000_000_000,
+				56_000_000_000_000_000_000_000,
+				1_000_000_000_000_000_000_000,
+				GetExchangeFee::get(),
+				Curve::ConstantProduct
+			),
+			3_140_495_867_768_595_041_323
+		);
+		assert_eq!(
+			DexModule::get_target_amount(
+				171_000_000_000_000_000_000_000,
+				56_000_000_000_000_000_000_000,
+				3_140_495_867_768_595_041_323,
+				GetExchangeFee::get(),
+				Curve::ConstantProduct
+			),
+			1_000_000_000_000_000_000_000
+		);
+	});
+}
+
+#[test]
+fn get_supply_amounts_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (100000, 10));
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT], 10000),
+				Error::<Runtime>::InvalidTradingPathLength,
+			);
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT, AUSD, BTC, DOT], 10000),
+				Error::<Runtime>::InvalidTradingPathLength,
+			);
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT, DOT], 10000),
+				Error::<Runtime>::InvalidTradingPath,
+			);
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT, AUSD, DOT], 10000),
+				Error::<Runtime>::InvalidTradingPath,
+			);
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT, AUSD, ACA], 10000),
+				Error::<Runtime>::MustBeEnabled,
+			);
+			assert_eq!(
+				DexModule::get_supply_amounts(&[DOT, AUSD], 24874),
+				Ok(vec![10000, 24874])
+			);
+			assert_eq!(
+				DexModule::get_supply_amounts(&[DOT, AUSD], 25000),
+				Ok(vec![10102, 25000])
+			);
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT, AUSD, BTC], 10000),
+				Error::<Runtime>::ZeroSupplyAmount,
+			);
+			assert_noop!(
+				DexModule::get_supply_amounts(&[DOT, BTC], 10000),
+				Error::<Runtime>::InsufficientLiquidity,
+			);
+		});
+}
+
+#[test]
+fn _swap_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (50000, 10000));
+			assert_noop!(
+				DexModule::_swap(AUSD, DOT, 50000, 5001),
+				Error::<Runtime>::InvariantCheckFailed
+			);
+			assert_ok!(DexModule::_swap(AUSD, DOT, 50000, 5000));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (100000, 5000));
+			assert_ok!(DexModule::_swap(DOT, AUSD, 100, 800));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (99200, 5100));
+		});
+}
+
+#[test]
+fn _swap_by_path_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (100000, 10));
+
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (50000, 10000));
+			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (100000, 10));
+			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD], &[10000, 25000]));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (25000, 20000));
+			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD, BTC], &[100000, 20000, 1]));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (5000, 120000));
+			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (120000, 9));
+		});
+}
+
+#[test]
+fn add_liquidity_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(ALICE), ACA, AUSD, 100_000_000, 100_000_000, 0, false),
+				Error::<Runtime>::MustBeEnabled
+			);
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 0, 100_000_000, 0, false),
+				Error::<Runtime>::InvalidLiquidityIncrement
+			);
+
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (0, 0));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 0);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				0
+			);
+			assert_eq!(
+				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				0
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000_000_000_000_000_000);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::AddLiquidity {
+				who: ALICE,
+				currency_0: AUSD,
+				pool_0: 5_000_000_000_000,
+				currency_1: DOT,
+				pool_1: 1_000_000_000_000,
+				share_increment: 10_000_000_000_000,
+			}));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(5_000_000_000_000, 1_000_000_000_000)
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 5_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				10_000_000_000_000
+			);
+			assert_eq!(
+				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				0
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_995_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_999_000_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				0
+			);
+			assert_eq!(
+				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				0
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000);
+
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(BOB), AUSD, DOT, 4, 1, 0, true,),
+				Error::<Runtime>::InvalidLiquidityIncrement,
+			);
+
+			assert_noop!(
+				DexModule::add_liquidity(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					50_000_000_000_000,
+					8_000_000_000_000,
+					80_000_000_000_001,
+					true,
+				),
+				Error::<Runtime>::UnacceptableShareIncrement
+			);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				50_000_000_000_000,
+				8_000_000_000_000,
+				80_000_000_000_000,
+				true,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::AddLiquidity {
+				who: BOB,
+				currency_0: AUSD,
+				pool_0: 40_000_000_000_000,
+				currency_1: DOT,
+				pool_1: 8_000_000_000_000,
+				share_increment: 80_000_000_000_000,
+			}));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(45_000_000_000_000, 9_000_000_000_000)
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 45_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 9_000_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				0
+			);
+			assert_eq!(
+				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				80_000_000_000_000
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 999_960_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_992_000_000_000_000);
+		});
+}
+
+#[test]
+fn remove_liquidity_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				false
+			));
+			assert_noop!(
+				DexModule::remove_liquidity(
+					Origin::signed(ALICE),
+					AUSDDOTPair::get().dex_share_currency_id(),
+					DOT,
+					100_000_000,
+					0,
+					0,
+					false,
+				),
+				Error::<Runtime>::InvalidCurrencyId
+			);
+
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(5_000_000_000_000, 1_000_000_000_000)
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 5_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				10_000_000_000_000
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_995_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_999_000_000_000_000);
+
+			assert_noop!(
+				DexModule::remove_liquidity(
+					Origin::signed(ALICE),
+					AUSD,
+					DOT,
+					8_000_000_000_000,
+					4_000_000_000_001,
+					800_000_000_000,
+					false,
+				),
+				Error::<Runtime>::UnacceptableLiquidityWithdrawn
+			);
+			assert_noop!(
+				DexModule::remove_liquidity(
+					Origin::signed(ALICE),
+					AUSD,
+					DOT,
+					8_000_000_000_000,
+					4_000_000_000_000,
+					800_000_000_001,
+					false,
+				),
+				Error::<Runtime>::UnacceptableLiquidityWithdrawn
+			);
+			assert_ok!(DexModule::remove_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				8_000_000_000_000,
+				4_000_000_000_000,
+				800_000_000_000,
+				false,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::RemoveLiquidity {
+				who: ALICE,
+				currency_0: AUSD,
+				pool_0: 4_000_000_000_000,
+				currency_1: DOT,
+				pool_1: 800_000_000_000,
+				share_decrement: 8_000_000_000_000,
+			}));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(1_000_000_000_000, 200_000_000_000)
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 1_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 200_000_000_000);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				2_000_000_000_000
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_999_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_999_800_000_000_000);
+
+			assert_ok!(DexModule::remove_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				2_000_000_000_000,
+				0,
+				0,
+				false,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::RemoveLiquidity {
+				who: ALICE,
+				currency_0: AUSD,
+				pool_0: 1_000_000_000_000,
+				currency_1: DOT,
+				pool_1: 200_000_000_000,
+				share_decrement: 2_000_000_000_000,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (0, 0));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 0);
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				0
+			);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000_000_000_000_000_000);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				5_000_000_000_000,
+				1_000_000_000_000,
+				0,
+				true
+			));
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				0
+			);
+			assert_eq!(
+				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				10_000_000_000_000
+			);
+			assert_ok!(DexModule::remove_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				2_000_000_000_000,
+				0,
+				0,
+				true,
+			));
+			assert_eq!(
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				0
 			);
 			assert_eq!(
-				DexModule::get_target_amounts(&[DOT, AUSD, BTC], 10000),
-				Ok(vec![10000, 24874, 1])
-			);
-			assert_noop!(
-				DexModule::get_target_amounts(&[DOT, AUSD, BTC], 100),
-				Error::<Runtime>::ZeroTargetAmount,
-			);
-			assert_noop!(
-				DexModule::get_target_amounts(&[DOT, BTC], 100),
-				Error::<Runtime>::InsufficientLiquidity,
+				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
+				8_000_000_000_000
 			);
 		});
 }
 
 #[test]
-fn calculate_amount_for_big_number_work() {
-	ExtBuilder::default().build().execute_with(|| {
-		LiquidityPool::<Runtime>::insert(
-			AUSDDOTPair::get(),
-			(171_000_000_000_000_000_000_000, 56_000_000_000_000_000_000_000),
-		);
-		assert_eq!(
-			DexModule::get_supply_amount(
-				171_000_000_000_000_000_000_000,
-				56_000_000_000_000_000_000_000,
-				1_000_000_000_000_000_000_000
-			),
-			3_140_495_867_768_595_041_323
-		);
-		assert_eq!(
-			DexModule::get_target_amount(
-				171_000_000_000_000_000_000_000,
-				56_000_000_000_000_000_000_000,
-				3_140_495_867_768_595_041_323
-			),
-			1_000_000_000_000_000_000_000
-		);
-	});
-}
-
-#[test]
-fn get_supply_amounts_work() {
+fn do_swap_with_exact_supply_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.build()
 		.execute_with(|| {
-			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
-			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (100000, 10));
-			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT], 10000),
-				Error::<Runtime>::InvalidTradingPathLength,
+			System::set_block_number(1);
+
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				500_000_000_000_000,
+				100_000_000_000_000,
+				0,
+				false,
+			));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				BTC,
+				100_000_000_000_000,
+				10_000_000_000,
+				0,
+				false,
+			));
+
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(500_000_000_000_000, 100_000_000_000_000)
+			);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, BTC),
+				(100_000_000_000_000, 10_000_000_000)
+			);
+			assert_eq!(
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				600_000_000_000_000
 			);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 100_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
+
 			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT, AUSD, BTC, DOT], 10000),
-				Error::<Runtime>::InvalidTradingPathLength,
+				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD], 100_000_000_000_000, 250_000_000_000_000,),
+				Error::<Runtime>::InsufficientTargetAmount
 			);
 			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT, DOT], 10000),
-				Error::<Runtime>::InvalidTradingPath,
+				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD, BTC, DOT], 100_000_000_000_000, 0),
+				Error::<Runtime>::InvalidTradingPathLength,
 			);
 			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT, AUSD, DOT], 10000),
+				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD, DOT], 100_000_000_000_000, 0),
 				Error::<Runtime>::InvalidTradingPath,
 			);
 			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT, AUSD, ACA], 10000),
+				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, ACA], 100_000_000_000_000, 0),
 				Error::<Runtime>::MustBeEnabled,
 			);
+
+			assert_ok!(DexModule::do_swap_with_exact_supply(
+				&BOB,
+				&[DOT, AUSD],
+				100_000_000_000_000,
+				200_000_000_000_000,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD],
+				liquidity_changes: vec![100_000_000_000_000, 248_743_718_592_964],
+			}));
 			assert_eq!(
-				DexModule::get_supply_amounts(&[DOT, AUSD], 24874),
-				Ok(vec![10000, 24874])
+				DexModule::get_liquidity(AUSD, DOT),
+				(251_256_281_407_036, 200_000_000_000_000)
 			);
 			assert_eq!(
-				DexModule::get_supply_amounts(&[DOT, AUSD], 25000),
-				Ok(vec![10102, 25000])
-			);
-			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT, AUSD, BTC], 10000),
-				Error::<Runtime>::ZeroSupplyAmount,
+				DexModule::get_liquidity(AUSD, BTC),
+				(100_000_000_000_000, 10_000_000_000)
 			);
-			assert_noop!(
-				DexModule::get_supply_amounts(&[DOT, BTC], 10000),
-				Error::<Runtime>::InsufficientLiquidity,
+			assert_eq!(
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				351_256_281_407_036
 			);
-		});
-}
-
-#[test]
-fn _swap_work() {
-	ExtBuilder::default()
-		.initialize_enabled_trading_pairs()
-		.build()
-		.execute_with(|| {
-			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 200_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_248_743_718_592_964);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_900_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
 
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (50000, 10000));
-			assert_noop!(
-				DexModule::_swap(AUSD, DOT, 50000, 5001),
-				Error::<Runtime>::InvariantCheckFailed
+			assert_ok!(DexModule::do_swap_with_exact_supply(
+				&BOB,
+				&[DOT, AUSD, BTC],
+				200_000_000_000_000,
+				1,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD, BTC],
+				liquidity_changes: vec![200_000_000_000_000, 124_996_843_514_053, 5_530_663_837],
+			}));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(126_259_437_892_983, 400_000_000_000_000)
 			);
-			assert_ok!(DexModule::_swap(AUSD, DOT, 50000, 5000));
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (100000, 5000));
-			assert_ok!(DexModule::_swap(DOT, AUSD, 100, 800));
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (99200, 5100));
-		});
-}
-
-#[test]
-fn _swap_by_path_work() {
-	ExtBuilder::default()
-		.initialize_enabled_trading_pairs()
-		.build()
-		.execute_with(|| {
-			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
-			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (100000, 10));
-
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (50000, 10000));
-			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (100000, 10));
-			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD], &[10000, 25000]));
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (25000, 20000));
-			assert_ok!(DexModule::_swap_by_path(&[DOT, AUSD, BTC], &[100000, 20000, 1]));
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (5000, 120000));
-			assert_eq!(DexModule::get_liquidity(AUSD, BTC), (120000, 9));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, BTC),
+				(224_996_843_514_053, 4_469_336_163)
+			);
+			assert_eq!(
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				351_256_281_407_036
+			);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 400_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 4_469_336_163);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_248_743_718_592_964);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_700_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_005_530_663_837);
 		});
 }
 
 #[test]
-fn add_liquidity_work() {
+fn do_swap_with_exact_target_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.build()
 		.execute_with(|| {
 			System::set_block_number(1);
 
-			assert_noop!(
-				DexModule::add_liquidity(Origin::signed(ALICE), ACA, AUSD, 100_000_000, 100_000_000, 0, false),
-				Error::<Runtime>::MustBeEnabled
-			);
-			assert_noop!(
-				DexModule::add_liquidity(Origin::signed(ALICE), AUSD, DOT, 0, 100_000_000, 0, false),
-				Error::<Runtime>::InvalidLiquidityIncrement
-			);
-
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (0, 0));
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 0);
-			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				0
-			);
-			assert_eq!(
-				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				0
-			);
-			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000_000_000_000_000_000);
-
 			assert_ok!(DexModule::add_liquidity(
 				Origin::signed(ALICE),
 				AUSD,
 				DOT,
-				5_000_000_000_000,
-				1_000_000_000_000,
+				500_000_000_000_000,
+				100_000_000_000_000,
 				0,
 				false,
 			));
-			System::assert_last_event(Event::DexModule(crate::Event::AddLiquidity {
-				who: ALICE,
-				currency_0: AUSD,
-				pool_0: 5_000_000_000_000,
-				currency_1: DOT,
-				pool_1: 1_000_000_000_000,
-				share_increment: 10_000_000_000_000,
-			}));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				BTC,
+				100_000_000_000_000,
+				10_000_000_000,
+				0,
+				false,
+			));
+
 			assert_eq!(
 				DexModule::get_liquidity(AUSD, DOT),
-				(5_000_000_000_000, 1_000_000_000_000)
-			);
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 5_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000_000_000_000);
-			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				10_000_000_000_000
-			);
-			assert_eq!(
-				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				0
+				(500_000_000_000_000, 100_000_000_000_000)
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_995_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_999_000_000_000_000);
 			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				0
+				DexModule::get_liquidity(AUSD, BTC),
+				(100_000_000_000_000, 10_000_000_000)
 			);
 			assert_eq!(
-				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				0
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				600_000_000_000_000
 			);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 100_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
 			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_000_000);
 			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
 
 			assert_noop!(
-				DexModule::add_liquidity(Origin::signed(BOB), AUSD, DOT, 4, 1, 0, true,),
-				Error::<Runtime>::InvalidLiquidityIncrement,
+				DexModule::do_swap_with_exact_target(&BOB, &[DOT, AUSD], 250_000_000_000_000, 100_000_000_000_000,),
+				Error::<Runtime>::ExcessiveSupplyAmount
 			);
-
 			assert_noop!(
-				DexModule::add_liquidity(
-					Origin::signed(BOB),
-					AUSD,
-					DOT,
-					50_000_000_000_000,
-					8_000_000_000_000,
-					80_000_000_000_001,
-					true,
+				DexModule::do_swap_with_exact_target(
+					&BOB,
+					&[DOT, AUSD, BTC, DOT],
+					250_000_000_000_000,
+					200_000_000_000_000,
 				),
-				Error::<Runtime>::UnacceptableShareIncrement
+				Error::<Runtime>::InvalidTradingPathLength,
+			);
+			assert_noop!(
+				DexModule::do_swap_with_exact_target(&BOB, &[DOT, AUSD, DOT], 250_000_000_000_000, 200_000_000_000_000,),
+				Error::<Runtime>::InvalidTradingPath,
+			);
+			assert_noop!(
+				DexModule::do_swap_with_exact_target(&BOB, &[DOT, ACA], 250_000_000_000_000, 200_000_000_000_000),
+				Error::<Runtime>::MustBeEnabled,
 			);
 
-			assert_ok!(DexModule::add_liquidity(
-				Origin::signed(BOB),
-				AUSD,
-				DOT,
-				50_000_000_000_000,
-				8_000_000_000_000,
-				80_000_000_000_000,
-				true,
+			assert_ok!(DexModule::do_swap_with_exact_target(
+				&BOB,
+				&[DOT, AUSD],
+				250_000_000_000_000,
+				200_000_000_000_000,
 			));
-			System::assert_last_event(Event::DexModule(crate::Event::AddLiquidity {
-				who: BOB,
-				currency_0: AUSD,
-				pool_0: 40_000_000_000_000,
-				currency_1: DOT,
-				pool_1: 8_000_000_000_000,
-				share_increment: 80_000_000_000_000,
+			System::assert_last_event(Event::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD],
+				liquidity_changes: vec![101_010_101_010_102, 250_000_000_000_000],
 			}));
 			assert_eq!(
 				DexModule::get_liquidity(AUSD, DOT),
-				(45_000_000_000_000, 9_000_000_000_000)
+				(250_000_000_000_000, 201_010_101_010_102)
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 45_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 9_000_000_000_000);
 			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				0
+				DexModule::get_liquidity(AUSD, BTC),
+				(100_000_000_000_000, 10_000_000_000)
 			);
 			assert_eq!(
-				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				80_000_000_000_000
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				350_000_000_000_000
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 999_960_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_992_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 201_010_101_010_102);
+			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_250_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_898_989_898_989_898);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
+
+			assert_ok!(DexModule::do_swap_with_exact_target(
+				&BOB,
+				&[DOT, AUSD, BTC],
+				5_000_000_000,
+				2_000_000_000_000_000,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::Swap {
+				trader: BOB,
+				path: vec![DOT, AUSD, BTC],
+				liquidity_changes: vec![137_654_580_386_993, 101_010_101_010_102, 5_000_000_000],
+			}));
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, DOT),
+				(148_989_898_989_898, 338_664_681_397_095)
+			);
+			assert_eq!(
+				DexModule::get_liquidity(AUSD, BTC),
+				(201_010_101_010_102, 5_000_000_000)
+			);
+			assert_eq!(
+				Tokens::free_balance(AUSD, &DexModule::account_id()),
+				350_000_000_000_000
+			);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 338_664_681_397_095);
+			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 5_000_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_250_000_000_000_000);
+			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_761_335_318_602_905);
+			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_005_000_000_000);
 		});
 }
 
 #[test]
-fn remove_liquidity_work() {
+fn swap_with_deadline_and_recipient_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.build()
 		.execute_with(|| {
-			System::set_block_number(1);
+			System::set_block_number(10);
+			Timestamp::set_timestamp(10_000);
 
 			assert_ok!(DexModule::add_liquidity(
 				Origin::signed(ALICE),
 				AUSD,
 				DOT,
-				5_000_000_000_000,
-				1_000_000_000_000,
+				500_000_000_000_000,
+				100_000_000_000_000,
 				0,
-				false
+				false,
 			));
+
 			assert_noop!(
-				DexModule::remove_liquidity(
-					Origin::signed(ALICE),
-					AUSDDOTPair::get().dex_share_currency_id(),
-					DOT,
-					100_000_000,
-					0,
+				DexModule::swap_with_exact_supply_to(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000_000,
 					0,
-					false,
+					ALICE,
+					SwapDeadline::BlockNumber(9),
 				),
-				Error::<Runtime>::InvalidCurrencyId
-			);
-
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(5_000_000_000_000, 1_000_000_000_000)
+				Error::<Runtime>::SwapExpired
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 5_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 1_000_000_000_000);
-			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				10_000_000_000_000
+			assert_noop!(
+				DexModule::swap_with_exact_supply_to(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000_000,
+					0,
+					ALICE,
+					SwapDeadline::Timestamp(9_999),
+				),
+				Error::<Runtime>::SwapExpired
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_995_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_999_000_000_000_000);
-
 			assert_noop!(
-				DexModule::remove_liquidity(
-					Origin::signed(ALICE),
-					AUSD,
-					DOT,
-					8_000_000_000_000,
-					4_000_000_000_001,
-					800_000_000_000,
-					false,
+				DexModule::swap_with_exact_target_to(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000_000,
+					100_000_000_000_000,
+					ALICE,
+					SwapDeadline::BlockNumber(9),
 				),
-				Error::<Runtime>::UnacceptableLiquidityWithdrawn
+				Error::<Runtime>::SwapExpired
 			);
 			assert_noop!(
-				DexModule::remove_liquidity(
-					Origin::signed(ALICE),
-					AUSD,
-					DOT,
-					8_000_000_000_000,
-					4_000_000_000_000,
-					800_000_000_001,
-					false,
+				DexModule::swap_with_exact_target_to(
+					Origin::signed(BOB),
+					vec![DOT, AUSD],
+					100_000_000_000_000,
+					100_000_000_000_000,
+					ALICE,
+					SwapDeadline::Timestamp(9_999),
 				),
-				Error::<Runtime>::UnacceptableLiquidityWithdrawn
+				Error::<Runtime>::SwapExpired
 			);
-			assert_ok!(DexModule::remove_liquidity(
-				Origin::signed(ALICE),
-				AUSD,
-				DOT,
-				8_000_000_000_000,
-				4_000_000_000_000,
-				800_000_000_000,
-				false,
+
+			let alice_ausd = Tokens::free_balance(AUSD, &ALICE);
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			let bob_dot = Tokens::free_balance(DOT, &BOB);
+
+			let target_amounts = DexModule::get_target_amounts(&[DOT, AUSD], 100_000_000_000_000).unwrap();
+			assert_ok!(DexModule::swap_with_exact_supply_to(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				100_000_000_000_000,
+				0,
+				ALICE,
+				SwapDeadline::BlockNumber(10),
+			));
+			assert_eq!(Tokens::free_balance(DOT, &BOB), bob_dot - 100_000_000_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd);
+			assert_eq!(Tokens::free_balance(AUSD, &ALICE), alice_ausd + target_amounts[1]);
+
+			let supply_amounts = DexModule::get_supply_amounts(&[DOT, AUSD], 10_000_000_000_000).unwrap();
+			assert_ok!(DexModule::swap_with_exact_target_to(
+				Origin::signed(BOB),
+				vec![DOT, AUSD],
+				10_000_000_000_000,
+				100_000_000_000_000,
+				ALICE,
+				SwapDeadline::Timestamp(10_000),
 			));
-			System::assert_last_event(Event::DexModule(crate::Event::RemoveLiquidity {
-				who: ALICE,
-				currency_0: AUSD,
-				pool_0: 4_000_000_000_000,
-				currency_1: DOT,
-				pool_1: 800_000_000_000,
-				share_decrement: 8_000_000_000_000,
-			}));
 			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(1_000_000_000_000, 200_000_000_000)
+				Tokens::free_balance(DOT, &BOB),
+				bob_dot - 100_000_000_000_000 - supply_amounts[0]
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 1_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 200_000_000_000);
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd);
 			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				2_000_000_000_000
+				Tokens::free_balance(AUSD, &ALICE),
+				alice_ausd + target_amounts[1] + 10_000_000_000_000
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 999_999_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &ALICE), 999_999_800_000_000_000);
+		});
+}
 
-			assert_ok!(DexModule::remove_liquidity(
-				Origin::signed(ALICE),
-				AUSD,
-				DOT,
-				2_000_000_000_000,
-				0,
-				0,
-				false,
-			));
-			System::assert_last_event(Event::DexModule(crate::Event::RemoveLiquidity {
-				who: ALICE,
-				currency_0: AUSD,
-				pool_0: 1_000_000_000_000,
-				currency_1: DOT,
-				pool_1: 200_000_000_000,
-				share_decrement: 2_000_000_000_000,
-			}));
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (0, 0));
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 0);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 0);
+#[test]
+fn initialize_added_liquidity_pools_genesis_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1000000, 2000000));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2000000);
+			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 3000000);
 			assert_eq!(
 				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				0
+				2000000
 			);
-			assert_eq!(Tokens::free_balance(AUSD, &ALICE), 1_000_000_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &ALICE), 1_000_000_000_000_000_000);
+		});
+}
 
-			assert_ok!(DexModule::add_liquidity(
-				Origin::signed(BOB),
-				AUSD,
-				DOT,
-				5_000_000_000_000,
-				1_000_000_000_000,
-				0,
-				true
-			));
+#[test]
+fn get_swap_amount_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
 			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				0
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 0)),
+				Some((10000, 24874))
 			);
 			assert_eq!(
-				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				10_000_000_000_000
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 24875)),
+				None
 			);
-			assert_ok!(DexModule::remove_liquidity(
-				Origin::signed(BOB),
-				AUSD,
-				DOT,
-				2_000_000_000_000,
-				0,
-				0,
-				true,
-			));
 			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				0
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactTarget(Balance::max_value(), 24874)),
+				Some((10000, 24874))
 			);
 			assert_eq!(
-				Tokens::reserved_balance(AUSDDOTPair::get().dex_share_currency_id(), &BOB),
-				8_000_000_000_000
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactTarget(9999, 24874)),
+				None
 			);
 		});
 }
 
 #[test]
-fn do_swap_with_exact_supply_work() {
+fn set_exchange_fee_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.build()
 		.execute_with(|| {
 			System::set_block_number(1);
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
+			assert_eq!(DexModule::exchange_fees(AUSDDOTPair::get()), None);
+			assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), GetExchangeFee::get());
 
-			assert_ok!(DexModule::add_liquidity(
-				Origin::signed(ALICE),
-				AUSD,
-				DOT,
-				500_000_000_000_000,
-				100_000_000_000_000,
-				0,
-				false,
-			));
-			assert_ok!(DexModule::add_liquidity(
-				Origin::signed(ALICE),
-				AUSD,
-				BTC,
-				100_000_000_000_000,
-				10_000_000_000,
-				0,
-				false,
-			));
-
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(500_000_000_000_000, 100_000_000_000_000)
-			);
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, BTC),
-				(100_000_000_000_000, 10_000_000_000)
-			);
-			assert_eq!(
-				Tokens::free_balance(AUSD, &DexModule::account_id()),
-				600_000_000_000_000
-			);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 100_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
-
-			assert_noop!(
-				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD], 100_000_000_000_000, 250_000_000_000_000,),
-				Error::<Runtime>::InsufficientTargetAmount
-			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD, BTC, DOT], 100_000_000_000_000, 0),
-				Error::<Runtime>::InvalidTradingPathLength,
+				DexModule::set_exchange_fee(Origin::signed(ALICE), AUSD, DOT, Some((3, 100))),
+				BadOrigin
 			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD, DOT], 100_000_000_000_000, 0),
-				Error::<Runtime>::InvalidTradingPath,
+				DexModule::set_exchange_fee(Origin::signed(ListingOrigin::get()), AUSD, DOT, Some((100, 100))),
+				Error::<Runtime>::InvalidExchangeFee
 			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_supply(&BOB, &[DOT, ACA], 100_000_000_000_000, 0),
-				Error::<Runtime>::MustBeEnabled,
+				DexModule::set_exchange_fee(Origin::signed(ListingOrigin::get()), AUSD, DOT, Some((0, 0))),
+				Error::<Runtime>::InvalidExchangeFee
 			);
 
-			assert_ok!(DexModule::do_swap_with_exact_supply(
-				&BOB,
-				&[DOT, AUSD],
-				100_000_000_000_000,
-				200_000_000_000_000,
+			assert_ok!(DexModule::set_exchange_fee(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				AUSD,
+				Some((3, 100))
 			));
-			System::assert_last_event(Event::DexModule(crate::Event::Swap {
-				trader: BOB,
-				path: vec![DOT, AUSD],
-				liquidity_changes: vec![100_000_000_000_000, 248_743_718_592_964],
+			System::assert_last_event(Event::DexModule(crate::Event::ExchangeFeeUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				exchange_fee: Some((3, 100)),
 			}));
+			assert_eq!(DexModule::exchange_fees(AUSDDOTPair::get()), Some((3, 100)));
+			assert_eq!(DexModule::get_exchange_fee(&AUSDDOTPair::get()), (3, 100));
+			assert_eq!(DexModule::get_exchange_fee(&AUSDBTCPair::get()), GetExchangeFee::get());
 			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(251_256_281_407_036, 200_000_000_000_000)
-			);
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, BTC),
-				(100_000_000_000_000, 10_000_000_000)
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 0)),
+				Some((10000, 24619))
 			);
+
+			assert_ok!(DexModule::set_exchange_fee(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Some((0, 1))
+			));
 			assert_eq!(
-				Tokens::free_balance(AUSD, &DexModule::account_id()),
-				351_256_281_407_036
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 0)),
+				Some((10000, 25000))
 			);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 200_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_248_743_718_592_964);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_900_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
 
-			assert_ok!(DexModule::do_swap_with_exact_supply(
-				&BOB,
-				&[DOT, AUSD, BTC],
-				200_000_000_000_000,
-				1,
+			assert_ok!(DexModule::set_exchange_fee(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				None
 			));
-			System::assert_last_event(Event::DexModule(crate::Event::Swap {
-				trader: BOB,
-				path: vec![DOT, AUSD, BTC],
-				liquidity_changes: vec![200_000_000_000_000, 124_996_843_514_053, 5_530_663_837],
+			System::assert_last_event(Event::DexModule(crate::Event::ExchangeFeeUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				exchange_fee: None,
 			}));
+			assert_eq!(DexModule::exchange_fees(AUSDDOTPair::get()), None);
 			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(126_259_437_892_983, 400_000_000_000_000)
-			);
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, BTC),
-				(224_996_843_514_053, 4_469_336_163)
-			);
-			assert_eq!(
-				Tokens::free_balance(AUSD, &DexModule::account_id()),
-				351_256_281_407_036
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 0)),
+				Some((10000, 24874))
 			);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 400_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 4_469_336_163);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_248_743_718_592_964);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_700_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_005_530_663_837);
 		});
 }
 
 #[test]
-fn do_swap_with_exact_target_work() {
+fn set_curve_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.build()
 		.execute_with(|| {
 			System::set_block_number(1);
+			assert_eq!(DexModule::curves(AUSDDOTPair::get()), Curve::ConstantProduct);
 
-			assert_ok!(DexModule::add_liquidity(
-				Origin::signed(ALICE),
-				AUSD,
-				DOT,
-				500_000_000_000_000,
-				100_000_000_000_000,
-				0,
-				false,
-			));
-			assert_ok!(DexModule::add_liquidity(
-				Origin::signed(ALICE),
-				AUSD,
-				BTC,
-				100_000_000_000_000,
-				10_000_000_000,
-				0,
-				false,
-			));
-
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(500_000_000_000_000, 100_000_000_000_000)
+			assert_noop!(
+				DexModule::set_curve(Origin::signed(ALICE), AUSD, DOT, Curve::stable_swap(100)),
+				BadOrigin
 			);
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, BTC),
-				(100_000_000_000_000, 10_000_000_000)
+			assert_noop!(
+				DexModule::set_curve(
+					Origin::signed(ListingOrigin::get()),
+					AUSD,
+					AUSD,
+					Curve::stable_swap(100)
+				),
+				Error::<Runtime>::InvalidCurrencyId
 			);
-			assert_eq!(
-				Tokens::free_balance(AUSD, &DexModule::account_id()),
-				600_000_000_000_000
+			assert_noop!(
+				DexModule::set_curve(Origin::signed(ListingOrigin::get()), AUSD, DOT, Curve::stable_swap(0)),
+				Error::<Runtime>::InvalidCurve
 			);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 100_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_000_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 1_000_000_000_000_000_000);
-			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
-
 			assert_noop!(
-				DexModule::do_swap_with_exact_target(&BOB, &[DOT, AUSD], 250_000_000_000_000, 100_000_000_000_000,),
-				Error::<Runtime>::ExcessiveSupplyAmount
+				DexModule::set_curve(
+					Origin::signed(ListingOrigin::get()),
+					AUSD,
+					DOT,
+					Curve::stable_swap(MAX_AMPLIFICATION + 1)
+				),
+				Error::<Runtime>::InvalidCurve
 			);
+
+			assert_ok!(DexModule::set_curve(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				AUSD,
+				Curve::stable_swap(100)
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::CurveUpdated {
+				trading_pair: AUSDDOTPair::get(),
+				curve: Curve::stable_swap(100),
+			}));
+			assert_eq!(DexModule::curves(AUSDDOTPair::get()), Curve::stable_swap(100));
+			assert_eq!(DexModule::curves(AUSDBTCPair::get()), Curve::ConstantProduct);
+
+			assert_ok!(DexModule::set_curve(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Curve::ConstantProduct
+			));
+			assert_eq!(DexModule::curves(AUSDDOTPair::get()), Curve::ConstantProduct);
+
+			// the multipliers must not be zero
 			assert_noop!(
-				DexModule::do_swap_with_exact_target(
-					&BOB,
-					&[DOT, AUSD, BTC, DOT],
-					250_000_000_000_000,
-					200_000_000_000_000,
+				DexModule::set_curve(
+					Origin::signed(ListingOrigin::get()),
+					AUSD,
+					DOT,
+					Curve::StableSwap {
+						amplification: 100,
+						precision_multipliers: (1, 0),
+						rate_multipliers: (STABLE_SWAP_RATE_PRECISION, STABLE_SWAP_RATE_PRECISION),
+					}
 				),
-				Error::<Runtime>::InvalidTradingPathLength,
+				Error::<Runtime>::InvalidCurve
 			);
 			assert_noop!(
-				DexModule::do_swap_with_exact_target(&BOB, &[DOT, AUSD, DOT], 250_000_000_000_000, 200_000_000_000_000,),
-				Error::<Runtime>::InvalidTradingPath,
+				DexModule::set_curve(
+					Origin::signed(ListingOrigin::get()),
+					AUSD,
+					DOT,
+					Curve::StableSwap {
+						amplification: 100,
+						precision_multipliers: (1, 1),
+						rate_multipliers: (0, STABLE_SWAP_RATE_PRECISION),
+					}
+				),
+				Error::<Runtime>::InvalidCurve
 			);
+
+			// the curve of Enabled trading pair with liquidity can't be changed
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (100000, 100000));
 			assert_noop!(
-				DexModule::do_swap_with_exact_target(&BOB, &[DOT, ACA], 250_000_000_000_000, 200_000_000_000_000),
-				Error::<Runtime>::MustBeEnabled,
+				DexModule::set_curve(Origin::signed(ListingOrigin::get()), AUSD, DOT, Curve::stable_swap(100)),
+				Error::<Runtime>::NotAllowedSetCurve
 			);
+			assert_ok!(DexModule::disable_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT
+			));
+			assert_ok!(DexModule::set_curve(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Curve::stable_swap(100)
+			));
+			assert_eq!(DexModule::curves(AUSDDOTPair::get()), Curve::stable_swap(100));
+		});
+}
+
+#[test]
+fn stable_swap_amount_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		let curve = Curve::stable_swap(100);
+		assert_eq!(
+			DexModule::get_target_amount(100000, 0, 1000, GetExchangeFee::get(), curve),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(100000, 100000, 0, GetExchangeFee::get(), curve),
+			0
+		);
+		assert_eq!(
+			DexModule::get_target_amount(100000, 100000, 1000, GetExchangeFee::get(), Curve::ConstantProduct),
+			980
+		);
+		assert_eq!(
+			DexModule::get_target_amount(100000, 100000, 1000, GetExchangeFee::get(), curve),
+			989
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(100000, 100000, 989, GetExchangeFee::get(), curve),
+			1001
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(100000, 100000, 100000, GetExchangeFee::get(), curve),
+			0
+		);
+
+		// the supply currency has 6 decimals less than the target currency
+		let curve = Curve::StableSwap {
+			amplification: 100,
+			precision_multipliers: (1_000_000, 1),
+			rate_multipliers: (STABLE_SWAP_RATE_PRECISION, STABLE_SWAP_RATE_PRECISION),
+		};
+		assert_eq!(
+			DexModule::get_target_amount(100000, 100_000_000_000, 1000, GetExchangeFee::get(), curve),
+			989_951_236
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(100000, 100_000_000_000, 989_951_236, GetExchangeFee::get(), curve),
+			1001
+		);
+
+		// one supply currency is worth two target currencies
+		let curve = Curve::StableSwap {
+			amplification: 100,
+			precision_multipliers: (1, 1),
+			rate_multipliers: (2 * STABLE_SWAP_RATE_PRECISION, STABLE_SWAP_RATE_PRECISION),
+		};
+		assert_eq!(
+			DexModule::get_target_amount(100000, 200000, 1000, GetExchangeFee::get(), curve),
+			1979
+		);
+		assert_eq!(
+			DexModule::get_supply_amount(100000, 200000, 1979, GetExchangeFee::get(), curve),
+			1001
+		);
+		assert_eq!(
+			DexModule::get_target_amount(100000, 200000, 1000, GetExchangeFee::get(), Curve::stable_swap(100)),
+			994
+		);
+	});
+}
+
+#[test]
+fn stable_swap_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::set_curve(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Curve::stable_swap(100)
+			));
 
-			assert_ok!(DexModule::do_swap_with_exact_target(
-				&BOB,
-				&[DOT, AUSD],
-				250_000_000_000_000,
-				200_000_000_000_000,
+			// initial dex share is the invariant of StableSwap
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				100000,
+				100000,
+				200000,
+				false,
 			));
-			System::assert_last_event(Event::DexModule(crate::Event::Swap {
-				trader: BOB,
-				path: vec![DOT, AUSD],
-				liquidity_changes: vec![101_010_101_010_102, 250_000_000_000_000],
-			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (100000, 100000));
 			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(250_000_000_000_000, 201_010_101_010_102)
+				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
+				200000
 			);
+
 			assert_eq!(
-				DexModule::get_liquidity(AUSD, BTC),
-				(100_000_000_000_000, 10_000_000_000)
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(1000, 0)),
+				Some((1000, 989))
 			);
 			assert_eq!(
-				Tokens::free_balance(AUSD, &DexModule::account_id()),
-				350_000_000_000_000
+				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactTarget(Balance::MAX, 989)),
+				Some((1001, 989))
 			);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 201_010_101_010_102);
-			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 10_000_000_000);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_250_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_898_989_898_989_898);
-			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_000_000_000_000);
+			assert_ok!(DexModule::do_swap_with_exact_supply(&BOB, &[DOT, AUSD], 1000, 989));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (99011, 101000));
 
-			assert_ok!(DexModule::do_swap_with_exact_target(
-				&BOB,
-				&[DOT, AUSD, BTC],
-				5_000_000_000,
-				2_000_000_000_000_000,
-			));
-			System::assert_last_event(Event::DexModule(crate::Event::Swap {
-				trader: BOB,
-				path: vec![DOT, AUSD, BTC],
-				liquidity_changes: vec![137_654_580_386_993, 101_010_101_010_102, 5_000_000_000],
-			}));
-			assert_eq!(
-				DexModule::get_liquidity(AUSD, DOT),
-				(148_989_898_989_898, 338_664_681_397_095)
+			// invariant check of StableSwap
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (100000, 100000));
+			assert_noop!(
+				DexModule::_swap(DOT, AUSD, 1000, 1001),
+				Error::<Runtime>::InvariantCheckFailed
 			);
+			assert_ok!(DexModule::_swap(DOT, AUSD, 1000, 1000));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (99000, 101000));
+
+			// flash swap checks the invariant of StableSwap
 			assert_eq!(
-				DexModule::get_liquidity(AUSD, BTC),
-				(201_010_101_010_102, 5_000_000_000)
+				DexModule::do_flash_swap(&BOB, AUSD, DOT, 1000, 0, H160::default(), vec![]),
+				Ok((1011, 0, 100))
 			);
-			assert_eq!(
-				Tokens::free_balance(AUSD, &DexModule::account_id()),
-				350_000_000_000_000
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (99011, 101000));
+		});
+}
+
+#[test]
+fn stable_swap_add_liquidity_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			System::set_block_number(1);
+			assert_ok!(DexModule::set_curve(
+				Origin::signed(ListingOrigin::get()),
+				AUSD,
+				DOT,
+				Curve::stable_swap(100)
+			));
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(ALICE),
+				AUSD,
+				DOT,
+				100000,
+				100000,
+				0,
+				false,
+			));
+
+			// balanced amounts are charged no fee
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				DOT,
+				AUSD,
+				10000,
+				10000,
+				0,
+				false,
+			));
+			System::assert_last_event(Event::DexModule(crate::Event::AddLiquidity {
+				who: BOB,
+				currency_0: AUSD,
+				pool_0: 10000,
+				currency_1: DOT,
+				pool_1: 10000,
+				share_increment: 20000,
+			}));
+
+			// all the imbalanced amounts are added, the imbalanced part is charged fee
+			assert_noop!(
+				DexModule::add_liquidity(Origin::signed(BOB), AUSD, DOT, 20000, 1, 19898, false),
+				Error::<Runtime>::UnacceptableShareIncrement
 			);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 338_664_681_397_095);
-			assert_eq!(Tokens::free_balance(BTC, &DexModule::account_id()), 5_000_000_000);
-			assert_eq!(Tokens::free_balance(AUSD, &BOB), 1_000_250_000_000_000_000);
-			assert_eq!(Tokens::free_balance(DOT, &BOB), 999_761_335_318_602_905);
-			assert_eq!(Tokens::free_balance(BTC, &BOB), 1_000_000_005_000_000_000);
+			assert_ok!(DexModule::add_liquidity(
+				Origin::signed(BOB),
+				AUSD,
+				DOT,
+				20000,
+				1,
+				19897,
+				false,
+			));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (130000, 110001));
 		});
 }
 
 #[test]
-fn initialize_added_liquidity_pools_genesis_work() {
+fn set_protocol_fee_share_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_eq!(DexModule::protocol_fee_share(), Ratio::zero());
+
+		assert_noop!(
+			DexModule::set_protocol_fee_share(Origin::signed(ALICE), Ratio::saturating_from_rational(1, 6)),
+			BadOrigin
+		);
+		assert_noop!(
+			DexModule::set_protocol_fee_share(
+				Origin::signed(ListingOrigin::get()),
+				Ratio::saturating_from_rational(7, 6)
+			),
+			Error::<Runtime>::InvalidProtocolFeeShare
+		);
+
+		assert_ok!(DexModule::set_protocol_fee_share(
+			Origin::signed(ListingOrigin::get()),
+			Ratio::saturating_from_rational(1, 6)
+		));
+		System::assert_last_event(Event::DexModule(crate::Event::ProtocolFeeShareUpdated {
+			share: Ratio::saturating_from_rational(1, 6),
+		}));
+		assert_eq!(DexModule::protocol_fee_share(), Ratio::saturating_from_rational(1, 6));
+	});
+}
+
+#[test]
+fn protocol_fee_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
 		.initialize_added_liquidity_pools(ALICE)
 		.build()
 		.execute_with(|| {
 			System::set_block_number(1);
+			assert_ok!(DexModule::set_protocol_fee_share(
+				Origin::signed(ListingOrigin::get()),
+				Ratio::saturating_from_rational(1, 2)
+			));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_000_000, 2_000_000));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2_000_000);
+
+			assert_ok!(DexModule::do_swap_with_exact_supply(&BOB, &[AUSD, DOT], 10_000, 0));
+			System::assert_has_event(Event::DexModule(crate::Event::ProtocolFeeCharged {
+				trading_pair: AUSDDOTPair::get(),
+				currency_id: AUSD,
+				amount: 50,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_009_950, 1_980_395));
+			assert_eq!(DexModule::protocol_fee_pool(AUSDDOTPair::get()), (50, 0));
+			assert_eq!(DexModule::collected_protocol_fees(AUSDDOTPair::get()), (50, 0));
+			assert_eq!(DexModule::get_pending_protocol_fees(DOT, AUSD), (0, 50));
+			assert_eq!(DexModule::get_collected_protocol_fees(DOT, AUSD), (0, 50));
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2_010_000);
+
+			assert_ok!(DexModule::withdraw_protocol_fee(Origin::signed(BOB), DOT, AUSD));
+			System::assert_last_event(Event::DexModule(crate::Event::ProtocolFeeWithdrawn {
+				trading_pair: AUSDDOTPair::get(),
+				amount_0: 50,
+				amount_1: 0,
+			}));
+			assert_eq!(Tokens::free_balance(AUSD, &TREASURY), 50);
+			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2_009_950);
+			assert_eq!(DexModule::protocol_fee_pool(AUSDDOTPair::get()), (0, 0));
+			assert_eq!(DexModule::collected_protocol_fees(AUSDDOTPair::get()), (50, 0));
 
-			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1000000, 2000000));
-			assert_eq!(Tokens::free_balance(AUSD, &DexModule::account_id()), 2000000);
-			assert_eq!(Tokens::free_balance(DOT, &DexModule::account_id()), 3000000);
-			assert_eq!(
-				Tokens::free_balance(AUSDDOTPair::get().dex_share_currency_id(), &ALICE),
-				2000000
-			);
+			// switch off protocol fee
+			assert_ok!(DexModule::set_protocol_fee_share(
+				Origin::signed(ListingOrigin::get()),
+				Ratio::zero()
+			));
+			assert_ok!(DexModule::do_swap_with_exact_supply(&BOB, &[AUSD, DOT], 10_000, 0));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT).0, 1_019_950);
+			assert_eq!(DexModule::protocol_fee_pool(AUSDDOTPair::get()), (0, 0));
+			assert_eq!(DexModule::collected_protocol_fees(AUSDDOTPair::get()), (50, 0));
 		});
 }
 
 #[test]
-fn get_swap_amount_work() {
+fn flash_swap_work() {
 	ExtBuilder::default()
 		.initialize_enabled_trading_pairs()
+		.initialize_added_liquidity_pools(ALICE)
 		.build()
 		.execute_with(|| {
-			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (50000, 10000));
-			assert_eq!(
-				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 0)),
-				Some((10000, 24874))
+			System::set_block_number(1);
+
+			assert_noop!(
+				DexModule::flash_swap(Origin::signed(BOB), AUSD, AUSD, 10_000, 0, H160::default(), vec![]),
+				Error::<Runtime>::InvalidCurrencyId
 			);
-			assert_eq!(
-				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactSupply(10000, 24875)),
-				None
+			assert_noop!(
+				DexModule::flash_swap(Origin::signed(BOB), AUSD, ACA, 10_000, 0, H160::default(), vec![]),
+				Error::<Runtime>::MustBeEnabled
 			);
-			assert_eq!(
-				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactTarget(Balance::max_value(), 24874)),
-				Some((10000, 24874))
+			assert_noop!(
+				DexModule::flash_swap(Origin::signed(BOB), AUSD, DOT, 0, 0, H160::default(), vec![]),
+				Error::<Runtime>::InvalidFlashSwapAmount
+			);
+			assert_noop!(
+				DexModule::flash_swap(Origin::signed(BOB), AUSD, DOT, 1_000_000, 0, H160::default(), vec![]),
+				Error::<Runtime>::InsufficientLiquidity
+			);
+
+			// the pool can't be added or removed liquidity during the flash swap
+			assert_noop!(
+				DexModule::flash_swap(
+					Origin::signed(BOB),
+					AUSD,
+					DOT,
+					10_000,
+					0,
+					H160::default(),
+					b"add_liquidity".to_vec()
+				),
+				Error::<Runtime>::FlashSwapLocked
+			);
+
+			// the borrower can't repay
+			assert_noop!(
+				DexModule::flash_swap(Origin::signed(4), AUSD, DOT, 10_000, 0, H160::default(), vec![]),
+				orml_tokens::Error::<Runtime>::BalanceTooLow
+			);
+
+			let bob_ausd = Tokens::free_balance(AUSD, &BOB);
+			let post_info =
+				DexModule::flash_swap(Origin::signed(BOB), AUSD, DOT, 10_000, 0, H160::default(), vec![]).unwrap();
+			// the gas used by the callback is charged
+			assert_eq!(post_info.actual_weight, Some(<() as WeightInfo>::flash_swap() + 100));
+			System::assert_last_event(Event::DexModule(crate::Event::FlashSwap {
+				who: BOB,
+				currency_0: AUSD,
+				borrowed_0: 10_000,
+				repaid_0: 10_102,
+				currency_1: DOT,
+				borrowed_1: 0,
+				repaid_1: 0,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_000_102, 2_000_000));
+			assert_eq!(AUSD_DOT_POOL_RECORD.with(|v| *v.borrow()), (1_000_102, 2_000_000));
+			assert_eq!(Tokens::free_balance(AUSD, &BOB), bob_ausd - 102);
+			assert!(!DexModule::flash_swap_locks(AUSDDOTPair::get()));
+
+			// the drained pool can't be swapped with in the callback
+			assert_noop!(
+				DexModule::do_flash_swap(&BOB, DOT, AUSD, 0, 10_102, H160::default(), b"swap".to_vec()),
+				Error::<Runtime>::FlashSwapLocked
 			);
+
+			// the protocol fee share of the flash swap fee is kept out of liquidity pool
+			assert_ok!(DexModule::set_protocol_fee_share(
+				Origin::signed(ListingOrigin::get()),
+				Ratio::saturating_from_rational(1, 2)
+			));
 			assert_eq!(
-				DexModule::get_swap_amount(&vec![DOT, AUSD], SwapLimit::ExactTarget(9999, 24874)),
-				None
+				DexModule::do_flash_swap(&BOB, DOT, AUSD, 0, 10_000, H160::default(), vec![]),
+				Ok((0, 10_102, 100))
 			);
+			System::assert_has_event(Event::DexModule(crate::Event::ProtocolFeeCharged {
+				trading_pair: AUSDDOTPair::get(),
+				currency_id: AUSD,
+				amount: 51,
+			}));
+			assert_eq!(DexModule::get_liquidity(AUSD, DOT), (1_000_153, 2_000_000));
+			assert_eq!(DexModule::protocol_fee_pool(AUSDDOTPair::get()), (51, 0));
+			assert_eq!(DexModule::collected_protocol_fees(AUSDDOTPair::get()), (51, 0));
 		});
 }
 
@@ -1534,6 +2577,118 @@ fn get_best_price_swap_path_work() {
 				DexModule::get_best_price_swap_path(DOT, AUSD, SwapLimit::ExactTarget(100000, 20000), vec![vec![BTC]]),
 				Some(vec![DOT, AUSD])
 			);
+
+			// the best route through `RouteTokens` is compared even without alternative paths
+			assert_ok!(DexModule::set_route_tokens(
+				Origin::signed(ListingOrigin::get()),
+				vec![BTC]
+			));
+			assert_eq!(
+				DexModule::get_best_price_swap_path(DOT, AUSD, SwapLimit::ExactSupply(10, 0), vec![]),
+				Some(vec![DOT, BTC, AUSD])
+			);
+			assert_eq!(
+				DexModule::get_best_price_swap_path(DOT, AUSD, SwapLimit::ExactTarget(20, 30), vec![]),
+				Some(vec![DOT, BTC, AUSD])
+			);
+			assert_eq!(
+				DexModule::get_best_price_swap_path(DOT, AUSD, SwapLimit::ExactSupply(10000, 0), vec![]),
+				Some(vec![DOT, AUSD])
+			);
+		});
+}
+
+#[test]
+fn set_route_tokens_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+
+		assert_noop!(DexModule::set_route_tokens(Origin::signed(ALICE), vec![BTC]), BadOrigin);
+		assert_noop!(
+			DexModule::set_route_tokens(Origin::signed(ListingOrigin::get()), vec![ACA, AUSD, BTC, DOT]),
+			Error::<Runtime>::TooManyRouteTokens
+		);
+
+		assert_ok!(DexModule::set_route_tokens(
+			Origin::signed(ListingOrigin::get()),
+			vec![AUSD, BTC]
+		));
+		System::assert_last_event(Event::DexModule(crate::Event::RouteTokensUpdated {
+			route_tokens: vec![AUSD, BTC],
+		}));
+		assert_eq!(DexModule::route_tokens().into_inner(), vec![AUSD, BTC]);
+	});
+}
+
+#[test]
+fn get_best_route_work() {
+	ExtBuilder::default()
+		.initialize_enabled_trading_pairs()
+		.build()
+		.execute_with(|| {
+			LiquidityPool::<Runtime>::insert(AUSDDOTPair::get(), (300000, 100000));
+			LiquidityPool::<Runtime>::insert(AUSDBTCPair::get(), (50000, 10000));
+			LiquidityPool::<Runtime>::insert(DOTBTCPair::get(), (10000, 10000));
+
+			// only the trading pair between supply and target currencies is searched without route
+			// tokens
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 0)),
+				(Some((vec![DOT, AUSD], 10, 29)), 2)
+			);
+
+			assert_ok!(DexModule::set_route_tokens(
+				Origin::signed(ListingOrigin::get()),
+				vec![BTC]
+			));
+			assert_eq!(
+				DexModule::get_best_route(DOT, DOT, SwapLimit::ExactSupply(10, 0)),
+				(None, 0)
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, ACA, SwapLimit::ExactSupply(10, 0)).0,
+				None
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(0, 0)).0,
+				None
+			);
+			// read 3 trading pairs and visit [DOT, AUSD], [DOT, BTC], [DOT, BTC, AUSD]
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 0)),
+				(Some((vec![DOT, BTC, AUSD], 10, 44)), 6)
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 45)).0,
+				None
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactTarget(Balance::MAX, 30)),
+				(Some((vec![DOT, BTC, AUSD], 8, 30)), 6)
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactTarget(7, 30)).0,
+				None
+			);
+			assert_eq!(
+				DexModule::get_swap_amount(&[DOT, BTC, AUSD], SwapLimit::ExactTarget(Balance::MAX, 30)),
+				Some((8, 30))
+			);
+
+			// the disabled trading pair is not in the route
+			assert_ok!(DexModule::disable_trading_pair(
+				Origin::signed(ListingOrigin::get()),
+				DOT,
+				BTC
+			));
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactSupply(10, 0)),
+				(Some((vec![DOT, AUSD], 10, 29)), 4)
+			);
+			assert_eq!(
+				DexModule::get_best_route(DOT, AUSD, SwapLimit::ExactTarget(Balance::MAX, 30)).0,
+				Some((vec![DOT, AUSD], 11, 30))
+			);
 		});
 }
 
@@ -59,6 +59,16 @@ pub trait WeightInfo {
 	fn remove_liquidity_by_unstake() -> Weight;
 	fn swap_with_exact_supply(u: u32, ) -> Weight;
 	fn swap_with_exact_target(u: u32, ) -> Weight;
+	fn abort_provisioning() -> Weight;
+	fn refund_provision() -> Weight;
+	fn set_exchange_fee() -> Weight;
+	fn set_protocol_fee_share() -> Weight;
+	fn withdraw_protocol_fee() -> Weight;
+	fn flash_swap() -> Weight;
+	fn swap_with_exact_supply_to(u: u32, ) -> Weight;
+	fn swap_with_exact_target_to(u: u32, ) -> Weight;
+	fn set_curve() -> Weight;
+	fn set_route_tokens() -> Weight;
 }
 
 /// Weights for module_dex using the Acala node and recommended hardware.
@@ -124,7 +134,7 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			// Standard Error: 117_000
 			.saturating_add((16_008_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
@@ -133,10 +143,65 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			// Standard Error: 226_000
 			.saturating_add((16_058_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
+	fn abort_provisioning() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn refund_provision() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	fn set_exchange_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn withdraw_protocol_fee() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(182_563_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	fn swap_with_exact_supply_to(u: u32, ) -> Weight {
+		(96_799_000 as Weight)
+			// Standard Error: 117_000
+			.saturating_add((16_008_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn swap_with_exact_target_to(u: u32, ) -> Weight {
+		(96_966_000 as Weight)
+			// Standard Error: 226_000
+			.saturating_add((16_058_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn set_curve() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_route_tokens() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -201,7 +266,7 @@ impl WeightInfo for () {
 			// Standard Error: 117_000
 			.saturating_add((16_008_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
@@ -210,8 +275,63 @@ impl WeightInfo for () {
 			// Standard Error: 226_000
 			.saturating_add((16_058_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
+	fn abort_provisioning() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn refund_provision() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(6 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
+	}
+	fn set_exchange_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_protocol_fee_share() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn withdraw_protocol_fee() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
+	fn flash_swap() -> Weight {
+		(182_563_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(14 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(12 as Weight))
+	}
+	fn swap_with_exact_supply_to(u: u32, ) -> Weight {
+		(96_799_000 as Weight)
+			// Standard Error: 117_000
+			.saturating_add((16_008_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn swap_with_exact_target_to(u: u32, ) -> Weight {
+		(96_966_000 as Weight)
+			// Standard Error: 226_000
+			.saturating_add((16_058_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn set_curve() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn set_route_tokens() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
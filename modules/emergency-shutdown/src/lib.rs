@@ -28,6 +28,12 @@
 //! CDPs has debit, cancel all active auctions module, when debits and gaps are
 //! settled, the stable currency holder are allowed to refund a basket of
 //! remaining collateral assets.
+//!
+//! A single collateral type can also be wound down while the rest of the
+//! system keeps running. Its price is locked, its CDPs are settled, and
+//! after settlement the stable currency holder are allowed to refund the
+//! collateral confiscated by the settlement at the locked price, until the
+//! wind-down is closed.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -37,7 +43,7 @@ use frame_system::{ensure_signed, pallet_prelude::*};
 use primitives::{Balance, CurrencyId};
 use sp_runtime::{traits::Zero, FixedPointNumber};
 use sp_std::prelude::*;
-use support::{AuctionManager, CDPTreasury, EmergencyShutdown, LockablePrice, Ratio};
+use support::{AuctionManager, CDPTreasury, EmergencyShutdown, LockablePrice, PriceProvider, Ratio};
 
 mod mock;
 mod tests;
@@ -61,6 +67,14 @@ pub mod module {
 		/// Price source to freeze currencies' price
 		type PriceSource: LockablePrice<CurrencyId>;
 
+		/// Price source which provides the locked price in priority, used to
+		/// price the collateral-specific refund
+		type LockedPriceSource: PriceProvider<CurrencyId>;
+
+		/// Stablecoin currency id
+		#[pallet::constant]
+		type GetStableCurrencyId: Get<CurrencyId>;
+
 		/// CDP treasury to escrow collateral assets after settlement
 		type CDPTreasury: CDPTreasury<Self::AccountId, Balance = Balance, CurrencyId = CurrencyId>;
 
@@ -88,6 +102,16 @@ pub mod module {
 		ExistPotentialSurplus,
 		/// Exist unhandled debit, means settlement has not been completed
 		ExistUnhandledDebit,
+		/// Invalid collateral type
+		InvalidCollateralType,
+		/// The collateral type has already been shutdown
+		CollateralAlreadyShutdown,
+		/// Must after the collateral type shutdown
+		MustAfterCollateralShutdown,
+		/// Feed price is invalid
+		InvalidFeedPrice,
+		/// The refund exceeds the collateral confiscated by the settlement
+		ExceedRefundableCollateral,
 	}
 
 	#[pallet::event]
@@ -103,6 +127,28 @@ pub mod module {
 			stable_coin_amount: Balance,
 			refund_list: Vec<(CurrencyId, Balance)>,
 		},
+		/// The collateral type is shutdown.
+		CollateralShutdown {
+			collateral_type: CurrencyId,
+			block_number: T::BlockNumber,
+		},
+		/// The refund of the shutdown collateral type opened.
+		OpenCollateralSpecificRefund {
+			collateral_type: CurrencyId,
+			block_number: T::BlockNumber,
+		},
+		/// Refund the shutdown collateral type.
+		CollateralSpecificRefund {
+			who: T::AccountId,
+			collateral_type: CurrencyId,
+			stable_coin_amount: Balance,
+			refund_amount: Balance,
+		},
+		/// The shutdown of the collateral type closed.
+		CollateralShutdownClosed {
+			collateral_type: CurrencyId,
+			block_number: T::BlockNumber,
+		},
 	}
 
 	/// Emergency shutdown flag
@@ -119,6 +165,28 @@ pub mod module {
 	#[pallet::getter(fn can_refund)]
 	pub type CanRefund<T: Config> = StorageValue<_, bool, ValueQuery>;
 
+	/// Mapping from collateral type to its shutdown flag
+	///
+	/// IsCollateralShutdown: map CurrencyId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_collateral_shutdown)]
+	pub type IsCollateralShutdown<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// Mapping from collateral type to its refund flag
+	///
+	/// CanRefundCollateral: map CurrencyId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn can_refund_collateral)]
+	pub type CanRefundCollateral<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, bool, ValueQuery>;
+
+	/// Mapping from the shutdown collateral type to the collateral confiscated
+	/// by settlement which hasn't been refunded
+	///
+	/// RefundableCollaterals: map CurrencyId => Balance
+	#[pallet::storage]
+	#[pallet::getter(fn refundable_collaterals)]
+	pub type RefundableCollaterals<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Balance, ValueQuery>;
+
 	#[pallet::pallet]
 	pub struct Pallet<T>(_);
 
@@ -165,6 +233,7 @@ pub mod module {
 			// been done or canceled. Settle all collaterals type CDPs which have debit,
 			// cancel all collateral auctions in forward stage and wait for all collateral
 			// auctions in reverse stage to be ended.
+			Self::ensure_no_surplus_and_debit_in_auction()?;
 			let collateral_currency_ids = T::CollateralCurrencyIds::get();
 			for currency_id in collateral_currency_ids {
 				// there's no collateral auction
@@ -223,6 +292,154 @@ pub mod module {
 			});
 			Ok(())
 		}
+
+		/// Start emergency shutdown of a single collateral type, the rest of
+		/// the system keeps running.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[pallet::weight((T::WeightInfo::emergency_shutdown_collateral(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn emergency_shutdown_collateral(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				T::CollateralCurrencyIds::get().contains(&currency_id),
+				Error::<T>::InvalidCollateralType,
+			);
+			ensure!(
+				!Self::is_collateral_shutdown(currency_id),
+				Error::<T>::CollateralAlreadyShutdown
+			);
+
+			<T as Config>::PriceSource::lock_price(currency_id)?;
+
+			IsCollateralShutdown::<T>::insert(currency_id, true);
+			Self::deposit_event(Event::CollateralShutdown {
+				collateral_type: currency_id,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+			});
+			Ok(())
+		}
+
+		/// Open the refund of the shutdown collateral type if its settlement is
+		/// completed.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[pallet::weight((T::WeightInfo::open_collateral_specific_refund(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn open_collateral_specific_refund(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+			ensure!(
+				Self::is_collateral_shutdown(currency_id),
+				Error::<T>::MustAfterCollateralShutdown
+			);
+
+			// Ensure all CDPs of the collateral type have been settled, and all its collateral
+			// auctions have been done.
+			Self::ensure_no_surplus_and_debit_in_auction()?;
+			ensure!(
+				<T as Config>::AuctionManagerHandler::get_total_collateral_in_auction(currency_id).is_zero(),
+				Error::<T>::ExistPotentialSurplus,
+			);
+			ensure!(
+				<loans::Pallet<T>>::total_positions(currency_id).debit.is_zero(),
+				Error::<T>::ExistUnhandledDebit,
+			);
+
+			CanRefundCollateral::<T>::insert(currency_id, true);
+			Self::deposit_event(Event::OpenCollateralSpecificRefund {
+				collateral_type: currency_id,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+			});
+			Ok(())
+		}
+
+		/// Refund the shutdown collateral type at the locked price to caller.
+		/// The stable currency is deposited to CDP treasury to offset the debit
+		/// of the settlement, and the refund is capped by the collateral
+		/// confiscated by the settlement.
+		///
+		/// - `currency_id`: collateral type.
+		/// - `amount`: stable currency amount used to refund.
+		#[pallet::weight(T::WeightInfo::refund_collateral())]
+		#[transactional]
+		pub fn refund_collateral(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] amount: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(Self::can_refund_collateral(currency_id), Error::<T>::CanNotRefund);
+
+			let price = T::LockedPriceSource::get_relative_price(T::GetStableCurrencyId::get(), currency_id)
+				.ok_or(Error::<T>::InvalidFeedPrice)?;
+			let refund_amount = price.saturating_mul_int(amount);
+			RefundableCollaterals::<T>::try_mutate(currency_id, |refundable| -> DispatchResult {
+				*refundable = refundable
+					.checked_sub(refund_amount)
+					.ok_or(Error::<T>::ExceedRefundableCollateral)?;
+				Ok(())
+			})?;
+
+			<T as Config>::CDPTreasury::deposit_surplus(&who, amount)?;
+			<T as Config>::CDPTreasury::withdraw_collateral(&who, currency_id, refund_amount)?;
+
+			Self::deposit_event(Event::CollateralSpecificRefund {
+				who,
+				collateral_type: currency_id,
+				stable_coin_amount: amount,
+				refund_amount,
+			});
+			Ok(())
+		}
+
+		/// Close the shutdown of the collateral type and unlock its price. The
+		/// collateral which hasn't been refunded is left in CDP treasury.
+		///
+		/// The dispatch origin of this call must be `ShutdownOrigin`.
+		///
+		/// - `currency_id`: collateral type.
+		#[pallet::weight((T::WeightInfo::close_collateral_shutdown(), DispatchClass::Operational))]
+		#[transactional]
+		pub fn close_collateral_shutdown(origin: OriginFor<T>, currency_id: CurrencyId) -> DispatchResult {
+			T::ShutdownOrigin::ensure_origin(origin)?;
+			ensure!(!Self::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				Self::is_collateral_shutdown(currency_id),
+				Error::<T>::MustAfterCollateralShutdown
+			);
+
+			<T as Config>::PriceSource::unlock_price(currency_id)?;
+
+			IsCollateralShutdown::<T>::remove(currency_id);
+			CanRefundCollateral::<T>::remove(currency_id);
+			RefundableCollaterals::<T>::remove(currency_id);
+			Self::deposit_event(Event::CollateralShutdownClosed {
+				collateral_type: currency_id,
+				block_number: <frame_system::Pallet<T>>::block_number(),
+			});
+			Ok(())
+		}
+	}
+}
+
+impl<T: Config> Pallet<T> {
+	/// Ensure there's no surplus or debit auction, whose stable currency hasn't been settled
+	/// into CDP treasury.
+	fn ensure_no_surplus_and_debit_in_auction() -> DispatchResult {
+		ensure!(
+			<T as Config>::AuctionManagerHandler::get_total_surplus_in_auction().is_zero(),
+			Error::<T>::ExistPotentialSurplus,
+		);
+		ensure!(
+			<T as Config>::AuctionManagerHandler::get_total_debit_in_auction().is_zero(),
+			Error::<T>::ExistUnhandledDebit,
+		);
+		Ok(())
 	}
 }
 
@@ -230,4 +447,16 @@ impl<T: Config> EmergencyShutdown for Pallet<T> {
 	fn is_shutdown() -> bool {
 		Self::is_shutdown()
 	}
+
+	fn is_collateral_shutdown(currency_id: CurrencyId) -> bool {
+		Self::is_shutdown() || Self::is_collateral_shutdown(currency_id)
+	}
+
+	fn on_collateral_settled(currency_id: CurrencyId, amount: Balance) {
+		if Self::is_collateral_shutdown(currency_id) {
+			RefundableCollaterals::<T>::mutate(currency_id, |refundable| {
+				*refundable = refundable.saturating_add(amount)
+			});
+		}
+	}
 }
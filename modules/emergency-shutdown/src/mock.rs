@@ -35,7 +35,8 @@ use sp_runtime::{
 	traits::{AccountIdConversion, Convert, IdentityLookup},
 	DispatchResult,
 };
-use support::{AuctionManager, LockablePrice};
+use sp_std::cell::RefCell;
+use support::{AuctionManager, LockablePrice, Price, PriceProvider};
 
 pub type AccountId = u128;
 pub type AuctionId = u32;
@@ -163,6 +164,28 @@ impl LockablePrice<CurrencyId> for MockLockablePrice {
 	}
 }
 
+pub struct MockPriceSource;
+impl PriceProvider<CurrencyId> for MockPriceSource {
+	fn get_price(currency_id: CurrencyId) -> Option<Price> {
+		match currency_id {
+			AUSD => Some(Price::saturating_from_integer(1)),
+			BTC => Some(Price::saturating_from_integer(10)),
+			DOT => Some(Price::saturating_from_integer(5)),
+			_ => None,
+		}
+	}
+}
+
+thread_local! {
+	static TOTAL_SURPLUS_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+	static TOTAL_DEBIT_IN_AUCTION: RefCell<Balance> = RefCell::new(0);
+}
+
+pub fn mock_surplus_and_debit_in_auction(surplus: Balance, debit: Balance) {
+	TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow_mut() = surplus);
+	TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow_mut() = debit);
+}
+
 pub struct MockAuctionManager;
 impl AuctionManager<AccountId> for MockAuctionManager {
 	type Balance = Balance;
@@ -178,6 +201,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		unimplemented!()
 	}
 
+	fn new_surplus_auction(_amount: Self::Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, _fix_debit: Self::Balance) -> DispatchResult {
+		unimplemented!()
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		unimplemented!()
 	}
@@ -186,6 +217,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		unimplemented!()
 	}
 
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		TOTAL_SURPLUS_IN_AUCTION.with(|v| *v.borrow())
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		TOTAL_DEBIT_IN_AUCTION.with(|v| *v.borrow())
+	}
+
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		Default::default()
 	}
@@ -214,6 +253,7 @@ impl cdp_treasury::Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = EmergencyShutdownModule;
 	type WeightInfo = ();
 }
 
@@ -225,6 +265,8 @@ impl Config for Runtime {
 	type Event = Event;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = MockLockablePrice;
+	type LockedPriceSource = MockPriceSource;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CDPTreasuryModule;
 	type AuctionManagerHandler = MockAuctionManager;
 	type ShutdownOrigin = EnsureSignedBy<One, AccountId>;
@@ -23,6 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
+use orml_traits::MultiCurrency;
 use sp_runtime::traits::BadOrigin;
 
 #[test]
@@ -67,6 +68,20 @@ fn open_collateral_refund_work() {
 			EmergencyShutdownModule::open_collateral_refund(Origin::signed(5)),
 			BadOrigin,
 		);
+
+		// the surplus and debit auctions must be done
+		mock_surplus_and_debit_in_auction(100, 0);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)),
+			Error::<Runtime>::ExistPotentialSurplus,
+		);
+		mock_surplus_and_debit_in_auction(0, 100);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)),
+			Error::<Runtime>::ExistUnhandledDebit,
+		);
+
+		mock_surplus_and_debit_in_auction(0, 0);
 		assert_ok!(EmergencyShutdownModule::open_collateral_refund(Origin::signed(1)));
 		System::assert_last_event(Event::EmergencyShutdownModule(crate::Event::OpenRefund {
 			block_number: 1,
@@ -84,3 +99,207 @@ fn refund_collaterals_fail() {
 		);
 	});
 }
+
+#[test]
+fn emergency_shutdown_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::emergency_shutdown_collateral(Origin::signed(5), BTC),
+			BadOrigin,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::emergency_shutdown_collateral(Origin::signed(1), ACA),
+			Error::<Runtime>::InvalidCollateralType,
+		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown_collateral(
+			Origin::signed(1),
+			BTC
+		));
+		System::assert_last_event(Event::EmergencyShutdownModule(crate::Event::CollateralShutdown {
+			collateral_type: BTC,
+			block_number: 1,
+		}));
+		assert!(!EmergencyShutdownModule::is_shutdown());
+		assert!(<EmergencyShutdownModule as EmergencyShutdown>::is_collateral_shutdown(
+			BTC
+		));
+		assert!(!<EmergencyShutdownModule as EmergencyShutdown>::is_collateral_shutdown(
+			DOT
+		));
+		assert_noop!(
+			EmergencyShutdownModule::emergency_shutdown_collateral(Origin::signed(1), BTC),
+			Error::<Runtime>::CollateralAlreadyShutdown,
+		);
+
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert!(<EmergencyShutdownModule as EmergencyShutdown>::is_collateral_shutdown(
+			DOT
+		));
+		assert_noop!(
+			EmergencyShutdownModule::emergency_shutdown_collateral(Origin::signed(1), DOT),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn open_collateral_specific_refund_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_specific_refund(Origin::signed(1), BTC),
+			Error::<Runtime>::MustAfterCollateralShutdown,
+		);
+		assert_ok!(Loans::update_loan(&ALICE, BTC, 100, 500));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown_collateral(
+			Origin::signed(1),
+			BTC
+		));
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_specific_refund(Origin::signed(5), BTC),
+			BadOrigin,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_specific_refund(Origin::signed(1), BTC),
+			Error::<Runtime>::ExistUnhandledDebit,
+		);
+
+		assert_ok!(Loans::update_loan(&ALICE, BTC, 0, -500));
+		mock_surplus_and_debit_in_auction(100, 0);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_specific_refund(Origin::signed(1), BTC),
+			Error::<Runtime>::ExistPotentialSurplus,
+		);
+		mock_surplus_and_debit_in_auction(0, 100);
+		assert_noop!(
+			EmergencyShutdownModule::open_collateral_specific_refund(Origin::signed(1), BTC),
+			Error::<Runtime>::ExistUnhandledDebit,
+		);
+
+		mock_surplus_and_debit_in_auction(0, 0);
+		assert_ok!(EmergencyShutdownModule::open_collateral_specific_refund(
+			Origin::signed(1),
+			BTC
+		));
+		System::assert_last_event(Event::EmergencyShutdownModule(
+			crate::Event::OpenCollateralSpecificRefund {
+				collateral_type: BTC,
+				block_number: 1,
+			},
+		));
+		assert!(EmergencyShutdownModule::can_refund_collateral(BTC));
+		assert!(!EmergencyShutdownModule::can_refund_collateral(DOT));
+		assert!(!EmergencyShutdownModule::can_refund());
+	});
+}
+
+#[test]
+fn refund_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::refund_collateral(Origin::signed(ALICE), BTC, 200),
+			Error::<Runtime>::CanNotRefund,
+		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown_collateral(
+			Origin::signed(1),
+			BTC
+		));
+		assert_ok!(EmergencyShutdownModule::open_collateral_specific_refund(
+			Origin::signed(1),
+			BTC
+		));
+		assert_ok!(CDPTreasuryModule::deposit_collateral(&BOB, BTC, 100));
+		assert_ok!(CDPTreasuryModule::issue_debit(&ALICE, 500, true));
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(BTC), 100);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(), 0);
+
+		// only the collateral confiscated by the settlement can be refunded
+		assert_noop!(
+			EmergencyShutdownModule::refund_collateral(Origin::signed(ALICE), BTC, 200),
+			Error::<Runtime>::ExceedRefundableCollateral,
+		);
+		<EmergencyShutdownModule as EmergencyShutdown>::on_collateral_settled(DOT, 30);
+		<EmergencyShutdownModule as EmergencyShutdown>::on_collateral_settled(BTC, 30);
+		assert_eq!(EmergencyShutdownModule::refundable_collaterals(DOT), 0);
+		assert_eq!(EmergencyShutdownModule::refundable_collaterals(BTC), 30);
+
+		assert_ok!(EmergencyShutdownModule::refund_collateral(
+			Origin::signed(ALICE),
+			BTC,
+			200
+		));
+		System::assert_last_event(Event::EmergencyShutdownModule(crate::Event::CollateralSpecificRefund {
+			who: ALICE,
+			collateral_type: BTC,
+			stable_coin_amount: 200,
+			refund_amount: 20,
+		}));
+		assert_eq!(Tokens::free_balance(AUSD, &ALICE), 300);
+		assert_eq!(Tokens::free_balance(BTC, &ALICE), 1020);
+		assert_eq!(CDPTreasuryModule::get_total_collaterals(BTC), 80);
+		assert_eq!(CDPTreasuryModule::get_surplus_pool(), 200);
+		assert_eq!(EmergencyShutdownModule::refundable_collaterals(BTC), 10);
+
+		assert_noop!(
+			EmergencyShutdownModule::refund_collateral(Origin::signed(ALICE), BTC, 200),
+			Error::<Runtime>::ExceedRefundableCollateral,
+		);
+		assert_noop!(
+			EmergencyShutdownModule::refund_collateral(Origin::signed(ALICE), DOT, 100),
+			Error::<Runtime>::CanNotRefund,
+		);
+	});
+}
+
+#[test]
+fn close_collateral_shutdown_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_noop!(
+			EmergencyShutdownModule::close_collateral_shutdown(Origin::signed(1), BTC),
+			Error::<Runtime>::MustAfterCollateralShutdown,
+		);
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown_collateral(
+			Origin::signed(1),
+			BTC
+		));
+		assert_ok!(EmergencyShutdownModule::open_collateral_specific_refund(
+			Origin::signed(1),
+			BTC
+		));
+		<EmergencyShutdownModule as EmergencyShutdown>::on_collateral_settled(BTC, 30);
+		assert_noop!(
+			EmergencyShutdownModule::close_collateral_shutdown(Origin::signed(5), BTC),
+			BadOrigin,
+		);
+
+		assert_ok!(EmergencyShutdownModule::close_collateral_shutdown(
+			Origin::signed(1),
+			BTC
+		));
+		System::assert_last_event(Event::EmergencyShutdownModule(crate::Event::CollateralShutdownClosed {
+			collateral_type: BTC,
+			block_number: 1,
+		}));
+		assert!(!EmergencyShutdownModule::is_collateral_shutdown(BTC));
+		assert!(!EmergencyShutdownModule::can_refund_collateral(BTC));
+		assert_eq!(EmergencyShutdownModule::refundable_collaterals(BTC), 0);
+		assert_noop!(
+			EmergencyShutdownModule::refund_collateral(Origin::signed(ALICE), BTC, 100),
+			Error::<Runtime>::CanNotRefund,
+		);
+
+		// the collateral type can't be reopened after the system shutdown
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown_collateral(
+			Origin::signed(1),
+			BTC
+		));
+		assert_ok!(EmergencyShutdownModule::emergency_shutdown(Origin::signed(1)));
+		assert_noop!(
+			EmergencyShutdownModule::close_collateral_shutdown(Origin::signed(1), BTC),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
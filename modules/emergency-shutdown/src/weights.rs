@@ -51,6 +51,10 @@ pub trait WeightInfo {
 	fn emergency_shutdown(c: u32, ) -> Weight;
 	fn open_collateral_refund() -> Weight;
 	fn refund_collaterals(c: u32, ) -> Weight;
+	fn emergency_shutdown_collateral() -> Weight;
+	fn open_collateral_specific_refund() -> Weight;
+	fn refund_collateral() -> Weight;
+	fn close_collateral_shutdown() -> Weight;
 }
 
 /// Weights for module_emergency_shutdown using the Acala node and recommended hardware.
@@ -66,7 +70,7 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 	}
 	fn open_collateral_refund() -> Weight {
 		(62_000_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(17 as Weight))
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	fn refund_collaterals(c: u32, ) -> Weight {
@@ -78,6 +82,26 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
 	}
+	fn emergency_shutdown_collateral() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	fn open_collateral_specific_refund() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn refund_collateral() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	fn close_collateral_shutdown() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -92,7 +116,7 @@ impl WeightInfo for () {
 	}
 	fn open_collateral_refund() -> Weight {
 		(62_000_000 as Weight)
-			.saturating_add(RocksDbWeight::get().reads(17 as Weight))
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
 	}
 	fn refund_collaterals(c: u32, ) -> Weight {
@@ -104,4 +128,24 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
 			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
 	}
+	fn emergency_shutdown_collateral() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(3 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+	}
+	fn open_collateral_specific_refund() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(5 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
+	fn refund_collateral() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(8 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(5 as Weight))
+	}
+	fn close_collateral_shutdown() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 }
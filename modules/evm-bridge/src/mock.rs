@@ -123,6 +123,7 @@ impl module_evm::Config for Runtime {
 	type ChainId = ();
 	type GasToWeight = ();
 	type ChargeTransactionPayment = ();
+	type EnableLondon = frame_support::traits::ConstBool<false>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
 
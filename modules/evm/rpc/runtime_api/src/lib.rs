@@ -19,18 +19,33 @@
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::all)]
 
-use ethereum_types::H160;
-use primitives::evm::{CallInfo, CreateInfo, EstimateResourcesRequest};
+use ethereum_types::{H160, H256, U256};
+use primitives::evm::{
+	AccessListItem, CallInfo, CreateInfo, EstimateResourcesRequest, EthereumReceipt, EthereumTransaction, TraceOutcome,
+	TracerConfig,
+};
 use sp_runtime::{
 	codec::Codec,
-	traits::{MaybeDisplay, MaybeFromStr},
+	traits::{Block as BlockT, MaybeDisplay, MaybeFromStr},
 };
 use sp_std::vec::Vec;
 
 sp_api::decl_runtime_apis! {
+	#[api_version(2)]
 	pub trait EVMRuntimeRPCApi<Balance> where
 		Balance: Codec + MaybeDisplay + MaybeFromStr,
 	{
+		#[changed_in(2)]
+		fn call(
+			from: H160,
+			to: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			estimate: bool,
+		) -> Result<CallInfo, sp_runtime::DispatchError>;
+
 		fn call(
 			from: H160,
 			to: H160,
@@ -38,9 +53,11 @@ sp_api::decl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError>;
 
+		#[changed_in(2)]
 		fn create(
 			from: H160,
 			data: Vec<u8>,
@@ -50,6 +67,79 @@ sp_api::decl_runtime_apis! {
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError>;
 
+		fn create(
+			from: H160,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			estimate: bool,
+		) -> Result<CreateInfo, sp_runtime::DispatchError>;
+
 		fn get_estimate_resources_request(data: Vec<u8>) -> Result<EstimateResourcesRequest, sp_runtime::DispatchError>;
 	}
+
+	#[api_version(2)]
+	pub trait EVMTraceApi<Balance> where
+		Balance: Codec + MaybeDisplay + MaybeFromStr,
+	{
+		/// Trace a call, or a create if `to` is `None`.
+		#[changed_in(2)]
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			tracer_config: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		/// Trace a call, or a create if `to` is `None`.
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer_config: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+
+		/// Apply `extrinsics` in order and then trace `traced`.
+		///
+		/// The block must be initialized before calling this.
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			traced: <Block as BlockT>::Extrinsic,
+			tracer_config: TracerConfig,
+		) -> Result<TraceOutcome, sp_runtime::DispatchError>;
+	}
+
+	pub trait EthRuntimeRPCApi {
+		fn chain_id() -> u64;
+
+		/// The free balance of `address` in the EVM decimals.
+		fn balance(address: H160) -> U256;
+
+		fn nonce(address: H160) -> U256;
+
+		fn code(address: H160) -> Vec<u8>;
+
+		fn storage_at(address: H160, index: H256) -> H256;
+
+		/// The timestamp of the current block in milliseconds.
+		fn timestamp() -> u64;
+
+		/// The EVM transaction of `extrinsic`, `None` if it doesn't execute the EVM.
+		fn transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<EthereumTransaction>;
+
+		/// The receipts of the EVM transactions of the current block.
+		fn receipts() -> Vec<EthereumReceipt>;
+
+		/// Convert a raw signed Ethereum transaction into an extrinsic.
+		fn convert_transaction(transaction: Vec<u8>) -> Result<<Block as BlockT>::Extrinsic, sp_runtime::DispatchError>;
+	}
 }
@@ -0,0 +1,502 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum compatible rpc implementation.
+
+use ethereum_types::{BigEndianHash, Bloom, H160, H256, H64, U256, U64};
+use frame_support::log;
+use jsonrpc_core::{BoxFuture, Error, ErrorCode, Result};
+use module_evm::TransactionAction;
+use sc_client_api::BlockBackend;
+use sc_transaction_pool_api::{TransactionPool, TransactionSource};
+use sp_api::ProvideRuntimeApi;
+use sp_blockchain::HeaderBackend;
+use sp_core::{Bytes, Encode};
+use sp_runtime::{
+	codec::Codec,
+	generic::BlockId,
+	traits::{Block as BlockT, Hash as HashT, Header as HeaderT, MaybeDisplay, MaybeFromStr, Zero},
+	SaturatedConversion,
+};
+use std::{marker::PhantomData, sync::Arc};
+
+use crate::{
+	error_on_execution_failure,
+	eth_api::EthApi as EthApiT,
+	eth_types::{
+		logs_bloom, BlockNumber, BlockTransactions, EthCallRequest, Filter, Log, Receipt, RichBlock, Transaction,
+	},
+	internal_err, runtime_call, runtime_create, runtime_err, EVMRuntimeRPCApi, EthRuntimeRPCApi, ExitReason,
+	MAX_GAS_LIMIT, MAX_LOGS_BLOCK_RANGE, MAX_RECEIPT_SEARCH_DEPTH, MAX_STROAGE_LIMIT,
+};
+
+/// Keccak256 of the RLP encoded empty list, the uncles hash of a block without uncles.
+const EMPTY_UNCLES_HASH: H256 = H256([
+	0x1d, 0xcc, 0x4d, 0xe8, 0xde, 0xc7, 0x5d, 0x7a, 0xab, 0x85, 0xb5, 0x67, 0xb6, 0xcc, 0xd4, 0x1a, 0xd3, 0x12, 0x45,
+	0x1b, 0x94, 0x8a, 0x74, 0x13, 0xf0, 0xa1, 0x42, 0xfd, 0x40, 0xd4, 0x93, 0x47,
+]);
+
+pub struct EthApi<B, C, P, Balance> {
+	client: Arc<C>,
+	pool: Arc<P>,
+	_marker: PhantomData<(B, Balance)>,
+}
+
+impl<B, C, P, Balance> EthApi<B, C, P, Balance> {
+	pub fn new(client: Arc<C>, pool: Arc<P>) -> Self {
+		Self {
+			client,
+			pool,
+			_marker: Default::default(),
+		}
+	}
+}
+
+fn blockchain_err<T: std::fmt::Debug>(err: T) -> Error {
+	internal_err(format!("blockchain error: {:?}", err))
+}
+
+impl<B, C, P, Balance> EthApi<B, C, P, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: EthRuntimeRPCApi<B>,
+	P: TransactionPool<Block = B, Hash = H256> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn best_number(&self) -> u64 {
+		self.client.info().best_number.saturated_into()
+	}
+
+	fn to_number(&self, number: BlockNumber) -> u64 {
+		match number {
+			BlockNumber::Num(number) => number,
+			BlockNumber::Earliest => 0,
+			BlockNumber::Latest | BlockNumber::Pending => self.best_number(),
+		}
+	}
+
+	fn hash_of_number(&self, number: u64) -> Result<Option<H256>> {
+		self.client.hash(number.saturated_into()).map_err(blockchain_err)
+	}
+
+	fn block_id(&self, number: Option<BlockNumber>) -> Result<BlockId<B>> {
+		match number.unwrap_or_default() {
+			BlockNumber::Latest | BlockNumber::Pending => Ok(BlockId::Hash(self.client.info().best_hash)),
+			number => {
+				let number = self.to_number(number);
+				self.hash_of_number(number)?
+					.map(BlockId::Hash)
+					.ok_or_else(|| internal_err(format!("block #{} not found", number)))
+			}
+		}
+	}
+
+	fn block(&self, hash: H256) -> Result<Option<B>> {
+		Ok(self
+			.client
+			.block(&BlockId::Hash(hash))
+			.map_err(blockchain_err)?
+			.map(|block| block.block))
+	}
+
+	fn transactions(&self, hash: H256, number: U256, extrinsics: &[B::Extrinsic]) -> Result<Vec<Transaction>> {
+		let api = self.client.runtime_api();
+		let mut transactions = Vec::new();
+		for (index, xt) in extrinsics.iter().enumerate() {
+			let tx = match api.transaction(&BlockId::Hash(hash), xt.clone()).map_err(runtime_err)? {
+				Some(tx) => tx,
+				None => continue,
+			};
+
+			transactions.push(Transaction {
+				hash: <<B::Header as HeaderT>::Hashing as HashT>::hash_of(xt),
+				nonce: tx.nonce.into(),
+				block_hash: hash,
+				block_number: number,
+				transaction_index: index.into(),
+				from: tx.from,
+				to: match tx.action {
+					TransactionAction::Call(to) => Some(to),
+					TransactionAction::Create => None,
+				},
+				value: tx.value,
+				gas: tx.gas_limit.into(),
+				input: Bytes(tx.input),
+			});
+		}
+		Ok(transactions)
+	}
+
+	fn receipts(&self, hash: H256, number: U256, extrinsics: &[B::Extrinsic]) -> Result<Vec<Receipt>> {
+		let receipts = self
+			.client
+			.runtime_api()
+			.receipts(&BlockId::Hash(hash))
+			.map_err(runtime_err)?;
+
+		let mut cumulative_gas_used = U256::zero();
+		let mut log_index = U256::zero();
+		receipts
+			.into_iter()
+			.map(|receipt| {
+				let transaction_hash = extrinsics
+					.get(receipt.extrinsic_index as usize)
+					.map(<<B::Header as HeaderT>::Hashing as HashT>::hash_of)
+					.ok_or_else(|| internal_err(format!("extrinsic {} not found", receipt.extrinsic_index)))?;
+				let transaction_index = U256::from(receipt.extrinsic_index);
+
+				let logs = receipt
+					.logs
+					.into_iter()
+					.map(|log| {
+						let log = Log {
+							address: log.address,
+							topics: log.topics,
+							data: Bytes(log.data),
+							block_hash: hash,
+							block_number: number,
+							transaction_hash,
+							transaction_index,
+							log_index,
+							removed: false,
+						};
+						log_index += U256::one();
+						log
+					})
+					.collect::<Vec<_>>();
+
+				cumulative_gas_used += U256::from(receipt.used_gas);
+
+				Ok(Receipt {
+					transaction_hash,
+					transaction_index,
+					block_hash: hash,
+					block_number: number,
+					from: receipt.from,
+					to: (!receipt.is_create).then(|| receipt.contract),
+					cumulative_gas_used,
+					gas_used: receipt.used_gas.into(),
+					contract_address: receipt.is_create.then(|| receipt.contract),
+					logs_bloom: logs_bloom(&logs),
+					logs,
+					status: U64::from(receipt.succeed as u64),
+				})
+			})
+			.collect()
+	}
+
+	fn rich_block(&self, hash: H256, full: bool) -> Result<Option<RichBlock>> {
+		let block = match self.block(hash)? {
+			Some(block) => block,
+			None => return Ok(None),
+		};
+		let size = block.encoded_size();
+		let (header, extrinsics) = block.deconstruct();
+		let number = U256::from((*header.number()).saturated_into::<u64>());
+
+		let timestamp = self
+			.client
+			.runtime_api()
+			.timestamp(&BlockId::Hash(hash))
+			.map_err(runtime_err)?;
+		let receipts = self.receipts(hash, number, &extrinsics)?;
+		let gas_used = receipts
+			.iter()
+			.fold(U256::zero(), |acc, receipt| acc + receipt.gas_used);
+		let mut bloom = Bloom::default();
+		receipts
+			.iter()
+			.for_each(|receipt| bloom.accrue_bloom(&receipt.logs_bloom));
+
+		let transactions = self.transactions(hash, number, &extrinsics)?;
+		let transactions = if full {
+			BlockTransactions::Full(transactions)
+		} else {
+			BlockTransactions::Hashes(transactions.into_iter().map(|tx| tx.hash).collect())
+		};
+
+		Ok(Some(RichBlock {
+			hash,
+			parent_hash: *header.parent_hash(),
+			sha3_uncles: EMPTY_UNCLES_HASH,
+			miner: H160::zero(),
+			state_root: *header.state_root(),
+			transactions_root: *header.extrinsics_root(),
+			// the receipts are collected from the events and aren't committed to by the header
+			receipts_root: H256::zero(),
+			logs_bloom: bloom,
+			difficulty: U256::zero(),
+			total_difficulty: U256::zero(),
+			number,
+			gas_limit: MAX_GAS_LIMIT.into(),
+			gas_used,
+			timestamp: (timestamp / 1000).into(),
+			extra_data: Bytes(Vec::new()),
+			nonce: H64::zero(),
+			size: size.into(),
+			transactions,
+			uncles: Vec::new(),
+		}))
+	}
+
+	fn logs_at(&self, hash: H256, filter: &Filter) -> Result<Vec<Log>> {
+		let block = match self.block(hash)? {
+			Some(block) => block,
+			None => return Ok(Vec::new()),
+		};
+		let (header, extrinsics) = block.deconstruct();
+		let number = U256::from((*header.number()).saturated_into::<u64>());
+
+		Ok(self
+			.receipts(hash, number, &extrinsics)?
+			.into_iter()
+			.flat_map(|receipt| receipt.logs)
+			.filter(|log| filter.matches(log))
+			.collect())
+	}
+
+	/// Execute the call request, returning the exit reason, the output and the used gas.
+	fn execute(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<(ExitReason, Vec<u8>, U256)> {
+		let at = self.block_id(number)?;
+
+		let EthCallRequest {
+			from,
+			to,
+			gas,
+			value,
+			data,
+			input,
+			access_list,
+		} = request;
+
+		let from = from.unwrap_or_default();
+		let gas_limit = gas.map_or(MAX_GAS_LIMIT, |gas| gas.min(MAX_GAS_LIMIT.into()).as_u64());
+		let data = data.or(input).map(|d| d.0).unwrap_or_default();
+		let access_list = access_list.map(|list| list.into_iter().map(Into::into).collect());
+
+		// the value is in the EVM decimals
+		let balance_value = u128::try_from(value.unwrap_or_default())
+			.ok()
+			.and_then(primitives::convert_decimals_from_evm)
+			.and_then(|v| TryInto::<Balance>::try_into(v).ok())
+			.ok_or_else(|| Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("Invalid parameter value: {:?}", value),
+				data: None,
+			})?;
+
+		match to {
+			Some(to) => {
+				let info = runtime_call(
+					&*self.client,
+					&at,
+					from,
+					to,
+					data,
+					balance_value,
+					gas_limit,
+					MAX_STROAGE_LIMIT,
+					access_list,
+					true,
+				)?;
+
+				Ok((info.exit_reason, info.value, info.used_gas))
+			}
+			None => {
+				let info = runtime_create(
+					&*self.client,
+					&at,
+					from,
+					data,
+					balance_value,
+					gas_limit,
+					MAX_STROAGE_LIMIT,
+					access_list,
+					true,
+				)?;
+
+				Ok((info.exit_reason, info.value.as_bytes().to_vec(), info.used_gas))
+			}
+		}
+	}
+}
+
+impl<B, C, P, Balance> EthApiT for EthApi<B, C, P, Balance>
+where
+	B: BlockT<Hash = H256>,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: EthRuntimeRPCApi<B>,
+	P: TransactionPool<Block = B, Hash = H256> + 'static,
+	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
+{
+	fn chain_id(&self) -> Result<U64> {
+		let hash = self.client.info().best_hash;
+		self.client
+			.runtime_api()
+			.chain_id(&BlockId::Hash(hash))
+			.map(Into::into)
+			.map_err(runtime_err)
+	}
+
+	fn block_number(&self) -> Result<U256> {
+		Ok(self.best_number().into())
+	}
+
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		let at = self.block_id(number)?;
+		self.client.runtime_api().balance(&at, address).map_err(runtime_err)
+	}
+
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes> {
+		let at = self.block_id(number)?;
+		self.client
+			.runtime_api()
+			.code(&at, address)
+			.map(Bytes)
+			.map_err(runtime_err)
+	}
+
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256> {
+		let at = self.block_id(number)?;
+		self.client
+			.runtime_api()
+			.storage_at(&at, address, H256::from_uint(&index))
+			.map_err(runtime_err)
+	}
+
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256> {
+		let at = self.block_id(number)?;
+		self.client.runtime_api().nonce(&at, address).map_err(runtime_err)
+	}
+
+	fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<Bytes> {
+		let (exit_reason, data, _) = self.execute(request, number)?;
+
+		log::debug!(
+			target: "evm",
+			"eth_call, exit_reason: {:?}, data: {:?}",
+			exit_reason, data,
+		);
+		error_on_execution_failure(&exit_reason, &data)?;
+
+		Ok(Bytes(data))
+	}
+
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<U256> {
+		let (exit_reason, data, used_gas) = self.execute(request, number)?;
+		error_on_execution_failure(&exit_reason, &data)?;
+
+		Ok(used_gas)
+	}
+
+	fn send_raw_transaction(&self, transaction: Bytes) -> BoxFuture<Result<H256>> {
+		let best = BlockId::Hash(self.client.info().best_hash);
+
+		let xt = self
+			.client
+			.runtime_api()
+			.convert_transaction(&best, transaction.to_vec())
+			.map_err(runtime_err)
+			.and_then(|xt| xt.map_err(|err| internal_err(format!("invalid transaction: {:?}", err))));
+
+		let pool = self.pool.clone();
+		Box::pin(async move {
+			pool.submit_one(&best, TransactionSource::External, xt?)
+				.await
+				.map_err(|err| internal_err(format!("submit transaction failed: {:?}", err)))
+		})
+	}
+
+	fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<RichBlock>> {
+		self.rich_block(hash, full)
+	}
+
+	fn block_by_number(&self, number: BlockNumber, full: bool) -> Result<Option<RichBlock>> {
+		match self.hash_of_number(self.to_number(number))? {
+			Some(hash) => self.rich_block(hash, full),
+			None => Ok(None),
+		}
+	}
+
+	fn transaction_receipt(&self, transaction_hash: H256) -> Result<Option<Receipt>> {
+		let mut hash = self.client.info().best_hash;
+		for _ in 0..MAX_RECEIPT_SEARCH_DEPTH {
+			let block = self
+				.block(hash)?
+				.ok_or_else(|| internal_err(format!("block {:?} not found", hash)))?;
+			let (header, extrinsics) = block.deconstruct();
+
+			if extrinsics
+				.iter()
+				.any(|xt| <<B::Header as HeaderT>::Hashing as HashT>::hash_of(xt) == transaction_hash)
+			{
+				let number = U256::from((*header.number()).saturated_into::<u64>());
+				// `None` if the extrinsic didn't execute the EVM
+				return Ok(self
+					.receipts(hash, number, &extrinsics)?
+					.into_iter()
+					.find(|receipt| receipt.transaction_hash == transaction_hash));
+			}
+
+			if header.number().is_zero() {
+				// not included in the chain
+				return Ok(None);
+			}
+			hash = *header.parent_hash();
+		}
+
+		// still pending
+		if self.pool.ready_transaction(&transaction_hash).is_some() {
+			return Ok(None);
+		}
+
+		Err(internal_err(format!(
+			"transaction {:?} not found in the latest {} blocks",
+			transaction_hash, MAX_RECEIPT_SEARCH_DEPTH
+		)))
+	}
+
+	fn logs(&self, filter: Filter) -> Result<Vec<Log>> {
+		if let Some(hash) = filter.block_hash {
+			return self.logs_at(hash, &filter);
+		}
+
+		let from = self.to_number(filter.from_block.unwrap_or_default());
+		let to = self
+			.to_number(filter.to_block.unwrap_or_default())
+			.min(self.best_number());
+		if to.saturating_sub(from) >= MAX_LOGS_BLOCK_RANGE {
+			return Err(Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("block range exceeds the maximum of {}", MAX_LOGS_BLOCK_RANGE),
+				data: None,
+			});
+		}
+
+		let mut logs = Vec::new();
+		for number in from..=to {
+			if let Some(hash) = self.hash_of_number(number)? {
+				logs.extend(self.logs_at(hash, &filter)?);
+			}
+		}
+		Ok(logs)
+	}
+}
@@ -0,0 +1,94 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum compatible rpc interface.
+
+use ethereum_types::{H160, H256, U256, U64};
+use jsonrpc_core::{BoxFuture, Result};
+use jsonrpc_derive::rpc;
+use sp_core::Bytes;
+
+pub use rpc_impl_EthApi::gen_server::EthApi as EthApiServer;
+
+use crate::eth_types::{BlockNumber, EthCallRequest, Filter, Log, Receipt, RichBlock};
+
+/// Ethereum compatible rpc interface.
+///
+/// Values are in the EVM decimals (wei), the block tag `pending` is treated as `latest`.
+///
+/// An extrinsic executing several EVM transactions has a receipt for each of them, and the
+/// `receiptsRoot` of the blocks is always zero as the header doesn't commit to the receipts.
+#[rpc(server)]
+pub trait EthApi {
+	/// Returns the chain id.
+	#[rpc(name = "eth_chainId")]
+	fn chain_id(&self) -> Result<U64>;
+
+	/// Returns the number of the best block.
+	#[rpc(name = "eth_blockNumber")]
+	fn block_number(&self) -> Result<U256>;
+
+	/// Returns the free balance of the given address.
+	#[rpc(name = "eth_getBalance")]
+	fn balance(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Returns the code of the given contract.
+	#[rpc(name = "eth_getCode")]
+	fn code_at(&self, address: H160, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Returns the storage value of the given contract at the given index.
+	#[rpc(name = "eth_getStorageAt")]
+	fn storage_at(&self, address: H160, index: U256, number: Option<BlockNumber>) -> Result<H256>;
+
+	/// Returns the nonce of the given address.
+	#[rpc(name = "eth_getTransactionCount")]
+	fn transaction_count(&self, address: H160, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Call contract, returning the output data.
+	#[rpc(name = "eth_call")]
+	fn call(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<Bytes>;
+
+	/// Estimate the gas needed for execution of the given call.
+	#[rpc(name = "eth_estimateGas")]
+	fn estimate_gas(&self, request: EthCallRequest, number: Option<BlockNumber>) -> Result<U256>;
+
+	/// Submit a signed Ethereum transaction, returning the extrinsic hash.
+	#[rpc(name = "eth_sendRawTransaction")]
+	fn send_raw_transaction(&self, transaction: Bytes) -> BoxFuture<Result<H256>>;
+
+	/// Returns the block of the given hash.
+	#[rpc(name = "eth_getBlockByHash")]
+	fn block_by_hash(&self, hash: H256, full: bool) -> Result<Option<RichBlock>>;
+
+	/// Returns the block of the given number.
+	#[rpc(name = "eth_getBlockByNumber")]
+	fn block_by_number(&self, number: BlockNumber, full: bool) -> Result<Option<RichBlock>>;
+
+	/// Returns the receipt of the given transaction.
+	///
+	/// The transaction is searched from the best block back to its ancestors, at most 256 blocks
+	/// deep. Returns an error if it is not found within them and it is not pending in the pool,
+	/// `None` if it is pending or not in the chain. The first receipt is returned if the extrinsic
+	/// executed several EVM transactions.
+	#[rpc(name = "eth_getTransactionReceipt")]
+	fn transaction_receipt(&self, hash: H256) -> Result<Option<Receipt>>;
+
+	/// Returns the logs matching the given filter.
+	#[rpc(name = "eth_getLogs")]
+	fn logs(&self, filter: Filter) -> Result<Vec<Log>>;
+}
@@ -0,0 +1,344 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Ethereum JSON-RPC types.
+
+use ethereum_types::{Bloom, BloomInput, H160, H256, H64, U256, U64};
+use module_evm::AccessListItem;
+use serde::{de::Error as _, Deserialize, Deserializer, Serialize};
+use sp_core::Bytes;
+
+/// A block number or a block tag.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum BlockNumber {
+	Num(u64),
+	Latest,
+	Earliest,
+	Pending,
+}
+
+impl Default for BlockNumber {
+	fn default() -> Self {
+		BlockNumber::Latest
+	}
+}
+
+impl<'de> Deserialize<'de> for BlockNumber {
+	fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+	where
+		D: Deserializer<'de>,
+	{
+		#[derive(Deserialize)]
+		#[serde(untagged)]
+		enum NumberOrTag {
+			Num(u64),
+			Tag(String),
+		}
+
+		match NumberOrTag::deserialize(deserializer)? {
+			NumberOrTag::Num(number) => Ok(BlockNumber::Num(number)),
+			NumberOrTag::Tag(tag) => match tag.as_str() {
+				"latest" => Ok(BlockNumber::Latest),
+				"earliest" => Ok(BlockNumber::Earliest),
+				"pending" => Ok(BlockNumber::Pending),
+				_ => tag
+					.strip_prefix("0x")
+					.and_then(|hex| u64::from_str_radix(hex, 16).ok())
+					.map(BlockNumber::Num)
+					.ok_or_else(|| D::Error::custom(format!("Invalid block number: {}", tag))),
+			},
+		}
+	}
+}
+
+/// Call request of `eth_call` and `eth_estimateGas`.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct EthCallRequest {
+	/// From
+	pub from: Option<H160>,
+	/// To, `None` to create a contract
+	pub to: Option<H160>,
+	/// Gas Limit
+	pub gas: Option<U256>,
+	/// Value in the EVM decimals
+	pub value: Option<U256>,
+	/// Data
+	pub data: Option<Bytes>,
+	/// Data, an alias of `data`
+	pub input: Option<Bytes>,
+	/// The addresses and storage keys to warm up before execution (EIP-2930)
+	pub access_list: Option<Vec<AccessListEntry>>,
+}
+
+/// An entry of the access list of a call request.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct AccessListEntry {
+	pub address: H160,
+	pub storage_keys: Vec<H256>,
+}
+
+impl From<AccessListEntry> for AccessListItem {
+	fn from(entry: AccessListEntry) -> Self {
+		AccessListItem {
+			address: entry.address,
+			slots: entry.storage_keys,
+		}
+	}
+}
+
+/// A single value or a list of values of a filter.
+#[derive(Debug, PartialEq, Deserialize, Clone)]
+#[serde(untagged)]
+pub enum VariadicValue<T> {
+	Single(T),
+	Multiple(Vec<T>),
+}
+
+impl<T: PartialEq> VariadicValue<T> {
+	fn contains(&self, value: &T) -> bool {
+		match self {
+			VariadicValue::Single(v) => v == value,
+			VariadicValue::Multiple(values) => values.contains(value),
+		}
+	}
+}
+
+/// Log filter of `eth_getLogs`.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Filter {
+	pub from_block: Option<BlockNumber>,
+	pub to_block: Option<BlockNumber>,
+	/// Filter a single block, can't be used with `from_block` or `to_block`
+	pub block_hash: Option<H256>,
+	pub address: Option<VariadicValue<H160>>,
+	/// The topics by position, `None` matches any topic
+	pub topics: Option<Vec<Option<VariadicValue<H256>>>>,
+}
+
+impl Filter {
+	pub fn matches(&self, log: &Log) -> bool {
+		if let Some(address) = &self.address {
+			if !address.contains(&log.address) {
+				return false;
+			}
+		}
+
+		self.topics.iter().flatten().enumerate().all(|(i, topic)| match topic {
+			Some(topic) => log.topics.get(i).map_or(false, |t| topic.contains(t)),
+			None => true,
+		})
+	}
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Log {
+	pub address: H160,
+	pub topics: Vec<H256>,
+	pub data: Bytes,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub transaction_hash: H256,
+	pub transaction_index: U256,
+	pub log_index: U256,
+	pub removed: bool,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Transaction {
+	pub hash: H256,
+	pub nonce: U256,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub transaction_index: U256,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub value: U256,
+	pub gas: U256,
+	pub input: Bytes,
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct Receipt {
+	pub transaction_hash: H256,
+	pub transaction_index: U256,
+	pub block_hash: H256,
+	pub block_number: U256,
+	pub from: H160,
+	pub to: Option<H160>,
+	pub cumulative_gas_used: U256,
+	pub gas_used: U256,
+	pub contract_address: Option<H160>,
+	pub logs: Vec<Log>,
+	pub logs_bloom: Bloom,
+	/// 1 for success, 0 for failure
+	pub status: U64,
+}
+
+/// The transactions of a block, only the hashes unless the full transactions are requested.
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(untagged)]
+pub enum BlockTransactions {
+	Hashes(Vec<H256>),
+	Full(Vec<Transaction>),
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct RichBlock {
+	pub hash: H256,
+	pub parent_hash: H256,
+	pub sha3_uncles: H256,
+	pub miner: H160,
+	pub state_root: H256,
+	pub transactions_root: H256,
+	pub receipts_root: H256,
+	pub logs_bloom: Bloom,
+	pub difficulty: U256,
+	pub total_difficulty: U256,
+	pub number: U256,
+	pub gas_limit: U256,
+	pub gas_used: U256,
+	pub timestamp: U256,
+	pub extra_data: Bytes,
+	pub nonce: H64,
+	pub size: U256,
+	pub transactions: BlockTransactions,
+	pub uncles: Vec<H256>,
+}
+
+/// The bloom of the logs.
+pub fn logs_bloom<'a>(logs: impl IntoIterator<Item = &'a Log>) -> Bloom {
+	let mut bloom = Bloom::default();
+	for log in logs {
+		bloom.accrue(BloomInput::Raw(log.address.as_bytes()));
+		for topic in &log.topics {
+			bloom.accrue(BloomInput::Raw(topic.as_bytes()));
+		}
+	}
+	bloom
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use std::str::FromStr;
+
+	fn log(address: H160, topics: Vec<H256>) -> Log {
+		Log {
+			address,
+			topics,
+			data: Bytes(vec![]),
+			block_hash: H256::zero(),
+			block_number: U256::zero(),
+			transaction_hash: H256::zero(),
+			transaction_index: U256::zero(),
+			log_index: U256::zero(),
+			removed: false,
+		}
+	}
+
+	#[test]
+	fn block_number_deserialize_works() {
+		let numbers: Vec<BlockNumber> =
+			serde_json::from_str(r#"["latest", "earliest", "pending", "0x1f", 31]"#).unwrap();
+		assert_eq!(
+			numbers,
+			vec![
+				BlockNumber::Latest,
+				BlockNumber::Earliest,
+				BlockNumber::Pending,
+				BlockNumber::Num(31),
+				BlockNumber::Num(31)
+			]
+		);
+
+		assert!(serde_json::from_str::<BlockNumber>(r#""1f""#).is_err());
+		assert!(serde_json::from_str::<BlockNumber>(r#""finalized""#).is_err());
+	}
+
+	#[test]
+	fn call_request_deserialize_works() {
+		let request: EthCallRequest = serde_json::from_str(
+			r#"{
+				"to": "0x1000000000000000000000000000000000000001",
+				"data": "0x12",
+				"accessList": [{
+					"address": "0x1000000000000000000000000000000000000002",
+					"storageKeys": ["0x0000000000000000000000000000000000000000000000000000000000000001"]
+				}]
+			}"#,
+		)
+		.unwrap();
+		assert_eq!(request.data, Some(Bytes(vec![0x12])));
+
+		let access_list: Vec<AccessListItem> = request.access_list.unwrap().into_iter().map(Into::into).collect();
+		assert_eq!(
+			access_list,
+			vec![AccessListItem {
+				address: H160::from_str("0x1000000000000000000000000000000000000002").unwrap(),
+				slots: vec![H256::from_low_u64_be(1)],
+			}]
+		);
+
+		let request: EthCallRequest = serde_json::from_str(r#"{"data": "0x12"}"#).unwrap();
+		assert_eq!(request.to, None);
+		assert_eq!(request.access_list, None);
+	}
+
+	#[test]
+	fn filter_matches_works() {
+		let address = H160::from_str("0x1000000000000000000000000000000000000001").unwrap();
+		let topic_a = H256::from_low_u64_be(1);
+		let topic_b = H256::from_low_u64_be(2);
+
+		let filter: Filter = serde_json::from_str(r#"{"fromBlock": "0x1", "toBlock": "latest"}"#).unwrap();
+		assert_eq!(filter.from_block, Some(BlockNumber::Num(1)));
+		assert!(filter.matches(&log(address, vec![])));
+
+		let filter: Filter = serde_json::from_str(
+			r#"{
+				"address": "0x1000000000000000000000000000000000000001",
+				"topics": [null, ["0x0000000000000000000000000000000000000000000000000000000000000002"]]
+			}"#,
+		)
+		.unwrap();
+		assert!(filter.matches(&log(address, vec![topic_a, topic_b])));
+		assert!(filter.matches(&log(address, vec![topic_b, topic_b])));
+		assert!(!filter.matches(&log(address, vec![topic_a, topic_a])));
+		assert!(!filter.matches(&log(address, vec![topic_a])));
+		assert!(!filter.matches(&log(H160::zero(), vec![topic_a, topic_b])));
+	}
+
+	#[test]
+	fn logs_bloom_works() {
+		let address = H160::from_str("0x1000000000000000000000000000000000000001").unwrap();
+		let topic = H256::from_low_u64_be(1);
+		let bloom = logs_bloom(&[log(address, vec![topic])]);
+
+		assert!(bloom.contains_input(BloomInput::Raw(address.as_bytes())));
+		assert!(bloom.contains_input(BloomInput::Raw(topic.as_bytes())));
+		assert!(!bloom.contains_input(BloomInput::Raw(H160::zero().as_bytes())));
+		assert_eq!(logs_bloom(&[]), Bloom::default());
+	}
+}
@@ -25,7 +25,10 @@ use sp_core::Bytes;
 
 pub use rpc_impl_EVMApi::gen_server::EVMApi as EVMApiServer;
 
-use crate::call_request::{CallRequest, EstimateResourcesResponse};
+use crate::{
+	call_request::{CallRequest, EstimateResourcesResponse},
+	trace::{TraceOptions, TraceResponse},
+};
 
 /// EVM rpc interface.
 #[rpc(server)]
@@ -42,4 +45,20 @@ pub trait EVMApi<BlockHash> {
 		unsigned_extrinsic: Bytes,
 		at: Option<BlockHash>,
 	) -> Result<EstimateResourcesResponse>;
+
+	/// Trace the given transaction, compatible with Geth `debug_traceTransaction`.
+	///
+	/// The transaction is searched from `at` (the best block by default) back to its ancestors.
+	#[rpc(name = "debug_traceTransaction")]
+	fn trace_transaction(
+		&self,
+		transaction_hash: BlockHash,
+		options: Option<TraceOptions>,
+		at: Option<BlockHash>,
+	) -> Result<TraceResponse>;
+
+	/// Trace a call, compatible with Geth `debug_traceCall`.
+	#[rpc(name = "debug_traceCall")]
+	fn trace_call(&self, _: CallRequest, at: Option<BlockHash>, options: Option<TraceOptions>)
+		-> Result<TraceResponse>;
 }
@@ -22,28 +22,37 @@ use ethereum_types::{H160, U256};
 use frame_support::log;
 use jsonrpc_core::{Error, ErrorCode, Result, Value};
 use pallet_transaction_payment_rpc_runtime_api::TransactionPaymentApi;
+use primitives::evm::{AccessListItem, CallInfo, CreateInfo};
 use rustc_hex::ToHex;
+use sc_client_api::BlockBackend;
 use sc_rpc_api::DenyUnsafe;
-use sp_api::ProvideRuntimeApi;
+use sp_api::{ApiExt, Core, ProvideRuntimeApi};
 use sp_blockchain::HeaderBackend;
 use sp_core::{Bytes, Decode};
 use sp_rpc::number::NumberOrHex;
 use sp_runtime::{
 	codec::Codec,
 	generic::BlockId,
-	traits::{self, Block as BlockT, MaybeDisplay, MaybeFromStr},
+	traits::{self, Block as BlockT, Hash as HashT, Header as HeaderT, MaybeDisplay, MaybeFromStr, Zero},
 	SaturatedConversion,
 };
 use std::{marker::PhantomData, sync::Arc};
 
 use call_request::{CallRequest, EstimateResourcesResponse};
 pub use module_evm::{ExitError, ExitReason};
-pub use module_evm_rpc_runtime_api::EVMRuntimeRPCApi;
+pub use module_evm_rpc_runtime_api::{EVMRuntimeRPCApi, EVMTraceApi, EthRuntimeRPCApi};
+use trace::{TraceOptions, TraceResponse};
 
+pub use crate::eth::EthApi;
+pub use crate::eth_api::{EthApi as EthApiT, EthApiServer};
 pub use crate::evm_api::{EVMApi as EVMApiT, EVMApiServer};
 
 mod call_request;
+mod eth;
+mod eth_api;
+mod eth_types;
 mod evm_api;
+mod trace;
 
 fn internal_err<T: ToString>(message: T) -> Error {
 	Error {
@@ -107,15 +116,15 @@ fn decode_revert_message(data: &[u8]) -> Option<String> {
 
 pub struct EVMApi<B, C, Balance> {
 	client: Arc<C>,
-	_deny_unsafe: DenyUnsafe,
+	deny_unsafe: DenyUnsafe,
 	_marker: PhantomData<(B, Balance)>,
 }
 
 impl<B, C, Balance> EVMApi<B, C, Balance> {
-	pub fn new(client: Arc<C>, _deny_unsafe: DenyUnsafe) -> Self {
+	pub fn new(client: Arc<C>, deny_unsafe: DenyUnsafe) -> Self {
 		Self {
 			client,
-			_deny_unsafe,
+			deny_unsafe,
 			_marker: Default::default(),
 		}
 	}
@@ -125,16 +134,120 @@ fn to_u128(val: NumberOrHex) -> std::result::Result<u128, ()> {
 	val.into_u256().try_into().map_err(|_| ())
 }
 
+fn runtime_err<T: std::fmt::Debug>(err: T) -> Error {
+	internal_err(format!("runtime error: {:?}", err))
+}
+
+fn execution_err<T: std::fmt::Debug>(err: T) -> Error {
+	internal_err(format!("execution fatal: {:?}", err))
+}
+
+// The runtime APIs before version 2 don't support the access list.
+fn ensure_no_access_list(access_list: &Option<Vec<AccessListItem>>) -> Result<()> {
+	match access_list {
+		Some(list) if !list.is_empty() => Err(Error {
+			code: ErrorCode::InvalidParams,
+			message: "Invalid parameter accessList, not supported by the runtime".into(),
+			data: None,
+		}),
+		_ => Ok(()),
+	}
+}
+
+/// Execute `EVMRuntimeRPCApi::call`, falling back to the API before version 2 for older runtimes.
+#[allow(clippy::too_many_arguments, deprecated)]
+fn runtime_call<B, C, Balance>(
+	client: &C,
+	at: &BlockId<B>,
+	from: H160,
+	to: H160,
+	data: Vec<u8>,
+	value: Balance,
+	gas_limit: u64,
+	storage_limit: u32,
+	access_list: Option<Vec<AccessListItem>>,
+	estimate: bool,
+) -> Result<CallInfo>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	let api = client.runtime_api();
+	if api
+		.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(at, |version| version >= 2)
+		.map_err(runtime_err)?
+	{
+		api.call(
+			at,
+			from,
+			to,
+			data,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list,
+			estimate,
+		)
+	} else {
+		ensure_no_access_list(&access_list)?;
+		api.call_before_version_2(at, from, to, data, value, gas_limit, storage_limit, estimate)
+	}
+	.map_err(runtime_err)?
+	.map_err(execution_err)
+}
+
+/// Execute `EVMRuntimeRPCApi::create`, falling back to the API before version 2 for older runtimes.
+#[allow(clippy::too_many_arguments, deprecated)]
+fn runtime_create<B, C, Balance>(
+	client: &C,
+	at: &BlockId<B>,
+	from: H160,
+	data: Vec<u8>,
+	value: Balance,
+	gas_limit: u64,
+	storage_limit: u32,
+	access_list: Option<Vec<AccessListItem>>,
+	estimate: bool,
+) -> Result<CreateInfo>
+where
+	B: BlockT,
+	C: ProvideRuntimeApi<B>,
+	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	Balance: Codec + MaybeDisplay + MaybeFromStr,
+{
+	let api = client.runtime_api();
+	if api
+		.has_api_with::<dyn EVMRuntimeRPCApi<B, Balance>, _>(at, |version| version >= 2)
+		.map_err(runtime_err)?
+	{
+		api.create(at, from, data, value, gas_limit, storage_limit, access_list, estimate)
+	} else {
+		ensure_no_access_list(&access_list)?;
+		api.create_before_version_2(at, from, data, value, gas_limit, storage_limit, estimate)
+	}
+	.map_err(runtime_err)?
+	.map_err(execution_err)
+}
+
 // 20M. TODO: use value from runtime
 const MAX_GAS_LIMIT: u64 = 20_000_000;
 // 4M. TODO: use value from runtime
 const MAX_STROAGE_LIMIT: u32 = 4 * 1024 * 1024;
+// The max number of blocks to search for the traced transaction.
+const MAX_TRACE_SEARCH_DEPTH: u32 = 256;
+// The max number of blocks to search for the transaction of a receipt.
+const MAX_RECEIPT_SEARCH_DEPTH: u32 = 256;
+// The max number of blocks to query in a single `eth_getLogs`.
+const MAX_LOGS_BLOCK_RANGE: u64 = 1024;
 
 impl<B, C, Balance> EVMApiT<<B as BlockT>::Hash> for EVMApi<B, C, Balance>
 where
 	B: BlockT,
-	C: ProvideRuntimeApi<B> + HeaderBackend<B> + Send + Sync + 'static,
+	C: ProvideRuntimeApi<B> + HeaderBackend<B> + BlockBackend<B> + Send + Sync + 'static,
 	C::Api: EVMRuntimeRPCApi<B, Balance>,
+	C::Api: EVMTraceApi<B, Balance>,
 	C::Api: TransactionPaymentApi<B, Balance>,
 	Balance: Codec + MaybeDisplay + MaybeFromStr + Default + Send + Sync + 'static + TryFrom<u128> + Into<U256>,
 {
@@ -154,8 +267,6 @@ where
 		let storage_limit = storage_limit.unwrap_or(MAX_STROAGE_LIMIT);
 		let data = data.map(|d| d.0).unwrap_or_default();
 
-		let api = self.client.runtime_api();
-
 		let balance_value = if let Some(value) = value {
 			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
 		} else {
@@ -170,19 +281,18 @@ where
 
 		match to {
 			Some(to) => {
-				let info = api
-					.call(
-						&BlockId::Hash(hash),
-						from.unwrap_or_default(),
-						to,
-						data,
-						balance_value,
-						gas_limit,
-						storage_limit,
-						true,
-					)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+				let info = runtime_call(
+					&*self.client,
+					&BlockId::Hash(hash),
+					from.unwrap_or_default(),
+					to,
+					data,
+					balance_value,
+					gas_limit,
+					storage_limit,
+					None,
+					true,
+				)?;
 
 				log::debug!(
 					target: "evm",
@@ -194,18 +304,17 @@ where
 				Ok(Bytes(info.value))
 			}
 			None => {
-				let info = api
-					.create(
-						&BlockId::Hash(hash),
-						from.unwrap_or_default(),
-						data,
-						balance_value,
-						gas_limit,
-						storage_limit,
-						true,
-					)
-					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+				let info = runtime_create(
+					&*self.client,
+					&BlockId::Hash(hash),
+					from.unwrap_or_default(),
+					data,
+					balance_value,
+					gas_limit,
+					storage_limit,
+					None,
+					true,
+				)?;
 
 				log::debug!(
 					target: "evm",
@@ -289,39 +398,33 @@ where
 
 			let (exit_reason, data, used_gas, used_storage) = match to {
 				Some(to) => {
-					let info = self
-						.client
-						.runtime_api()
-						.call(
-							&BlockId::Hash(hash),
-							from.unwrap_or_default(),
-							to,
-							data,
-							balance_value,
-							gas_limit,
-							storage_limit,
-							true,
-						)
-						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-						.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+					let info = runtime_call(
+						&*self.client,
+						&BlockId::Hash(hash),
+						from.unwrap_or_default(),
+						to,
+						data,
+						balance_value,
+						gas_limit,
+						storage_limit,
+						None,
+						true,
+					)?;
 
 					(info.exit_reason, info.value, info.used_gas, info.used_storage)
 				}
 				None => {
-					let info = self
-						.client
-						.runtime_api()
-						.create(
-							&BlockId::Hash(hash),
-							from.unwrap_or_default(),
-							data,
-							balance_value,
-							gas_limit,
-							storage_limit,
-							true,
-						)
-						.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
-						.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+					let info = runtime_create(
+						&*self.client,
+						&BlockId::Hash(hash),
+						from.unwrap_or_default(),
+						data,
+						balance_value,
+						gas_limit,
+						storage_limit,
+						None,
+						true,
+					)?;
 
 					(info.exit_reason, Vec::new(), info.used_gas, info.used_storage)
 				}
@@ -431,6 +534,127 @@ where
 			weight_fee: adjusted_weight_fee.into(),
 		})
 	}
+
+	fn trace_transaction(
+		&self,
+		transaction_hash: <B as BlockT>::Hash,
+		options: Option<TraceOptions>,
+		at: Option<<B as BlockT>::Hash>,
+	) -> Result<TraceResponse> {
+		self.deny_unsafe.check_if_safe()?;
+		let tracer_config = TraceOptions::tracer_config(options)?;
+
+		let mut hash = at.unwrap_or_else(|| self.client.info().best_hash);
+		for _ in 0..MAX_TRACE_SEARCH_DEPTH {
+			let block = self
+				.client
+				.block(&BlockId::Hash(hash))
+				.map_err(|err| internal_err(format!("blockchain error: {:?}", err)))?
+				.ok_or_else(|| internal_err(format!("block {:?} not found", hash)))?
+				.block;
+			let (header, extrinsics) = block.deconstruct();
+
+			if let Some(index) = extrinsics
+				.iter()
+				.position(|xt| <<<B as BlockT>::Header as HeaderT>::Hashing as HashT>::hash_of(xt) == transaction_hash)
+			{
+				let parent = BlockId::Hash(*header.parent_hash());
+				let api = self.client.runtime_api();
+
+				// replay the block on top of the parent state until the traced transaction
+				api.initialize_block(&parent, &header)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?;
+				let mut extrinsics = extrinsics;
+				let traced = extrinsics.remove(index);
+				extrinsics.truncate(index);
+
+				let outcome = api
+					.trace_extrinsic(&parent, extrinsics, traced, tracer_config)
+					.map_err(|err| internal_err(format!("runtime error: {:?}", err)))?
+					.map_err(|err| internal_err(format!("execution fatal: {:?}", err)))?;
+
+				return TraceResponse::try_from(outcome);
+			}
+
+			if header.number().is_zero() {
+				break;
+			}
+			hash = *header.parent_hash();
+		}
+
+		Err(internal_err(format!("transaction {:?} not found", transaction_hash)))
+	}
+
+	#[allow(deprecated)]
+	fn trace_call(
+		&self,
+		request: CallRequest,
+		at: Option<<B as BlockT>::Hash>,
+		options: Option<TraceOptions>,
+	) -> Result<TraceResponse> {
+		self.deny_unsafe.check_if_safe()?;
+		let tracer_config = TraceOptions::tracer_config(options)?;
+		let hash = at.unwrap_or_else(|| self.client.info().best_hash);
+
+		let CallRequest {
+			from,
+			to,
+			gas_limit,
+			storage_limit,
+			value,
+			data,
+		} = request;
+
+		let gas_limit = gas_limit.unwrap_or(MAX_GAS_LIMIT);
+		let storage_limit = storage_limit.unwrap_or(MAX_STROAGE_LIMIT);
+		let data = data.map(|d| d.0).unwrap_or_default();
+
+		let balance_value = if let Some(value) = value {
+			to_u128(value).and_then(|v| TryInto::<Balance>::try_into(v).map_err(|_| ()))
+		} else {
+			Ok(Default::default())
+		};
+
+		let balance_value = balance_value.map_err(|_| Error {
+			code: ErrorCode::InvalidParams,
+			message: format!("Invalid parameter value: {:?}", value),
+			data: None,
+		})?;
+
+		let at = BlockId::Hash(hash);
+		let api = self.client.runtime_api();
+		let outcome = if api
+			.has_api_with::<dyn EVMTraceApi<B, Balance>, _>(&at, |version| version >= 2)
+			.map_err(runtime_err)?
+		{
+			api.trace_call(
+				&at,
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				None,
+				tracer_config,
+			)
+		} else {
+			api.trace_call_before_version_2(
+				&at,
+				from.unwrap_or_default(),
+				to,
+				data,
+				balance_value,
+				gas_limit,
+				storage_limit,
+				tracer_config,
+			)
+		}
+		.map_err(runtime_err)?
+		.map_err(execution_err)?;
+
+		TraceResponse::try_from(outcome)
+	}
 }
 
 #[test]
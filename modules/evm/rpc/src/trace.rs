@@ -0,0 +1,357 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Geth compatible trace types.
+
+use ethereum_types::{H160, U256};
+use jsonrpc_core::{Error, ErrorCode, Result};
+use module_evm::{
+	runner::{TraceOutcome, TracerConfig},
+	ExitError, ExitReason,
+};
+use primitives::evm::{CallTrace, CallType, OpcodeTrace, Step};
+use rustc_hex::ToHex;
+use serde::{Deserialize, Serialize};
+use sp_core::Bytes;
+use std::collections::BTreeMap;
+
+/// Trace options, same as the `TraceConfig` of Geth.
+#[derive(Debug, Default, PartialEq, Deserialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct TraceOptions {
+	/// `callTracer`, or `None` for the struct logger
+	pub tracer: Option<String>,
+	/// Disable the stack of the struct logger
+	pub disable_stack: Option<bool>,
+	/// Enable the memory of the struct logger
+	pub enable_memory: Option<bool>,
+	/// Disable the storage of the struct logger
+	pub disable_storage: Option<bool>,
+}
+
+impl TraceOptions {
+	pub fn tracer_config(options: Option<Self>) -> Result<TracerConfig> {
+		let options = options.unwrap_or_default();
+		match options.tracer.as_deref() {
+			None => Ok(TracerConfig::OpcodeTracer {
+				disable_stack: options.disable_stack.unwrap_or_default(),
+				enable_memory: options.enable_memory.unwrap_or_default(),
+				disable_storage: options.disable_storage.unwrap_or_default(),
+			}),
+			Some("callTracer") => Ok(TracerConfig::CallTracer),
+			Some(tracer) => Err(Error {
+				code: ErrorCode::InvalidParams,
+				message: format!("Invalid parameter tracer: {} is not supported", tracer),
+				data: None,
+			}),
+		}
+	}
+}
+
+/// A call frame, same as the output of the `callTracer` of Geth.
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct CallFrame {
+	#[serde(rename = "type")]
+	pub call_type: String,
+	pub from: H160,
+	pub to: H160,
+	pub value: U256,
+	pub gas: U256,
+	pub gas_used: U256,
+	pub input: Bytes,
+	pub output: Bytes,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub error: Option<String>,
+	#[serde(skip_serializing_if = "Vec::is_empty")]
+	pub calls: Vec<CallFrame>,
+}
+
+impl From<CallTrace> for CallFrame {
+	fn from(trace: CallTrace) -> Self {
+		let call_type = match trace.call_type {
+			CallType::Call => "CALL",
+			CallType::CallCode => "CALLCODE",
+			CallType::DelegateCall => "DELEGATECALL",
+			CallType::StaticCall => "STATICCALL",
+			CallType::Create => "CREATE",
+			CallType::Create2 => "CREATE2",
+		};
+
+		Self {
+			call_type: call_type.into(),
+			from: trace.from,
+			to: trace.to,
+			value: trace.value,
+			gas: trace.gas.into(),
+			gas_used: trace.gas_used.into(),
+			input: Bytes(trace.input),
+			output: Bytes(trace.output),
+			error: trace.exit_reason.as_ref().and_then(exit_error),
+			calls: trace.calls.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+/// An executed opcode, same as the `StructLog` of Geth.
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLog {
+	pub pc: u64,
+	pub op: String,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub stack: Option<Vec<U256>>,
+	/// 32 bytes words, without the `0x` prefix
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub memory: Option<Vec<String>>,
+	/// Slot to value, without the `0x` prefix
+	#[serde(skip_serializing_if = "Option::is_none")]
+	pub storage: Option<BTreeMap<String, String>>,
+}
+
+impl From<Step> for StructLog {
+	fn from(step: Step) -> Self {
+		Self {
+			pc: step.pc,
+			op: opcode_name(step.op),
+			gas: step.gas,
+			gas_cost: step.gas_cost,
+			depth: step.depth,
+			stack: step.stack.map(|stack| {
+				stack
+					.iter()
+					.map(|item| U256::from_big_endian(item.as_bytes()))
+					.collect()
+			}),
+			memory: step
+				.memory
+				.map(|memory| memory.chunks(32).map(|word| word.to_hex::<String>()).collect()),
+			storage: step.storage.map(|storage| {
+				storage
+					.iter()
+					.map(|(key, value)| (key.as_bytes().to_hex::<String>(), value.as_bytes().to_hex::<String>()))
+					.collect()
+			}),
+		}
+	}
+}
+
+/// The output of the struct logger of Geth.
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(rename_all = "camelCase")]
+pub struct StructLogs {
+	pub gas: u64,
+	pub failed: bool,
+	/// Without the `0x` prefix
+	pub return_value: String,
+	pub struct_logs: Vec<StructLog>,
+}
+
+impl From<OpcodeTrace> for StructLogs {
+	fn from(trace: OpcodeTrace) -> Self {
+		Self {
+			gas: trace.gas_used,
+			failed: trace.failed,
+			return_value: trace.return_value.to_hex::<String>(),
+			struct_logs: trace.steps.into_iter().map(Into::into).collect(),
+		}
+	}
+}
+
+#[derive(Debug, Eq, PartialEq, Serialize, Clone)]
+#[serde(untagged)]
+pub enum TraceResponse {
+	/// A single top level call.
+	Call(CallFrame),
+	/// Several top level calls, e.g. in a batch extrinsic.
+	Calls(Vec<CallFrame>),
+	StructLogs(StructLogs),
+}
+
+impl TryFrom<TraceOutcome> for TraceResponse {
+	type Error = Error;
+
+	fn try_from(outcome: TraceOutcome) -> Result<Self> {
+		match outcome {
+			TraceOutcome::Calls(calls) if calls.is_empty() => Err(Error {
+				code: ErrorCode::InternalError,
+				message: "No EVM execution found".into(),
+				data: None,
+			}),
+			TraceOutcome::Calls(mut calls) if calls.len() == 1 => Ok(TraceResponse::Call(calls.remove(0).into())),
+			TraceOutcome::Calls(calls) => Ok(TraceResponse::Calls(calls.into_iter().map(Into::into).collect())),
+			TraceOutcome::Steps(trace) => Ok(TraceResponse::StructLogs(trace.into())),
+		}
+	}
+}
+
+fn exit_error(reason: &ExitReason) -> Option<String> {
+	match reason {
+		ExitReason::Succeed(_) => None,
+		ExitReason::Revert(_) => Some("execution reverted".into()),
+		ExitReason::Error(ExitError::OutOfGas) => Some("out of gas".into()),
+		ExitReason::Error(e) => Some(format!("{:?}", e)),
+		ExitReason::Fatal(e) => Some(format!("{:?}", e)),
+	}
+}
+
+fn opcode_name(opcode: u8) -> String {
+	let name = match opcode {
+		0x00 => "STOP",
+		0x01 => "ADD",
+		0x02 => "MUL",
+		0x03 => "SUB",
+		0x04 => "DIV",
+		0x05 => "SDIV",
+		0x06 => "MOD",
+		0x07 => "SMOD",
+		0x08 => "ADDMOD",
+		0x09 => "MULMOD",
+		0x0a => "EXP",
+		0x0b => "SIGNEXTEND",
+		0x10 => "LT",
+		0x11 => "GT",
+		0x12 => "SLT",
+		0x13 => "SGT",
+		0x14 => "EQ",
+		0x15 => "ISZERO",
+		0x16 => "AND",
+		0x17 => "OR",
+		0x18 => "XOR",
+		0x19 => "NOT",
+		0x1a => "BYTE",
+		0x1b => "SHL",
+		0x1c => "SHR",
+		0x1d => "SAR",
+		0x20 => "SHA3",
+		0x30 => "ADDRESS",
+		0x31 => "BALANCE",
+		0x32 => "ORIGIN",
+		0x33 => "CALLER",
+		0x34 => "CALLVALUE",
+		0x35 => "CALLDATALOAD",
+		0x36 => "CALLDATASIZE",
+		0x37 => "CALLDATACOPY",
+		0x38 => "CODESIZE",
+		0x39 => "CODECOPY",
+		0x3a => "GASPRICE",
+		0x3b => "EXTCODESIZE",
+		0x3c => "EXTCODECOPY",
+		0x3d => "RETURNDATASIZE",
+		0x3e => "RETURNDATACOPY",
+		0x3f => "EXTCODEHASH",
+		0x40 => "BLOCKHASH",
+		0x41 => "COINBASE",
+		0x42 => "TIMESTAMP",
+		0x43 => "NUMBER",
+		0x44 => "DIFFICULTY",
+		0x45 => "GASLIMIT",
+		0x46 => "CHAINID",
+		0x47 => "SELFBALANCE",
+		0x48 => "BASEFEE",
+		0x50 => "POP",
+		0x51 => "MLOAD",
+		0x52 => "MSTORE",
+		0x53 => "MSTORE8",
+		0x54 => "SLOAD",
+		0x55 => "SSTORE",
+		0x56 => "JUMP",
+		0x57 => "JUMPI",
+		0x58 => "PC",
+		0x59 => "MSIZE",
+		0x5a => "GAS",
+		0x5b => "JUMPDEST",
+		0x60..=0x7f => return format!("PUSH{}", opcode - 0x5f),
+		0x80..=0x8f => return format!("DUP{}", opcode - 0x7f),
+		0x90..=0x9f => return format!("SWAP{}", opcode - 0x8f),
+		0xa0..=0xa4 => return format!("LOG{}", opcode - 0xa0),
+		0xf0 => "CREATE",
+		0xf1 => "CALL",
+		0xf2 => "CALLCODE",
+		0xf3 => "RETURN",
+		0xf4 => "DELEGATECALL",
+		0xf5 => "CREATE2",
+		0xfa => "STATICCALL",
+		0xfd => "REVERT",
+		0xfe => "INVALID",
+		0xff => "SELFDESTRUCT",
+		_ => return format!("opcode {:#04x} not defined", opcode),
+	};
+	name.into()
+}
+
+#[test]
+fn trace_response_serialization_should_work() {
+	use module_evm::ExitSucceed;
+	use sp_core::H256;
+
+	let call = CallTrace {
+		call_type: CallType::Call,
+		from: H160::from_low_u64_be(1),
+		to: H160::from_low_u64_be(2),
+		input: vec![0x12, 0x34],
+		value: U256::zero(),
+		gas: 100_000,
+		gas_used: 21_000,
+		output: vec![],
+		exit_reason: Some(ExitReason::Succeed(ExitSucceed::Returned)),
+		calls: vec![CallTrace {
+			call_type: CallType::StaticCall,
+			from: H160::from_low_u64_be(2),
+			to: H160::from_low_u64_be(3),
+			input: vec![],
+			value: U256::zero(),
+			gas: 50_000,
+			gas_used: 50_000,
+			output: vec![],
+			exit_reason: Some(ExitReason::Error(ExitError::OutOfGas)),
+			calls: vec![],
+		}],
+	};
+	let response = TraceResponse::try_from(TraceOutcome::Calls(vec![call])).unwrap();
+	assert_eq!(
+		serde_json::to_string(&response).unwrap(),
+		r#"{"type":"CALL","from":"0x0000000000000000000000000000000000000001","to":"0x0000000000000000000000000000000000000002","value":"0x0","gas":"0x186a0","gasUsed":"0x5208","input":"0x1234","output":"0x","calls":[{"type":"STATICCALL","from":"0x0000000000000000000000000000000000000002","to":"0x0000000000000000000000000000000000000003","value":"0x0","gas":"0xc350","gasUsed":"0xc350","input":"0x","output":"0x","error":"out of gas"}]}"#
+	);
+
+	let trace = OpcodeTrace {
+		gas_used: 3,
+		failed: false,
+		return_value: vec![0xab],
+		steps: vec![Step {
+			pc: 0,
+			op: 0x54,
+			gas: 100,
+			gas_cost: 3,
+			depth: 1,
+			stack: Some(vec![H256::from_low_u64_be(1)]),
+			memory: None,
+			storage: Some(vec![(H256::from_low_u64_be(1), H256::from_low_u64_be(2))]),
+		}],
+	};
+	let response = TraceResponse::try_from(TraceOutcome::Steps(trace)).unwrap();
+	assert_eq!(
+		serde_json::to_string(&response).unwrap(),
+		r#"{"gas":3,"failed":false,"returnValue":"ab","structLogs":[{"pc":0,"op":"SLOAD","gas":100,"gasCost":3,"depth":1,"stack":["0x1"],"storage":{"0000000000000000000000000000000000000000000000000000000000000001":"0000000000000000000000000000000000000000000000000000000000000002"}}]}"#
+	);
+
+	assert!(TraceResponse::try_from(TraceOutcome::Calls(vec![])).is_err());
+}
@@ -197,6 +197,7 @@ impl Config for Runtime {
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = DefaultTransactionPayment;
+	type EnableLondon = frame_support::traits::ConstBool<false>;
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
@@ -208,6 +208,7 @@ macro_rules! evm_call {
 						0,
 						21_000_000,
 						1_000_000,
+						vec![],
 						<Runtime as Config>::config(),
 					)
 				})
@@ -50,7 +50,7 @@ use frame_support::{
 use frame_system::{ensure_root, ensure_signed, pallet_prelude::*, EnsureRoot, EnsureSigned};
 use hex_literal::hex;
 pub use module_evm_utiltity::{
-	ethereum::{Log, TransactionAction},
+	ethereum::{AccessListItem, Log, TransactionAction},
 	evm::{self, Config as EvmConfig, Context, ExitError, ExitFatal, ExitReason, ExitRevert, ExitSucceed},
 	Account,
 };
@@ -63,7 +63,7 @@ use primitive_types::{H160, H256, U256};
 pub use primitives::{
 	convert_decimals_from_evm, convert_decimals_to_evm,
 	evm::{
-		CallInfo, CreateInfo, EvmAddress, ExecutionInfo, Vicinity, MIRRORED_NFT_ADDRESS_START,
+		CallInfo, CreateInfo, EthereumReceipt, EvmAddress, ExecutionInfo, Vicinity, MIRRORED_NFT_ADDRESS_START,
 		MIRRORED_TOKENS_ADDRESS_START, PREDEPLOY_ADDRESS_START,
 	},
 	task::TaskResult,
@@ -154,6 +154,53 @@ static ACALA_CONFIG: EvmConfig = EvmConfig {
 	estimate: false,
 };
 
+// Based on `ACALA_CONFIG` with the Berlin hard fork gas schedule, warm and cold
+// state access (EIP-2929) and access lists (EIP-2930). Like `ACALA_CONFIG`, there
+// is no gas refund for `SSTORE`.
+static ACALA_LONDON_CONFIG: EvmConfig = EvmConfig {
+	gas_ext_code: 0,
+	gas_ext_code_hash: 0,
+	gas_balance: 0,
+	gas_sload: 0,
+	gas_sload_cold: 2100,
+	gas_sstore_set: 20000,
+	gas_sstore_reset: 2900,
+	refund_sstore_clears: 0, // no gas refund
+	gas_suicide: 5000,
+	gas_suicide_new_account: 25000,
+	gas_call: 0,
+	gas_expbyte: 50,
+	gas_transaction_create: 53000,
+	gas_transaction_call: 21000,
+	gas_transaction_zero_data: 4,
+	gas_transaction_non_zero_data: 16,
+	gas_access_list_address: 2400,
+	gas_access_list_storage_key: 1900,
+	gas_account_access_cold: 2600,
+	gas_storage_read_warm: 100,
+	sstore_gas_metering: false,         // no gas refund
+	sstore_revert_under_stipend: false, // ignored
+	increase_state_access_gas: true,
+	err_on_call_with_more_gas: false,
+	empty_considered_exists: false,
+	create_increase_nonce: true,
+	call_l64_after_gas: true,
+	stack_limit: 1024,
+	memory_limit: usize::max_value(),
+	call_stack_limit: 1024,
+	create_contract_limit: Some(MaxCodeSize::get() as usize),
+	call_stipend: 2300,
+	has_delegate_call: true,
+	has_create2: true,
+	has_revert: true,
+	has_return_data: true,
+	has_bitwise_shifting: true,
+	has_chain_id: true,
+	has_self_balance: true,
+	has_ext_code_hash: true,
+	estimate: false,
+};
+
 /// Create an empty contract `contract Empty { }`.
 pub const BASE_CREATE_GAS: u64 = 67_066;
 /// Call function that just set a storage `function store(uint256 num) public { number = num; }`.
@@ -251,9 +298,19 @@ pub mod module {
 		/// ChargeTransactionPayment convert weight to fee.
 		type ChargeTransactionPayment: TransactionPayment<Self::AccountId, BalanceOf<Self>, NegativeImbalanceOf<Self>>;
 
+		/// Enable the Berlin and London hard fork semantics: access lists and
+		/// warm/cold state access (EIP-2929, EIP-2930), the `BASEFEE` opcode
+		/// (EIP-3198) and the reduced gas refunds (EIP-3529).
+		#[pallet::constant]
+		type EnableLondon: Get<bool>;
+
 		/// EVM config used in the module.
 		fn config() -> &'static EvmConfig {
-			&ACALA_CONFIG
+			if Self::EnableLondon::get() {
+				&ACALA_LONDON_CONFIG
+			} else {
+				&ACALA_CONFIG
+			}
 		}
 
 		/// Required origin for creating system contract.
@@ -386,6 +443,14 @@ pub mod module {
 	#[pallet::getter(fn extrinsic_origin)]
 	pub type ExtrinsicOrigin<T: Config> = StorageValue<_, T::AccountId, OptionQuery>;
 
+	/// The gas used by each EVM execution of the extrinsics of the current block, with the index
+	/// of the extrinsic, in the order of the execution events.
+	///
+	/// TransactionGasUsed: Vec<(u32, u64)>
+	#[pallet::storage]
+	#[pallet::getter(fn transaction_gas_used)]
+	pub type TransactionGasUsed<T: Config> = StorageValue<_, Vec<(u32, u64)>, ValueQuery>;
+
 	#[pallet::genesis_config]
 	pub struct GenesisConfig<T: Config> {
 		pub accounts: BTreeMap<EvmAddress, GenesisAccount<BalanceOf<T>, T::Index>>,
@@ -555,6 +620,8 @@ pub mod module {
 		ChargeStorageFailed,
 		/// Invalid decimals
 		InvalidDecimals,
+		/// The weight of the gas limit exceeds the maximum weight can be used on idle
+		ExceedMaxIdleWeight,
 	}
 
 	#[pallet::pallet]
@@ -563,6 +630,11 @@ pub mod module {
 
 	#[pallet::hooks]
 	impl<T: Config> Hooks<T::BlockNumber> for Pallet<T> {
+		fn on_initialize(_: T::BlockNumber) -> Weight {
+			TransactionGasUsed::<T>::kill();
+			<T as Config>::WeightInfo::on_initialize()
+		}
+
 		fn integrity_test() {
 			assert!(convert_decimals_from_evm(T::StorageDepositPerByte::get()).is_some());
 		}
@@ -611,52 +683,7 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
-			match T::Runner::call(
-				source,
-				source,
-				target,
-				input,
-				value,
-				gas_limit,
-				storage_limit,
-				T::config(),
-			) {
-				Err(e) => {
-					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
-						from: source,
-						contract: target,
-						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
-						output: vec![],
-						logs: vec![],
-					});
-
-					Ok(().into())
-				}
-				Ok(info) => {
-					if info.exit_reason.is_succeed() {
-						Pallet::<T>::deposit_event(Event::<T>::Executed {
-							from: source,
-							contract: target,
-							logs: info.logs.clone(),
-						});
-					} else {
-						Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
-							from: source,
-							contract: target,
-							exit_reason: info.exit_reason.clone(),
-							output: info.value.clone(),
-							logs: info.logs.clone(),
-						});
-					}
-
-					let used_gas: u64 = info.used_gas.unique_saturated_into();
-
-					Ok(PostDispatchInfo {
-						actual_weight: Some(call_weight::<T>(used_gas)),
-						pays_fee: Pays::Yes,
-					})
-				}
-			}
+			Self::do_call(source, target, input, value, gas_limit, storage_limit, vec![])
 		}
 
 		/// Issue an EVM call operation on a scheduled contract call, and
@@ -693,7 +720,17 @@ pub mod module {
 				_payed = imbalance;
 			}
 
-			match T::Runner::call(from, from, target, input, value, gas_limit, storage_limit, T::config()) {
+			match T::Runner::call(
+				from,
+				from,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				vec![],
+				T::config(),
+			) {
 				Err(e) => {
 					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
 						from,
@@ -702,6 +739,7 @@ pub mod module {
 						output: vec![],
 						logs: vec![],
 					});
+					Self::note_transaction_gas_used(0);
 
 					Ok(().into())
 				}
@@ -723,6 +761,7 @@ pub mod module {
 					}
 
 					let used_gas: u64 = info.used_gas.unique_saturated_into();
+					Self::note_transaction_gas_used(used_gas);
 
 					#[cfg(not(feature = "with-ethereum-compatibility"))]
 					{
@@ -748,6 +787,30 @@ pub mod module {
 			}
 		}
 
+		/// Schedule an EVM call operation to be issued by the idle scheduler. The transaction fee
+		/// for `gas_limit` is reserved until the call is issued by `scheduled_call`.
+		///
+		/// - `target`: the contract address to call
+		/// - `input`: the data supplied for the call
+		/// - `value`: the amount sent for payable calls
+		/// - `gas_limit`: the maximum gas the call can use
+		/// - `storage_limit`: the total bytes the contract's storage can increase by
+		#[pallet::weight(<T as Config>::WeightInfo::schedule_call())]
+		#[transactional]
+		pub fn schedule_call(
+			origin: OriginFor<T>,
+			target: EvmAddress,
+			input: Vec<u8>,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+
+			Self::do_schedule_call(source, target, input, value, gas_limit, storage_limit)
+		}
+
 		/// Issue an EVM create operation. This is similar to a contract
 		/// creation transaction in Ethereum.
 		///
@@ -767,41 +830,7 @@ pub mod module {
 			let who = ensure_signed(origin)?;
 			let source = T::AddressMapping::get_or_create_evm_address(&who);
 
-			match T::Runner::create(source, init, value, gas_limit, storage_limit, T::config()) {
-				Err(e) => {
-					Pallet::<T>::deposit_event(Event::<T>::CreatedFailed {
-						from: source,
-						contract: H160::default(),
-						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
-						logs: vec![],
-					});
-
-					Ok(().into())
-				}
-				Ok(info) => {
-					if info.exit_reason.is_succeed() {
-						Pallet::<T>::deposit_event(Event::<T>::Created {
-							from: source,
-							contract: info.value,
-							logs: info.logs.clone(),
-						});
-					} else {
-						Pallet::<T>::deposit_event(Event::<T>::CreatedFailed {
-							from: source,
-							contract: info.value,
-							exit_reason: info.exit_reason.clone(),
-							logs: info.logs.clone(),
-						});
-					}
-
-					let used_gas: u64 = info.used_gas.unique_saturated_into();
-
-					Ok(PostDispatchInfo {
-						actual_weight: Some(create_weight::<T>(used_gas)),
-						pays_fee: Pays::Yes,
-					})
-				}
-			}
+			Self::do_create(source, init, value, gas_limit, storage_limit, vec![])
 		}
 
 		/// Issue an EVM create2 operation.
@@ -833,6 +862,7 @@ pub mod module {
 						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
 						logs: vec![],
 					});
+					Self::note_transaction_gas_used(0);
 
 					Ok(().into())
 				}
@@ -853,6 +883,7 @@ pub mod module {
 					}
 
 					let used_gas: u64 = info.used_gas.unique_saturated_into();
+					Self::note_transaction_gas_used(used_gas);
 
 					Ok(PostDispatchInfo {
 						actual_weight: Some(create2_weight::<T>(used_gas)),
@@ -903,6 +934,7 @@ pub mod module {
 						exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
 						logs: vec![],
 					});
+					Self::note_transaction_gas_used(0);
 
 					Ok(().into())
 				}
@@ -925,6 +957,7 @@ pub mod module {
 					}
 
 					let used_gas: u64 = info.used_gas.unique_saturated_into();
+					Self::note_transaction_gas_used(used_gas);
 
 					Ok(PostDispatchInfo {
 						actual_weight: Some(create_nft_contract::<T>(used_gas)),
@@ -995,6 +1028,7 @@ pub mod module {
 					contract: target,
 					logs: vec![],
 				});
+				Self::note_transaction_gas_used(0);
 
 				Ok(PostDispatchInfo {
 					actual_weight: Some(<T as Config>::WeightInfo::create_predeploy_mirror_token_contract()),
@@ -1009,6 +1043,7 @@ pub mod module {
 							exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
 							logs: vec![],
 						});
+						Self::note_transaction_gas_used(0);
 
 						Ok(().into())
 					}
@@ -1029,6 +1064,7 @@ pub mod module {
 						}
 
 						let used_gas: u64 = info.used_gas.unique_saturated_into();
+						Self::note_transaction_gas_used(used_gas);
 
 						Ok(PostDispatchInfo {
 							actual_weight: Some(create_predeploy_contract::<T>(used_gas)),
@@ -1142,10 +1178,232 @@ pub mod module {
 
 			Ok(().into())
 		}
+
+		/// Same as `eth_call`, with the access list (EIP-2930) of the transaction.
+		#[pallet::weight(match *action {
+			TransactionAction::Call(_) => call_weight::<T>(*gas_limit),
+			TransactionAction::Create => create_weight::<T>(*gas_limit)
+		})]
+		#[transactional]
+		pub fn eth_call_v2(
+			origin: OriginFor<T>,
+			action: TransactionAction,
+			input: Vec<u8>,
+			#[pallet::compact] value: BalanceOf<T>,
+			#[pallet::compact] gas_limit: u64,
+			#[pallet::compact] storage_limit: u32,
+			#[pallet::compact] _valid_until: T::BlockNumber, // checked by tx validation logic
+			access_list: Vec<AccessListItem>,
+		) -> DispatchResultWithPostInfo {
+			let who = ensure_signed(origin)?;
+			let source = T::AddressMapping::get_or_create_evm_address(&who);
+
+			match action {
+				TransactionAction::Call(target) => {
+					Self::do_call(source, target, input, value, gas_limit, storage_limit, access_list)
+				}
+				TransactionAction::Create => {
+					Self::do_create(source, input, value, gas_limit, storage_limit, access_list)
+				}
+			}
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
+	fn do_call(
+		source: EvmAddress,
+		target: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+	) -> DispatchResultWithPostInfo {
+		match T::Runner::call(
+			source,
+			source,
+			target,
+			input,
+			value,
+			gas_limit,
+			storage_limit,
+			access_list,
+			T::config(),
+		) {
+			Err(e) => {
+				Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+					from: source,
+					contract: target,
+					exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+					output: vec![],
+					logs: vec![],
+				});
+				Self::note_transaction_gas_used(0);
+
+				Ok(().into())
+			}
+			Ok(info) => {
+				if info.exit_reason.is_succeed() {
+					Pallet::<T>::deposit_event(Event::<T>::Executed {
+						from: source,
+						contract: target,
+						logs: info.logs.clone(),
+					});
+				} else {
+					Pallet::<T>::deposit_event(Event::<T>::ExecutedFailed {
+						from: source,
+						contract: target,
+						exit_reason: info.exit_reason.clone(),
+						output: info.value.clone(),
+						logs: info.logs.clone(),
+					});
+				}
+
+				let used_gas: u64 = info.used_gas.unique_saturated_into();
+				Self::note_transaction_gas_used(used_gas);
+
+				Ok(PostDispatchInfo {
+					actual_weight: Some(call_weight::<T>(used_gas)),
+					pays_fee: Pays::Yes,
+				})
+			}
+		}
+	}
+
+	/// Reserve the transaction fee for `gas_limit` from `source`, and schedule the call as an
+	/// `EvmTask::Schedule` to be issued on idle. The call can never be issued if the weight of
+	/// `gas_limit` exceeds the maximum weight left on idle.
+	pub fn do_schedule_call(
+		source: EvmAddress,
+		target: EvmAddress,
+		input: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+	) -> DispatchResult {
+		let block_weights = T::BlockWeights::get();
+		ensure!(
+			T::GasToWeight::convert(gas_limit) <= block_weights.max_block.saturating_sub(block_weights.base_block),
+			Error::<T>::ExceedMaxIdleWeight
+		);
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		{
+			// reserve the transaction fee for gas_limit, which is charged by `scheduled_call`
+			let from_account = T::AddressMapping::get_account_id(&source);
+			T::ChargeTransactionPayment::reserve_fee(&from_account, T::GasToWeight::convert(gas_limit))
+				.map_err(|_| Error::<T>::ChargeFeeFailed)?;
+		}
+
+		T::IdleScheduler::schedule(
+			EvmTask::Schedule {
+				from: source,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+			}
+			.into(),
+		)
+	}
+
+	fn do_create(
+		source: EvmAddress,
+		init: Vec<u8>,
+		value: BalanceOf<T>,
+		gas_limit: u64,
+		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
+	) -> DispatchResultWithPostInfo {
+		match T::Runner::create(source, init, value, gas_limit, storage_limit, access_list, T::config()) {
+			Err(e) => {
+				Pallet::<T>::deposit_event(Event::<T>::CreatedFailed {
+					from: source,
+					contract: H160::default(),
+					exit_reason: ExitReason::Error(ExitError::Other(Into::<&str>::into(e).into())),
+					logs: vec![],
+				});
+				Self::note_transaction_gas_used(0);
+
+				Ok(().into())
+			}
+			Ok(info) => {
+				if info.exit_reason.is_succeed() {
+					Pallet::<T>::deposit_event(Event::<T>::Created {
+						from: source,
+						contract: info.value,
+						logs: info.logs.clone(),
+					});
+				} else {
+					Pallet::<T>::deposit_event(Event::<T>::CreatedFailed {
+						from: source,
+						contract: info.value,
+						exit_reason: info.exit_reason.clone(),
+						logs: info.logs.clone(),
+					});
+				}
+
+				let used_gas: u64 = info.used_gas.unique_saturated_into();
+				Self::note_transaction_gas_used(used_gas);
+
+				Ok(PostDispatchInfo {
+					actual_weight: Some(create_weight::<T>(used_gas)),
+					pays_fee: Pays::Yes,
+				})
+			}
+		}
+	}
+
+	fn note_transaction_gas_used(used_gas: u64) {
+		// only the transactions of a block have an extrinsic index
+		if let Some(index) = frame_system::Pallet::<T>::extrinsic_index() {
+			TransactionGasUsed::<T>::append((index, used_gas));
+		}
+	}
+
+	/// Collect the receipts of the EVM transactions of the current block.
+	///
+	/// `events` are the events of this pallet with the index of the extrinsic which deposited
+	/// them. Each execution has its own receipt, so an extrinsic executing several EVM
+	/// transactions has several receipts.
+	pub fn receipts(events: impl IntoIterator<Item = (u32, Event<T>)>) -> Vec<EthereumReceipt> {
+		// the gas used is noted along with every execution event, in the same order
+		let mut gas_used = Self::transaction_gas_used().into_iter();
+
+		events
+			.into_iter()
+			.filter_map(|(extrinsic_index, event)| {
+				let (from, contract, is_create, succeed, logs) = match event {
+					Event::Created { from, contract, logs } => (from, contract, true, true, logs),
+					Event::CreatedFailed {
+						from, contract, logs, ..
+					} => (from, contract, true, false, logs),
+					Event::Executed { from, contract, logs } => (from, contract, false, true, logs),
+					Event::ExecutedFailed {
+						from, contract, logs, ..
+					} => (from, contract, false, false, logs),
+					_ => return None,
+				};
+
+				let used_gas = gas_used
+					.find(|(index, _)| *index == extrinsic_index)
+					.map_or(0, |(_, gas)| gas);
+
+				Some(EthereumReceipt {
+					extrinsic_index,
+					from,
+					contract,
+					is_create,
+					succeed,
+					used_gas,
+					logs,
+				})
+			})
+			.collect()
+	}
+
 	/// Get StorageDepositPerByte of actual decimals
 	pub fn get_storage_deposit_per_byte() -> BalanceOf<T> {
 		// StorageDepositPerByte decimals is 18, KAR/ACA decimals is 12, convert to 12 here.
@@ -1699,12 +1957,14 @@ impl<T: Config> EVMTrait<T::AccountId> for Pallet<T> {
 				value,
 				gas_limit,
 				storage_limit,
+				vec![],
 				&config,
 			);
 
 			match result {
 				Ok(info) => match mode {
 					ExecutionMode::Execute => {
+						Pallet::<T>::note_transaction_gas_used(info.used_gas.unique_saturated_into());
 						if info.exit_reason.is_succeed() {
 							Pallet::<T>::deposit_event(Event::<T>::Executed {
 								from: context.sender,
@@ -1884,7 +2144,8 @@ impl<T: Config + Send + Sync> SignedExtension for SetEvmOrigin<T> {
 
 #[derive(Clone, RuntimeDebug, PartialEq, Encode, Decode, TypeInfo)]
 pub enum EvmTask<T: Config> {
-	// TODO: update
+	/// Execute `scheduled_call` once there is enough weight for `gas_limit`. The fee is reserved
+	/// from `from` by `schedule_call`.
 	Schedule {
 		from: EvmAddress,
 		target: EvmAddress,
@@ -1903,13 +2164,48 @@ pub enum EvmTask<T: Config> {
 impl<T: Config> DispatchableTask for EvmTask<T> {
 	fn dispatch(self, weight: Weight) -> TaskResult {
 		match self {
-			// TODO: update
-			EvmTask::Schedule { .. } => {
-				// check weight and call `scheduled_call`
+			EvmTask::Schedule {
+				from,
+				target,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+			} => {
+				let required_weight = T::GasToWeight::convert(gas_limit);
+				if required_weight > weight {
+					// not enough weight left, try again in the next block
+					return TaskResult {
+						result: Ok(()),
+						used_weight: 0,
+						finished: false,
+					};
+				}
+
+				let res = Pallet::<T>::scheduled_call(
+					frame_system::RawOrigin::Root.into(),
+					from,
+					target,
+					input,
+					value,
+					gas_limit,
+					storage_limit,
+				);
+				log::debug!(
+					target: "evm",
+					"EvmTask::Schedule: [from: {:?}, target: {:?}, gas_limit: {:?}, storage_limit: {:?}, result: {:?}]",
+					from, target, gas_limit, storage_limit, res
+				);
+
+				let (result, actual_weight) = match res {
+					Ok(post_info) => (Ok(()), post_info.actual_weight),
+					Err(e) => (Err(e.error), e.post_info.actual_weight),
+				};
+
 				TaskResult {
-					result: Ok(()),
-					used_weight: 0,
-					finished: false,
+					result,
+					used_weight: actual_weight.unwrap_or(required_weight),
+					finished: true,
 				}
 			}
 			EvmTask::Remove {
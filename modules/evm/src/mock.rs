@@ -171,6 +171,7 @@ impl FindAuthor<AccountId32> for AuthorGiven {
 
 parameter_types! {
 	pub NetworkContractSource: H160 = alice();
+	pub static EnableLondon: bool = false;
 }
 
 ord_parameter_types! {
@@ -198,6 +199,7 @@ impl Config for Runtime {
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ();
+	type EnableLondon = EnableLondon;
 
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
@@ -27,6 +27,14 @@ use ripemd160::Digest;
 use sp_std::{cmp::min, marker::PhantomData, vec::Vec};
 use tiny_keccak::Hasher;
 
+mod blake2;
+mod bn128;
+mod modexp;
+
+pub use blake2::Blake2F;
+pub use bn128::{Bn128Add, Bn128Mul, Bn128Pairing};
+pub use modexp::Modexp;
+
 /// Custom precompiles to be used by EVM engine.
 pub trait PrecompileSet {
 	#![allow(clippy::type_complexity)]
@@ -79,25 +87,74 @@ impl PrecompileSet for Tuple {
 	}
 }
 
-pub struct EvmPrecompiles<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>(
+pub struct EvmPrecompiles<
+	ECRecover,
+	Sha256,
+	Ripemd160,
+	Identity,
+	Modexp,
+	Bn128Add,
+	Bn128Mul,
+	Bn128Pairing,
+	Blake2F,
+	ECRecoverPublicKey,
+	Sha3FIPS256,
+	Sha3FIPS512,
+>(
 	PhantomData<(
 		ECRecover,
 		Sha256,
 		Ripemd160,
 		Identity,
+		Modexp,
+		Bn128Add,
+		Bn128Mul,
+		Bn128Pairing,
+		Blake2F,
 		ECRecoverPublicKey,
 		Sha3FIPS256,
 		Sha3FIPS512,
 	)>,
 );
 
-impl<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512> PrecompileSet
-	for EvmPrecompiles<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>
+impl<
+		ECRecover,
+		Sha256,
+		Ripemd160,
+		Identity,
+		Modexp,
+		Bn128Add,
+		Bn128Mul,
+		Bn128Pairing,
+		Blake2F,
+		ECRecoverPublicKey,
+		Sha3FIPS256,
+		Sha3FIPS512,
+	> PrecompileSet
+	for EvmPrecompiles<
+		ECRecover,
+		Sha256,
+		Ripemd160,
+		Identity,
+		Modexp,
+		Bn128Add,
+		Bn128Mul,
+		Bn128Pairing,
+		Blake2F,
+		ECRecoverPublicKey,
+		Sha3FIPS256,
+		Sha3FIPS512,
+	>
 where
 	ECRecover: Precompile,
 	Sha256: Precompile,
 	Ripemd160: Precompile,
 	Identity: Precompile,
+	Modexp: Precompile,
+	Bn128Add: Precompile,
+	Bn128Mul: Precompile,
+	Bn128Pairing: Precompile,
+	Blake2F: Precompile,
 	ECRecoverPublicKey: Precompile,
 	Sha3FIPS256: Precompile,
 	Sha3FIPS512: Precompile,
@@ -118,6 +175,16 @@ where
 			Some(Ripemd160::execute(input, target_gas, context))
 		} else if address == H160::from_low_u64_be(4) {
 			Some(Identity::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(5) {
+			Some(Modexp::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(6) {
+			Some(Bn128Add::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(7) {
+			Some(Bn128Mul::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(8) {
+			Some(Bn128Pairing::execute(input, target_gas, context))
+		} else if address == H160::from_low_u64_be(9) {
+			Some(Blake2F::execute(input, target_gas, context))
 		}
 		// Non-standard precompile starts with 128
 		else if address == H160::from_low_u64_be(128) {
@@ -0,0 +1,317 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The alt_bn128 precompiles, EIP-196 and EIP-197 with the gas cost of EIP-1108.
+
+use super::Precompile;
+use crate::runner::state::PrecompileOutput;
+use module_evm_utiltity::evm::{Context, ExitError, ExitSucceed};
+use sp_std::{cmp::min, vec::Vec};
+
+const ADD_GAS_COST: u64 = 150;
+const MUL_GAS_COST: u64 = 6_000;
+const PAIRING_BASE_GAS_COST: u64 = 45_000;
+const PAIRING_PER_POINT_GAS_COST: u64 = 34_000;
+
+/// Length of a pairing element, a G1 point followed by a G2 point.
+const PAIRING_ELEMENT_LENGTH: usize = 192;
+
+/// Copy `input` into a buffer of `N` bytes, truncated or padded with zeros.
+fn read_input<const N: usize>(input: &[u8]) -> [u8; N] {
+	let mut buf = [0u8; N];
+	let len = min(input.len(), N);
+	buf[..len].copy_from_slice(&input[..len]);
+	buf
+}
+
+fn ensure_gas(cost: u64, target_gas: Option<u64>) -> Result<(), ExitError> {
+	if let Some(target_gas) = target_gas {
+		if cost > target_gas {
+			return Err(ExitError::OutOfGas);
+		}
+	}
+	Ok(())
+}
+
+fn read_fr(input: &[u8]) -> Result<bn::Fr, ExitError> {
+	bn::Fr::from_slice(&input[0..32]).map_err(|_| ExitError::Other("Invalid field element".into()))
+}
+
+fn read_fq(input: &[u8]) -> Result<bn::Fq, ExitError> {
+	bn::Fq::from_slice(&input[0..32]).map_err(|_| ExitError::Other("Invalid field element".into()))
+}
+
+/// Read a G1 point from 64 bytes, the point at infinity is encoded as zeros.
+fn read_point(input: &[u8]) -> Result<bn::G1, ExitError> {
+	use bn::{AffineG1, Group, G1};
+
+	let px = read_fq(&input[0..32])?;
+	let py = read_fq(&input[32..64])?;
+	if px == bn::Fq::zero() && py == bn::Fq::zero() {
+		Ok(G1::zero())
+	} else {
+		AffineG1::new(px, py)
+			.map(Into::into)
+			.map_err(|_| ExitError::Other("Invalid curve point".into()))
+	}
+}
+
+/// Read a G2 point from 128 bytes, the point at infinity is encoded as zeros.
+fn read_twisted_point(input: &[u8]) -> Result<bn::G2, ExitError> {
+	use bn::{AffineG2, Fq2, Group, G2};
+
+	// the imaginary part comes first
+	let ax = read_fq(&input[0..32])?;
+	let ay = read_fq(&input[32..64])?;
+	let bx = read_fq(&input[64..96])?;
+	let by = read_fq(&input[96..128])?;
+	let x = Fq2::new(ay, ax);
+	let y = Fq2::new(by, bx);
+	if x.is_zero() && y.is_zero() {
+		Ok(G2::zero())
+	} else {
+		AffineG2::new(x, y)
+			.map(Into::into)
+			.map_err(|_| ExitError::Other("Invalid curve point".into()))
+	}
+}
+
+/// Encode a G1 point as 64 bytes.
+fn encode_point(point: bn::G1) -> Vec<u8> {
+	let mut output = [0u8; 64];
+	// the point at infinity has no affine coordinates and is encoded as zeros
+	if let Some(point) = bn::AffineG1::from_jacobian(point) {
+		// can't fail, the buffers are exactly 32 bytes
+		let _ = point.x().to_big_endian(&mut output[0..32]);
+		let _ = point.y().to_big_endian(&mut output[32..64]);
+	}
+	output.to_vec()
+}
+
+/// The bn128 point addition precompile.
+pub struct Bn128Add;
+
+impl Precompile for Bn128Add {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<PrecompileOutput, ExitError> {
+		ensure_gas(ADD_GAS_COST, target_gas)?;
+
+		let input = read_input::<128>(input);
+		let p1 = read_point(&input[0..64])?;
+		let p2 = read_point(&input[64..128])?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: ADD_GAS_COST,
+			output: encode_point(p1 + p2),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The bn128 scalar multiplication precompile.
+pub struct Bn128Mul;
+
+impl Precompile for Bn128Mul {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<PrecompileOutput, ExitError> {
+		ensure_gas(MUL_GAS_COST, target_gas)?;
+
+		let input = read_input::<96>(input);
+		let p = read_point(&input[0..64])?;
+		let fr = read_fr(&input[64..96])?;
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost: MUL_GAS_COST,
+			output: encode_point(p * fr),
+			logs: Default::default(),
+		})
+	}
+}
+
+/// The bn128 pairing check precompile.
+pub struct Bn128Pairing;
+
+impl Precompile for Bn128Pairing {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<PrecompileOutput, ExitError> {
+		if input.len() % PAIRING_ELEMENT_LENGTH != 0 {
+			return Err(ExitError::Other("Invalid input length, must be multiple of 192".into()));
+		}
+
+		let elements = input.len() / PAIRING_ELEMENT_LENGTH;
+		let cost = PAIRING_PER_POINT_GAS_COST
+			.checked_mul(elements as u64)
+			.and_then(|cost| cost.checked_add(PAIRING_BASE_GAS_COST))
+			.ok_or(ExitError::OutOfGas)?;
+		ensure_gas(cost, target_gas)?;
+
+		let pairs = input
+			.chunks(PAIRING_ELEMENT_LENGTH)
+			.map(|element| Ok((read_point(&element[0..64])?, read_twisted_point(&element[64..192])?)))
+			.collect::<Result<Vec<_>, ExitError>>()?;
+
+		// the pairing of an empty input is one
+		let success = bn::pairing_batch(&pairs) == bn::Gt::one();
+
+		let mut output = [0u8; 32];
+		if success {
+			output[31] = 1;
+		}
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost,
+			output: output.to_vec(),
+			logs: Default::default(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::bytes::from_hex;
+
+	fn context() -> Context {
+		Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: Default::default(),
+		}
+	}
+
+	#[test]
+	fn bn128_add_should_work() {
+		// (1, 2) + (1, 2)
+		let input = from_hex(
+			"0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002",
+		)
+		.unwrap();
+		let expected = from_hex(
+			"0x\
+			030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+			15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+		)
+		.unwrap();
+
+		let output = Bn128Add::execute(&input, Some(150), &context()).unwrap();
+		assert_eq!(output.output, expected);
+		assert_eq!(output.cost, 150);
+		assert_eq!(
+			Bn128Add::execute(&input, Some(149), &context()),
+			Err(ExitError::OutOfGas)
+		);
+
+		// the empty input is the sum of two points at infinity
+		let output = Bn128Add::execute(&[], Some(150), &context()).unwrap();
+		assert_eq!(output.output, vec![0u8; 64]);
+
+		// (1, 3) is not on the curve
+		let mut input = input;
+		input[63] = 3;
+		assert_eq!(
+			Bn128Add::execute(&input, Some(150), &context()),
+			Err(ExitError::Other("Invalid curve point".into()))
+		);
+	}
+
+	#[test]
+	fn bn128_mul_should_work() {
+		// (1, 2) * 2
+		let input = from_hex(
+			"0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002\
+			0000000000000000000000000000000000000000000000000000000000000002",
+		)
+		.unwrap();
+		let expected = from_hex(
+			"0x\
+			030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+			15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+		)
+		.unwrap();
+
+		let output = Bn128Mul::execute(&input, Some(6_000), &context()).unwrap();
+		assert_eq!(output.output, expected);
+		assert_eq!(output.cost, 6_000);
+		assert_eq!(
+			Bn128Mul::execute(&input, Some(5_999), &context()),
+			Err(ExitError::OutOfGas)
+		);
+	}
+
+	#[test]
+	fn bn128_pairing_should_work() {
+		// e(P, Q) * e(-P, Q) == 1
+		let input = from_hex(
+			"0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002\
+			198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+			1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+			090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+			12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			30644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd45\
+			198e9393920d483a7260bfb731fb5d25f1aa493335a9e71297e485b7aef312c2\
+			1800deef121f1e76426a00665e5c4479674322d4f75edadd46debd5cd992f6ed\
+			090689d0585ff075ec9e99ad690c3395bc4b313370b38ef355acdadcd122975b\
+			12c85ea5db8c6deb4aab71808dcb408fe3d1e7690c43d37b4ce6cc0166fa7daa",
+		)
+		.unwrap();
+
+		let output = Bn128Pairing::execute(&input, Some(113_000), &context()).unwrap();
+		let mut expected = vec![0u8; 32];
+		expected[31] = 1;
+		assert_eq!(output.output, expected);
+		assert_eq!(output.cost, 113_000);
+		assert_eq!(
+			Bn128Pairing::execute(&input, Some(112_999), &context()),
+			Err(ExitError::OutOfGas)
+		);
+
+		// e(P, Q) != 1
+		let output = Bn128Pairing::execute(&input[0..192], Some(79_000), &context()).unwrap();
+		assert_eq!(output.output, vec![0u8; 32]);
+
+		// the empty input
+		let output = Bn128Pairing::execute(&[], Some(45_000), &context()).unwrap();
+		assert_eq!(output.output, expected);
+
+		assert_eq!(
+			Bn128Pairing::execute(&input[0..191], Some(113_000), &context()),
+			Err(ExitError::Other("Invalid input length, must be multiple of 192".into()))
+		);
+	}
+}
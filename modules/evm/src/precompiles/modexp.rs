@@ -0,0 +1,208 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! The modexp precompile, EIP-198 with the gas cost of EIP-2565.
+
+use super::Precompile;
+use crate::runner::state::PrecompileOutput;
+use core::cmp::max;
+use module_evm_utiltity::evm::{Context, ExitError, ExitSucceed};
+use num_bigint::BigUint;
+use primitive_types::U256;
+use sp_std::{vec, vec::Vec};
+
+const MIN_GAS_COST: u64 = 200;
+
+/// Copy `len` bytes of `input` from `offset`, padded with zeros.
+fn read_input(input: &[u8], offset: usize, len: usize) -> Vec<u8> {
+	let mut buf = vec![0u8; len];
+	if offset < input.len() {
+		let end = min_usize(input.len(), offset.saturating_add(len));
+		buf[..end - offset].copy_from_slice(&input[offset..end]);
+	}
+	buf
+}
+
+fn min_usize(a: usize, b: usize) -> usize {
+	if a < b {
+		a
+	} else {
+		b
+	}
+}
+
+/// EIP-2565: `max(200, words(max(base_len, mod_len))^2 * max(adjusted_exp_len, 1) / 3)`.
+fn calculate_gas_cost(base_len: u64, exp_len: u64, mod_len: u64, exp_head: &U256) -> U256 {
+	let words = U256::from(max(base_len, mod_len).saturating_add(7) / 8);
+	let multiplication_complexity = words.saturating_mul(words);
+
+	let mut iteration_count = U256::zero();
+	if exp_len > 32 {
+		iteration_count = U256::from(exp_len - 32).saturating_mul(U256::from(8));
+	}
+	if !exp_head.is_zero() {
+		iteration_count = iteration_count.saturating_add(U256::from(exp_head.bits() - 1));
+	}
+	let iteration_count = max(iteration_count, U256::one());
+
+	max(
+		U256::from(MIN_GAS_COST),
+		multiplication_complexity.saturating_mul(iteration_count) / 3,
+	)
+}
+
+/// The modexp precompile.
+pub struct Modexp;
+
+impl Precompile for Modexp {
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> core::result::Result<PrecompileOutput, ExitError> {
+		let base_len = U256::from_big_endian(&read_input(input, 0, 32));
+		let exp_len = U256::from_big_endian(&read_input(input, 32, 32));
+		let mod_len = U256::from_big_endian(&read_input(input, 64, 32));
+
+		// the gas cost of any length above u32 can't be afforded
+		let too_large = |len: &U256| *len > U256::from(u32::MAX);
+		if too_large(&base_len) || too_large(&exp_len) || too_large(&mod_len) {
+			return Err(ExitError::OutOfGas);
+		}
+		let (base_len, exp_len, mod_len) = (base_len.as_usize(), exp_len.as_usize(), mod_len.as_usize());
+
+		let base_start = 96usize;
+		let exp_start = base_start.saturating_add(base_len);
+		let mod_start = exp_start.saturating_add(exp_len);
+
+		let exp_head = U256::from_big_endian(&read_input(input, exp_start, min_usize(exp_len, 32)));
+		let cost = calculate_gas_cost(base_len as u64, exp_len as u64, mod_len as u64, &exp_head);
+		if cost > U256::from(u64::MAX) {
+			return Err(ExitError::OutOfGas);
+		}
+		let cost = cost.as_u64();
+		if let Some(target_gas) = target_gas {
+			if cost > target_gas {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+
+		let output = if base_len == 0 && mod_len == 0 {
+			Vec::new()
+		} else {
+			let base = BigUint::from_bytes_be(&read_input(input, base_start, base_len));
+			let exponent = BigUint::from_bytes_be(&read_input(input, exp_start, exp_len));
+			let modulus = BigUint::from_bytes_be(&read_input(input, mod_start, mod_len));
+
+			// the result is zero if the modulus is zero
+			let mut output = vec![0u8; mod_len];
+			if modulus.bits() != 0 {
+				// the result is less than the modulus, so it fits in `mod_len` bytes
+				let result = base.modpow(&exponent, &modulus).to_bytes_be();
+				output[mod_len - result.len()..].copy_from_slice(&result);
+			}
+			output
+		};
+
+		Ok(PrecompileOutput {
+			exit_status: ExitSucceed::Returned,
+			cost,
+			output,
+			logs: Default::default(),
+		})
+	}
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use sp_core::bytes::from_hex;
+
+	fn execute(input: &str, target_gas: u64) -> core::result::Result<PrecompileOutput, ExitError> {
+		let context = Context {
+			address: Default::default(),
+			caller: Default::default(),
+			apparent_value: Default::default(),
+		};
+		Modexp::execute(&from_hex(input).unwrap(), Some(target_gas), &context)
+	}
+
+	#[test]
+	fn modexp_should_work() {
+		// 3 ^ (2^256 - 2^32 - 978) % (2^256 - 2^32 - 977) == 1, EIP-198 example
+		let input = "0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			03\
+			fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e\
+			fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2f";
+		let output = execute(input, 100_000).unwrap();
+		assert_eq!(
+			output.output,
+			from_hex("0x0000000000000000000000000000000000000000000000000000000000000001").unwrap()
+		);
+		assert_eq!(output.cost, 1360);
+		assert_eq!(execute(input, 1359), Err(ExitError::OutOfGas));
+
+		// the modulus is zero
+		let input = "0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			0000000000000000000000000000000000000000000000000000000000000020\
+			03\
+			fffffffffffffffffffffffffffffffffffffffffffffffffffffffefffffc2e";
+		let output = execute(input, 100_000).unwrap();
+		assert_eq!(output.output, vec![0u8; 32]);
+
+		// 2 ^ 100 % 1000000007
+		let input = "0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000004\
+			02\
+			64\
+			3b9aca07";
+		let output = execute(input, 100_000).unwrap();
+		assert_eq!(output.output, 976371285u32.to_be_bytes().to_vec());
+		assert_eq!(output.cost, 200);
+
+		// empty input
+		let output = execute("0x", 100_000).unwrap();
+		assert_eq!(output.output, Vec::<u8>::new());
+		assert_eq!(output.cost, 200);
+	}
+
+	#[test]
+	fn modexp_large_numbers_should_work() {
+		// checked against python `pow`
+		let input = "0x\
+			0000000000000000000000000000000000000000000000000000000000000018\
+			0000000000000000000000000000000000000000000000000000000000000003\
+			0000000000000000000000000000000000000000000000000000000000000017\
+			1234567890abcdef1234567890abcdef1234567890abcdef\
+			010001\
+			fedcba0987654321fedcba0987654321fedcba09876543";
+		let output = execute(input, 100_000).unwrap();
+		assert_eq!(
+			output.output,
+			from_hex("0x7bda41809edc985f144d85a7d38ccb05ef2f41bb4b74ed").unwrap()
+		);
+		assert_eq!(output.cost, 200);
+	}
+}
@@ -19,12 +19,17 @@
 pub mod stack;
 pub mod state;
 pub mod storage_meter;
+#[cfg(feature = "tracing")]
+pub mod tracing;
 
 use crate::{BalanceOf, CallInfo, Config, CreateInfo, ExitError};
 use frame_support::dispatch::DispatchError;
-use module_evm_utiltity::evm::{self, backend::Backend, Transfer};
-pub use primitives::evm::{EvmAddress, Vicinity};
-use sp_core::{H160, H256};
+use module_evm_utiltity::{
+	ethereum::AccessListItem,
+	evm::{self, backend::Backend, Transfer},
+};
+pub use primitives::evm::{EvmAddress, TraceOutcome, TracerConfig, Vicinity};
+use sp_core::{H160, H256, U256};
 use sp_std::vec::Vec;
 use state::StackSubstateMetadata;
 
@@ -37,6 +42,7 @@ pub trait Runner<T: Config> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
 	) -> Result<CallInfo, DispatchError>;
 
@@ -46,6 +52,7 @@ pub trait Runner<T: Config> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError>;
 
@@ -70,6 +77,20 @@ pub trait Runner<T: Config> {
 	) -> Result<CreateInfo, DispatchError>;
 }
 
+/// Execute `f` and collect the EVM trace with the given tracer config.
+#[cfg(feature = "tracing")]
+pub fn trace<R, F: FnOnce() -> R>(config: TracerConfig, f: F) -> Result<(R, TraceOutcome), DispatchError> {
+	let mut tracer = tracing::Tracer::new(config);
+	let result = tracing::using(&mut tracer, f);
+	Ok((result, tracer.finalize()))
+}
+
+/// Tracing requires the `tracing` feature, `f` is not executed without it.
+#[cfg(not(feature = "tracing"))]
+pub fn trace<R, F: FnOnce() -> R>(_config: TracerConfig, _f: F) -> Result<(R, TraceOutcome), DispatchError> {
+	Err(DispatchError::Other("EVM tracing is not enabled"))
+}
+
 pub trait StackState<'config>: Backend {
 	fn metadata(&self) -> &StackSubstateMetadata<'config>;
 	fn metadata_mut(&mut self) -> &mut StackSubstateMetadata<'config>;
@@ -83,6 +104,8 @@ pub trait StackState<'config>: Backend {
 	fn deleted(&self, address: H160) -> bool;
 	fn is_cold(&self, address: H160) -> bool;
 	fn is_storage_cold(&self, address: H160, key: H256) -> bool;
+	/// The base fee per gas of the block, `None` if the London hard fork is not enabled.
+	fn block_base_fee_per_gas(&self) -> Option<U256>;
 
 	fn inc_nonce(&mut self, address: H160);
 	fn set_storage(&mut self, address: H160, key: H256, value: H256);
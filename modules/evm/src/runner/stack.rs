@@ -22,7 +22,7 @@
 use crate::{
 	precompiles::PrecompileSet,
 	runner::{
-		state::{StackExecutor, StackSubstateMetadata},
+		state::{Accessed, StackExecutor, StackSubstateMetadata},
 		Runner as RunnerT, StackState as StackStateT,
 	},
 	AccountInfo, AccountStorages, Accounts, BalanceOf, CallInfo, Config, CreateInfo, Error, ExecutionInfo, One, Pallet,
@@ -35,7 +35,7 @@ use frame_support::{
 	transactional,
 };
 use module_evm_utiltity::{
-	ethereum::Log,
+	ethereum::{AccessListItem, Log},
 	evm::{self, backend::Backend as BackendT, ExitError, ExitReason, Transfer},
 };
 use module_support::AddressMapping;
@@ -72,6 +72,8 @@ impl<T: Config> Runner<T> {
 		let vicinity = Vicinity {
 			gas_price,
 			origin,
+			// Acala has no tip, the base fee is the gas price
+			block_base_fee_per_gas: if T::EnableLondon::get() { Some(gas_price) } else { None },
 			..Default::default()
 		};
 
@@ -226,6 +228,7 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
 	) -> Result<CallInfo, DispatchError> {
 		// if the contract not published, the caller must be developer or contract or maintainer.
@@ -237,8 +240,7 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 
 		let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
 		Self::execute(source, origin, value, gas_limit, storage_limit, config, |executor| {
-			// TODO: EIP-2930
-			executor.transact_call(source, target, value, input, gas_limit, vec![])
+			executor.transact_call(source, target, value, input, gas_limit, into_access_list(access_list))
 		})
 	}
 
@@ -250,6 +252,7 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 		value: BalanceOf<T>,
 		gas_limit: u64,
 		storage_limit: u32,
+		access_list: Vec<AccessListItem>,
 		config: &evm::Config,
 	) -> Result<CreateInfo, DispatchError> {
 		let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
@@ -258,8 +261,7 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 				.create_address(evm::CreateScheme::Legacy { caller: source })
 				.unwrap_or_default(); // transact_create will check the address
 			(
-				// TODO: EIP-2930
-				executor.transact_create(source, value, init, gas_limit, vec![]),
+				executor.transact_create(source, value, init, gas_limit, into_access_list(access_list)),
 				address,
 			)
 		})
@@ -287,7 +289,6 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 				})
 				.unwrap_or_default(); // transact_create2 will check the address
 			(
-				// TODO: EIP-2930
 				executor.transact_create2(source, value, init, salt, gas_limit, vec![]),
 				address,
 			)
@@ -308,7 +309,6 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 		let value = U256::from(UniqueSaturatedInto::<u128>::unique_saturated_into(value));
 		Self::execute(source, source, value, gas_limit, storage_limit, config, |executor| {
 			(
-				// TODO: EIP-2930
 				executor.transact_create_at_address(source, address, value, init, gas_limit, vec![]),
 				address,
 			)
@@ -316,6 +316,10 @@ impl<T: Config> RunnerT<T> for Runner<T> {
 	}
 }
 
+fn into_access_list(access_list: Vec<AccessListItem>) -> Vec<(H160, Vec<H256>)> {
+	access_list.into_iter().map(|item| (item.address, item.slots)).collect()
+}
+
 struct SubstrateStackSubstate<'config> {
 	metadata: StackSubstateMetadata<'config>,
 	deletes: BTreeSet<H160>,
@@ -412,6 +416,16 @@ impl<'config> SubstrateStackSubstate<'config> {
 	pub fn log(&mut self, address: H160, topics: Vec<H256>, data: Vec<u8>) {
 		self.logs.push(Log { address, topics, data });
 	}
+
+	/// Cold if not accessed in this substate nor any of its parents.
+	pub fn recursive_is_cold<F: Fn(&Accessed) -> bool>(&self, f: &F) -> bool {
+		let local_is_accessed = self.metadata.accessed().as_ref().map(f).unwrap_or(false);
+		if local_is_accessed {
+			false
+		} else {
+			self.parent.as_ref().map(|p| p.recursive_is_cold(f)).unwrap_or(true)
+		}
+	}
 }
 
 /// Substrate backend for EVM.
@@ -546,14 +560,18 @@ impl<'vicinity, 'config, T: Config> StackStateT<'config> for SubstrateStackState
 		self.substate.deleted(address)
 	}
 
-	fn is_cold(&self, _address: H160) -> bool {
-		// TODO: EIP-2930
-		false
+	fn is_cold(&self, address: H160) -> bool {
+		self.substate
+			.recursive_is_cold(&|a| a.accessed_addresses.contains(&address))
 	}
 
-	fn is_storage_cold(&self, _address: H160, _key: H256) -> bool {
-		// TODO: EIP-2930
-		false
+	fn is_storage_cold(&self, address: H160, key: H256) -> bool {
+		self.substate
+			.recursive_is_cold(&|a| a.accessed_storage.contains(&(address, key)))
+	}
+
+	fn block_base_fee_per_gas(&self) -> Option<U256> {
+		self.vicinity.block_base_fee_per_gas
 	}
 
 	fn inc_nonce(&mut self, address: H160) {
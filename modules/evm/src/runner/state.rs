@@ -24,42 +24,38 @@ use frame_support::log;
 use module_evm_utiltity::{
 	ethereum::Log,
 	evm::{
-		Capture, Config, Context, CreateScheme, ExitError, ExitReason, ExitRevert, ExitSucceed, Opcode, Runtime, Stack,
-		Transfer,
+		Capture, Config, Context, CreateScheme, ExitError, ExitReason, ExitRevert, ExitSucceed, Machine, Opcode,
+		Runtime, Stack, Transfer,
 	},
-	evm_gasometer::{self as gasometer, Gasometer},
+	evm_gasometer::{self as gasometer, Gasometer, StorageTarget},
 	evm_runtime::Handler,
 };
 use primitive_types::{H160, H256, U256};
 pub use primitives::{
 	currency::CurrencyIdType,
 	evm::{
-		EvmAddress, Vicinity, H160_POSITION_CURRENCY_ID_TYPE, H160_POSITION_TOKEN_NFT, MIRRORED_NFT_ADDRESS_START,
-		PREDEPLOY_ADDRESS_START, SYSTEM_CONTRACT_ADDRESS_PREFIX,
+		is_acala_precompile, EvmAddress, Vicinity, H160_POSITION_CURRENCY_ID_TYPE, H160_POSITION_TOKEN_NFT,
+		MIRRORED_NFT_ADDRESS_START, PREDEPLOY_ADDRESS_START, SYSTEM_CONTRACT_ADDRESS_PREFIX,
 	},
 	ReserveIdentifier,
 };
 use sha3::{Digest, Keccak256};
-use sp_std::{rc::Rc, vec::Vec};
+use sp_std::{collections::btree_set::BTreeSet, rc::Rc, vec::Vec};
 
 macro_rules! event {
 	($x:expr) => {};
 }
 
 #[cfg(feature = "tracing")]
-mod tracing {
-	pub struct Tracer;
-	impl module_evm_utiltity::evm_runtime::tracing::EventListener for Tracer {
-		fn event(&mut self, event: module_evm_utiltity::evm_runtime::tracing::Event) {
-			frame_support::log::debug!(
-				target: "evm", "evm_runtime tracing: {:?}", event
-			);
-		}
-	}
-}
+use crate::runner::tracing;
 
-#[cfg(feature = "tracing")]
-use tracing::*;
+/// The `BASEFEE` opcode of EIP-3198.
+const BASEFEE: Opcode = Opcode(0x48);
+/// The gas cost of `BASEFEE`, same as the other block information opcodes.
+const GAS_BASEFEE: u64 = 2;
+/// The max refund is 1/5 of the used gas after EIP-3529, 1/2 before.
+const MAX_REFUND_QUOTIENT: u64 = 2;
+const LONDON_MAX_REFUND_QUOTIENT: u64 = 5;
 
 pub enum StackExitKind {
 	Succeeded,
@@ -76,6 +72,35 @@ pub struct StackSubstateMetadata<'config> {
 	caller: Option<H160>,
 	// save the contract to charge storage
 	target: Option<H160>,
+	// the warm addresses and storage slots of EIP-2929, `None` before Berlin
+	accessed: Option<Accessed>,
+}
+
+/// The addresses and storage slots accessed in a substate.
+#[derive(Clone, Debug, Default)]
+pub struct Accessed {
+	pub accessed_addresses: BTreeSet<H160>,
+	pub accessed_storage: BTreeSet<(H160, H256)>,
+}
+
+impl Accessed {
+	pub fn access_address(&mut self, address: H160) {
+		self.accessed_addresses.insert(address);
+	}
+
+	pub fn access_addresses<I>(&mut self, addresses: I)
+	where
+		I: Iterator<Item = H160>,
+	{
+		self.accessed_addresses.extend(addresses);
+	}
+
+	pub fn access_storages<I>(&mut self, storages: I)
+	where
+		I: Iterator<Item = (H160, H256)>,
+	{
+		self.accessed_storage.extend(storages);
+	}
 }
 
 impl<'config> StackSubstateMetadata<'config> {
@@ -87,6 +112,11 @@ impl<'config> StackSubstateMetadata<'config> {
 			depth: None,
 			caller: None,
 			target: None,
+			accessed: if config.increase_state_access_gas {
+				Some(Accessed::default())
+			} else {
+				None
+			},
 		}
 	}
 
@@ -97,6 +127,16 @@ impl<'config> StackSubstateMetadata<'config> {
 		// merge child meter into parent meter
 		self.storage_meter.merge(other.storage_meter());
 
+		// the accesses are kept only if the substate is committed
+		if let (Some(mut other_accessed), Some(self_accessed)) = (other.accessed, self.accessed.as_mut()) {
+			self_accessed
+				.accessed_addresses
+				.append(&mut other_accessed.accessed_addresses);
+			self_accessed
+				.accessed_storage
+				.append(&mut other_accessed.accessed_storage);
+		}
+
 		Ok(())
 	}
 
@@ -121,6 +161,7 @@ impl<'config> StackSubstateMetadata<'config> {
 			},
 			caller: None,
 			target: None,
+			accessed: self.accessed.as_ref().map(|_| Accessed::default()),
 		}
 	}
 
@@ -163,6 +204,40 @@ impl<'config> StackSubstateMetadata<'config> {
 	pub fn target_mut(&mut self) -> &mut Option<H160> {
 		&mut self.target
 	}
+
+	pub fn accessed(&self) -> &Option<Accessed> {
+		&self.accessed
+	}
+
+	pub fn access_address(&mut self, address: H160) {
+		if let Some(accessed) = &mut self.accessed {
+			accessed.access_address(address)
+		}
+	}
+
+	pub fn access_addresses<I>(&mut self, addresses: I)
+	where
+		I: Iterator<Item = H160>,
+	{
+		if let Some(accessed) = &mut self.accessed {
+			accessed.access_addresses(addresses)
+		}
+	}
+
+	pub fn access_storage(&mut self, address: H160, key: H256) {
+		if let Some(accessed) = &mut self.accessed {
+			accessed.accessed_storage.insert((address, key));
+		}
+	}
+
+	pub fn access_storages<I>(&mut self, storages: I)
+	where
+		I: Iterator<Item = (H160, H256)>,
+	{
+		if let Some(accessed) = &mut self.accessed {
+			accessed.access_storages(storages)
+		}
+	}
 }
 
 #[derive(Debug, Eq, PartialEq, Clone)]
@@ -195,6 +270,13 @@ fn no_precompile(
 	None
 }
 
+/// The precompiles are always warm (EIP-2929).
+fn is_precompile(address: H160) -> bool {
+	// the Ethereum precompiles, and the non-standard ones starting with 128
+	let builtin = (1..=9).chain(128..=130).any(|n| address == H160::from_low_u64_be(n));
+	builtin || is_acala_precompile(address)
+}
+
 impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 	/// Create a new stack-based executor.
 	pub fn new(state: S, config: &'config Config) -> Self {
@@ -243,7 +325,13 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 
 	/// Execute the runtime until it returns.
 	pub fn execute(&mut self, runtime: &mut Runtime) -> ExitReason {
-		match runtime.run(self) {
+		#[cfg(not(feature = "tracing"))]
+		let capture = runtime.run(self);
+		#[cfg(feature = "tracing")]
+		let capture =
+			module_evm_utiltity::evm_runtime::tracing::using(&mut tracing::EvmRuntimeListener, || runtime.run(self));
+
+		match capture {
 			Capture::Exit(s) => s,
 			Capture::Trap(_) => unreachable!("Trap is Infallible"),
 		}
@@ -254,6 +342,22 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 		self.state.metadata().gasometer().gas()
 	}
 
+	/// `BASEFEE` and the reduced refunds are enabled with the London hard fork.
+	fn is_london(&self) -> bool {
+		self.state.block_base_fee_per_gas().is_some()
+	}
+
+	/// Warm up the addresses and storage slots of the access list (EIP-2930).
+	fn initialize_with_access_list(&mut self, access_list: Vec<(H160, Vec<H256>)>) {
+		let addresses = access_list.iter().map(|(address, _)| *address);
+		self.state.metadata_mut().access_addresses(addresses);
+
+		let storages = access_list
+			.into_iter()
+			.flat_map(|(address, keys)| keys.into_iter().map(move |key| (address, key)));
+		self.state.metadata_mut().access_storages(storages);
+	}
+
 	/// Execute a `CREATE` transaction.
 	pub fn transact_create(
 		&mut self,
@@ -274,6 +378,10 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 			Err(e) => return e.into(),
 		}
 
+		if self.config.increase_state_access_gas {
+			self.initialize_with_access_list(access_list);
+		}
+
 		match self.create_inner(
 			caller,
 			CreateScheme::Legacy { caller },
@@ -307,6 +415,10 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 			Ok(()) => (),
 			Err(e) => return e.into(),
 		}
+
+		if self.config.increase_state_access_gas {
+			self.initialize_with_access_list(access_list);
+		}
 		let code_hash = H256::from_slice(Keccak256::digest(&init_code).as_slice());
 
 		match self.create_inner(
@@ -347,6 +459,10 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 			Err(e) => return e.into(),
 		}
 
+		if self.config.increase_state_access_gas {
+			self.initialize_with_access_list(access_list);
+		}
+
 		match self.create_inner(
 			caller,
 			CreateScheme::Fixed(address),
@@ -381,6 +497,13 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 			Err(e) => return (e.into(), Vec::new()),
 		}
 
+		if self.config.increase_state_access_gas {
+			self.state
+				.metadata_mut()
+				.access_addresses([caller, address].iter().copied());
+			self.initialize_with_access_list(access_list);
+		}
+
 		self.state.inc_nonce(caller);
 
 		let context = Context {
@@ -410,9 +533,14 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 
 	/// Get used gas for the current executor, given the price.
 	pub fn used_gas(&self) -> u64 {
+		let max_refund_quotient = if self.is_london() {
+			LONDON_MAX_REFUND_QUOTIENT
+		} else {
+			MAX_REFUND_QUOTIENT
+		};
 		self.state.metadata().gasometer().total_used_gas()
 			- min(
-				self.state.metadata().gasometer().total_used_gas() / 2,
+				self.state.metadata().gasometer().total_used_gas() / max_refund_quotient,
 				self.state.metadata().gasometer().refunded_gas() as u64,
 			)
 	}
@@ -465,6 +593,18 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 		}
 	}
 
+	/// The gas limit of a sub call, used only to report the frame to the tracer.
+	#[cfg(feature = "tracing")]
+	fn trace_gas_limit(&self, target_gas: Option<u64>, take_l64: bool) -> u64 {
+		let gas = self.gas();
+		let after_gas = if take_l64 && self.config.call_l64_after_gas {
+			gas - gas / 64
+		} else {
+			gas
+		};
+		min(target_gas.unwrap_or(after_gas), after_gas)
+	}
+
 	fn create_inner(
 		&mut self,
 		caller: H160,
@@ -473,6 +613,38 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 		init_code: Vec<u8>,
 		target_gas: Option<u64>,
 		take_l64: bool,
+	) -> Capture<(ExitReason, Option<H160>, Vec<u8>), Infallible> {
+		#[cfg(feature = "tracing")]
+		let gas_before = {
+			tracing::enter(
+				tracing::create_type(&scheme),
+				caller,
+				self.create_address(scheme).unwrap_or_default(),
+				&init_code,
+				value,
+				self.trace_gas_limit(target_gas, take_l64),
+			);
+			self.gas()
+		};
+
+		let ret = self.execute_create(caller, scheme, value, init_code, target_gas, take_l64);
+
+		#[cfg(feature = "tracing")]
+		if let Capture::Exit((reason, _, output)) = &ret {
+			tracing::exit(reason, output, gas_before.saturating_sub(self.gas()));
+		}
+
+		ret
+	}
+
+	fn execute_create(
+		&mut self,
+		caller: H160,
+		scheme: CreateScheme,
+		value: U256,
+		init_code: Vec<u8>,
+		target_gas: Option<u64>,
+		take_l64: bool,
 	) -> Capture<(ExitReason, Option<H160>, Vec<u8>), Infallible> {
 		macro_rules! try_or_fail {
 			( $e:expr ) => {
@@ -494,6 +666,9 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 			Ok(address) => address,
 		};
 
+		self.state.metadata_mut().access_address(caller);
+		self.state.metadata_mut().access_address(address);
+
 		*self.state.metadata_mut().caller_mut() = Some(caller);
 		*self.state.metadata_mut().target_mut() = Some(address);
 
@@ -634,6 +809,56 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 		take_l64: bool,
 		take_stipend: bool,
 		context: Context,
+	) -> Capture<(ExitReason, Vec<u8>), Infallible> {
+		#[cfg(feature = "tracing")]
+		let gas_before = {
+			let mut gas = self.trace_gas_limit(target_gas, take_l64);
+			if let Some(transfer) = transfer.as_ref() {
+				if take_stipend && transfer.value != U256::zero() {
+					gas = gas.saturating_add(self.config.call_stipend);
+				}
+			}
+			tracing::enter(
+				tracing::call_type(code_address, &transfer, is_static, &context),
+				context.caller,
+				code_address,
+				&input,
+				context.apparent_value,
+				gas,
+			);
+			self.gas()
+		};
+
+		let ret = self.execute_call(
+			code_address,
+			transfer,
+			input,
+			target_gas,
+			is_static,
+			take_l64,
+			take_stipend,
+			context,
+		);
+
+		#[cfg(feature = "tracing")]
+		if let Capture::Exit((reason, output)) = &ret {
+			tracing::exit(reason, output, gas_before.saturating_sub(self.gas()));
+		}
+
+		ret
+	}
+
+	#[allow(clippy::too_many_arguments)]
+	fn execute_call(
+		&mut self,
+		code_address: H160,
+		transfer: Option<Transfer>,
+		input: Vec<u8>,
+		target_gas: Option<u64>,
+		is_static: bool,
+		take_l64: bool,
+		take_stipend: bool,
+		context: Context,
 	) -> Capture<(ExitReason, Vec<u8>), Infallible> {
 		macro_rules! try_or_fail {
 			( $e:expr ) => {
@@ -737,10 +962,7 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 
 		let mut runtime = Runtime::new(Rc::new(code), Rc::new(input), context, self.config);
 
-		#[cfg(not(feature = "tracing"))]
 		let reason = self.execute(&mut runtime);
-		#[cfg(feature = "tracing")]
-		let reason = module_evm_utiltity::evm_runtime::tracing::using(&mut Tracer, || self.execute(&mut runtime));
 
 		log::debug!(target: "evm", "Call execution using address {}: {:?}", code_address, reason);
 
@@ -765,6 +987,43 @@ impl<'config, S: StackState<'config>> StackExecutor<'config, S> {
 			}
 		}
 	}
+
+	fn record_opcode_cost(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
+		// log::trace!(target: "evm", "Running opcode: {:?}, Pre gas-left: {:?}", opcode,
+		// gasometer().gas());
+
+		let is_london = self.is_london();
+
+		if opcode == BASEFEE && is_london {
+			self.state.metadata_mut().gasometer_mut().record_cost(GAS_BASEFEE)?;
+		} else if let Some(cost) = gasometer::static_opcode_cost(opcode) {
+			self.state.metadata_mut().gasometer_mut().record_cost(cost)?;
+		} else {
+			let is_static = self.state.metadata().is_static();
+			let (gas_cost, storage_target, memory_cost) =
+				gasometer::dynamic_opcode_cost(context.address, opcode, stack, is_static, self.config, self)?;
+
+			let gasometer = &mut self.state.metadata_mut().gasometer_mut();
+			let refunded_gas = gasometer.refunded_gas();
+
+			gasometer.record_dynamic_cost(gas_cost, memory_cost)?;
+
+			// EIP-3529: no refund for `SELFDESTRUCT`
+			if opcode == Opcode::SUICIDE && is_london {
+				let refund = gasometer.refunded_gas() - refunded_gas;
+				gasometer.record_refund(-refund)?;
+			}
+
+			// EIP-2929: the accessed address or storage slot is warm afterwards
+			match storage_target {
+				StorageTarget::Address(address) => self.state.metadata_mut().access_address(address),
+				StorageTarget::Slot(address, key) => self.state.metadata_mut().access_storage(address, key),
+				StorageTarget::None => (),
+			}
+		}
+
+		Ok(())
+	}
 }
 
 impl<'config, S: StackState<'config>> Handler for StackExecutor<'config, S> {
@@ -811,7 +1070,7 @@ impl<'config, S: StackState<'config>> Handler for StackExecutor<'config, S> {
 
 	fn is_cold(&self, address: H160, maybe_index: Option<H256>) -> bool {
 		match maybe_index {
-			None => self.state.is_cold(address),
+			None => !is_precompile(address) && self.state.is_cold(address),
 			Some(index) => self.state.is_storage_cold(address, index),
 		}
 	}
@@ -848,6 +1107,18 @@ impl<'config, S: StackState<'config>> Handler for StackExecutor<'config, S> {
 		self.state.chain_id()
 	}
 
+	fn other(&mut self, opcode: Opcode, machine: &mut Machine) -> Result<(), ExitError> {
+		match self.state.block_base_fee_per_gas() {
+			Some(base_fee) if opcode == BASEFEE => {
+				let mut value = H256::default();
+				base_fee.to_big_endian(&mut value[..]);
+				machine.stack_mut().push(value)
+			}
+			// same as the default handler
+			_ => Err(ExitError::OutOfGas),
+		}
+	}
+
 	fn deleted(&self, address: H160) -> bool {
 		self.state.deleted(address)
 	}
@@ -916,22 +1187,14 @@ impl<'config, S: StackState<'config>> Handler for StackExecutor<'config, S> {
 
 	#[inline]
 	fn pre_validate(&mut self, context: &Context, opcode: Opcode, stack: &Stack) -> Result<(), ExitError> {
-		// log::trace!(target: "evm", "Running opcode: {:?}, Pre gas-left: {:?}", opcode,
-		// gasometer().gas());
-
-		if let Some(cost) = gasometer::static_opcode_cost(opcode) {
-			self.state.metadata_mut().gasometer_mut().record_cost(cost)?;
-		} else {
-			let is_static = self.state.metadata().is_static();
-			// TODO: EIP-2930
-			let (gas_cost, _storage_target, memory_cost) =
-				gasometer::dynamic_opcode_cost(context.address, opcode, stack, is_static, self.config, self)?;
+		#[cfg(feature = "tracing")]
+		let gas_before = self.gas();
 
-			let gasometer = &mut self.state.metadata_mut().gasometer_mut();
+		let result = self.record_opcode_cost(context, opcode, stack);
 
-			gasometer.record_dynamic_cost(gas_cost, memory_cost)?;
-		}
+		#[cfg(feature = "tracing")]
+		tracing::step_gas(gas_before, gas_before.saturating_sub(self.gas()));
 
-		Ok(())
+		result
 	}
 }
@@ -0,0 +1,228 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Collect the call frames and the executed opcodes of an EVM execution.
+//!
+//! The `StackExecutor` reports the frames and the gas of every opcode, and the
+//! `EvmRuntimeListener` forwards the `evm_runtime` events. Nothing is collected
+//! unless the execution is wrapped with `using`.
+
+use module_evm_utiltity::{
+	evm::{Context, CreateScheme, ExitReason, Transfer},
+	evm_runtime::tracing::{Event as RuntimeEvent, EventListener},
+};
+use primitive_types::{H160, H256, U256};
+pub use primitives::evm::{CallTrace, CallType, OpcodeTrace, Step, TraceOutcome, TracerConfig};
+use sp_std::{collections::btree_map::BTreeMap, vec::Vec};
+
+environmental::environmental!(tracer: Tracer);
+
+pub struct Tracer {
+	config: TracerConfig,
+	/// The exited top level frames.
+	calls: Vec<CallTrace>,
+	/// The frames which have not exited yet, the last one is the current frame.
+	stack: Vec<CallTrace>,
+	steps: Vec<Step>,
+	/// The storage slots touched so far, grouped by contract.
+	storages: BTreeMap<H160, BTreeMap<H256, H256>>,
+}
+
+impl Tracer {
+	pub fn new(config: TracerConfig) -> Self {
+		Self {
+			config,
+			calls: Vec::new(),
+			stack: Vec::new(),
+			steps: Vec::new(),
+			storages: BTreeMap::new(),
+		}
+	}
+
+	/// Consume the tracer and return the collected trace.
+	pub fn finalize(mut self) -> TraceOutcome {
+		// frames left open by an early exit are still reported
+		while !self.stack.is_empty() {
+			self.exit_frame(None, Vec::new(), 0);
+		}
+
+		match self.config {
+			TracerConfig::CallTracer => TraceOutcome::Calls(self.calls),
+			TracerConfig::OpcodeTracer { .. } => {
+				let gas_used = self.calls.iter().map(|call| call.gas_used).sum();
+				let failed = self
+					.calls
+					.iter()
+					.any(|call| !matches!(call.exit_reason, Some(ExitReason::Succeed(_))));
+				let return_value = self.calls.pop().map(|call| call.output).unwrap_or_default();
+				TraceOutcome::Steps(OpcodeTrace {
+					gas_used,
+					failed,
+					return_value,
+					steps: self.steps,
+				})
+			}
+		}
+	}
+
+	fn is_opcode_tracer(&self) -> bool {
+		matches!(self.config, TracerConfig::OpcodeTracer { .. })
+	}
+
+	fn enter_frame(&mut self, call_type: CallType, from: H160, to: H160, input: &[u8], value: U256, gas: u64) {
+		self.stack.push(CallTrace {
+			call_type,
+			from,
+			to,
+			// the input of the opcode tracer is never returned
+			input: if self.is_opcode_tracer() {
+				Vec::new()
+			} else {
+				input.to_vec()
+			},
+			value,
+			gas,
+			gas_used: 0,
+			output: Vec::new(),
+			exit_reason: None,
+			calls: Vec::new(),
+		});
+	}
+
+	fn exit_frame(&mut self, exit_reason: Option<ExitReason>, output: Vec<u8>, gas_used: u64) {
+		if let Some(mut call) = self.stack.pop() {
+			call.exit_reason = exit_reason;
+			call.output = output;
+			call.gas_used = gas_used;
+			match self.stack.last_mut() {
+				Some(parent) => parent.calls.push(call),
+				None => self.calls.push(call),
+			}
+		}
+	}
+
+	fn on_step(&mut self, pc: u64, op: u8, stack: &[H256], memory: &[u8]) {
+		if let TracerConfig::OpcodeTracer {
+			disable_stack,
+			enable_memory,
+			..
+		} = self.config
+		{
+			self.steps.push(Step {
+				pc,
+				op,
+				gas: 0,
+				gas_cost: 0,
+				depth: self.stack.len() as u32,
+				stack: if disable_stack { None } else { Some(stack.to_vec()) },
+				memory: if enable_memory { Some(memory.to_vec()) } else { None },
+				storage: None,
+			});
+		}
+	}
+
+	fn on_step_gas(&mut self, gas: u64, gas_cost: u64) {
+		if let Some(step) = self.steps.last_mut() {
+			step.gas = gas;
+			step.gas_cost = gas_cost;
+		}
+	}
+
+	fn on_storage_access(&mut self, address: H160, index: H256, value: H256) {
+		if let TracerConfig::OpcodeTracer {
+			disable_storage: false, ..
+		} = self.config
+		{
+			let storage = self.storages.entry(address).or_default();
+			storage.insert(index, value);
+			if let Some(step) = self.steps.last_mut() {
+				step.storage = Some(storage.iter().map(|(k, v)| (*k, *v)).collect());
+			}
+		}
+	}
+}
+
+/// Execute `f` and collect the trace into `tracer`.
+pub fn using<R, F: FnOnce() -> R>(tracer: &mut Tracer, f: F) -> R {
+	tracer::using(tracer, f)
+}
+
+pub(crate) fn call_type(
+	code_address: H160,
+	transfer: &Option<Transfer>,
+	is_static: bool,
+	context: &Context,
+) -> CallType {
+	if is_static {
+		CallType::StaticCall
+	} else if context.address != code_address {
+		if transfer.is_some() {
+			CallType::CallCode
+		} else {
+			CallType::DelegateCall
+		}
+	} else {
+		CallType::Call
+	}
+}
+
+pub(crate) fn create_type(scheme: &CreateScheme) -> CallType {
+	match scheme {
+		CreateScheme::Create2 { .. } => CallType::Create2,
+		_ => CallType::Create,
+	}
+}
+
+pub(crate) fn enter(call_type: CallType, from: H160, to: H160, input: &[u8], value: U256, gas: u64) {
+	tracer::with(|t| t.enter_frame(call_type, from, to, input, value, gas));
+}
+
+pub(crate) fn exit(exit_reason: &ExitReason, output: &[u8], gas_used: u64) {
+	tracer::with(|t| t.exit_frame(Some(exit_reason.clone()), output.to_vec(), gas_used));
+}
+
+pub(crate) fn step_gas(gas: u64, gas_cost: u64) {
+	tracer::with(|t| t.on_step_gas(gas, gas_cost));
+}
+
+pub struct EvmRuntimeListener;
+
+impl EventListener for EvmRuntimeListener {
+	fn event(&mut self, event: RuntimeEvent) {
+		frame_support::log::debug!(
+			target: "evm", "evm_runtime tracing: {:?}", event
+		);
+
+		match event {
+			RuntimeEvent::Step {
+				opcode,
+				position,
+				stack,
+				memory,
+				..
+			} => {
+				let pc = position.as_ref().map(|pc| *pc as u64).unwrap_or_default();
+				tracer::with(|t| t.on_step(pc, opcode.0, stack.data(), memory.data()));
+			}
+			RuntimeEvent::SLoad { address, index, value } | RuntimeEvent::SStore { address, index, value } => {
+				tracer::with(|t| t.on_storage_access(address, index, value));
+			}
+			_ => {}
+		}
+	}
+}
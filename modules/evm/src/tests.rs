@@ -26,10 +26,11 @@ use crate::runner::{
 	state::{StackExecutor, StackSubstateMetadata},
 	StackState,
 };
-use frame_support::{assert_noop, assert_ok, dispatch::DispatchErrorWithPostInfo};
+use frame_support::{assert_noop, assert_ok, dispatch::DispatchErrorWithPostInfo, traits::Hooks};
 use module_support::AddressMapping;
 use sp_core::{
 	bytes::{from_hex, to_hex},
+	storage::well_known_keys,
 	H160,
 };
 use sp_runtime::{traits::BadOrigin, AccountId32};
@@ -109,6 +110,7 @@ fn should_create_and_call_contract() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		).unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
@@ -136,6 +138,7 @@ fn should_create_and_call_contract() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		).unwrap();
 		assert_eq!(
@@ -166,6 +169,89 @@ fn should_create_and_call_contract() {
 	});
 }
 
+#[test]
+fn schedule_task_should_work() {
+	// the contract of `should_create_and_call_contract`
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060b88061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063165c4a1614602d575b600080fd5b606060048036036040811015604157600080fd5b8101908080359060200190929190803590602001909291905050506076565b6040518082815260200191505060405180910390f35b600081830290509291505056fea265627a7a723158201f3db7301354b88b310868daf4395a6ab6cd42d16b1d8e68cdf4fdd9d34fffbf64736f6c63430005110032"
+	).unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			1000000,
+			1000000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// multiply(2, 3)
+		let multiply = from_hex(
+			"0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003"
+		).unwrap();
+		let alice_account = <Runtime as Config>::AddressMapping::get_account_id(&alice());
+		assert_noop!(
+			EVM::schedule_call(
+				Origin::signed(alice_account.clone()),
+				contract_address,
+				multiply.clone(),
+				0,
+				u64::MAX,
+				0
+			),
+			Error::<Runtime>::ExceedMaxIdleWeight
+		);
+		assert_ok!(EVM::schedule_call(
+			Origin::signed(alice_account),
+			contract_address,
+			multiply,
+			0,
+			100_000,
+			0
+		));
+		assert_eq!(
+			module_idle_scheduler::Tasks::<Runtime>::get(0),
+			Some(
+				EvmTask::<Runtime>::Schedule {
+					from: alice(),
+					target: contract_address,
+					input: from_hex("0x165c4a1600000000000000000000000000000000000000000000000000000000000000020000000000000000000000000000000000000000000000000000000000000003").unwrap(),
+					value: 0,
+					gas_limit: 100_000,
+					storage_limit: 0,
+				}
+				.into()
+			)
+		);
+		let nonce = Pallet::<Runtime>::account_basic(&alice()).nonce;
+
+		// not enough weight, keep the task
+		IdleScheduler::on_idle(0, 50_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 1);
+		assert_eq!(Pallet::<Runtime>::account_basic(&alice()).nonce, nonce);
+
+		IdleScheduler::on_idle(0, 1_000_000);
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 0);
+		assert_eq!(Pallet::<Runtime>::account_basic(&alice()).nonce, nonce + 1);
+		System::assert_has_event(Event::EVM(crate::Event::Executed {
+			from: alice(),
+			contract: contract_address,
+			logs: vec![],
+		}));
+		System::assert_last_event(Event::IdleScheduler(module_idle_scheduler::Event::TaskDispatched {
+			task_id: 0,
+			result: Ok(()),
+		}));
+	});
+}
+
 #[test]
 fn create_reverts_with_message() {
 	// pragma solidity ^0.5.0;
@@ -185,6 +271,7 @@ fn create_reverts_with_message() {
 			0,
 			12_000_000,
 			12_000_000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -218,6 +305,7 @@ fn call_reverts_with_message() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		).unwrap();
 
@@ -242,6 +330,7 @@ fn call_reverts_with_message() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		).unwrap();
 
@@ -259,6 +348,124 @@ fn call_reverts_with_message() {
 	});
 }
 
+#[cfg(feature = "tracing")]
+#[test]
+fn trace_call_should_work() {
+	use crate::runner::{trace, TraceOutcome, TracerConfig};
+	use primitives::evm::{CallTrace, CallType};
+
+	// the contract of `call_reverts_with_message`
+	let contract = from_hex(
+		"0x608060405234801561001057600080fd5b5060df8061001f6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063c298557814602d575b600080fd5b60336035565b005b600060a8576040517f08c379a000000000000000000000000000000000000000000000000000000000815260040180806020018281038252600d8152602001807f6572726f72206d6573736167650000000000000000000000000000000000000081525060200191505060405180910390fd5b56fea265627a7a7231582066b3ee33bedba8a318d0d66610145030fdc0f982b11f5160d366e15e4d8ba2ef64736f6c63430005110032"
+	).unwrap();
+	let caller = alice();
+
+	new_test_ext().execute_with(|| {
+		let (result, outcome) = trace(TracerConfig::CallTracer, || {
+			<Runtime as Config>::Runner::create(
+				caller,
+				contract,
+				0,
+				1000000,
+				1000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+		})
+		.unwrap();
+		let contract_address = result.unwrap().value;
+		match outcome {
+			TraceOutcome::Calls(calls) => {
+				assert_eq!(calls.len(), 1);
+				assert_eq!(calls[0].call_type, CallType::Create);
+				assert_eq!(calls[0].from, caller);
+				assert_eq!(calls[0].to, contract_address);
+				assert_eq!(calls[0].exit_reason, Some(ExitReason::Succeed(ExitSucceed::Returned)));
+			}
+			_ => panic!("unexpected trace outcome"),
+		}
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// call method `foo`
+		let foo = from_hex("0xc2985578").unwrap();
+		let (result, outcome) = trace(TracerConfig::CallTracer, || {
+			<Runtime as Config>::Runner::call(
+				caller,
+				caller,
+				contract_address,
+				foo.clone(),
+				0,
+				1000000,
+				1000000,
+				vec![],
+				<Runtime as Config>::config(),
+			)
+		})
+		.unwrap();
+		let result = result.unwrap();
+		match outcome {
+			TraceOutcome::Calls(calls) => {
+				assert_eq!(
+					calls,
+					vec![CallTrace {
+						call_type: CallType::Call,
+						from: caller,
+						to: contract_address,
+						input: foo.clone(),
+						value: U256::zero(),
+						gas: calls[0].gas,
+						gas_used: calls[0].gas_used,
+						output: result.value,
+						exit_reason: Some(ExitReason::Revert(ExitRevert::Reverted)),
+						calls: vec![],
+					}]
+				);
+				assert!(calls[0].gas_used > 0 && calls[0].gas_used < calls[0].gas);
+			}
+			_ => panic!("unexpected trace outcome"),
+		}
+
+		let (_, outcome) = trace(
+			TracerConfig::OpcodeTracer {
+				disable_stack: false,
+				enable_memory: true,
+				disable_storage: false,
+			},
+			|| {
+				<Runtime as Config>::Runner::call(
+					caller,
+					caller,
+					contract_address,
+					foo,
+					0,
+					1000000,
+					1000000,
+					vec![],
+					<Runtime as Config>::config(),
+				)
+			},
+		)
+		.unwrap();
+		match outcome {
+			TraceOutcome::Steps(trace) => {
+				assert!(trace.failed);
+				// PUSH1 0x80
+				assert_eq!(trace.steps[0].pc, 0);
+				assert_eq!(trace.steps[0].op, 0x60);
+				assert_eq!(trace.steps[0].depth, 1);
+				assert_eq!(trace.steps[0].gas_cost, 3);
+				assert_eq!(trace.steps[0].stack, Some(vec![]));
+				assert_eq!(trace.steps[1].stack, Some(vec![H256::from_low_u64_be(0x80)]));
+				// REVERT
+				assert_eq!(trace.steps.last().unwrap().op, 0xfd);
+			}
+			_ => panic!("unexpected trace outcome"),
+		}
+	});
+}
+
 #[test]
 fn should_publish_payable_contract() {
 	// pragma solidity ^0.5.0;
@@ -290,6 +497,7 @@ fn should_publish_payable_contract() {
 			convert_decimals_to_evm(amount),
 			1000000,
 			100000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -314,6 +522,7 @@ fn should_publish_payable_contract() {
 			convert_decimals_to_evm(amount),
 			100000,
 			100000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -380,6 +589,7 @@ fn should_transfer_from_contract() {
 			0,
 			10000000,
 			10000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.expect("create shouldn't fail");
@@ -410,6 +620,7 @@ fn should_transfer_from_contract() {
 			convert_decimals_to_evm(amount),
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -438,6 +649,7 @@ fn should_transfer_from_contract() {
 			convert_decimals_to_evm(amount),
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -466,6 +678,7 @@ fn should_transfer_from_contract() {
 			convert_decimals_to_evm(amount),
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -495,6 +708,7 @@ fn should_transfer_from_contract() {
 			convert_decimals_to_evm(dollar_aca), // 1 ACA
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -523,6 +737,7 @@ fn should_transfer_from_contract() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -558,6 +773,7 @@ fn contract_should_publish_contracts() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -589,6 +805,7 @@ fn contract_should_publish_contracts() {
 			convert_decimals_to_evm(amount),
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -637,6 +854,7 @@ fn contract_should_publish_contracts_without_payable() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -662,6 +880,7 @@ fn contract_should_publish_contracts_without_payable() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -700,9 +919,16 @@ fn publish_factory() {
 		"0x608060405234801561001057600080fd5b5060405161001d90610121565b604051809103906000f080158015610039573d6000803e3d6000fd5b506000806101000a81548173ffffffffffffffffffffffffffffffffffffffff021916908373ffffffffffffffffffffffffffffffffffffffff1602179055506000809054906101000a900473ffffffffffffffffffffffffffffffffffffffff1673ffffffffffffffffffffffffffffffffffffffff1663c29855786040518163ffffffff1660e01b815260040160206040518083038186803b1580156100e057600080fd5b505afa1580156100f4573d6000803e3d6000fd5b505050506040513d602081101561010a57600080fd5b81019080805190602001909291905050505061012d565b60a58061017983390190565b603e8061013b6000396000f3fe6080604052600080fdfea265627a7a7231582064177030ee644a03aaf8d65027df9e0331c8bc4b161de25bfb8aa3142848e0f864736f6c634300051100326080604052348015600f57600080fd5b5060878061001e6000396000f3fe6080604052348015600f57600080fd5b506004361060285760003560e01c8063c298557814602d575b600080fd5b60336049565b6040518082815260200191505060405180910390f35b6000607b90509056fea265627a7a7231582031e5a4abae00962cfe9875df1b5b0d3ce6624e220cb8c714a948794fcddb6b4f64736f6c63430005110032"
 	).unwrap();
 	new_test_ext().execute_with(|| {
-		let result =
-			<Runtime as Config>::Runner::create(alice(), contract, 0, 2_000_000, 5000, <Runtime as Config>::config())
-				.unwrap();
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			2_000_000,
+			5000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
 		assert_eq!(result.used_gas.as_u64(), 156_479u64);
 		assert_eq!(result.used_storage, 461);
@@ -908,6 +1134,7 @@ fn should_transfer_maintainer() {
 			0,
 			12_000_000,
 			12_000_000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -975,13 +1202,14 @@ fn should_publish() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		).unwrap();
 		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
 		assert_eq!(result.used_storage, 0);
 
 		// create contract
-		let result = <Runtime as Config>::Runner::create(alice(), contract, 0, 21_000_000, 21_000_000, <Runtime as Config>::config()).unwrap();
+		let result = <Runtime as Config>::Runner::create(alice(), contract, 0, 21_000_000, 21_000_000, vec![], <Runtime as Config>::config()).unwrap();
 		let contract_address = result.value;
 
 		assert_eq!(result.used_storage, 284);
@@ -1003,6 +1231,7 @@ fn should_publish() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		));
 
@@ -1033,6 +1262,7 @@ fn should_publish() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		));
 
@@ -1055,6 +1285,7 @@ fn should_publish() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		));
 
@@ -1081,7 +1312,7 @@ fn should_publish_free() {
 		assert_noop!(EVM::publish_free(Origin::signed(CouncilAccount::get()), H160::default()), Error::<Runtime>::ContractNotFound);
 
 		// create contract
-		let result = <Runtime as Config>::Runner::create(alice(), contract, 0, 21_000_000, 21_000_000, <Runtime as Config>::config()).unwrap();
+		let result = <Runtime as Config>::Runner::create(alice(), contract, 0, 21_000_000, 21_000_000, vec![], <Runtime as Config>::config()).unwrap();
 		let contract_address = result.value;
 
 		// multiply(2, 3)
@@ -1118,6 +1349,7 @@ fn should_publish_free() {
 			0,
 			1000000,
 			1000000,
+			vec![],
 			<Runtime as Config>::config(),
 		));
 
@@ -1202,6 +1434,7 @@ fn should_set_code() {
 			0,
 			21_000_000,
 			21_000_000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1346,6 +1579,7 @@ fn should_selfdestruct() {
 			convert_decimals_to_evm(amount),
 			1000000,
 			100000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1464,6 +1698,7 @@ fn storage_limit_should_work() {
 			0,
 			200_000,
 			1000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1524,6 +1759,7 @@ fn storage_limit_should_work() {
 			amount,
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1570,6 +1806,7 @@ fn storage_limit_should_work() {
 			amount,
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1607,6 +1844,7 @@ fn evm_execute_mode_should_work() {
 			0,
 			1000000000,
 			1000000000,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1765,9 +2003,16 @@ fn should_update_storage() {
 
 	new_test_ext().execute_with(|| {
 		// create contract
-		let result =
-			<Runtime as Config>::Runner::create(alice(), contract, 0, 500000, 100000, <Runtime as Config>::config())
-				.unwrap();
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract,
+			0,
+			500000,
+			100000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
 
 		let contract_address = result.value;
 
@@ -1817,6 +2062,7 @@ fn should_update_storage() {
 			0,
 			1000000,
 			STORAGE_SIZE,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1835,6 +2081,7 @@ fn should_update_storage() {
 			0,
 			1000000,
 			STORAGE_SIZE,
+			vec![],
 			<Runtime as Config>::config(),
 		)
 		.unwrap();
@@ -1977,3 +2224,260 @@ fn remove_account_works() {
 		assert_eq!(Accounts::<Runtime>::contains_key(&address), false);
 	});
 }
+
+#[test]
+fn basefee_opcode_works_with_london() {
+	// runtime code: BASEFEE PUSH1 0 MSTORE PUSH1 32 PUSH1 0 RETURN
+	let contract = from_hex("0x684860005260206000f360005260096017f3").unwrap();
+
+	new_test_ext().execute_with(|| {
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract.clone(),
+			0,
+			1_000_000,
+			1_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let contract_address = result.value;
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// BASEFEE is not available before London
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract_address,
+			vec![],
+			0,
+			1_000_000,
+			1_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert!(!result.exit_reason.is_succeed());
+
+		EnableLondon::set(true);
+
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract_address,
+			vec![],
+			0,
+			1_000_000,
+			1_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		// the base fee is the gas price
+		assert_eq!(U256::from(result.value.as_slice()), U256::one());
+		let used_gas = result.used_gas;
+
+		// the access list is charged upfront
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract_address,
+			vec![],
+			0,
+			1_000_000,
+			1_000_000,
+			vec![AccessListItem {
+				address: H160::from_low_u64_be(0x1234),
+				slots: vec![H256::zero()],
+			}],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(result.used_gas, used_gas + 2_400 + 1_900);
+
+		EnableLondon::set(false);
+	});
+}
+
+#[test]
+fn clear_storage_has_no_refund_with_london() {
+	// init code: PUSH1 1 PUSH1 0 SSTORE, then return the runtime code
+	// runtime code: PUSH1 0 PUSH1 0 SSTORE STOP
+	let contract = from_hex("0x60016000556006601160003960066000f3600060005500").unwrap();
+
+	new_test_ext().execute_with(|| {
+		EnableLondon::set(true);
+
+		let result = <Runtime as Config>::Runner::create(
+			alice(),
+			contract.clone(),
+			0,
+			1_000_000,
+			1_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		let contract_address = result.value;
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 1);
+
+		#[cfg(not(feature = "with-ethereum-compatibility"))]
+		publish_free(contract_address);
+
+		// clear the slot
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract_address,
+			vec![],
+			0,
+			1_000_000,
+			1_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+		assert_eq!(AccountStorages::<Runtime>::iter_prefix(&contract_address).count(), 0);
+		let clear_used_gas = result.used_gas;
+
+		// write zero to the empty slot
+		let result = <Runtime as Config>::Runner::call(
+			alice(),
+			alice(),
+			contract_address,
+			vec![],
+			0,
+			1_000_000,
+			1_000_000,
+			vec![],
+			<Runtime as Config>::config(),
+		)
+		.unwrap();
+		assert_eq!(result.exit_reason, ExitReason::Succeed(ExitSucceed::Stopped));
+
+		// clearing the slot is charged the same without any refund
+		assert_eq!(clear_used_gas, result.used_gas);
+
+		EnableLondon::set(false);
+	});
+}
+
+#[test]
+fn eth_call_v2_works() {
+	new_test_ext().execute_with(|| {
+		let origin = Origin::signed(<Runtime as Config>::AddressMapping::get_account_id(&alice()));
+		EnableLondon::set(true);
+		sp_io::storage::set(well_known_keys::EXTRINSIC_INDEX, &1u32.encode());
+
+		assert_ok!(EVM::eth_call(
+			origin.clone(),
+			TransactionAction::Call(bob()),
+			vec![],
+			0,
+			1_000_000,
+			0,
+			0
+		));
+		assert_ok!(EVM::eth_call_v2(
+			origin,
+			TransactionAction::Call(bob()),
+			vec![],
+			0,
+			1_000_000,
+			0,
+			0,
+			vec![AccessListItem {
+				address: H160::from_low_u64_be(0x1234),
+				slots: vec![H256::zero()],
+			}]
+		));
+
+		// the access list is charged upfront
+		let gas_used = EVM::transaction_gas_used();
+		assert_eq!(gas_used[1].1, gas_used[0].1 + 2_400 + 1_900);
+
+		EnableLondon::set(false);
+	});
+}
+
+#[test]
+fn receipts_works() {
+	new_test_ext().execute_with(|| {
+		let origin = Origin::signed(<Runtime as Config>::AddressMapping::get_account_id(&alice()));
+		let evm_events = |index: u32| {
+			System::events()
+				.into_iter()
+				.filter_map(move |record| match record.event {
+					Event::EVM(event) => Some((index, event)),
+					_ => None,
+				})
+				.collect::<Vec<_>>()
+		};
+
+		// the gas is noted only for the extrinsics of a block
+		assert_ok!(EVM::call(origin.clone(), contract_a(), Vec::new(), 0, 1000000, 0));
+		assert_eq!(EVM::transaction_gas_used(), vec![]);
+		System::reset_events();
+
+		sp_io::storage::set(well_known_keys::EXTRINSIC_INDEX, &1u32.encode());
+		assert_ok!(EVM::call(origin.clone(), contract_a(), Vec::new(), 0, 1000000, 0));
+		let mut events = evm_events(1);
+		System::reset_events();
+
+		// an extrinsic executing several EVM transactions has a receipt for each of them
+		sp_io::storage::set(well_known_keys::EXTRINSIC_INDEX, &2u32.encode());
+		assert_ok!(EVM::call(origin.clone(), contract_b(), Vec::new(), 0, 1000000, 0));
+		assert_ok!(EVM::call(origin, contract_a(), Vec::new(), 0, 1000000, 0));
+		events.append(&mut evm_events(2));
+
+		let gas_used = EVM::transaction_gas_used();
+		assert_eq!(
+			gas_used.iter().map(|(index, _)| *index).collect::<Vec<_>>(),
+			vec![1, 2, 2]
+		);
+
+		assert_eq!(
+			EVM::receipts(events),
+			vec![
+				EthereumReceipt {
+					extrinsic_index: 1,
+					from: alice(),
+					contract: contract_a(),
+					is_create: false,
+					succeed: true,
+					used_gas: gas_used[0].1,
+					logs: vec![],
+				},
+				EthereumReceipt {
+					extrinsic_index: 2,
+					from: alice(),
+					contract: contract_b(),
+					is_create: false,
+					succeed: true,
+					used_gas: gas_used[1].1,
+					logs: vec![],
+				},
+				EthereumReceipt {
+					extrinsic_index: 2,
+					from: alice(),
+					contract: contract_a(),
+					is_create: false,
+					succeed: true,
+					used_gas: gas_used[2].1,
+					logs: vec![],
+				}
+			]
+		);
+
+		// cleared in the next block
+		EVM::on_initialize(2);
+		assert_eq!(EVM::transaction_gas_used(), vec![]);
+	});
+}
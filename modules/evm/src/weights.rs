@@ -46,12 +46,14 @@ use sp_std::marker::PhantomData;
 
 /// Weight functions needed for module_evm.
 pub trait WeightInfo {
+	fn on_initialize() -> Weight;
 	fn create() -> Weight;
 	fn create2() -> Weight;
 	fn create_nft_contract() -> Weight;
 	fn create_predeploy_contract() -> Weight;
 	fn create_predeploy_mirror_token_contract() -> Weight;
 	fn call() -> Weight;
+	fn schedule_call() -> Weight;
 	fn transfer_maintainer() -> Weight;
 	fn publish_contract() -> Weight;
 	fn publish_free() -> Weight;
@@ -64,6 +66,11 @@ pub trait WeightInfo {
 /// Weights for module_evm using the Acala node and recommended hardware.
 pub struct AcalaWeight<T>(PhantomData<T>);
 impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
+	// Storage: EVM TransactionGasUsed (r:0 w:1)
+	fn on_initialize() -> Weight {
+		(2_341_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
 	// Storage: EvmAccounts Accounts (r:2 w:0)
 	// Storage: Balances Reserves (r:2 w:2)
@@ -137,6 +144,16 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: IdleScheduler NextTaskId (r:1 w:1)
+	// Storage: IdleScheduler Tasks (r:0 w:1)
+	fn schedule_call() -> Weight {
+		(61_248_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 	// Storage: EVM Accounts (r:1 w:1)
 	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
 	fn transfer_maintainer() -> Weight {
@@ -203,6 +220,10 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 
 // For backwards compatibility and tests
 impl WeightInfo for () {
+	fn on_initialize() -> Weight {
+		(2_341_000 as Weight)
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 	fn create() -> Weight {
 		(230_271_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(12 as Weight))
@@ -233,6 +254,11 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(11 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(6 as Weight))
 	}
+	fn schedule_call() -> Weight {
+		(61_248_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(4 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(4 as Weight))
+	}
 	fn transfer_maintainer() -> Weight {
 		(99_356_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
@@ -22,7 +22,12 @@
 //!
 //! The entry of the Honzon protocol for users, user can manipulate their CDP
 //! position to loan/payback, and can also authorize others to manage the their
-//! CDP under specific collateral type.
+//! CDP under specific collateral type. An authorization can be limited by a scope, which
+//! restricts the operations the authorizee is permitted to do, caps the collateral and debit
+//! the authorizee can adjust the loan to, and may expire at a specific block.
+//!
+//! User can also opt in the cross-collateral mode, then their CDPs of all collateral types are
+//! regarded as a whole position.
 //!
 //! After system shutdown, some operations will be restricted.
 
@@ -34,7 +39,7 @@ use frame_system::pallet_prelude::*;
 use primitives::{Amount, Balance, CurrencyId, ReserveIdentifier};
 use sp_runtime::{
 	traits::{StaticLookup, Zero},
-	DispatchResult,
+	DispatchError, DispatchResult,
 };
 use support::EmergencyShutdown;
 
@@ -45,6 +50,45 @@ pub mod weights;
 pub use module::*;
 pub use weights::WeightInfo;
 
+/// The operations on a loan that an authorization can permit.
+#[derive(Clone, Copy, RuntimeDebug, PartialEq, Eq)]
+pub enum Permission {
+	/// Adjust the collateral and debit of the loan.
+	Adjust,
+	/// Transfer the whole loan to the authorizee.
+	Transfer,
+	/// Close the loan which has debit by DEX.
+	CloseByDex,
+}
+
+/// The scope of an authorization.
+#[derive(Encode, Decode, Clone, Copy, RuntimeDebug, PartialEq, Eq, TypeInfo)]
+pub struct AuthorizationScope<BlockNumber> {
+	/// The authorization is invalid after this block, `None` means never expires.
+	pub expiry: Option<BlockNumber>,
+	/// The max collateral amount the authorizee can increase the loan to, `None` means no limit.
+	pub max_collateral: Option<Balance>,
+	/// The max debit amount the authorizee can increase the loan to, `None` means no limit.
+	pub max_debit: Option<Balance>,
+	/// Whether the authorizee can adjust the loan.
+	pub can_adjust: bool,
+	/// Whether the authorizee can transfer the loan.
+	pub can_transfer: bool,
+	/// Whether the authorizee can close the loan by DEX.
+	pub can_close_by_dex: bool,
+}
+
+impl<BlockNumber> AuthorizationScope<BlockNumber> {
+	/// Check if the scope permits `permission`.
+	pub fn permits(&self, permission: Permission) -> bool {
+		match permission {
+			Permission::Adjust => self.can_adjust,
+			Permission::Transfer => self.can_transfer,
+			Permission::CloseByDex => self.can_close_by_dex,
+		}
+	}
+}
+
 #[frame_support::pallet]
 pub mod module {
 	use super::*;
@@ -80,6 +124,14 @@ pub mod module {
 		AuthorizationNotExists,
 		// Have authorized already
 		AlreadyAuthorized,
+		// The authorization has expired
+		AuthorizationExpired,
+		// The scope of authorization is invalid
+		InvalidAuthorizationScope,
+		// Exceed the max collateral amount of the authorization
+		ExceedAuthorizedCollateral,
+		// Exceed the max debit amount of the authorization
+		ExceedAuthorizedDebit,
 	}
 
 	#[pallet::event]
@@ -91,6 +143,21 @@ pub mod module {
 			authorizee: T::AccountId,
 			collateral_type: CurrencyId,
 		},
+		/// Authorize someone to operate the loan of specific collateral within the scope.
+		ScopedAuthorization {
+			authorizer: T::AccountId,
+			authorizee: T::AccountId,
+			collateral_type: CurrencyId,
+			scope: AuthorizationScope<T::BlockNumber>,
+		},
+		/// The loan of authorizer has been adjusted by authorizee.
+		AdjustLoanByAuthorizee {
+			authorizer: T::AccountId,
+			authorizee: T::AccountId,
+			collateral_type: CurrencyId,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		},
 		/// Cancel the authorization of specific collateral for someone.
 		UnAuthorization {
 			authorizer: T::AccountId,
@@ -117,6 +184,23 @@ pub mod module {
 		OptionQuery,
 	>;
 
+	/// The scope of authorization, the authorization without scope permits all operations
+	/// without limits.
+	///
+	/// AuthorizationScopes: double_map AccountId, (CurrencyId, T::AccountId) =>
+	/// Option<AuthorizationScope>
+	#[pallet::storage]
+	#[pallet::getter(fn authorization_scopes)]
+	pub type AuthorizationScopes<T: Config> = StorageDoubleMap<
+		_,
+		Twox64Concat,
+		T::AccountId,
+		Blake2_128Concat,
+		(CurrencyId, T::AccountId),
+		AuthorizationScope<T::BlockNumber>,
+		OptionQuery,
+	>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -145,9 +229,12 @@ pub mod module {
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
 
-			// not allowed to adjust the debit after system shutdown
+			// not allowed to adjust the debit after system or the collateral type shutdown
 			if !debit_adjustment.is_zero() {
-				ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+				ensure!(
+					!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+					Error::<T>::AlreadyShutdown
+				);
 			}
 			<cdp_engine::Pallet<T>>::adjust_position(&who, currency_id, collateral_adjustment, debit_adjustment)?;
 			Ok(())
@@ -167,11 +254,73 @@ pub mod module {
 			#[pallet::compact] max_collateral_amount: Balance,
 		) -> DispatchResult {
 			let who = ensure_signed(origin)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
 			<cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(who, currency_id, max_collateral_amount)?;
 			Ok(())
 		}
 
+		/// Issue extra stable token, swap it to collateral on DEX and deposit the collateral into
+		/// caller's CDP, all in one call.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `increase_debit_value`: the value of debit to be increased.
+		/// - `min_increase_collateral`: the minimal collateral amount swapped from the increased
+		///   debit, it's the slippage limit.
+		#[pallet::weight(<T as Config>::WeightInfo::expand_position_collateral())]
+		#[transactional]
+		pub fn expand_position_collateral(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] increase_debit_value: Balance,
+			#[pallet::compact] min_increase_collateral: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
+			<cdp_engine::Pallet<T>>::expand_position_collateral(
+				&who,
+				currency_id,
+				increase_debit_value,
+				min_increase_collateral,
+			)?;
+			Ok(())
+		}
+
+		/// Swap collateral of caller's CDP to stable token on DEX and use it to repay the debit
+		/// without closing the CDP, the stable token exceeding the debit will be refunded to the
+		/// caller.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `decrease_collateral`: the collateral amount to be swapped.
+		/// - `min_decrease_debit_value`: the minimal stable token amount swapped from the
+		///   collateral, it's the slippage limit.
+		#[pallet::weight(<T as Config>::WeightInfo::shrink_position_debit())]
+		#[transactional]
+		pub fn shrink_position_debit(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			#[pallet::compact] decrease_collateral: Balance,
+			#[pallet::compact] min_decrease_debit_value: Balance,
+		) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
+			<cdp_engine::Pallet<T>>::shrink_position_debit(
+				&who,
+				currency_id,
+				decrease_collateral,
+				min_decrease_debit_value,
+			)?;
+			Ok(())
+		}
+
 		/// Transfer the whole CDP of `from` under `currency_id` to caller's CDP
 		/// under the same `currency_id`, caller must have the authorization of
 		/// `from` for the specific collateral type
@@ -187,8 +336,11 @@ pub mod module {
 		) -> DispatchResult {
 			let to = ensure_signed(origin)?;
 			let from = T::Lookup::lookup(from)?;
-			ensure!(!T::EmergencyShutdown::is_shutdown(), Error::<T>::AlreadyShutdown);
-			Self::check_authorization(&from, &to, currency_id)?;
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
+			Self::check_authorization(&from, &to, currency_id, Permission::Transfer)?;
 			<loans::Pallet<T>>::transfer_loan(&from, &to, currency_id)?;
 			Ok(())
 		}
@@ -210,21 +362,49 @@ pub mod module {
 				return Ok(());
 			}
 
-			Authorization::<T>::try_mutate_exists(&from, (currency_id, &to), |maybe_reserved| -> DispatchResult {
-				if maybe_reserved.is_none() {
-					let reserve_amount = T::DepositPerAuthorization::get();
-					<T as Config>::Currency::reserve_named(&RESERVE_ID, &from, reserve_amount)?;
-					*maybe_reserved = Some(reserve_amount);
-					Self::deposit_event(Event::Authorization {
-						authorizer: from.clone(),
-						authorizee: to.clone(),
-						collateral_type: currency_id,
-					});
-					Ok(())
-				} else {
-					Err(Error::<T>::AlreadyAuthorized.into())
-				}
-			})?;
+			Self::do_authorize(&from, &to, currency_id)?;
+			Self::deposit_event(Event::Authorization {
+				authorizer: from,
+				authorizee: to,
+				collateral_type: currency_id,
+			});
+			Ok(())
+		}
+
+		/// Authorize `to` to manipulate the loan under `currency_id` within `scope`
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `to`: authorizee account
+		/// - `scope`: the permitted operations, the max collateral and debit amount, and the expiry
+		///   block of the authorization.
+		#[pallet::weight(<T as Config>::WeightInfo::authorize_with_scope())]
+		#[transactional]
+		pub fn authorize_with_scope(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			to: <T::Lookup as StaticLookup>::Source,
+			scope: AuthorizationScope<T::BlockNumber>,
+		) -> DispatchResult {
+			let from = ensure_signed(origin)?;
+			let to = T::Lookup::lookup(to)?;
+			if from == to {
+				return Ok(());
+			}
+			if let Some(expiry) = scope.expiry {
+				ensure!(
+					expiry >= <frame_system::Pallet<T>>::block_number(),
+					Error::<T>::InvalidAuthorizationScope
+				);
+			}
+
+			Self::do_authorize(&from, &to, currency_id)?;
+			AuthorizationScopes::<T>::insert(&from, (currency_id, &to), scope);
+			Self::deposit_event(Event::ScopedAuthorization {
+				authorizer: from,
+				authorizee: to,
+				collateral_type: currency_id,
+				scope,
+			});
 			Ok(())
 		}
 
@@ -243,6 +423,7 @@ pub mod module {
 			let to = T::Lookup::lookup(to)?;
 			let reserved =
 				Authorization::<T>::take(&from, (currency_id, &to)).ok_or(Error::<T>::AuthorizationNotExists)?;
+			AuthorizationScopes::<T>::remove(&from, (currency_id, &to));
 			<T as Config>::Currency::unreserve_named(&RESERVE_ID, &from, reserved);
 			Self::deposit_event(Event::UnAuthorization {
 				authorizer: from,
@@ -258,20 +439,152 @@ pub mod module {
 		pub fn unauthorize_all(origin: OriginFor<T>) -> DispatchResult {
 			let from = ensure_signed(origin)?;
 			Authorization::<T>::remove_prefix(&from, None);
+			AuthorizationScopes::<T>::remove_prefix(&from, None);
 			<T as Config>::Currency::unreserve_all_named(&RESERVE_ID, &from);
 			Self::deposit_event(Event::UnAuthorizationAll { authorizer: from });
 			Ok(())
 		}
+
+		/// Enable or disable the cross-collateral mode of caller's CDPs. In cross-collateral mode,
+		/// the positions of all collateral types are regarded as a whole position, which is
+		/// liquidated together when it's unsafe.
+		///
+		/// - `enabled`: whether to enable the cross-collateral mode.
+		#[pallet::weight(<T as Config>::WeightInfo::set_cross_collateral(<T as cdp_engine::Config>::CollateralCurrencyIds::get().len() as u32))]
+		#[transactional]
+		pub fn set_cross_collateral(origin: OriginFor<T>, enabled: bool) -> DispatchResult {
+			let who = ensure_signed(origin)?;
+			<loans::Pallet<T>>::set_cross_collateral(&who, enabled)?;
+			Ok(())
+		}
+
+		/// Adjust the loan of `from` under `currency_id` by specific `collateral_adjustment`
+		/// and `debit_adjustment`, caller must have the authorization of `from` which permits
+		/// adjusting. The collateral and the stable token are transferred from/to `from`, and the
+		/// increased collateral and debit of the loan must be within the authorization scope.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `from`: authorizer account
+		/// - `collateral_adjustment`: signed amount, positive means to deposit collateral currency
+		///   into CDP, negative means withdraw collateral currency from CDP.
+		/// - `debit_adjustment`: signed amount, positive means to issue some amount of stablecoin
+		///   to authorizer according to the debit adjustment, negative means authorizer will
+		///   payback some amount of stablecoin to CDP according to to the debit adjustment.
+		#[pallet::weight(<T as Config>::WeightInfo::adjust_loan_by_authorizee())]
+		#[transactional]
+		pub fn adjust_loan_by_authorizee(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			from: <T::Lookup as StaticLookup>::Source,
+			collateral_adjustment: Amount,
+			debit_adjustment: Amount,
+		) -> DispatchResult {
+			let to = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+
+			// not allowed to adjust the debit after system or the collateral type shutdown
+			if !debit_adjustment.is_zero() {
+				ensure!(
+					!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+					Error::<T>::AlreadyShutdown
+				);
+			}
+			let maybe_scope = Self::check_authorization(&from, &to, currency_id, Permission::Adjust)?;
+			<cdp_engine::Pallet<T>>::adjust_position(&from, currency_id, collateral_adjustment, debit_adjustment)?;
+
+			if let Some(scope) = maybe_scope {
+				let position = <loans::Pallet<T>>::positions(currency_id, &from);
+				if let Some(max_collateral) = scope.max_collateral {
+					ensure!(
+						collateral_adjustment <= 0 || position.collateral <= max_collateral,
+						Error::<T>::ExceedAuthorizedCollateral
+					);
+				}
+				if let Some(max_debit) = scope.max_debit {
+					ensure!(
+						debit_adjustment <= 0 || position.debit <= max_debit,
+						Error::<T>::ExceedAuthorizedDebit
+					);
+				}
+			}
+
+			Self::deposit_event(Event::AdjustLoanByAuthorizee {
+				authorizer: from,
+				authorizee: to,
+				collateral_type: currency_id,
+				collateral_adjustment,
+				debit_adjustment,
+			});
+			Ok(())
+		}
+
+		/// Close the CDP of `from` which has debit but still in safe by use collateral to swap
+		/// stable token on DEX for clearing debit, caller must have the authorization of `from`
+		/// which permits closing by DEX. The remaining collateral is refunded to `from`.
+		///
+		/// - `currency_id`: collateral currency id.
+		/// - `from`: authorizer account
+		/// - `max_collateral_amount`: the max collateral amount which is used to swap enough
+		/// 	stable token to clear debit.
+		#[pallet::weight(<T as Config>::WeightInfo::close_loan_has_debit_by_dex_by_authorizee())]
+		#[transactional]
+		pub fn close_loan_has_debit_by_dex_by_authorizee(
+			origin: OriginFor<T>,
+			currency_id: CurrencyId,
+			from: <T::Lookup as StaticLookup>::Source,
+			#[pallet::compact] max_collateral_amount: Balance,
+		) -> DispatchResult {
+			let to = ensure_signed(origin)?;
+			let from = T::Lookup::lookup(from)?;
+			ensure!(
+				!T::EmergencyShutdown::is_collateral_shutdown(currency_id),
+				Error::<T>::AlreadyShutdown
+			);
+			Self::check_authorization(&from, &to, currency_id, Permission::CloseByDex)?;
+			<cdp_engine::Pallet<T>>::close_cdp_has_debit_by_dex(from, currency_id, max_collateral_amount)?;
+			Ok(())
+		}
 	}
 }
 
 impl<T: Config> Pallet<T> {
-	/// Check if `from` has the authorization of `to` under `currency_id`
-	fn check_authorization(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+	/// Check if `from` has the authorization of `to` under `currency_id` which permits
+	/// `permission`, return the scope of the authorization if it's limited.
+	fn check_authorization(
+		from: &T::AccountId,
+		to: &T::AccountId,
+		currency_id: CurrencyId,
+		permission: Permission,
+	) -> Result<Option<AuthorizationScope<T::BlockNumber>>, DispatchError> {
+		if from == to {
+			return Ok(None);
+		}
 		ensure!(
-			from == to || Authorization::<T>::contains_key(from, (currency_id, to)),
+			Authorization::<T>::contains_key(from, (currency_id, to)),
 			Error::<T>::NoPermission
 		);
-		Ok(())
+
+		let maybe_scope = Self::authorization_scopes(from, (currency_id, to));
+		if let Some(scope) = maybe_scope {
+			if let Some(expiry) = scope.expiry {
+				ensure!(
+					<frame_system::Pallet<T>>::block_number() <= expiry,
+					Error::<T>::AuthorizationExpired
+				);
+			}
+			ensure!(scope.permits(permission), Error::<T>::NoPermission);
+		}
+		Ok(maybe_scope)
+	}
+
+	/// Reserve the deposit and record the authorization of `to` under `currency_id`
+	fn do_authorize(from: &T::AccountId, to: &T::AccountId, currency_id: CurrencyId) -> DispatchResult {
+		Authorization::<T>::try_mutate_exists(from, (currency_id, to), |maybe_reserved| -> DispatchResult {
+			ensure!(maybe_reserved.is_none(), Error::<T>::AlreadyAuthorized);
+			let reserve_amount = T::DepositPerAuthorization::get();
+			<T as Config>::Currency::reserve_named(&RESERVE_ID, from, reserve_amount)?;
+			*maybe_reserved = Some(reserve_amount);
+			Ok(())
+		})
 	}
 }
@@ -173,6 +173,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_surplus_auction(_amount: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, _fix_debit: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		Ok(())
 	}
@@ -181,6 +189,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		Default::default()
 	}
@@ -226,6 +242,7 @@ impl cdp_treasury::Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -252,6 +269,8 @@ parameter_types! {
 impl cdp_engine::Config for Runtime {
 	type Event = Event;
 	type PriceSource = MockPriceSource;
+	type DEX = ();
+	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
@@ -23,7 +23,7 @@
 use super::*;
 use frame_support::{assert_noop, assert_ok};
 use mock::{Event, *};
-use orml_traits::Change;
+use orml_traits::{Change, MultiCurrency};
 use sp_runtime::FixedPointNumber;
 use support::{Rate, Ratio};
 
@@ -39,7 +39,12 @@ fn authorize_should_work() {
 			authorizee: BOB,
 			collateral_type: BTC,
 		}));
-		assert_ok!(HonzonModule::check_authorization(&ALICE, &BOB, BTC));
+		assert_ok!(HonzonModule::check_authorization(
+			&ALICE,
+			&BOB,
+			BTC,
+			Permission::Transfer
+		));
 		assert_noop!(
 			HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB),
 			Error::<Runtime>::AlreadyAuthorized
@@ -53,7 +58,12 @@ fn unauthorize_should_work() {
 		System::set_block_number(1);
 		assert_ok!(HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB));
 		assert_eq!(PalletBalances::reserved_balance(ALICE), 100);
-		assert_ok!(HonzonModule::check_authorization(&ALICE, &BOB, BTC));
+		assert_ok!(HonzonModule::check_authorization(
+			&ALICE,
+			&BOB,
+			BTC,
+			Permission::Transfer
+		));
 
 		assert_ok!(HonzonModule::unauthorize(Origin::signed(ALICE), BTC, BOB));
 		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
@@ -63,7 +73,7 @@ fn unauthorize_should_work() {
 			collateral_type: BTC,
 		}));
 		assert_noop!(
-			HonzonModule::check_authorization(&ALICE, &BOB, BTC),
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC, Permission::Transfer),
 			Error::<Runtime>::NoPermission
 		);
 		assert_noop!(
@@ -87,13 +97,209 @@ fn unauthorize_all_should_work() {
 		}));
 
 		assert_noop!(
-			HonzonModule::check_authorization(&ALICE, &BOB, BTC),
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC, Permission::Transfer),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, DOT, Permission::Transfer),
+			Error::<Runtime>::NoPermission
+		);
+	});
+}
+
+#[test]
+fn authorize_with_scope_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		let scope = AuthorizationScope {
+			expiry: Some(10),
+			max_collateral: None,
+			max_debit: None,
+			can_adjust: true,
+			can_transfer: false,
+			can_close_by_dex: false,
+		};
+		assert_noop!(
+			HonzonModule::authorize_with_scope(
+				Origin::signed(ALICE),
+				BTC,
+				BOB,
+				AuthorizationScope {
+					expiry: Some(0),
+					..scope
+				}
+			),
+			Error::<Runtime>::InvalidAuthorizationScope
+		);
+
+		assert_ok!(HonzonModule::authorize_with_scope(
+			Origin::signed(ALICE),
+			BTC,
+			BOB,
+			scope
+		));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), DepositPerAuthorization::get());
+		assert_eq!(HonzonModule::authorization_scopes(ALICE, (BTC, BOB)), Some(scope));
+		System::assert_last_event(Event::HonzonModule(crate::Event::ScopedAuthorization {
+			authorizer: ALICE,
+			authorizee: BOB,
+			collateral_type: BTC,
+			scope,
+		}));
+		assert_noop!(
+			HonzonModule::authorize_with_scope(Origin::signed(ALICE), BTC, BOB, scope),
+			Error::<Runtime>::AlreadyAuthorized
+		);
+		assert_noop!(
+			HonzonModule::authorize(Origin::signed(ALICE), BTC, BOB),
+			Error::<Runtime>::AlreadyAuthorized
+		);
+
+		assert_eq!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC, Permission::Adjust),
+			Ok(Some(scope))
+		);
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC, Permission::Transfer),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC, Permission::CloseByDex),
+			Error::<Runtime>::NoPermission
+		);
+		assert_noop!(
+			HonzonModule::transfer_loan_from(Origin::signed(BOB), BTC, ALICE),
+			Error::<Runtime>::NoPermission
+		);
+
+		System::set_block_number(11);
+		assert_noop!(
+			HonzonModule::check_authorization(&ALICE, &BOB, BTC, Permission::Adjust),
+			Error::<Runtime>::AuthorizationExpired
+		);
+
+		assert_ok!(HonzonModule::unauthorize(Origin::signed(ALICE), BTC, BOB));
+		assert_eq!(PalletBalances::reserved_balance(ALICE), 0);
+		assert_eq!(HonzonModule::authorization_scopes(ALICE, (BTC, BOB)), None);
+	});
+}
+
+#[test]
+fn adjust_loan_by_authorizee_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_noop!(
+			HonzonModule::adjust_loan_by_authorizee(Origin::signed(BOB), BTC, ALICE, 100, 50),
+			Error::<Runtime>::NoPermission
+		);
+
+		assert_ok!(HonzonModule::authorize_with_scope(
+			Origin::signed(ALICE),
+			BTC,
+			BOB,
+			AuthorizationScope {
+				expiry: None,
+				max_collateral: Some(200),
+				max_debit: Some(80),
+				can_adjust: true,
+				can_transfer: false,
+				can_close_by_dex: false,
+			}
+		));
+		assert_ok!(HonzonModule::adjust_loan_by_authorizee(
+			Origin::signed(BOB),
+			BTC,
+			ALICE,
+			100,
+			50
+		));
+		System::assert_last_event(Event::HonzonModule(crate::Event::AdjustLoanByAuthorizee {
+			authorizer: ALICE,
+			authorizee: BOB,
+			collateral_type: BTC,
+			collateral_adjustment: 100,
+			debit_adjustment: 50,
+		}));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 100);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 50);
+		assert_eq!(LoansModule::positions(BTC, BOB).collateral, 0);
+		assert_eq!(Currencies::free_balance(BTC, &ALICE), 900);
+		assert_eq!(Currencies::free_balance(BTC, &BOB), 1000);
+
+		assert_noop!(
+			HonzonModule::adjust_loan_by_authorizee(Origin::signed(BOB), BTC, ALICE, 101, 0),
+			Error::<Runtime>::ExceedAuthorizedCollateral
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_authorizee(Origin::signed(BOB), BTC, ALICE, 0, 31),
+			Error::<Runtime>::ExceedAuthorizedDebit
+		);
+		assert_noop!(
+			HonzonModule::close_loan_has_debit_by_dex_by_authorizee(Origin::signed(BOB), BTC, ALICE, 100),
 			Error::<Runtime>::NoPermission
 		);
+
+		// decreasing is always allowed
+		assert_ok!(HonzonModule::adjust_loan_by_authorizee(
+			Origin::signed(BOB),
+			BTC,
+			ALICE,
+			-10,
+			-10
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 90);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 40);
+	});
+}
+
+#[test]
+fn close_loan_has_debit_by_dex_by_authorizee_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		assert_ok!(CDPEngineModule::set_collateral_params(
+			Origin::signed(1),
+			BTC,
+			Change::NewValue(Some(Rate::saturating_from_rational(1, 100000))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(3, 2))),
+			Change::NewValue(Some(Rate::saturating_from_rational(2, 10))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(9, 5))),
+			Change::NewValue(10000),
+		));
+		assert_ok!(HonzonModule::adjust_loan(Origin::signed(ALICE), BTC, 100, 50));
+		assert_ok!(HonzonModule::authorize_with_scope(
+			Origin::signed(ALICE),
+			BTC,
+			BOB,
+			AuthorizationScope {
+				expiry: None,
+				max_collateral: None,
+				max_debit: None,
+				can_adjust: false,
+				can_transfer: false,
+				can_close_by_dex: true,
+			}
+		));
 		assert_noop!(
-			HonzonModule::check_authorization(&ALICE, &BOB, DOT),
+			HonzonModule::adjust_loan_by_authorizee(Origin::signed(BOB), BTC, ALICE, 10, 0),
 			Error::<Runtime>::NoPermission
 		);
+
+		assert_ok!(HonzonModule::close_loan_has_debit_by_dex_by_authorizee(
+			Origin::signed(BOB),
+			BTC,
+			ALICE,
+			100,
+		));
+		assert_eq!(LoansModule::positions(BTC, ALICE).collateral, 0);
+		assert_eq!(LoansModule::positions(BTC, ALICE).debit, 0);
 	});
 }
 
@@ -161,6 +367,38 @@ fn on_emergency_shutdown_should_work() {
 			HonzonModule::close_loan_has_debit_by_dex(Origin::signed(ALICE), BTC, 100),
 			Error::<Runtime>::AlreadyShutdown,
 		);
+		assert_noop!(
+			HonzonModule::expand_position_collateral(Origin::signed(ALICE), BTC, 100, 10),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::shrink_position_debit(Origin::signed(ALICE), BTC, 10, 100),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::adjust_loan_by_authorizee(Origin::signed(BOB), BTC, ALICE, 100, 50),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+		assert_noop!(
+			HonzonModule::close_loan_has_debit_by_dex_by_authorizee(Origin::signed(BOB), BTC, ALICE, 100),
+			Error::<Runtime>::AlreadyShutdown,
+		);
+	});
+}
+
+#[test]
+fn set_cross_collateral_should_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(HonzonModule::set_cross_collateral(Origin::signed(ALICE), true));
+		assert!(LoansModule::is_cross_collateral(ALICE));
+		System::assert_last_event(Event::LoansModule(loans::Event::CrossCollateralUpdated {
+			owner: ALICE,
+			enabled: true,
+		}));
+
+		assert_ok!(HonzonModule::set_cross_collateral(Origin::signed(ALICE), false));
+		assert!(!LoansModule::is_cross_collateral(ALICE));
 	});
 }
 
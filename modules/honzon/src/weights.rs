@@ -53,6 +53,12 @@ pub trait WeightInfo {
 	fn adjust_loan() -> Weight;
 	fn transfer_loan_from() -> Weight;
 	fn close_loan_has_debit_by_dex() -> Weight;
+	fn expand_position_collateral() -> Weight;
+	fn shrink_position_debit() -> Weight;
+	fn authorize_with_scope() -> Weight;
+	fn adjust_loan_by_authorizee() -> Weight;
+	fn close_loan_has_debit_by_dex_by_authorizee() -> Weight;
+	fn set_cross_collateral(c: u32, ) -> Weight;
 }
 
 /// Weights for module_honzon using the Acala node and recommended hardware.
@@ -66,14 +72,14 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 	fn unauthorize() -> Weight {
 		(53_283_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn unauthorize_all(c: u32, ) -> Weight {
 		(31_717_000 as Weight)
 			// Standard Error: 1_420_000
 			.saturating_add((16_494_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
 	}
 	fn adjust_loan() -> Weight {
 		(245_614_000 as Weight)
@@ -90,6 +96,38 @@ impl<T: frame_system::Config> WeightInfo for AcalaWeight<T> {
 			.saturating_add(T::DbWeight::get().reads(32 as Weight))
 			.saturating_add(T::DbWeight::get().writes(14 as Weight))
 	}
+	fn expand_position_collateral() -> Weight {
+		(197_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn shrink_position_debit() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn authorize_with_scope() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn adjust_loan_by_authorizee() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(27 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn close_loan_has_debit_by_dex_by_authorizee() -> Weight {
+		(375_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(34 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	fn set_cross_collateral(c: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
 
 // For backwards compatibility and tests
@@ -102,14 +140,14 @@ impl WeightInfo for () {
 	fn unauthorize() -> Weight {
 		(53_283_000 as Weight)
 			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
-			.saturating_add(RocksDbWeight::get().writes(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
 	}
 	fn unauthorize_all(c: u32, ) -> Weight {
 		(31_717_000 as Weight)
 			// Standard Error: 1_420_000
 			.saturating_add((16_494_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
-			.saturating_add(RocksDbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
 	}
 	fn adjust_loan() -> Weight {
 		(245_614_000 as Weight)
@@ -126,4 +164,36 @@ impl WeightInfo for () {
 			.saturating_add(RocksDbWeight::get().reads(32 as Weight))
 			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
 	}
+	fn expand_position_collateral() -> Weight {
+		(197_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn shrink_position_debit() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(19 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn authorize_with_scope() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(2 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(3 as Weight))
+	}
+	fn adjust_loan_by_authorizee() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(27 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(11 as Weight))
+	}
+	fn close_loan_has_debit_by_dex_by_authorizee() -> Weight {
+		(375_000_000 as Weight)
+			.saturating_add(RocksDbWeight::get().reads(34 as Weight))
+			.saturating_add(RocksDbWeight::get().writes(14 as Weight))
+	}
+	fn set_cross_collateral(c: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(RocksDbWeight::get().reads(1 as Weight))
+			.saturating_add(RocksDbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(RocksDbWeight::get().writes(1 as Weight))
+	}
 }
@@ -22,6 +22,11 @@
 //!
 //! Loans module manages CDP's collateral assets and the debits backed by these
 //! assets.
+//!
+//! The positions are recorded under each collateral type. An owner can opt in the
+//! cross-collateral mode, then the risk manager regards the positions of all collateral types
+//! of the owner as a whole. The debits are still recorded under each collateral type, so they
+//! accrue the interest of the collateral type as before.
 
 #![cfg_attr(not(feature = "std"), no_std)]
 #![allow(clippy::unused_unit)]
@@ -116,6 +121,8 @@ pub mod module {
 			to: T::AccountId,
 			currency_id: CurrencyId,
 		},
+		/// The cross-collateral mode of the owner has been updated.
+		CrossCollateralUpdated { owner: T::AccountId, enabled: bool },
 	}
 
 	/// The collateralized debit positions, map from
@@ -135,6 +142,14 @@ pub mod module {
 	#[pallet::getter(fn total_positions)]
 	pub type TotalPositions<T: Config> = StorageMap<_, Twox64Concat, CurrencyId, Position, ValueQuery>;
 
+	/// Whether the positions of the owner are in cross-collateral mode, map from
+	/// Owner -> bool
+	///
+	/// CrossCollateral: AccountId => bool
+	#[pallet::storage]
+	#[pallet::getter(fn is_cross_collateral)]
+	pub type CrossCollateral<T: Config> = StorageMap<_, Twox64Concat, T::AccountId, bool, ValueQuery>;
+
 	#[pallet::pallet]
 	#[pallet::without_storage_info]
 	pub struct Pallet<T>(_);
@@ -228,6 +243,7 @@ impl<T: Config> Pallet<T> {
 		// ensure pass risk check
 		let Position { collateral, debit } = Self::positions(currency_id, who);
 		T::RiskManager::check_position_valid(
+			who,
 			currency_id,
 			collateral,
 			debit,
@@ -260,7 +276,7 @@ impl<T: Config> Pallet<T> {
 			.expect("existing debit balance cannot overflow; qed");
 
 		// check new position
-		T::RiskManager::check_position_valid(currency_id, new_to_collateral_balance, new_to_debit_balance, true)?;
+		T::RiskManager::check_position_valid(to, currency_id, new_to_collateral_balance, new_to_debit_balance, true)?;
 
 		// balance -> amount
 		let collateral_adjustment = Self::amount_try_from_balance(collateral)?;
@@ -274,6 +290,11 @@ impl<T: Config> Pallet<T> {
 		)?;
 		Self::update_loan(to, currency_id, collateral_adjustment, debit_adjustment)?;
 
+		// the remain positions of `from` must be still valid if regarded as a whole
+		if Self::is_cross_collateral(from) {
+			T::RiskManager::check_position_valid(from, currency_id, Zero::zero(), Zero::zero(), true)?;
+		}
+
 		Self::deposit_event(Event::TransferLoan {
 			from: from.clone(),
 			to: to.clone(),
@@ -282,8 +303,40 @@ impl<T: Config> Pallet<T> {
 		Ok(())
 	}
 
-	/// mutate records of collaterals and debits
-	fn update_loan(
+	/// Enable or disable the cross-collateral mode of `who`. When disabling, each position of
+	/// `who` must be valid on its own.
+	///
+	/// Ensured atomic.
+	#[transactional]
+	pub fn set_cross_collateral(who: &T::AccountId, enabled: bool) -> DispatchResult {
+		if Self::is_cross_collateral(who) == enabled {
+			return Ok(());
+		}
+
+		if enabled {
+			CrossCollateral::<T>::insert(who, true);
+		} else {
+			CrossCollateral::<T>::remove(who);
+
+			// all collateral types which have positions are recorded in `TotalPositions`
+			for currency_id in TotalPositions::<T>::iter_keys() {
+				let Position { collateral, debit } = Self::positions(currency_id, who);
+				if !collateral.is_zero() || !debit.is_zero() {
+					T::RiskManager::check_position_valid(who, currency_id, collateral, debit, true)?;
+				}
+			}
+		}
+
+		Self::deposit_event(Event::CrossCollateralUpdated {
+			owner: who.clone(),
+			enabled,
+		});
+		Ok(())
+	}
+
+	/// mutate records of collaterals and debits, the caller must handle the
+	/// transfer of collateral and the issuance/burning of debit.
+	pub fn update_loan(
 		who: &T::AccountId,
 		currency_id: CurrencyId,
 		collateral_adjustment: Amount,
@@ -375,7 +428,7 @@ impl<T: Config> Pallet<T> {
 
 impl<T: Config> Pallet<T> {
 	/// Convert `Balance` to `Amount`.
-	fn amount_try_from_balance(b: Balance) -> Result<Amount, Error<T>> {
+	pub fn amount_try_from_balance(b: Balance) -> Result<Amount, Error<T>> {
 		TryInto::<Amount>::try_into(b).map_err(|_| Error::<T>::AmountConvertFailed)
 	}
 
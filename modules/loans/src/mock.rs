@@ -36,7 +36,7 @@ use sp_runtime::{
 };
 use sp_std::cell::RefCell;
 use std::collections::HashMap;
-use support::{AuctionManager, RiskManager};
+use support::{AuctionManager, EmergencyShutdown, RiskManager};
 
 pub type AccountId = u128;
 pub type AuctionId = u32;
@@ -146,6 +146,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Ok(())
 	}
 
+	fn new_surplus_auction(_amount: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
+	fn new_debit_auction(_initial_amount: Self::Balance, _fix_debit: Self::Balance) -> DispatchResult {
+		Ok(())
+	}
+
 	fn cancel_auction(_id: Self::AuctionId) -> DispatchResult {
 		Ok(())
 	}
@@ -154,6 +162,14 @@ impl AuctionManager<AccountId> for MockAuctionManager {
 		Default::default()
 	}
 
+	fn get_total_surplus_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
+	fn get_total_debit_in_auction() -> Self::Balance {
+		Default::default()
+	}
+
 	fn get_total_collateral_in_auction(_id: Self::CurrencyId) -> Self::Balance {
 		Default::default()
 	}
@@ -171,6 +187,13 @@ parameter_types! {
 	pub AlternativeSwapPathJointList: Vec<Vec<CurrencyId>> = vec![];
 }
 
+pub struct MockEmergencyShutdown;
+impl EmergencyShutdown for MockEmergencyShutdown {
+	fn is_shutdown() -> bool {
+		false
+	}
+}
+
 impl cdp_treasury::Config for Runtime {
 	type Event = Event;
 	type Currency = Currencies;
@@ -182,6 +205,7 @@ impl cdp_treasury::Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = TreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = MockEmergencyShutdown;
 	type WeightInfo = ();
 }
 
@@ -201,6 +225,7 @@ impl RiskManager<AccountId, CurrencyId, Balance, Balance> for MockRiskManager {
 	}
 
 	fn check_position_valid(
+		_who: &AccountId,
 		currency_id: CurrencyId,
 		_collateral_balance: Balance,
 		_debit_balance: Balance,
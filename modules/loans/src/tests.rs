@@ -186,6 +186,38 @@ fn transfer_loan_should_work() {
 	});
 }
 
+#[test]
+fn set_cross_collateral_work() {
+	ExtBuilder::default().build().execute_with(|| {
+		System::set_block_number(1);
+		assert_ok!(LoansModule::update_loan(&ALICE, BTC, 400, 500));
+		assert!(!LoansModule::is_cross_collateral(&ALICE));
+
+		assert_ok!(LoansModule::set_cross_collateral(&ALICE, true));
+		assert!(LoansModule::is_cross_collateral(&ALICE));
+		System::assert_last_event(Event::LoansModule(crate::Event::CrossCollateralUpdated {
+			owner: ALICE,
+			enabled: true,
+		}));
+
+		assert_ok!(LoansModule::set_cross_collateral(&ALICE, false));
+		assert!(!LoansModule::is_cross_collateral(&ALICE));
+		System::assert_last_event(Event::LoansModule(crate::Event::CrossCollateralUpdated {
+			owner: ALICE,
+			enabled: false,
+		}));
+
+		// the DOT position is invalid on its own
+		assert_ok!(LoansModule::set_cross_collateral(&ALICE, true));
+		assert_ok!(LoansModule::update_loan(&ALICE, DOT, 100, 100));
+		assert_noop!(
+			LoansModule::set_cross_collateral(&ALICE, false),
+			sp_runtime::DispatchError::Other("mock below required collateral ratio error")
+		);
+		assert!(LoansModule::is_cross_collateral(&ALICE));
+	});
+}
+
 #[test]
 fn confiscate_collateral_and_debit_work() {
 	ExtBuilder::default().build().execute_with(|| {
@@ -24,7 +24,7 @@ use frame_support::pallet_prelude::{DispatchClass, Pays, Weight};
 use primitives::{
 	evm::{CallInfo, EvmAddress},
 	task::TaskResult,
-	CurrencyId,
+	Balance, CurrencyId,
 };
 use scale_info::TypeInfo;
 use sp_core::H160;
@@ -52,6 +52,7 @@ pub trait RiskManager<AccountId, CurrencyId, Balance, DebitBalance> {
 	fn get_bad_debt_value(currency_id: CurrencyId, debit_balance: DebitBalance) -> Balance;
 
 	fn check_position_valid(
+		who: &AccountId,
 		currency_id: CurrencyId,
 		collateral_balance: Balance,
 		debit_balance: DebitBalance,
@@ -70,6 +71,7 @@ impl<AccountId, CurrencyId, Balance: Default, DebitBalance> RiskManager<AccountI
 	}
 
 	fn check_position_valid(
+		_who: &AccountId,
 		_currency_id: CurrencyId,
 		_collateral_balance: Balance,
 		_debit_balance: DebitBalance,
@@ -94,9 +96,13 @@ pub trait AuctionManager<AccountId> {
 		amount: Self::Balance,
 		target: Self::Balance,
 	) -> DispatchResult;
+	fn new_surplus_auction(amount: Self::Balance) -> DispatchResult;
+	fn new_debit_auction(initial_amount: Self::Balance, fix_debit: Self::Balance) -> DispatchResult;
 	fn cancel_auction(id: Self::AuctionId) -> DispatchResult;
 	fn get_total_collateral_in_auction(id: Self::CurrencyId) -> Self::Balance;
 	fn get_total_target_in_auction() -> Self::Balance;
+	fn get_total_surplus_in_auction() -> Self::Balance;
+	fn get_total_debit_in_auction() -> Self::Balance;
 }
 
 #[derive(RuntimeDebug, Encode, Decode, Clone, Copy, PartialEq, TypeInfo)]
@@ -207,6 +213,55 @@ where
 	}
 }
 
+/// Callback of DEX flash swap. It's called after the borrowed currencies have been sent to
+/// `who`, the repayment will be taken from `who` once the callback returns.
+pub trait DEXFlashSwapCallback<AccountId, CurrencyId, Balance> {
+	/// The max gas the callback can use.
+	fn gas_limit() -> u64;
+
+	/// The weight of the gas used by the callback.
+	fn gas_to_weight(gas: u64) -> Weight;
+
+	/// Returns the gas used by the callback.
+	#[allow(clippy::too_many_arguments)]
+	fn on_flash_swap(
+		who: &AccountId,
+		callee: EvmAddress,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+		repay_a: Balance,
+		repay_b: Balance,
+		data: Vec<u8>,
+	) -> Result<u64, DispatchError>;
+}
+
+#[cfg(feature = "std")]
+impl<AccountId, CurrencyId, Balance> DEXFlashSwapCallback<AccountId, CurrencyId, Balance> for () {
+	fn gas_limit() -> u64 {
+		0
+	}
+
+	fn gas_to_weight(_gas: u64) -> Weight {
+		0
+	}
+
+	fn on_flash_swap(
+		_who: &AccountId,
+		_callee: EvmAddress,
+		_currency_id_a: CurrencyId,
+		_currency_id_b: CurrencyId,
+		_amount_a: Balance,
+		_amount_b: Balance,
+		_repay_a: Balance,
+		_repay_b: Balance,
+		_data: Vec<u8>,
+	) -> Result<u64, DispatchError> {
+		Ok(0)
+	}
+}
+
 /// An abstraction of cdp treasury for Honzon Protocol.
 pub trait CDPTreasury<AccountId> {
 	type Balance;
@@ -241,6 +296,9 @@ pub trait CDPTreasury<AccountId> {
 	/// deposit surplus(stable currency) to cdp treasury by `from`
 	fn deposit_surplus(from: &AccountId, surplus: Self::Balance) -> DispatchResult;
 
+	/// withdraw surplus(stable currency) of cdp treasury to `to`
+	fn withdraw_surplus(to: &AccountId, surplus: Self::Balance) -> DispatchResult;
+
 	/// deposit collateral assets to cdp treasury by `who`
 	fn deposit_collateral(from: &AccountId, currency_id: Self::CurrencyId, amount: Self::Balance) -> DispatchResult;
 
@@ -292,6 +350,16 @@ pub trait ExchangeRateProvider {
 
 pub trait EmergencyShutdown {
 	fn is_shutdown() -> bool;
+
+	/// Whether the collateral type has been shut down, alone or by the
+	/// shutdown of the whole system.
+	fn is_collateral_shutdown(_currency_id: CurrencyId) -> bool {
+		Self::is_shutdown()
+	}
+
+	/// Record the collateral confiscated by the settlement of a CDP of the
+	/// shutdown collateral type.
+	fn on_collateral_settled(_currency_id: CurrencyId, _amount: Balance) {}
 }
 
 pub trait DEXIncentives<AccountId, CurrencyId, Balance> {
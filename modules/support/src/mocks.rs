@@ -18,6 +18,8 @@
 
 use crate::{AddressMapping, CurrencyId, Erc20InfoMapping};
 use codec::Encode;
+use core::time::Duration;
+use frame_support::traits::UnixTime;
 use primitives::{
 	currency::TokenInfo,
 	evm::{EvmAddress, H160_POSITION_TOKEN},
@@ -91,3 +93,12 @@ impl Erc20InfoMapping for MockErc20InfoMapping {
 			.ok()?
 	}
 }
+
+/// A mock `UnixTime` which always returns the unix epoch.
+pub struct MockUnixTime;
+
+impl UnixTime for MockUnixTime {
+	fn now() -> Duration {
+		Duration::default()
+	}
+}
@@ -39,7 +39,10 @@ use sp_runtime::{
 	Perbill,
 };
 use sp_std::cell::RefCell;
-use support::{mocks::MockAddressMapping, Price};
+use support::{
+	mocks::{MockAddressMapping, MockUnixTime},
+	Price,
+};
 
 pub type AccountId = AccountId32;
 pub type BlockNumber = u64;
@@ -167,6 +170,7 @@ parameter_types! {
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 	pub const GetExchangeFee: (u32, u32) = (0, 100);
 	pub const TradingPathLimit: u32 = 4;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2000;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::from_currency_ids(AUSD, ACA).unwrap(),
 		TradingPair::from_currency_ids(AUSD, DOT).unwrap(),
@@ -178,12 +182,17 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type MaxRouteTokens = frame_support::traits::ConstU32<3>;
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = ();
 	type DEXIncentives = ();
 	type WeightInfo = ();
 	type ListingOrigin = frame_system::EnsureSignedBy<Zero, AccountId>;
 	type OnLiquidityPoolUpdated = ();
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type TreasuryAccount = KaruraTreasuryAccount;
+	type FlashSwapCallback = ();
+	type UnixTime = MockUnixTime;
 }
 
 parameter_types! {
@@ -207,6 +207,7 @@ pub async fn start_dev_node(
 	// Channel for the rpc handler to communicate with the authorship task.
 	let (command_sink, commands_stream) = mpsc::channel(10);
 	let rpc_sink = command_sink.clone();
+	let rpc_client = client.clone();
 
 	match seal_mode {
 		SealMode::DevInstantSeal => {
@@ -309,9 +310,13 @@ pub async fn start_dev_node(
 		task_manager: &mut task_manager,
 		keystore: keystore_container.sync_keystore(),
 		transaction_pool: transaction_pool.clone(),
-		rpc_extensions_builder: Box::new(move |_, _| {
+		rpc_extensions_builder: Box::new(move |deny_unsafe, _| {
 			let mut io = jsonrpc_core::IoHandler::default();
 			io.extend_with(ManualSealApi::to_delegate(ManualSeal::new(rpc_sink.clone())));
+			io.extend_with(EVMApiServer::to_delegate(EVMApi::<Block, _, Balance>::new(
+				rpc_client.clone(),
+				deny_unsafe,
+			)));
 			Ok(io)
 		}),
 		network: network.clone(),
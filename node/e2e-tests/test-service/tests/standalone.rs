@@ -130,3 +130,37 @@ async fn transaction_pool_priority_order_test() {
 	assert!(tx1.priority > tx2.priority);
 	assert!(tx2.priority > tx3.priority);
 }
+
+#[substrate_test_utils::test]
+#[ignore]
+async fn evm_debug_trace_call_rpc_test() {
+	let para_id = ParaId::from(2000);
+	let tokio_handle = tokio::runtime::Handle::current();
+
+	let node = test_service::TestNodeBuilder::new(para_id, tokio_handle.clone(), Alice)
+		.with_seal_mode(SealMode::DevInstantSeal)
+		.build()
+		.await;
+
+	let (sink, _) = futures::channel::mpsc::unbounded();
+	let session = sc_service::RpcSession::new(sink);
+
+	// trace the call to the ECRecover precompile
+	let response = node
+		.rpc_handlers
+		.rpc_query(
+			&session,
+			r#"{"jsonrpc":"2.0","id":1,"method":"debug_traceCall","params":[{"to":"0x0000000000000000000000000000000000000001","gasLimit":100000,"storageLimit":0},null,{"tracer":"callTracer"}]}"#,
+		)
+		.await
+		.unwrap();
+
+	#[cfg(feature = "tracing")]
+	assert!(
+		response.contains(r#""type":"CALL","from":"0x0000000000000000000000000000000000000000","to":"0x0000000000000000000000000000000000000001""#),
+		"{}",
+		response
+	);
+	#[cfg(not(feature = "tracing"))]
+	assert!(response.contains("EVM tracing is not enabled"), "{}", response);
+}
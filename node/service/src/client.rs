@@ -18,7 +18,9 @@
 
 //! Acala Client abstractions.
 
-use acala_primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Nonce};
+use acala_primitives::{
+	AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Header, Moment, Nonce,
+};
 use runtime_common::TimeStampedPrice;
 use sc_client_api::{Backend as BackendT, BlockchainEvents, KeyIterator};
 use sp_api::{CallApiAt, NumberFor, ProvideRuntimeApi};
@@ -42,6 +44,11 @@ pub trait RuntimeApiCollection:
 	+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 	+ orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>
 	+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+	+ module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance>
+	+ module_evm_rpc_runtime_api::EthRuntimeRPCApi<Block>
+	+ module_dex_rpc_runtime_api::DexRuntimeApi<Block, Balance, BlockNumber>
+	+ module_dex_oracle_rpc_runtime_api::DexOracleRuntimeApi<Block, Moment>
+	+ module_cdp_engine_rpc_runtime_api::CdpEngineRuntimeApi<Block, AccountId>
 	+ sp_api::Metadata<Block>
 	+ sp_offchain::OffchainWorkerApi<Block>
 	+ sp_session::SessionKeys<Block>
@@ -61,6 +68,12 @@ where
 		+ orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, TimeStampedPrice>
 		+ orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>
 		+ module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance>
+		+ module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance>
+		+ module_evm_rpc_runtime_api::EthRuntimeRPCApi<Block>
+		+ module_dex_rpc_runtime_api::DexRuntimeApi<Block, Balance, BlockNumber>
+		+ module_dex_oracle_rpc_runtime_api::DexOracleRuntimeApi<Block, Moment>
+		+ module_cdp_engine_rpc_runtime_api::CdpEngineRuntimeApi<Block, AccountId>
+		+ module_cdp_engine_rpc_runtime_api::CdpEngineRuntimeApi<Block, AccountId>
 		+ sp_api::Metadata<Block>
 		+ sp_offchain::OffchainWorkerApi<Block>
 		+ sp_session::SessionKeys<Block>
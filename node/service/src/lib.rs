@@ -589,6 +589,15 @@ pub const KARURA_RUNTIME_NOT_AVAILABLE: &str =
 pub const ACALA_RUNTIME_NOT_AVAILABLE: &str =
 	"Acala runtime is not available. Please compile the node with `--features with-acala-runtime` to enable it.";
 
+/// Execute the runtime calls of RPC by the native runtime. Only the native runtime is built with
+/// the `tracing` feature which collects the EVM traces of the debug RPC, so the node must run the
+/// same runtime version as the chain to serve them.
+#[cfg(feature = "tracing")]
+pub fn with_native_tracing(mut config: Configuration) -> Configuration {
+	config.execution_strategies.other = sc_client_api::ExecutionStrategy::NativeWhenPossible;
+	config
+}
+
 /// Builds a new object suitable for chain operations.
 pub fn new_chain_ops(
 	mut config: &mut Configuration,
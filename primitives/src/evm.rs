@@ -22,6 +22,7 @@ use crate::{
 };
 use codec::{Decode, Encode};
 use core::ops::Range;
+pub use module_evm_utiltity::ethereum::AccessListItem;
 use module_evm_utiltity::{
 	ethereum::{Log, TransactionAction},
 	evm::ExitReason,
@@ -50,6 +51,8 @@ pub struct Vicinity {
 	pub block_gas_limit: Option<U256>,
 	/// Environmental block difficulty. Used only for testing
 	pub block_difficulty: Option<U256>,
+	/// Environmental base fee per gas. `None` if the London hard fork is not enabled.
+	pub block_base_fee_per_gas: Option<U256>,
 }
 
 #[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
@@ -95,6 +98,109 @@ pub struct EthereumTransactionMessage {
 	pub value: Balance,
 	pub input: Vec<u8>,
 	pub valid_until: BlockNumber,
+	pub access_list: Vec<AccessListItem>,
+}
+
+/// The tracer used to trace an EVM execution.
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TracerConfig {
+	/// Trace the nested calls, same as the `callTracer` of Geth.
+	CallTracer,
+	/// Trace every executed opcode, same as the default struct logger of Geth.
+	OpcodeTracer {
+		disable_stack: bool,
+		enable_memory: bool,
+		disable_storage: bool,
+	},
+}
+
+#[derive(Clone, Copy, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum CallType {
+	Call,
+	CallCode,
+	DelegateCall,
+	StaticCall,
+	Create,
+	Create2,
+}
+
+/// A call or create frame collected by the `CallTracer`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct CallTrace {
+	pub call_type: CallType,
+	pub from: H160,
+	pub to: H160,
+	pub input: Vec<u8>,
+	pub value: U256,
+	pub gas: u64,
+	pub gas_used: u64,
+	pub output: Vec<u8>,
+	/// `None` if the frame has not exited.
+	pub exit_reason: Option<ExitReason>,
+	pub calls: Vec<CallTrace>,
+}
+
+/// An executed opcode collected by the `OpcodeTracer`.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct Step {
+	pub pc: u64,
+	pub op: u8,
+	pub gas: u64,
+	pub gas_cost: u64,
+	pub depth: u32,
+	pub stack: Option<Vec<H256>>,
+	pub memory: Option<Vec<u8>>,
+	/// The storage slots of the current contract touched so far, only set for `SLOAD` and `SSTORE`.
+	pub storage: Option<Vec<(H256, H256)>>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct OpcodeTrace {
+	pub gas_used: u64,
+	pub failed: bool,
+	pub return_value: Vec<u8>,
+	pub steps: Vec<Step>,
+}
+
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub enum TraceOutcome {
+	Calls(Vec<CallTrace>),
+	Steps(OpcodeTrace),
+}
+
+/// An EVM transaction of a block, as seen by the Ethereum RPC.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthereumTransaction {
+	pub from: EvmAddress,
+	pub nonce: Nonce,
+	pub action: TransactionAction,
+	/// The transferred value in the EVM decimals.
+	pub value: U256,
+	pub gas_limit: u64,
+	pub storage_limit: u32,
+	pub input: Vec<u8>,
+}
+
+/// The receipt of an EVM transaction, collected from the events of the block.
+#[derive(Clone, Eq, PartialEq, Encode, Decode, RuntimeDebug, TypeInfo)]
+#[cfg_attr(feature = "std", derive(Serialize, Deserialize))]
+pub struct EthereumReceipt {
+	/// The index of the extrinsic in the block.
+	pub extrinsic_index: u32,
+	pub from: EvmAddress,
+	/// The called contract, or the created contract if `is_create`.
+	pub contract: EvmAddress,
+	pub is_create: bool,
+	pub succeed: bool,
+	pub used_gas: u64,
+	pub logs: Vec<Log>,
 }
 
 /// Ethereum precompiles
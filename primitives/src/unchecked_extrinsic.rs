@@ -16,14 +16,16 @@
 // You should have received a copy of the GNU General Public License
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
-use crate::{evm::EthereumTransactionMessage, signature::AcalaMultiSignature, to_bytes, Address, Balance};
+use crate::{evm::EthereumTransactionMessage, signature::AcalaMultiSignature, to_bytes, Address, Balance, BlockNumber};
 use codec::{Decode, Encode};
 use frame_support::{
 	log,
 	traits::{ExtrinsicCall, Get},
 	weights::{DispatchInfo, GetDispatchInfo},
 };
-use module_evm_utiltity::ethereum::{EIP1559TransactionMessage, LegacyTransactionMessage, TransactionAction};
+use module_evm_utiltity::ethereum::{
+	EIP1559TransactionMessage, EnvelopedDecodable, LegacyTransactionMessage, TransactionAction, TransactionV2,
+};
 use module_evm_utiltity_macro::keccak256;
 use scale_info::TypeInfo;
 use sp_core::{H160, H256};
@@ -117,6 +119,11 @@ where
 					return Err(InvalidTransaction::BadProof.into());
 				}
 
+				if !eth_msg.access_list.is_empty() {
+					// The legacy transaction doesn't sign the access list
+					return Err(InvalidTransaction::BadProof.into());
+				}
+
 				let (tx_gas_price, tx_gas_limit) =
 					recover_sign_data(&eth_msg, TxFeePerGas::get(), StorageDepositPerByte::get())
 						.ok_or(InvalidTransaction::BadProof)?;
@@ -176,7 +183,7 @@ where
 					action: eth_msg.action,
 					value: eth_msg.value.into(),
 					input: eth_msg.input,
-					access_list: vec![],
+					access_list: eth_msg.access_list,
 				};
 
 				let msg_hash = msg.hash(); // TODO: consider rewirte this to use `keccak_256` for hashing because it could be faster
@@ -200,6 +207,11 @@ where
 
 				let (eth_msg, eth_extra) = ConvertTx::convert((function.clone(), extra))?;
 
+				if !eth_msg.access_list.is_empty() {
+					// The EIP-712 transaction doesn't sign the access list
+					return Err(InvalidTransaction::BadProof.into());
+				}
+
 				let signer = verify_eip712_signature(eth_msg, sig).ok_or(InvalidTransaction::BadProof)?;
 
 				let account_id = lookup.lookup(Address::Address20(signer.into()))?;
@@ -330,6 +342,118 @@ fn recover_sign_data(
 	Some((tx_gas_price, tx_gas_limit))
 }
 
+/// The reverse of `recover_sign_data`, returns the `gas_limit`, the `storage_limit` and the
+/// `valid_until` encoded in the gas price and the gas limit of an Ethereum transaction.
+fn decode_sign_data(
+	tx_gas_price: u128,
+	tx_gas_limit: u128,
+	ts_fee_per_gas: u128,
+	storage_deposit_per_byte: u128,
+) -> Option<(u64, u32, BlockNumber)> {
+	let extra = tx_gas_price.checked_sub(ts_fee_per_gas)?;
+	let storage_entry_limit = extra & 0xffff;
+	let block_period: u32 = (extra >> 16).try_into().ok()?;
+	let storage_entry_deposit = storage_deposit_per_byte.saturating_mul(64);
+	let gas_limit = tx_gas_limit.checked_sub(
+		storage_entry_deposit
+			.checked_div(ts_fee_per_gas)?
+			.checked_mul(storage_entry_limit)?,
+	)?;
+
+	Some((
+		gas_limit.try_into().ok()?,
+		(storage_entry_limit as u32).checked_mul(64)?,
+		block_period.checked_mul(30)?,
+	))
+}
+
+/// Decode a raw signed Ethereum transaction, returns the signer, the signature and the message
+/// to build an `eth_call` extrinsic.
+///
+/// The `genesis` of the message is not signed by an Ethereum transaction and is left empty.
+/// The EIP-2930 transactions are not supported.
+pub fn decode_ethereum_transaction(
+	transaction: &[u8],
+	ts_fee_per_gas: u128,
+	storage_deposit_per_byte: u128,
+) -> Option<(H160, AcalaMultiSignature, EthereumTransactionMessage)> {
+	let mut sig = [0u8; 65];
+
+	match <TransactionV2 as EnvelopedDecodable>::decode(transaction).ok()? {
+		TransactionV2::Legacy(tx) => {
+			sig[0..32].copy_from_slice(tx.signature.r().as_bytes());
+			sig[32..64].copy_from_slice(tx.signature.s().as_bytes());
+			sig[64] = tx.signature.standard_v();
+
+			let chain_id = tx.signature.chain_id()?;
+			let (gas_limit, storage_limit, valid_until) = decode_sign_data(
+				tx.gas_price.try_into().ok()?,
+				tx.gas_limit.try_into().ok()?,
+				ts_fee_per_gas,
+				storage_deposit_per_byte,
+			)?;
+
+			let msg_hash = LegacyTransactionMessage::from(tx.clone()).hash();
+			let signer = recover_signer(&sig, msg_hash.as_fixed_bytes())?;
+
+			Some((
+				signer,
+				AcalaMultiSignature::Ethereum(sig),
+				EthereumTransactionMessage {
+					chain_id,
+					genesis: Default::default(),
+					nonce: tx.nonce.try_into().ok()?,
+					tip: 0,
+					gas_limit,
+					storage_limit,
+					action: tx.action,
+					value: tx.value.try_into().ok()?,
+					input: tx.input,
+					valid_until,
+					access_list: Vec::new(),
+				},
+			))
+		}
+		TransactionV2::EIP1559(tx) => {
+			sig[0..32].copy_from_slice(tx.r.as_bytes());
+			sig[32..64].copy_from_slice(tx.s.as_bytes());
+			sig[64] = tx.odd_y_parity as u8;
+
+			let (gas_limit, storage_limit, valid_until) = decode_sign_data(
+				tx.max_fee_per_gas.try_into().ok()?,
+				tx.gas_limit.try_into().ok()?,
+				ts_fee_per_gas,
+				storage_deposit_per_byte,
+			)?;
+			// tip = priority_fee * gas_limit
+			let priority_fee: Balance = tx.max_priority_fee_per_gas.try_into().ok()?;
+			let tip = priority_fee.checked_mul(gas_limit.into())?;
+
+			let msg_hash = EIP1559TransactionMessage::from(tx.clone()).hash();
+			let signer = recover_signer(&sig, msg_hash.as_fixed_bytes())?;
+
+			Some((
+				signer,
+				AcalaMultiSignature::Eip1559(sig),
+				EthereumTransactionMessage {
+					chain_id: tx.chain_id,
+					genesis: Default::default(),
+					nonce: tx.nonce.try_into().ok()?,
+					tip,
+					gas_limit,
+					storage_limit,
+					action: tx.action,
+					value: tx.value.try_into().ok()?,
+					input: tx.input,
+					valid_until,
+					access_list: tx.access_list,
+				},
+			))
+		}
+		TransactionV2::EIP2930(_) => None,
+	}
+}
+
 #[cfg(test)]
 mod tests {
 	use super::*;
@@ -351,6 +475,7 @@ mod tests {
 			value: 111,
 			input: vec![],
 			valid_until: 444,
+			access_list: vec![],
 		};
 		let sign = hex!("acb56f12b407bd0bc8f7abefe2e2585affe28009abcb6980aa33aecb815c56b324ab60a41eff339a88631c4b0e5183427be1fcfde3c05fb9b6c71a691e977c4a1b");
 		let sender = Some(H160::from_str("0x14791697260E4c9A71f18484C9f997B308e59325").unwrap());
@@ -516,6 +641,7 @@ mod tests {
 			value: 0,
 			input: vec![],
 			valid_until: 30,
+			access_list: vec![],
 		};
 
 		let ts_fee_per_gas = 200u128.saturating_mul(10u128.saturating_pow(9)) & !0xffff;
@@ -555,4 +681,84 @@ mod tests {
 
 		assert_eq!(recover_sign_data(&msg, u128::MAX, u128::MAX), None);
 	}
+
+	#[test]
+	fn decode_sign_data_should_works() {
+		let ts_fee_per_gas = 200u128.saturating_mul(10u128.saturating_pow(9)) & !0xffff;
+		let storage_deposit_per_byte = 100_000_000_000_000u128;
+
+		assert_eq!(
+			decode_sign_data(200000013288, 34100000, ts_fee_per_gas, storage_deposit_per_byte),
+			Some((2100000, 64000, 30))
+		);
+		assert_eq!(
+			decode_sign_data(207864333288, 34100000, ts_fee_per_gas, storage_deposit_per_byte),
+			Some((2100000, 64000, 3600030))
+		);
+		// valid_until is rounded down to the block period
+		assert_eq!(
+			decode_sign_data(9582499136488, 34100000, ts_fee_per_gas, storage_deposit_per_byte),
+			Some((2100000, 64000, 4294967280))
+		);
+		assert_eq!(
+			decode_sign_data(9582499201023, 2099220000, ts_fee_per_gas, storage_deposit_per_byte),
+			Some((2100000, 0xffff * 64, 4294967280))
+		);
+
+		// the gas price is less than the fee per gas
+		assert_eq!(
+			decode_sign_data(ts_fee_per_gas - 1, 34100000, ts_fee_per_gas, storage_deposit_per_byte),
+			None
+		);
+		// the gas limit doesn't cover the storage
+		assert_eq!(
+			decode_sign_data(200000013288, 2100000, ts_fee_per_gas, storage_deposit_per_byte),
+			None
+		);
+	}
+
+	#[test]
+	fn decode_ethereum_transaction_should_works() {
+		let sender = H160::from_str("0x14791697260E4c9A71f18484C9f997B308e59325").unwrap();
+		let ts_fee_per_gas = 0x6400000000;
+
+		// the signed message of `verify_eth_should_works`
+		let tx = hex!("f8690185640000006a8252089411111111112222222222333333333344444444448301e0f3808204caa0f84345a6459785986a1b2df711fe02597d70c1393757a243f8f924ea541d2ecba051476de1aa437cd820d59e1d9836e37e643fec711fe419464e637cab59291875");
+		let mut sig = hex!("f84345a6459785986a1b2df711fe02597d70c1393757a243f8f924ea541d2ecb51476de1aa437cd820d59e1d9836e37e643fec711fe419464e637cab592918751c");
+		sig[64] = 1;
+		let msg = EthereumTransactionMessage {
+			chain_id: 595,
+			genesis: Default::default(),
+			nonce: 1,
+			tip: 0,
+			gas_limit: 21000,
+			storage_limit: 0x6a * 64,
+			action: TransactionAction::Call(H160::from_str("0x1111111111222222222233333333334444444444").unwrap()),
+			value: 123123,
+			input: vec![],
+			valid_until: 0,
+			access_list: vec![],
+		};
+		assert_eq!(
+			decode_ethereum_transaction(&tx, ts_fee_per_gas, 0),
+			Some((sender, AcalaMultiSignature::Ethereum(sig), msg.clone()))
+		);
+
+		// the signed message of `verify_eth_1559_should_works`
+		let tx = hex!("02f86c820253010185640000006a8252089411111111112222222222333333333344444444448301e0f380c080a0e88df53d4d66cb7a4f54ea44a44942b9b7f4fb4951525d416d3f7d24755a1f81a07734270872b103ac04c59d74f4dacdb8a6eff09a6638bd95dad1fa3eda921d89");
+		let mut sig = hex!("e88df53d4d66cb7a4f54ea44a44942b9b7f4fb4951525d416d3f7d24755a1f817734270872b103ac04c59d74f4dacdb8a6eff09a6638bd95dad1fa3eda921d891b");
+		sig[64] = 0;
+		assert_eq!(
+			decode_ethereum_transaction(&tx, ts_fee_per_gas, 0),
+			Some((
+				sender,
+				AcalaMultiSignature::Eip1559(sig),
+				EthereumTransactionMessage { tip: 21000, ..msg }
+			))
+		);
+
+		// the gas price is less than the fee per gas
+		assert_eq!(decode_ethereum_transaction(&tx, ts_fee_per_gas + 0x6b, 0), None);
+		assert_eq!(decode_ethereum_transaction(&tx[1..], ts_fee_per_gas, 0), None);
+	}
 }
@@ -20,7 +20,8 @@
 
 #![warn(missing_docs)]
 
-use primitives::{AccountId, Balance, Block, CurrencyId, DataProviderId, Hash, Nonce};
+use primitives::{AccountId, Balance, Block, BlockNumber, CurrencyId, DataProviderId, Hash, Nonce};
+use sc_client_api::BlockBackend;
 use sc_consensus_manual_seal::rpc::{EngineCommand, ManualSeal, ManualSealApi};
 pub use sc_rpc_api::DenyUnsafe;
 use sc_transaction_pool_api::TransactionPool;
@@ -31,7 +32,9 @@ use std::sync::Arc;
 
 pub use sc_rpc::SubscriptionTaskExecutor;
 
-pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi};
+pub use cdp_engine_rpc::{CdpEngine, CdpEngineApi, CdpEngineRuntimeApi};
+pub use dex_rpc::{Dex, DexApi, DexRuntimeApi};
+pub use evm_rpc::{EVMApi, EVMApiServer, EVMRuntimeRPCApi, EVMTraceApi, EthApi, EthApiServer, EthRuntimeRPCApi};
 
 /// A type representing all RPC extensions.
 pub type RpcExtension = jsonrpc_core::IoHandler<sc_rpc::Metadata>;
@@ -53,14 +56,19 @@ pub fn create_full<C, P>(deps: FullDeps<C, P>) -> RpcExtension
 where
 	C: ProvideRuntimeApi<Block>,
 	C: HeaderBackend<Block> + HeaderMetadata<Block, Error = BlockChainError>,
+	C: BlockBackend<Block>,
 	C: Send + Sync + 'static,
 	C::Api: substrate_frame_rpc_system::AccountNonceApi<Block, AccountId, Nonce>,
 	C::Api: pallet_transaction_payment_rpc::TransactionPaymentRuntimeApi<Block, Balance>,
 	C::Api: orml_oracle_rpc::OracleRuntimeApi<Block, DataProviderId, CurrencyId, runtime_common::TimeStampedPrice>,
 	C::Api: orml_tokens_rpc::TokensRuntimeApi<Block, CurrencyId, Balance>,
 	C::Api: EVMRuntimeRPCApi<Block, Balance>,
+	C::Api: EVMTraceApi<Block, Balance>,
+	C::Api: EthRuntimeRPCApi<Block>,
+	C::Api: DexRuntimeApi<Block, Balance, BlockNumber>,
+	C::Api: CdpEngineRuntimeApi<Block, AccountId>,
 	C::Api: BlockBuilder<Block>,
-	P: TransactionPool + Sync + Send + 'static,
+	P: TransactionPool<Block = Block, Hash = Hash> + Sync + Send + 'static,
 {
 	use orml_oracle_rpc::{Oracle, OracleApi};
 	use orml_tokens_rpc::{Tokens, TokensApi};
@@ -77,7 +85,7 @@ where
 
 	io.extend_with(SystemApi::to_delegate(FullSystem::new(
 		client.clone(),
-		pool,
+		pool.clone(),
 		deny_unsafe,
 	)));
 	io.extend_with(TransactionPaymentApi::to_delegate(TransactionPayment::new(
@@ -88,7 +96,10 @@ where
 	// These RPCs should use an asynchronous caller instead.
 	io.extend_with(OracleApi::to_delegate(Oracle::new(client.clone())));
 	io.extend_with(TokensApi::to_delegate(Tokens::new(client.clone())));
-	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client, deny_unsafe)));
+	io.extend_with(DexApi::to_delegate(Dex::new(client.clone())));
+	io.extend_with(CdpEngineApi::to_delegate(CdpEngine::new(client.clone())));
+	io.extend_with(EVMApiServer::to_delegate(EVMApi::new(client.clone(), deny_unsafe)));
+	io.extend_with(EthApiServer::to_delegate(EthApi::new(client, pool)));
 
 	if let Some(command_sink) = command_sink {
 		io.extend_with(
@@ -155,6 +155,7 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 	}
 	// Storage: Dex TradingPairStatuses (r:1 w:0)
 	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ExchangeFees (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Tokens Accounts (r:2 w:2)
 	fn swap_with_exact_supply(u: u32, ) -> Weight {
@@ -162,12 +163,13 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			// Standard Error: 327_000
 			.saturating_add((15_255_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
 	// Storage: Dex TradingPairStatuses (r:1 w:0)
 	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ExchangeFees (r:1 w:0)
 	// Storage: System Account (r:1 w:1)
 	// Storage: Tokens Accounts (r:2 w:2)
 	fn swap_with_exact_target(u: u32, ) -> Weight {
@@ -175,8 +177,93 @@ impl<T: frame_system::Config> module_dex::WeightInfo for WeightInfo<T> {
 			// Standard Error: 340_000
 			.saturating_add((15_423_000 as Weight).saturating_mul(u as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
 			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
 	}
+	// Storage: Dex TradingPairStatuses (r:1 w:1)
+	// Storage: Dex ProvisioningPool (r:1 w:0)
+	// Storage: Dex AbortedProvisioning (r:0 w:1)
+	fn abort_provisioning() -> Weight {
+		(23_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex AbortedProvisioning (r:1 w:1)
+	// Storage: Dex ProvisioningPool (r:2 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn refund_provision() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(6 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
+	}
+	// Storage: Dex ExchangeFees (r:0 w:1)
+	fn set_exchange_fee() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dex ProtocolFeeShare (r:0 w:1)
+	fn set_protocol_fee_share() -> Weight {
+		(11_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Dex ProtocolFeePool (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	fn withdraw_protocol_fee() -> Weight {
+		(76_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex FlashSwapLocks (r:1 w:1)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ExchangeFees (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:1 w:1)
+	fn flash_swap() -> Weight {
+		(182_563_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(14 as Weight))
+			.saturating_add(T::DbWeight::get().writes(12 as Weight))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ExchangeFees (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn swap_with_exact_supply_to(u: u32, ) -> Weight {
+		(79_991_000 as Weight)
+			// Standard Error: 327_000
+			.saturating_add((15_255_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ExchangeFees (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: Timestamp Now (r:1 w:0)
+	fn swap_with_exact_target_to(u: u32, ) -> Weight {
+		(80_377_000 as Weight)
+			// Standard Error: 340_000
+			.saturating_add((15_423_000 as Weight).saturating_mul(u as Weight))
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads((3 as Weight).saturating_mul(u as Weight)))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes((1 as Weight).saturating_mul(u as Weight)))
+	}
+	fn set_curve() -> Weight {
+		(13_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn set_route_tokens() -> Weight {
+		(12_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
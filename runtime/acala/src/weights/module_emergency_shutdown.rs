@@ -68,12 +68,14 @@ impl<T: frame_system::Config> module_emergency_shutdown::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
 	}
 	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: AuctionManager TotalSurplusInAuction (r:1 w:0)
+	// Storage: AuctionManager TotalDebitInAuction (r:1 w:0)
 	// Storage: AuctionManager TotalCollateralInAuction (r:3 w:0)
 	// Storage: Loans TotalPositions (r:3 w:0)
 	// Storage: EmergencyShutdown CanRefund (r:0 w:1)
 	fn open_collateral_refund() -> Weight {
 		(38_796_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(7 as Weight))
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
 			.saturating_add(T::DbWeight::get().writes(1 as Weight))
 	}
 	// Storage: EmergencyShutdown CanRefund (r:1 w:0)
@@ -92,4 +94,43 @@ impl<T: frame_system::Config> module_emergency_shutdown::WeightInfo for WeightIn
 			.saturating_add(T::DbWeight::get().writes(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
 	}
+	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: EmergencyShutdown IsCollateralShutdown (r:1 w:1)
+	// Storage: Prices LockedPrice (r:0 w:1)
+	// Storage: AcalaOracle Values (r:1 w:0)
+	fn emergency_shutdown_collateral() -> Weight {
+		(38_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+	}
+	// Storage: EmergencyShutdown IsCollateralShutdown (r:1 w:0)
+	// Storage: AuctionManager TotalSurplusInAuction (r:1 w:0)
+	// Storage: AuctionManager TotalDebitInAuction (r:1 w:0)
+	// Storage: AuctionManager TotalCollateralInAuction (r:1 w:0)
+	// Storage: Loans TotalPositions (r:1 w:0)
+	// Storage: EmergencyShutdown CanRefundCollateral (r:0 w:1)
+	fn open_collateral_specific_refund() -> Weight {
+		(27_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: EmergencyShutdown CanRefundCollateral (r:1 w:0)
+	// Storage: Prices LockedPrice (r:2 w:0)
+	// Storage: EmergencyShutdown RefundableCollaterals (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	fn refund_collateral() -> Weight {
+		(96_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(8 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: EmergencyShutdown IsCollateralShutdown (r:1 w:1)
+	// Storage: Prices LockedPrice (r:0 w:1)
+	// Storage: EmergencyShutdown CanRefundCollateral (r:0 w:1)
+	// Storage: EmergencyShutdown RefundableCollaterals (r:0 w:1)
+	fn close_collateral_shutdown() -> Weight {
+		(32_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 }
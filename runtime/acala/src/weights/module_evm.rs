@@ -46,6 +46,11 @@ use sp_std::marker::PhantomData;
 /// Weight functions for module_evm.
 pub struct WeightInfo<T>(PhantomData<T>);
 impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
+	// Storage: EVM TransactionGasUsed (r:0 w:1)
+	fn on_initialize() -> Weight {
+		(2_187_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
 	// Storage: EvmAccounts Accounts (r:2 w:0)
 	// Storage: Balances Reserves (r:2 w:2)
@@ -119,6 +124,16 @@ impl<T: frame_system::Config> module_evm::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(11 as Weight))
 			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
+	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: System Account (r:1 w:1)
+	// Storage: IdleScheduler NextTaskId (r:1 w:1)
+	// Storage: IdleScheduler Tasks (r:0 w:1)
+	fn schedule_call() -> Weight {
+		(59_413_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
 	// Storage: EVM Accounts (r:1 w:1)
 	// Storage: EvmAccounts EvmAddresses (r:1 w:0)
 	fn transfer_maintainer() -> Weight {
@@ -55,8 +55,8 @@ mod mock;
 pub use check_nonce::CheckNonce;
 use orml_traits::GetByKey;
 pub use precompile::{
-	AllPrecompiles, DexPrecompile, MultiCurrencyPrecompile, NFTPrecompile, OraclePrecompile, ScheduleCallPrecompile,
-	StateRentPrecompile,
+	AllPrecompiles, DexOraclePrecompile, DexPrecompile, EvmFlashSwapCallback, MultiCurrencyPrecompile, NFTPrecompile,
+	OraclePrecompile, ScheduleCallPrecompile, StateRentPrecompile,
 };
 pub use primitives::{
 	currency::{TokenInfo, ACA, AUSD, BNC, DOT, KAR, KBTC, KINT, KSM, KUSD, LCDOT, LDOT, LKSM, PHA, RENBTC, VSKSM},
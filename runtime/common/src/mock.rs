@@ -213,6 +213,7 @@ impl module_evm::Config for TestRuntime {
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ();
+	type EnableLondon = frame_support::traits::ConstBool<false>;
 
 	type NetworkContractOrigin = frame_system::EnsureSignedBy<NetworkContractAccount, AccountId32>;
 	type NetworkContractSource = NetworkContractSource;
@@ -18,12 +18,19 @@
 
 use super::input::{Input, InputT, Output};
 use crate::precompile::PrecompileOutput;
-use frame_support::log;
-use module_evm::{Context, ExitError, ExitSucceed, Precompile};
-use module_support::{DEXManager, SwapLimit};
+use ethabi::Token;
+use frame_support::{log, weights::Weight};
+use module_evm::{Context, ExitError, ExitReason, ExitSucceed, Precompile};
+use module_support::{
+	AddressMapping, DEXFlashSwapCallback, DEXManager, Erc20InfoMapping, ExecutionMode, InvokeContext, SwapLimit, EVM,
+};
 use num_enum::{IntoPrimitive, TryFromPrimitive};
-use primitives::{Balance, CurrencyId};
-use sp_runtime::RuntimeDebug;
+use primitives::{evm::EvmAddress, Balance, CurrencyId};
+use sp_core::U256;
+use sp_runtime::{
+	traits::{Convert, SaturatedConversion},
+	DispatchError, DispatchResult, RuntimeDebug,
+};
 use sp_std::{marker::PhantomData, prelude::*, result};
 
 /// The `DEX` impl precompile.
@@ -35,6 +42,12 @@ use sp_std::{marker::PhantomData, prelude::*, result};
 /// - Get liquidity. Rest `input` bytes: `currency_id_a`, `currency_id_b`.
 /// - Swap with exact supply. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`,
 ///   `supply_amount`, `min_target_amount`.
+/// - Swap with exact supply and send to `dest` before deadline. Rest `input` bytes: `who`, `dest`,
+///   `path`, `supply_amount`, `min_target_amount`, `deadline` (unix timestamp in seconds).
+/// - Flash swap. Rest `input` bytes: `who`, `currency_id_a`, `currency_id_b`, `amount_a`,
+///   `amount_b`, `data`. `who` is called back by `onFlashSwap` and must repay in the same call.
+/// - Get best swap path. Rest `input` bytes: `supply_currency_id`, `target_currency_id`,
+///   `supply_amount` or `target_amount`.
 pub struct DexPrecompile<R>(PhantomData<R>);
 
 #[module_evm_utiltity_macro::generate_function_selector]
@@ -49,19 +62,29 @@ pub enum Action {
 	SwapWithExactTarget = "swapWithExactTarget(address,address[],uint256,uint256)",
 	AddLiquidity = "addLiquidity(address,address,address,uint256,uint256,uint256)",
 	RemoveLiquidity = "removeLiquidity(address,address,address,uint256,uint256,uint256)",
+	FlashSwap = "flashSwap(address,address,address,uint256,uint256,bytes)",
+	GetBestSwapPathWithExactSupply = "getBestSwapPathWithExactSupply(address,address,uint256)",
+	GetBestSwapPathWithExactTarget = "getBestSwapPathWithExactTarget(address,address,uint256)",
+	SwapWithExactSupplyTo = "swapWithExactSupplyTo(address,address,address[],uint256,uint256,uint256)",
+	SwapWithExactTargetTo = "swapWithExactTargetTo(address,address,address[],uint256,uint256,uint256)",
 }
 
 impl<Runtime> Precompile for DexPrecompile<Runtime>
 where
-	Runtime: module_evm::Config + module_prices::Config,
+	Runtime: module_evm::Config + module_prices::Config + module_dex::Config,
 	module_dex::Pallet<Runtime>: DEXManager<Runtime::AccountId, CurrencyId, Balance>,
 {
 	fn execute(
 		input: &[u8],
-		_target_gas: Option<u64>,
+		target_gas: Option<u64>,
 		_context: &Context,
 	) -> result::Result<PrecompileOutput, ExitError> {
-		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(input);
+		let input = Input::<
+			Action,
+			Runtime::AccountId,
+			Runtime::AddressMapping,
+			<Runtime as module_dex::Config>::Erc20InfoMapping,
+		>::new(input);
 
 		let action = input.action()?;
 
@@ -115,6 +138,8 @@ where
 				for i in 0..path_len {
 					path.push(input.currency_id_at((4 + i) as usize)?);
 				}
+				// the exchange fee of each trading pair of the path
+				let cost = Self::exchange_fee_cost(path_len, target_gas)?;
 				log::debug!(
 					target: "evm",
 					"dex: get_swap_target_amount path: {:?}, supply_amount: {:?}",
@@ -127,7 +152,7 @@ where
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
-					cost: 0,
+					cost,
 					output: Output::default().encode_u128(value),
 					logs: Default::default(),
 				})
@@ -140,6 +165,8 @@ where
 				for i in 0..path_len {
 					path.push(input.currency_id_at((4 + i) as usize)?);
 				}
+				// the exchange fee of each trading pair of the path
+				let cost = Self::exchange_fee_cost(path_len, target_gas)?;
 				log::debug!(
 					target: "evm",
 					"dex: get_swap_supply_amount path: {:?}, target_amount: {:?}",
@@ -152,7 +179,7 @@ where
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
-					cost: 0,
+					cost,
 					output: Output::default().encode_u128(value),
 					logs: Default::default(),
 				})
@@ -167,6 +194,8 @@ where
 				for i in 0..path_len {
 					path.push(input.currency_id_at((6 + i) as usize)?);
 				}
+				// the exchange fee of each trading pair of the path
+				let cost = Self::exchange_fee_cost(path_len, target_gas)?;
 				log::debug!(
 					target: "evm",
 					"dex: swap_with_exact_supply who: {:?}, path: {:?}, supply_amount: {:?}, min_target_amount: {:?}",
@@ -181,7 +210,7 @@ where
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
-					cost: 0,
+					cost,
 					output: Output::default().encode_u128(value),
 					logs: Default::default(),
 				})
@@ -196,6 +225,8 @@ where
 				for i in 0..path_len {
 					path.push(input.currency_id_at((6 + i) as usize)?);
 				}
+				// the exchange fee of each trading pair of the path
+				let cost = Self::exchange_fee_cost(path_len, target_gas)?;
 				log::debug!(
 					target: "evm",
 					"dex: swap_with_exact_target who: {:?}, path: {:?}, target_amount: {:?}, max_supply_amount: {:?}",
@@ -210,7 +241,91 @@ where
 
 				Ok(PrecompileOutput {
 					exit_status: ExitSucceed::Returned,
-					cost: 0,
+					cost,
+					output: Output::default().encode_u128(value),
+					logs: Default::default(),
+				})
+			}
+			Action::SwapWithExactSupplyTo => {
+				let who = input.account_id_at(1)?;
+				let dest = input.account_id_at(2)?;
+				// solidity abi enocde array will add an offset at input[3]
+				let supply_amount = input.balance_at(4)?;
+				let min_target_amount = input.balance_at(5)?;
+				let deadline = input.u64_at(6)?;
+				let path_len = input.u32_at(7)?;
+				let mut path = vec![];
+				for i in 0..path_len {
+					path.push(input.currency_id_at((8 + i) as usize)?);
+				}
+				// the exchange fee of each trading pair of the path
+				let cost = Self::exchange_fee_cost(path_len, target_gas)?;
+				log::debug!(
+					target: "evm",
+					"dex: swap_with_exact_supply_to who: {:?}, dest: {:?}, path: {:?}, supply_amount: {:?}, min_target_amount: {:?}, deadline: {:?}",
+					who, dest, path, supply_amount, min_target_amount, deadline
+				);
+
+				let value = Self::ensure_before_deadline(deadline)
+					.and_then(|_| {
+						module_dex::Pallet::<Runtime>::do_swap_with_exact_supply_to(
+							&who,
+							&dest,
+							&path,
+							supply_amount,
+							min_target_amount,
+						)
+					})
+					.map_err(|e| {
+						let err_msg: &str = e.into();
+						ExitError::Other(err_msg.into())
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: Output::default().encode_u128(value),
+					logs: Default::default(),
+				})
+			}
+			Action::SwapWithExactTargetTo => {
+				let who = input.account_id_at(1)?;
+				let dest = input.account_id_at(2)?;
+				// solidity abi enocde array will add an offset at input[3]
+				let target_amount = input.balance_at(4)?;
+				let max_supply_amount = input.balance_at(5)?;
+				let deadline = input.u64_at(6)?;
+				let path_len = input.u32_at(7)?;
+				let mut path = vec![];
+				for i in 0..path_len {
+					path.push(input.currency_id_at((8 + i) as usize)?);
+				}
+				// the exchange fee of each trading pair of the path
+				let cost = Self::exchange_fee_cost(path_len, target_gas)?;
+				log::debug!(
+					target: "evm",
+					"dex: swap_with_exact_target_to who: {:?}, dest: {:?}, path: {:?}, target_amount: {:?}, max_supply_amount: {:?}, deadline: {:?}",
+					who, dest, path, target_amount, max_supply_amount, deadline
+				);
+
+				let value = Self::ensure_before_deadline(deadline)
+					.and_then(|_| {
+						module_dex::Pallet::<Runtime>::do_swap_with_exact_target_to(
+							&who,
+							&dest,
+							&path,
+							target_amount,
+							max_supply_amount,
+						)
+					})
+					.map_err(|e| {
+						let err_msg: &str = e.into();
+						ExitError::Other(err_msg.into())
+					})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
 					output: Output::default().encode_u128(value),
 					logs: Default::default(),
 				})
@@ -285,6 +400,234 @@ where
 					logs: Default::default(),
 				})
 			}
+			Action::FlashSwap => {
+				let who = input.account_id_at(1)?;
+				let callee = input.evm_address_at(1)?;
+				let currency_id_a = input.currency_id_at(2)?;
+				let currency_id_b = input.currency_id_at(3)?;
+				let amount_a = input.balance_at(4)?;
+				let amount_b = input.balance_at(5)?;
+				// solidity abi enocde bytes will add an offset at input[6]
+				let data_len = input.u32_at(7)?;
+				let data = input.bytes_at(8, data_len as usize)?;
+
+				log::debug!(
+					target: "evm",
+					"dex: flash_swap who: {:?}, currency_id_a: {:?}, currency_id_b: {:?}, amount_a: {:?}, amount_b: {:?}",
+					who, currency_id_a, currency_id_b, amount_a, amount_b,
+				);
+
+				// the callback can use up to `FLASH_SWAP_CALLBACK_GAS_LIMIT`, which is charged as the
+				// cost of the precompile.
+				if let Some(gas) = target_gas {
+					if gas < FLASH_SWAP_CALLBACK_GAS_LIMIT {
+						return Err(ExitError::OutOfGas);
+					}
+				}
+
+				let (repay_a, repay_b, used_gas) = module_dex::Pallet::<Runtime>::do_flash_swap(
+					&who,
+					currency_id_a,
+					currency_id_b,
+					amount_a,
+					amount_b,
+					callee,
+					data,
+				)
+				.map_err(|e| {
+					let err_msg: &str = e.into();
+					ExitError::Other(err_msg.into())
+				})?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost: used_gas,
+					output: Output::default().encode_u128_tuple(repay_a, repay_b),
+					logs: Default::default(),
+				})
+			}
+			Action::GetBestSwapPathWithExactSupply => {
+				let supply_currency_id = input.currency_id_at(1)?;
+				let target_currency_id = input.currency_id_at(2)?;
+				let supply_amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"dex: get_best_swap_path_with_exact_supply supply_currency_id: {:?}, target_currency_id: {:?}, supply_amount: {:?}",
+					supply_currency_id, target_currency_id, supply_amount
+				);
+
+				let (route, visited) = module_dex::Pallet::<Runtime>::get_best_route(
+					supply_currency_id,
+					target_currency_id,
+					SwapLimit::ExactSupply(supply_amount, Balance::MIN),
+				);
+				let cost = Self::route_search_cost(visited, target_gas)?;
+				let (path, _, target_amount) =
+					route.ok_or_else(|| ExitError::Other("Dex get_best_swap_path_with_exact_supply failed".into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: Output::default().encode_address_array_and_u128(&Self::encode_path(&path)?, target_amount),
+					logs: Default::default(),
+				})
+			}
+			Action::GetBestSwapPathWithExactTarget => {
+				let supply_currency_id = input.currency_id_at(1)?;
+				let target_currency_id = input.currency_id_at(2)?;
+				let target_amount = input.balance_at(3)?;
+				log::debug!(
+					target: "evm",
+					"dex: get_best_swap_path_with_exact_target supply_currency_id: {:?}, target_currency_id: {:?}, target_amount: {:?}",
+					supply_currency_id, target_currency_id, target_amount
+				);
+
+				let (route, visited) = module_dex::Pallet::<Runtime>::get_best_route(
+					supply_currency_id,
+					target_currency_id,
+					SwapLimit::ExactTarget(Balance::MAX, target_amount),
+				);
+				let cost = Self::route_search_cost(visited, target_gas)?;
+				let (path, supply_amount, _) =
+					route.ok_or_else(|| ExitError::Other("Dex get_best_swap_path_with_exact_target failed".into()))?;
+
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: Output::default().encode_address_array_and_u128(&Self::encode_path(&path)?, supply_amount),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+impl<Runtime> DexPrecompile<Runtime>
+where
+	Runtime: module_dex::Config,
+{
+	fn encode_path(path: &[CurrencyId]) -> result::Result<Vec<EvmAddress>, ExitError> {
+		path.iter()
+			.map(|currency_id| {
+				<Runtime as module_dex::Config>::Erc20InfoMapping::encode_evm_address(*currency_id)
+					.ok_or_else(|| ExitError::Other("invalid currency id".into()))
+			})
+			.collect()
+	}
+
+	/// `deadline` is the unix timestamp in seconds as used by solidity.
+	fn ensure_before_deadline(deadline: u64) -> DispatchResult {
+		module_dex::Pallet::<Runtime>::ensure_before_deadline(module_dex::SwapDeadline::Timestamp(
+			deadline.saturating_mul(1_000),
+		))
+	}
+
+	/// The cost of reading the exchange fee rate of each trading pair of a path of `path_len`
+	/// currencies, which isn't covered by the other costs of the swap.
+	fn exchange_fee_cost(path_len: u32, target_gas: Option<u64>) -> result::Result<u64, ExitError> {
+		let cost = EXCHANGE_FEE_READ_GAS.saturating_mul(path_len.saturating_sub(1).into());
+		if let Some(gas) = target_gas {
+			if gas < cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		Ok(cost)
+	}
+
+	/// The cost of the best route search which has visited `visited` trading pairs and paths.
+	fn route_search_cost(visited: u32, target_gas: Option<u64>) -> result::Result<u64, ExitError> {
+		let cost = ROUTE_SEARCH_STEP_GAS.saturating_mul(visited.into());
+		if let Some(gas) = target_gas {
+			if gas < cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		Ok(cost)
+	}
+}
+
+/// The gas charged for reading the exchange fee rate of each trading pair of a swap path.
+pub const EXCHANGE_FEE_READ_GAS: u64 = 2_100;
+
+/// The gas charged for each trading pair and path visited by the best route search, which
+/// reads the status, liquidity pool, exchange fee and curve of a trading pair at most.
+pub const ROUTE_SEARCH_STEP_GAS: u64 = 4 * 2_100;
+
+/// The gas limit of `onFlashSwap` callback.
+pub const FLASH_SWAP_CALLBACK_GAS_LIMIT: u64 = 2_100_000;
+/// The storage limit of `onFlashSwap` callback.
+pub const FLASH_SWAP_CALLBACK_STORAGE_LIMIT: u32 = 1_000;
+
+#[module_evm_utiltity_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum FlashSwapCallbackAction {
+	OnFlashSwap = "onFlashSwap(address,address,address,uint256,uint256,uint256,uint256,bytes)",
+}
+
+/// The `DEXFlashSwapCallback` impl which calls `onFlashSwap` of the `callee` contract, the
+/// `msg.sender` of the callback is the EVM address of the borrower.
+pub struct EvmFlashSwapCallback<R>(PhantomData<R>);
+
+impl<Runtime> DEXFlashSwapCallback<Runtime::AccountId, CurrencyId, Balance> for EvmFlashSwapCallback<Runtime>
+where
+	Runtime: module_evm::Config + module_dex::Config,
+{
+	fn gas_limit() -> u64 {
+		FLASH_SWAP_CALLBACK_GAS_LIMIT
+	}
+
+	fn gas_to_weight(gas: u64) -> Weight {
+		<Runtime as module_evm::Config>::GasToWeight::convert(gas)
+	}
+
+	fn on_flash_swap(
+		who: &Runtime::AccountId,
+		callee: EvmAddress,
+		currency_id_a: CurrencyId,
+		currency_id_b: CurrencyId,
+		amount_a: Balance,
+		amount_b: Balance,
+		repay_a: Balance,
+		repay_b: Balance,
+		data: Vec<u8>,
+	) -> result::Result<u64, DispatchError> {
+		let sender = Runtime::AddressMapping::get_or_create_evm_address(who);
+		let token_a = <Runtime as module_dex::Config>::Erc20InfoMapping::encode_evm_address(currency_id_a)
+			.ok_or(DispatchError::Other("invalid currency id"))?;
+		let token_b = <Runtime as module_dex::Config>::Erc20InfoMapping::encode_evm_address(currency_id_b)
+			.ok_or(DispatchError::Other("invalid currency id"))?;
+
+		let mut input = Into::<u32>::into(FlashSwapCallbackAction::OnFlashSwap)
+			.to_be_bytes()
+			.to_vec();
+		input.extend_from_slice(&ethabi::encode(&[
+			Token::Address(sender),
+			Token::Address(token_a),
+			Token::Address(token_b),
+			Token::Uint(U256::from(amount_a)),
+			Token::Uint(U256::from(amount_b)),
+			Token::Uint(U256::from(repay_a)),
+			Token::Uint(U256::from(repay_b)),
+			Token::Bytes(data),
+		]));
+
+		let info = <module_evm::Pallet<Runtime> as EVM<Runtime::AccountId>>::execute(
+			InvokeContext {
+				contract: callee,
+				sender,
+				origin: sender,
+			},
+			input,
+			Default::default(),
+			FLASH_SWAP_CALLBACK_GAS_LIMIT,
+			FLASH_SWAP_CALLBACK_STORAGE_LIMIT,
+			ExecutionMode::Execute,
+		)?;
+
+		match info.exit_reason {
+			ExitReason::Succeed(_) => Ok(info.used_gas.saturated_into()),
+			_ => Err(DispatchError::Other("flash swap callback failed")),
 		}
 	}
 }
@@ -0,0 +1,122 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use super::input::{Input, InputT, Output};
+use crate::precompile::PrecompileOutput;
+use frame_support::{log, sp_runtime::FixedPointNumber, traits::Get};
+use module_dex_oracle::MomentOf;
+use module_evm::{Context, ExitError, ExitSucceed, Precompile};
+use num_enum::{IntoPrimitive, TryFromPrimitive};
+use sp_runtime::{traits::SaturatedConversion, RuntimeDebug};
+use sp_std::{marker::PhantomData, prelude::*, result};
+
+/// The `DexOracle` impl precompile.
+///
+///
+/// `input` data starts with `action`.
+///
+/// Actions:
+/// - Get current price. Rest `input` bytes: `base_currency_id`, `quote_currency_id`.
+/// - Get time-weighted average price. Rest `input` bytes: `base_currency_id`, `quote_currency_id`,
+///   `window` (in seconds).
+///
+/// The prices are the ratio of the raw pool amounts in 18 decimals. Return 0 if the price is
+/// not available, Solidity should handle the situation of price 0.
+///
+/// The storage reads are charged by `STORAGE_READ_GAS`, the TWAP is charged by the most reads of
+/// the binary search over the observations.
+pub struct DexOraclePrecompile<R>(PhantomData<R>);
+
+#[module_evm_utiltity_macro::generate_function_selector]
+#[derive(RuntimeDebug, Eq, PartialEq, TryFromPrimitive, IntoPrimitive)]
+#[repr(u32)]
+pub enum Action {
+	GetCurrentPrice = "getCurrentPrice(address,address)",
+	GetTwap = "getTwap(address,address,uint256)",
+}
+
+impl<Runtime> Precompile for DexOraclePrecompile<Runtime>
+where
+	Runtime: module_evm::Config + module_dex_oracle::Config,
+{
+	fn execute(
+		input: &[u8],
+		target_gas: Option<u64>,
+		_context: &Context,
+	) -> result::Result<PrecompileOutput, ExitError> {
+		let input = Input::<Action, Runtime::AccountId, Runtime::AddressMapping, Runtime::Erc20InfoMapping>::new(input);
+
+		let action = input.action()?;
+
+		match action {
+			Action::GetCurrentPrice => {
+				let base = input.currency_id_at(1)?;
+				let quote = input.currency_id_at(2)?;
+				// the liquidity pool
+				let cost = Self::read_cost(1, target_gas)?;
+
+				let price =
+					module_dex_oracle::Pallet::<Runtime>::get_relative_current_price(base, quote).unwrap_or_default();
+
+				log::debug!(target: "evm", "dex_oracle: getCurrentPrice base: {:?}, quote: {:?}, price: {:?}", base, quote, price);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: Output::default().encode_u128(price.into_inner()),
+					logs: Default::default(),
+				})
+			}
+			Action::GetTwap => {
+				let base = input.currency_id_at(1)?;
+				let quote = input.currency_id_at(2)?;
+				// the window is in seconds, Moment is in milliseconds
+				let window: MomentOf<Runtime> = input.u64_at(3)?.saturating_mul(1_000).saturated_into();
+				// the average price, observation ring and latest observation, and the observations
+				// read by the binary search.
+				let max_observations = <Runtime as module_dex_oracle::Config>::MaxObservations::get();
+				let search_reads = u64::from(u32::BITS - max_observations.leading_zeros()).saturating_add(1);
+				let cost = Self::read_cost(search_reads.saturating_add(3), target_gas)?;
+
+				let price = module_dex_oracle::Pallet::<Runtime>::get_twap(base, quote, window).unwrap_or_default();
+
+				log::debug!(target: "evm", "dex_oracle: getTwap base: {:?}, quote: {:?}, window: {:?}, price: {:?}", base, quote, window, price);
+				Ok(PrecompileOutput {
+					exit_status: ExitSucceed::Returned,
+					cost,
+					output: Output::default().encode_u128(price.into_inner()),
+					logs: Default::default(),
+				})
+			}
+		}
+	}
+}
+
+impl<Runtime> DexOraclePrecompile<Runtime> {
+	fn read_cost(reads: u64, target_gas: Option<u64>) -> result::Result<u64, ExitError> {
+		let cost = STORAGE_READ_GAS.saturating_mul(reads);
+		if let Some(gas) = target_gas {
+			if gas < cost {
+				return Err(ExitError::OutOfGas);
+			}
+		}
+		Ok(cost)
+	}
+}
+
+/// The gas of a storage read, the cold `SLOAD` cost of EIP-2929.
+pub const STORAGE_READ_GAS: u64 = 2_100;
@@ -218,6 +218,11 @@ impl Output {
 		let out = Token::Address(H160::from_slice(b.as_bytes()));
 		ethabi::encode(&[out])
 	}
+
+	pub fn encode_address_array_and_u128(&self, b: &[H160], c: u128) -> Vec<u8> {
+		let addresses = b.iter().map(|address| Token::Address(*address)).collect();
+		ethabi::encode(&[Token::Array(addresses), Token::Uint(U256::from(c))])
+	}
 }
 
 #[cfg(test)]
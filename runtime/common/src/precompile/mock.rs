@@ -373,6 +373,7 @@ ord_parameter_types! {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 100);
 	pub const TradingPathLimit: u32 = 4;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2000;
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
 }
 
@@ -381,12 +382,31 @@ impl module_dex::Config for Test {
 	type Currency = Tokens;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type MaxRouteTokens = frame_support::traits::ConstU32<3>;
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = EvmErc20InfoMapping;
 	type WeightInfo = ();
 	type DEXIncentives = MockDEXIncentives;
 	type ListingOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
 	type OnLiquidityPoolUpdated = ();
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type TreasuryAccount = KaruraTreasuryAccount;
+	type FlashSwapCallback = crate::EvmFlashSwapCallback<Test>;
+	type UnixTime = Timestamp;
+}
+
+parameter_types! {
+	pub const DexOracleObservationPeriod: u64 = 100;
+	pub const DexOracleMaxObservations: u32 = 10;
+}
+
+impl module_dex_oracle::Config for Test {
+	type DEX = DexModule;
+	type Time = Timestamp;
+	type UpdateOrigin = EnsureSignedBy<ListingOrigin, AccountId>;
+	type ObservationPeriod = DexOracleObservationPeriod;
+	type MaxObservations = DexOracleMaxObservations;
+	type WeightInfo = ();
 }
 
 pub type AdaptedBasicCurrency = module_currencies::BasicCurrencyAdapter<Test, Balances, Amount, BlockNumber>;
@@ -428,6 +448,7 @@ impl module_evm::Config for Test {
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = ChargeTransactionPayment;
+	type EnableLondon = frame_support::traits::ConstBool<false>;
 	type NetworkContractOrigin = EnsureSignedBy<NetworkContractAccount, AccountId>;
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
@@ -558,6 +579,7 @@ frame_support::construct_runtime!(
 		Utility: pallet_utility::{Pallet, Call, Event},
 		Scheduler: pallet_scheduler::{Pallet, Call, Storage, Event<T>},
 		DexModule: module_dex::{Pallet, Storage, Call, Event<T>, Config<T>},
+		DexOracle: module_dex_oracle::{Pallet, Storage, Call},
 		EVMModule: module_evm::{Pallet, Config<T>, Call, Storage, Event<T>},
 		IdleScheduler: module_idle_scheduler::{Pallet, Call, Storage, Event<T>},
 	}
@@ -27,8 +27,8 @@ mod tests;
 use frame_support::log;
 use module_evm::{
 	precompiles::{
-		ECRecover, ECRecoverPublicKey, EvmPrecompiles, Identity, Precompile, PrecompileSet, Ripemd160, Sha256,
-		Sha3FIPS256, Sha3FIPS512,
+		Blake2F, Bn128Add, Bn128Mul, Bn128Pairing, ECRecover, ECRecoverPublicKey, EvmPrecompiles, Identity, Modexp,
+		Precompile, PrecompileSet, Ripemd160, Sha256, Sha3FIPS256, Sha3FIPS512,
 	},
 	runner::state::PrecompileOutput,
 	Context, ExitError,
@@ -39,6 +39,7 @@ use sp_core::H160;
 use sp_std::marker::PhantomData;
 
 pub mod dex;
+pub mod dex_oracle;
 pub mod input;
 pub mod multicurrency;
 pub mod nft;
@@ -47,7 +48,8 @@ pub mod schedule_call;
 pub mod state_rent;
 
 use crate::SystemContractsFilter;
-pub use dex::DexPrecompile;
+pub use dex::{DexPrecompile, EvmFlashSwapCallback};
+pub use dex_oracle::DexOraclePrecompile;
 pub use multicurrency::MultiCurrencyPrecompile;
 pub use nft::NFTPrecompile;
 pub use oracle::OraclePrecompile;
@@ -65,6 +67,7 @@ where
 	OraclePrecompile<R>: Precompile,
 	DexPrecompile<R>: Precompile,
 	ScheduleCallPrecompile<R>: Precompile,
+	DexOraclePrecompile<R>: Precompile,
 {
 	#[allow(clippy::type_complexity)]
 	fn execute(
@@ -73,9 +76,20 @@ where
 		target_gas: Option<u64>,
 		context: &Context,
 	) -> Option<core::result::Result<PrecompileOutput, ExitError>> {
-		EvmPrecompiles::<ECRecover, Sha256, Ripemd160, Identity, ECRecoverPublicKey, Sha3FIPS256, Sha3FIPS512>::execute(
-			address, input, target_gas, context,
-		)
+		EvmPrecompiles::<
+			ECRecover,
+			Sha256,
+			Ripemd160,
+			Identity,
+			Modexp,
+			Bn128Add,
+			Bn128Mul,
+			Bn128Pairing,
+			Blake2F,
+			ECRecoverPublicKey,
+			Sha3FIPS256,
+			Sha3FIPS512,
+		>::execute(address, input, target_gas, context)
 		.or_else(|| {
 			if !is_acala_precompile(address) {
 				return None;
@@ -100,6 +114,8 @@ where
 				Some(ScheduleCallPrecompile::<R>::execute(input, target_gas, context))
 			} else if address == PRECOMPILE_ADDRESS_START | H160::from_low_u64_be(5) {
 				Some(DexPrecompile::<R>::execute(input, target_gas, context))
+			} else if address == PRECOMPILE_ADDRESS_START | H160::from_low_u64_be(6) {
+				Some(DexOraclePrecompile::<R>::execute(input, target_gas, context))
 			} else {
 				None
 			};
@@ -22,17 +22,18 @@ use super::*;
 use crate::precompile::{
 	mock::{
 		aca_evm_address, alice, alice_evm_addr, ausd_evm_address, bob, bob_evm_addr, erc20_address_not_exists,
-		get_task_id, lp_aca_ausd_evm_address, new_test_ext, renbtc_evm_address, run_to_block, Balances, DexModule,
-		EVMModule, Event as TestEvent, Oracle, Origin, Price, System, Test, ALICE, AUSD, INITIAL_BALANCE, RENBTC,
+		get_task_id, lp_aca_ausd_evm_address, new_test_ext, renbtc_evm_address, run_to_block, Balances, Currencies,
+		DexModule, DexOracle, EVMModule, Event as TestEvent, Oracle, Origin, Price, System, Test, Timestamp, ALICE,
+		AUSD, INITIAL_BALANCE, RENBTC,
 	},
 	schedule_call::TaskInfo,
 };
 use codec::Encode;
-use frame_support::{assert_noop, assert_ok};
+use frame_support::{assert_noop, assert_ok, traits::OnInitialize};
 use hex_literal::hex;
 use module_evm::{Context, ExitError, ExitReason, ExitSucceed, Precompile, Runner};
-use module_support::AddressMapping;
-use orml_traits::DataFeeder;
+use module_support::{AddressMapping, DEXManager};
+use orml_traits::{DataFeeder, MultiCurrency};
 use primitives::{
 	evm::{PRECOMPILE_ADDRESS_START, PREDEPLOY_ADDRESS_START},
 	Balance,
@@ -715,7 +716,155 @@ fn dex_precompile_get_swap_target_amount_should_work() {
 		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(resp.exit_status, ExitSucceed::Returned);
 		assert_eq!(resp.output, expected_output);
-		assert_eq!(resp.cost, 0);
+		// read the exchange fee of RENBTC/AUSD
+		assert_eq!(resp.cost, dex::EXCHANGE_FEE_READ_GAS);
+		assert_eq!(
+			DexPrecompile::execute(&input, Some(dex::EXCHANGE_FEE_READ_GAS - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+	});
+}
+
+#[test]
+fn dex_precompile_get_swap_target_amount_with_stable_swap_curve_should_work() {
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/AUSD
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+		assert_ok!(DexModule::set_curve(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			module_dex::Curve::stable_swap(100)
+		));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			100_000,
+			100_000,
+			0,
+			true
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// action + path_len + currency_id_a + currency_id_b +
+		// supply_amount
+		let mut input = [0u8; 6 * 32];
+		// action
+		input[0..4].copy_from_slice(&Into::<u32>::into(dex::Action::GetSwapTargetAmount).to_be_bytes());
+		// skip offset
+		// supply_amount
+		U256::from(1_000).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// path_len
+		U256::from(2).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// AUSD
+		U256::from_big_endian(ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+
+		// constant product curve gets 980
+		let mut expected_output = [0u8; 32];
+		U256::from(989).to_big_endian(&mut expected_output[..32]);
+
+		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, expected_output);
+		assert_eq!(resp.cost, dex::EXCHANGE_FEE_READ_GAS);
+	});
+}
+
+#[test]
+fn dex_oracle_precompile_should_work() {
+	new_test_ext().execute_with(|| {
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// action + base_currency_id + quote_currency_id
+		let mut current_price_input = [0u8; 4 + 2 * 32];
+		// action
+		current_price_input[0..4]
+			.copy_from_slice(&Into::<u32>::into(dex_oracle::Action::GetCurrentPrice).to_be_bytes());
+		// RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut current_price_input[4..4 + 32]);
+		// AUSD
+		U256::from_big_endian(ausd_evm_address().as_bytes())
+			.to_big_endian(&mut current_price_input[4 + 32..4 + 2 * 32]);
+
+		// action + base_currency_id + quote_currency_id + window
+		let mut twap_input = [0u8; 4 + 3 * 32];
+		// action
+		twap_input[0..4].copy_from_slice(&Into::<u32>::into(dex_oracle::Action::GetTwap).to_be_bytes());
+		// RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut twap_input[4..4 + 32]);
+		// AUSD
+		U256::from_big_endian(ausd_evm_address().as_bytes()).to_big_endian(&mut twap_input[4 + 32..4 + 2 * 32]);
+		// window: 5 seconds
+		U256::from(5).to_big_endian(&mut twap_input[4 + 2 * 32..4 + 3 * 32]);
+
+		// no price yet
+		let resp = DexOraclePrecompile::execute(&current_price_input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, [0u8; 32]);
+		assert_eq!(resp.cost, dex_oracle::STORAGE_READ_GAS);
+
+		// MaxObservations is 10, the binary search reads 5 observations at most
+		let resp = DexOraclePrecompile::execute(&twap_input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, [0u8; 32]);
+		assert_eq!(resp.cost, 8 * dex_oracle::STORAGE_READ_GAS);
+		assert_eq!(
+			DexOraclePrecompile::execute(&twap_input, Some(8 * dex_oracle::STORAGE_READ_GAS - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+
+		// enable RENBTC/AUSD
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+		assert_ok!(DexOracle::enable_average_price(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			1_000
+		));
+
+		let mut expected_output = [0u8; 32];
+		U256::from(Price::saturating_from_integer(1_000).into_inner()).to_big_endian(&mut expected_output[..]);
+
+		let resp = DexOraclePrecompile::execute(&current_price_input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, expected_output);
+		assert_eq!(resp.cost, dex_oracle::STORAGE_READ_GAS);
+
+		// the observations can't cover the window yet
+		let resp = DexOraclePrecompile::execute(&twap_input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, [0u8; 32]);
+		assert_eq!(resp.cost, 8 * dex_oracle::STORAGE_READ_GAS);
+
+		Timestamp::set_timestamp(10_001);
+		DexOracle::on_initialize(2);
+
+		let resp = DexOraclePrecompile::execute(&twap_input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, expected_output);
+		assert_eq!(resp.cost, 8 * dex_oracle::STORAGE_READ_GAS);
 	});
 }
 
@@ -762,7 +911,7 @@ fn dex_precompile_get_swap_supply_amount_should_work() {
 		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(resp.exit_status, ExitSucceed::Returned);
 		assert_eq!(resp.output, expected_output);
-		assert_eq!(resp.cost, 0);
+		assert_eq!(resp.cost, dex::EXCHANGE_FEE_READ_GAS);
 	});
 }
 
@@ -813,7 +962,7 @@ fn dex_precompile_swap_with_exact_supply_should_work() {
 		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(resp.exit_status, ExitSucceed::Returned);
 		assert_eq!(resp.output, expected_output);
-		assert_eq!(resp.cost, 0);
+		assert_eq!(resp.cost, dex::EXCHANGE_FEE_READ_GAS);
 	});
 }
 
@@ -861,10 +1010,191 @@ fn dex_precompile_swap_with_exact_target_should_work() {
 		let mut expected_output = [0u8; 32];
 		U256::from(1).to_big_endian(&mut expected_output[..32]);
 
+		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, expected_output);
+		assert_eq!(resp.cost, dex::EXCHANGE_FEE_READ_GAS);
+	});
+}
+
+#[test]
+fn dex_precompile_swap_with_exact_supply_to_should_work() {
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/AUSD
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// action + who + dest + path_len + currency_id_a + currency_id_b +
+		// supply_amount + min_target_amount + deadline
+		let mut input = [0u8; 10 * 32];
+		// action
+		input[0..4].copy_from_slice(&Into::<u32>::into(dex::Action::SwapWithExactSupplyTo).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 0 * 32..4 + 1 * 32]);
+		// dest
+		U256::from(bob_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// skip offset
+		// supply_amount
+		U256::from(1).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// min_target_amount
+		U256::from(0).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		// deadline
+		U256::from(1).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+		// path_len
+		U256::from(2).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+		// RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 7 * 32..4 + 8 * 32]);
+		// AUSD
+		U256::from_big_endian(ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 8 * 32..4 + 9 * 32]);
+
+		let mut expected_output = [0u8; 32];
+		U256::from(989).to_big_endian(&mut expected_output[..32]);
+
+		let bob_ausd = Currencies::free_balance(AUSD, &bob());
+		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, expected_output);
+		assert_eq!(resp.cost, dex::EXCHANGE_FEE_READ_GAS);
+		assert_eq!(Currencies::free_balance(AUSD, &bob()), bob_ausd + 989);
+
+		// expired
+		Timestamp::set_timestamp(2_000);
+		assert_noop!(
+			DexPrecompile::execute(&input, None, &context),
+			ExitError::Other("SwapExpired".into())
+		);
+	});
+}
+
+#[test]
+fn dex_precompile_get_best_swap_path_should_work() {
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/AUSD
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// action + supply_currency_id + target_currency_id + supply_amount
+		let mut input = [0u8; 3 * 32 + 4];
+		// action
+		input[0..4].copy_from_slice(&Into::<u32>::into(dex::Action::GetBestSwapPathWithExactSupply).to_be_bytes());
+		// RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 0 * 32..4 + 1 * 32]);
+		// AUSD
+		U256::from_big_endian(ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// supply_amount
+		U256::from(1).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+
+		// offset + target_amount + path_len + RENBTC + AUSD
+		let mut expected_output = [0u8; 5 * 32];
+		U256::from(2 * 32).to_big_endian(&mut expected_output[0 * 32..1 * 32]);
+		U256::from(989).to_big_endian(&mut expected_output[1 * 32..2 * 32]);
+		U256::from(2).to_big_endian(&mut expected_output[2 * 32..3 * 32]);
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut expected_output[3 * 32..4 * 32]);
+		U256::from_big_endian(ausd_evm_address().as_bytes()).to_big_endian(&mut expected_output[4 * 32..5 * 32]);
+
+		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
+		assert_eq!(resp.exit_status, ExitSucceed::Returned);
+		assert_eq!(resp.output, expected_output);
+		// read RENBTC/AUSD and visit path [RENBTC, AUSD]
+		assert_eq!(resp.cost, 2 * dex::ROUTE_SEARCH_STEP_GAS);
+
+		assert_eq!(
+			DexPrecompile::execute(&input, Some(2 * dex::ROUTE_SEARCH_STEP_GAS - 1), &context),
+			Err(ExitError::OutOfGas)
+		);
+
+		// no path from RENBTC to RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		assert_eq!(
+			DexPrecompile::execute(&input, None, &context),
+			Err(ExitError::Other(
+				"Dex get_best_swap_path_with_exact_supply failed".into()
+			))
+		);
+	});
+}
+
+#[test]
+fn dex_precompile_flash_swap_should_work() {
+	new_test_ext().execute_with(|| {
+		// enable RENBTC/AUSD
+		assert_ok!(DexModule::enable_trading_pair(Origin::signed(ALICE), RENBTC, AUSD,));
+
+		assert_ok!(DexModule::add_liquidity(
+			Origin::signed(ALICE),
+			RENBTC,
+			AUSD,
+			1_000,
+			1_000_000,
+			0,
+			true
+		));
+
+		let context = Context {
+			address: Default::default(),
+			caller: alice_evm_addr(),
+			apparent_value: Default::default(),
+		};
+
+		// action + who + currency_id_a + currency_id_b + amount_a + amount_b +
+		// offset + data_len
+		let mut input = [0u8; 7 * 32 + 4];
+		// action
+		input[0..4].copy_from_slice(&Into::<u32>::into(dex::Action::FlashSwap).to_be_bytes());
+		// who
+		U256::from(alice_evm_addr().as_bytes()).to_big_endian(&mut input[4 + 0 * 32..4 + 1 * 32]);
+		// RENBTC
+		U256::from_big_endian(renbtc_evm_address().as_bytes()).to_big_endian(&mut input[4 + 1 * 32..4 + 2 * 32]);
+		// AUSD
+		U256::from_big_endian(ausd_evm_address().as_bytes()).to_big_endian(&mut input[4 + 2 * 32..4 + 3 * 32]);
+		// amount_a
+		U256::from(0).to_big_endian(&mut input[4 + 3 * 32..4 + 4 * 32]);
+		// amount_b
+		U256::from(10_000).to_big_endian(&mut input[4 + 4 * 32..4 + 5 * 32]);
+		// offset
+		U256::from(6 * 32).to_big_endian(&mut input[4 + 5 * 32..4 + 6 * 32]);
+		// data_len
+		U256::from(0).to_big_endian(&mut input[4 + 6 * 32..4 + 7 * 32]);
+
+		let mut expected_output = [0u8; 64];
+		U256::from(0).to_big_endian(&mut expected_output[..32]);
+		U256::from(10_102).to_big_endian(&mut expected_output[32..64]);
+
 		let resp = DexPrecompile::execute(&input, None, &context).unwrap();
 		assert_eq!(resp.exit_status, ExitSucceed::Returned);
 		assert_eq!(resp.output, expected_output);
 		assert_eq!(resp.cost, 0);
+		assert_eq!(DexModule::get_liquidity_pool(RENBTC, AUSD), (1_000, 1_000_102));
 	});
 }
 
@@ -940,7 +1270,7 @@ fn publish_contract_precompile_works() {
 		).unwrap();
 
 		// create contract
-		let info = <Test as module_evm::Config>::Runner::create(alice_evm_addr(), contract.clone(), 0, 21_000_000, 21_000_000, <Test as module_evm::Config>::config()).unwrap();
+		let info = <Test as module_evm::Config>::Runner::create(alice_evm_addr(), contract.clone(), 0, 21_000_000, 21_000_000, vec![], <Test as module_evm::Config>::config()).unwrap();
 		let contract_address = info.value;
 
 		// multiply(2, 3)
@@ -1023,3 +1353,99 @@ fn task_id_max_and_min() {
 
 	assert_eq!(38, task_id.len());
 }
+
+#[test]
+fn ethereum_precompiles_should_work_in_evm() {
+	new_test_ext().execute_with(|| {
+		let call = |precompile: u64, input: &str| {
+			<Test as module_evm::Config>::Runner::call(
+				alice_evm_addr(),
+				alice_evm_addr(),
+				H160::from_low_u64_be(precompile),
+				from_hex(input).unwrap(),
+				0,
+				1_000_000,
+				0,
+				vec![],
+				<Test as module_evm::Config>::config(),
+			)
+			.unwrap()
+		};
+		let point_2g = from_hex(
+			"0x\
+			030644e72e131a029b85045b68181585d97816a916871ca8d3c208c16d87cfd3\
+			15ed738c0e0a7c92e7845f96b2ae9c0a68a6a449e3538fc7ff3ebf7a5a18a2c4",
+		)
+		.unwrap();
+
+		// modexp: 2 ^ 100 % 1000000007
+		let info = call(
+			5,
+			"0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000004\
+			02\
+			64\
+			3b9aca07",
+		);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.value, 976371285u32.to_be_bytes().to_vec());
+
+		// bn128 add: (1, 2) + (1, 2)
+		let info = call(
+			6,
+			"0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002",
+		);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.value, point_2g);
+
+		// bn128 mul: (1, 2) * 2
+		let info = call(
+			7,
+			"0x\
+			0000000000000000000000000000000000000000000000000000000000000001\
+			0000000000000000000000000000000000000000000000000000000000000002\
+			0000000000000000000000000000000000000000000000000000000000000002",
+		);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(info.value, point_2g);
+
+		// bn128 pairing: the empty input is true
+		let info = call(8, "0x");
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(
+			info.value,
+			from_hex("0x0000000000000000000000000000000000000000000000000000000000000001").unwrap()
+		);
+
+		// blake2f: EIP-152 test vector 5
+		let info = call(
+			9,
+			"0x\
+			0000000c\
+			48c9bdf267e6096a3ba7ca8485ae67bb2bf894fe72f36e3cf1361d5f3af54fa5\
+			d182e6ad7f520e511f6c3e2b8c68059b6bbd41fbabd9831f79217e1319cde05b\
+			6162630000000000000000000000000000000000000000000000000000000000\
+			0000000000000000000000000000000000000000000000000000000000000000\
+			0000000000000000000000000000000000000000000000000000000000000000\
+			0000000000000000000000000000000000000000000000000000000000000000\
+			0300000000000000\
+			0000000000000000\
+			01",
+		);
+		assert_eq!(info.exit_reason, ExitReason::Succeed(ExitSucceed::Returned));
+		assert_eq!(
+			info.value,
+			from_hex(
+				"0xba80a53f981c4d0d6a2797b69f12f6e94c212f14685ac4b74b12bb6fdbffa2d1\
+				7d87c5392aab792dc252d5de4533cc9518d38aa8dbf1925ab92386edd4009923"
+			)
+			.unwrap()
+		);
+	});
+}
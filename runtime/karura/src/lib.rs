@@ -52,10 +52,10 @@ use sp_version::RuntimeVersion;
 use frame_system::{EnsureRoot, RawOrigin};
 use module_asset_registry::{AssetIdMaps, EvmErc20InfoMapping, FixedRateOfForeignAsset};
 use module_currencies::BasicCurrencyAdapter;
-use module_evm::{CallInfo, CreateInfo, EvmTask, Runner};
+use module_evm::{AccessListItem, CallInfo, CreateInfo, EvmTask, Runner};
 use module_evm_accounts::EvmAddressMapping;
 use module_relaychain::RelayChainCallBuilder;
-use module_support::{AssetIdMapping, DispatchableTask, ExchangeRateProvider};
+use module_support::{AssetIdMapping, DEXManager, DispatchableTask, ExchangeRateProvider, SwapLimit};
 use module_transaction_payment::{Multiplier, TargetedFeeAdjustment, TransactionFeePoolTrader};
 
 use orml_traits::{
@@ -110,7 +110,7 @@ pub use runtime_common::{
 	EnsureRootOrAllTechnicalCommittee, EnsureRootOrHalfFinancialCouncil, EnsureRootOrHalfGeneralCouncil,
 	EnsureRootOrHalfHomaCouncil, EnsureRootOrOneGeneralCouncil, EnsureRootOrOneThirdsTechnicalCommittee,
 	EnsureRootOrThreeFourthsGeneralCouncil, EnsureRootOrTwoThirdsGeneralCouncil,
-	EnsureRootOrTwoThirdsTechnicalCommittee, ExchangeRate, FinancialCouncilInstance,
+	EnsureRootOrTwoThirdsTechnicalCommittee, EvmFlashSwapCallback, ExchangeRate, FinancialCouncilInstance,
 	FinancialCouncilMembershipInstance, GasToWeight, GeneralCouncilInstance, GeneralCouncilMembershipInstance,
 	HomaCouncilInstance, HomaCouncilMembershipInstance, MaxTipsOfPriority, OperationalFeeMultiplier,
 	OperatorMembershipInstanceAcala, Price, ProxyType, Rate, Ratio, RelayChainBlockNumberProvider,
@@ -985,10 +985,12 @@ impl module_auction_manager::Config for Runtime {
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
@@ -1074,6 +1076,8 @@ parameter_types! {
 impl module_cdp_engine::Config for Runtime {
 	type Event = Event;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
+	type DEX = Dex;
+	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
@@ -1104,6 +1108,8 @@ impl module_emergency_shutdown::Config for Runtime {
 	type Event = Event;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = Prices;
+	type LockedPriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRoot<AccountId>;
@@ -1113,6 +1119,8 @@ impl module_emergency_shutdown::Config for Runtime {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (3, 1000);	// 0.3%
 	pub const TradingPathLimit: u32 = 4;
+	pub const MaxRouteTokens: u32 = 8;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2 * DAYS;
 }
 
 impl module_dex::Config for Runtime {
@@ -1120,18 +1128,30 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type MaxRouteTokens = MaxRouteTokens;
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = EvmErc20InfoMapping<Runtime>;
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
 	type OnLiquidityPoolUpdated = ();
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type TreasuryAccount = KaruraTreasuryAccount;
+	type FlashSwapCallback = EvmFlashSwapCallback<Runtime>;
+	type UnixTime = Timestamp;
+}
+
+parameter_types! {
+	pub const DexOracleObservationPeriod: Moment = 1000 * 60 * 10; // 10 mins
+	pub const DexOracleMaxObservations: u32 = 24 * 60 / 10 + 1; // 24 hours of observations
 }
 
 impl module_dex_oracle::Config for Runtime {
 	type DEX = Dex;
 	type Time = Timestamp;
 	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type ObservationPeriod = DexOracleObservationPeriod;
+	type MaxObservations = DexOracleMaxObservations;
 	type WeightInfo = weights::module_dex_oracle::WeightInfo<Runtime>;
 }
 
@@ -1155,6 +1175,7 @@ impl module_cdp_treasury::Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1364,6 +1385,7 @@ impl pallet_proxy::Config for Runtime {
 
 parameter_types! {
 	pub const ChainId: u64 = 686;
+	pub const EnableLondon: bool = false;
 	pub const NewContractExtraBytes: u32 = 10_000;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
 	pub DeveloperDeposit: Balance = 100 * dollar(KAR);
@@ -1401,6 +1423,7 @@ impl module_evm::Config for Runtime {
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
+	type EnableLondon = EnableLondon;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
@@ -2385,6 +2408,79 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_dex_oracle_rpc_runtime_api::DexOracleRuntimeApi<Block, Moment> for Runtime {
+		fn get_current_price(base: CurrencyId, quote: CurrencyId) -> Option<ExchangeRate> {
+			DexOracle::get_relative_current_price(base, quote)
+		}
+
+		fn get_twap(base: CurrencyId, quote: CurrencyId, window: Moment) -> Option<ExchangeRate> {
+			DexOracle::get_twap(base, quote, window)
+		}
+	}
+
+	impl module_cdp_engine_rpc_runtime_api::CdpEngineRuntimeApi<Block, AccountId> for Runtime {
+		fn get_position_risk(who: AccountId, currency_id: CurrencyId) -> Option<module_cdp_engine::RiskInfo> {
+			CdpEngine::get_position_risk(&who, currency_id)
+		}
+
+		fn get_collateral_risk(currency_id: CurrencyId) -> Option<module_cdp_engine::RiskInfo> {
+			CdpEngine::get_collateral_risk(currency_id)
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexRuntimeApi<Block, Balance, BlockNumber> for Runtime {
+		fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_liquidity_pool(currency_id_a, currency_id_b)
+		}
+
+		fn get_swap_amount(path: Vec<CurrencyId>, limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+			Dex::get_swap_amount(&path, limit)
+		}
+
+		fn get_trading_pair_status(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> Option<module_dex::TradingPairStatus<Balance, BlockNumber>> {
+			Dex::get_trading_pair_status(currency_id_a, currency_id_b)
+		}
+
+		fn get_liquidity_share_value(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			share_amount: Balance,
+		) -> Option<(Balance, Balance)> {
+			Dex::get_liquidity_share_value(currency_id_a, currency_id_b, share_amount)
+		}
+
+		fn get_collected_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_collected_protocol_fees(currency_id_a, currency_id_b)
+		}
+
+		fn get_pending_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_pending_protocol_fees(currency_id_a, currency_id_b)
+		}
+
+		fn get_best_swap_path_with_exact_supply(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, Balance)> {
+			Dex::get_best_route(supply_currency_id, target_currency_id, SwapLimit::ExactSupply(supply_amount, Balance::MIN))
+				.0
+				.map(|(path, _, target_amount)| (path, target_amount))
+		}
+
+		fn get_best_swap_path_with_exact_target(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			target_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, Balance)> {
+			Dex::get_best_route(supply_currency_id, target_currency_id, SwapLimit::ExactTarget(Balance::MAX, target_amount))
+				.0
+				.map(|(path, supply_amount, _)| (path, supply_amount))
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -2393,6 +2489,7 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError> {
 			let config = if estimate {
@@ -2411,6 +2508,7 @@ impl_runtime_apis! {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list.unwrap_or_default(),
 				config.as_ref().unwrap_or(<Runtime as module_evm::Config>::config()),
 			)
 		}
@@ -2421,6 +2519,7 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError> {
 			let config = if estimate {
@@ -2437,6 +2536,7 @@ impl_runtime_apis! {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list.unwrap_or_default(),
 				config.as_ref().unwrap_or(<Runtime as module_evm::Config>::config()),
 			)
 		}
@@ -2479,6 +2579,135 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer_config: module_evm::runner::TracerConfig,
+		) -> Result<module_evm::runner::TraceOutcome, sp_runtime::DispatchError> {
+			let config = <Runtime as module_evm::Config>::config();
+			let (result, trace) = module_evm::runner::trace(tracer_config, || match to {
+				Some(to) => module_evm::runner::stack::Runner::<Runtime>::call(
+					from,
+					from,
+					to,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default(),
+					config,
+				)
+				.map(|_| ()),
+				None => module_evm::runner::stack::Runner::<Runtime>::create(
+					from,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default(),
+					config,
+				)
+				.map(|_| ()),
+			})?;
+			result?;
+
+			Ok(trace)
+		}
+
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			traced: <Block as BlockT>::Extrinsic,
+			tracer_config: module_evm::runner::TracerConfig,
+		) -> Result<module_evm::runner::TraceOutcome, sp_runtime::DispatchError> {
+			for extrinsic in extrinsics {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			}
+
+			let (result, trace) = module_evm::runner::trace(tracer_config, || Executive::apply_extrinsic(traced))?;
+			result.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter traced, apply failed"))?;
+
+			Ok(trace)
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EthRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			ChainId::get()
+		}
+
+		fn balance(address: H160) -> sp_core::U256 {
+			EVM::account_basic(&address).balance
+		}
+
+		fn nonce(address: H160) -> sp_core::U256 {
+			EVM::account_basic(&address).nonce
+		}
+
+		fn code(address: H160) -> Vec<u8> {
+			EVM::code_at_address(&address).into_inner()
+		}
+
+		fn storage_at(address: H160, index: sp_core::H256) -> sp_core::H256 {
+			EVM::account_storages(address, index)
+		}
+
+		fn timestamp() -> u64 {
+			Timestamp::now()
+		}
+
+		fn transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<primitives::evm::EthereumTransaction> {
+			use module_support::AddressMapping;
+
+			let (address, _, extra) = extrinsic.signature?;
+			let who = <<Runtime as frame_system::Config>::Lookup as StaticLookup>::lookup(address).ok()?;
+			let from = EvmAddressMapping::<Runtime>::get_evm_address(&who)
+				.unwrap_or_else(|| EvmAddressMapping::<Runtime>::get_default_evm_address(&who));
+
+			let (action, input, value, gas_limit, storage_limit) = match extrinsic.function {
+				Call::EVM(module_evm::Call::eth_call { action, input, value, gas_limit, storage_limit, .. }) => {
+					(action, input, value, gas_limit, storage_limit)
+				}
+				Call::EVM(module_evm::Call::eth_call_v2 { action, input, value, gas_limit, storage_limit, .. }) => {
+					(action, input, value, gas_limit, storage_limit)
+				}
+				Call::EVM(module_evm::Call::call { target, input, value, gas_limit, storage_limit }) => {
+					(module_evm::TransactionAction::Call(target), input, value, gas_limit, storage_limit)
+				}
+				Call::EVM(module_evm::Call::create { init, value, gas_limit, storage_limit }) => {
+					(module_evm::TransactionAction::Create, init, value, gas_limit, storage_limit)
+				}
+				_ => return None,
+			};
+
+			Some(primitives::evm::EthereumTransaction {
+				from,
+				nonce: extra.5.0,
+				action,
+				value: module_evm::convert_decimals_to_evm(value).into(),
+				gas_limit,
+				storage_limit,
+				input,
+			})
+		}
+
+		fn receipts() -> Vec<primitives::evm::EthereumReceipt> {
+			EVM::receipts(System::events().into_iter().filter_map(|record| match (record.phase, record.event) {
+				(frame_system::Phase::ApplyExtrinsic(index), Event::EVM(event)) => Some((index, event)),
+				_ => None,
+			}))
+		}
+
+		fn convert_transaction(_transaction: Vec<u8>) -> Result<<Block as BlockT>::Extrinsic, sp_runtime::DispatchError> {
+			Err(sp_runtime::DispatchError::Other("Ethereum transactions are not supported"))
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -49,6 +49,8 @@ impl<T: frame_system::Config> module_cdp_engine::WeightInfo for WeightInfo<T> {
 	// Storage: CdpEngine LastAccumulationSecs (r:1 w:1)
 	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
 	// Storage: CdpEngine CollateralParams (r:4 w:0)
+	// Storage: CdpEngine InterestRateModels (r:4 w:0)
+	// Storage: CdpEngine ModelInterestRatePerSec (r:4 w:4)
 	// Storage: CdpEngine GlobalInterestRatePerSec (r:1 w:0)
 	// Storage: Loans TotalPositions (r:4 w:0)
 	// Storage: Timestamp Now (r:0 w:1)
@@ -56,8 +58,8 @@ impl<T: frame_system::Config> module_cdp_engine::WeightInfo for WeightInfo<T> {
 		(39_349_000 as Weight)
 			// Standard Error: 184_000
 			.saturating_add((4_421_000 as Weight).saturating_mul(c as Weight))
-			.saturating_add(T::DbWeight::get().reads(11 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(6 as Weight))
 	}
 	// Storage: CdpEngine CollateralParams (r:1 w:1)
 	fn set_collateral_params() -> Weight {
@@ -140,9 +142,34 @@ impl<T: frame_system::Config> module_cdp_engine::WeightInfo for WeightInfo<T> {
 	// Storage: Rewards SharesAndWithdrawnRewards (r:1 w:1)
 	// Storage: Rewards PoolInfos (r:1 w:1)
 	// Storage: Loans TotalPositions (r:1 w:1)
+	// Storage: EmergencyShutdown IsCollateralShutdown (r:1 w:0)
+	// Storage: EmergencyShutdown RefundableCollaterals (r:1 w:1)
 	fn settle() -> Weight {
 		(123_702_000 as Weight)
-			.saturating_add(T::DbWeight::get().reads(13 as Weight))
-			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+			.saturating_add(T::DbWeight::get().reads(15 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+	// Storage: CdpEngine LiquidationCloseFactors (r:0 w:1)
+	fn set_liquidation_close_factor() -> Weight {
+		(16_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: CdpEngine KeeperRewardRatio (r:0 w:1)
+	fn set_keeper_reward_ratio() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:1)
+	fn keeper_reward() -> Weight {
+		(42_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(3 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: CdpEngine InterestRateModels (r:0 w:1)
+	// Storage: CdpEngine ModelInterestRatePerSec (r:0 w:1)
+	fn set_interest_rate_model() -> Weight {
+		(17_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(2 as Weight))
 	}
 }
@@ -88,4 +88,29 @@ impl<T: frame_system::Config> module_cdp_treasury::WeightInfo for WeightInfo<T>
 			.saturating_add(T::DbWeight::get().reads(4 as Weight))
 			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
+	// Storage: CdpTreasury SurplusAuctionFixedSize (r:0 w:1)
+	// Storage: CdpTreasury SurplusBufferSize (r:0 w:1)
+	// Storage: CdpTreasury InitialAmountPerDebitAuction (r:0 w:1)
+	// Storage: CdpTreasury DebitAuctionFixedSize (r:0 w:1)
+	fn set_surplus_and_debit_auction_params() -> Weight {
+		(26_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: CdpTreasury DebitPool (r:1 w:1)
+	// Storage: Tokens Accounts (r:1 w:1)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: CdpTreasury SurplusAuctionFixedSize (r:1 w:0)
+	// Storage: CdpTreasury SurplusBufferSize (r:1 w:0)
+	// Storage: CdpTreasury DebitAuctionFixedSize (r:1 w:0)
+	// Storage: CdpTreasury InitialAmountPerDebitAuction (r:1 w:0)
+	// Storage: AuctionManager TotalSurplusInAuction (r:1 w:1)
+	// Storage: AuctionManager TotalDebitInAuction (r:1 w:1)
+	// Storage: Auction AuctionsIndex (r:1 w:1)
+	// Storage: Auction Auctions (r:0 w:1)
+	// Storage: Auction AuctionEndTime (r:0 w:1)
+	fn on_finalize() -> Weight {
+		(98_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(12 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
 }
@@ -55,13 +55,15 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 	}
 	// Storage: Honzon Authorization (r:1 w:1)
 	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Honzon AuthorizationScopes (r:0 w:1)
 	fn unauthorize() -> Weight {
 		(43_326_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	// Storage: Balances Reserves (r:1 w:0)
 	// Storage: Honzon Authorization (r:0 w:1)
+	// Storage: Honzon AuthorizationScopes (r:0 w:1)
 	fn unauthorize_all(c: u32, ) -> Weight {
 		(29_615_000 as Weight)
 			// Standard Error: 839_000
@@ -135,4 +137,84 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(33 as Weight))
 			.saturating_add(T::DbWeight::get().writes(17 as Weight))
 	}
+	// Storage: CdpEngine CollateralParams (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: Dex TradingPairStatuses (r:2 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Loans Positions (r:1 w:1)
+	// Storage: Loans TotalPositions (r:1 w:1)
+	// Storage: CdpEngine DebitExchangeRate (r:1 w:0)
+	// Storage: Prices LockedPrice (r:2 w:0)
+	// Storage: AcalaOracle Values (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Rewards PoolInfos (r:1 w:1)
+	// Storage: Rewards SharesAndWithdrawnRewards (r:1 w:1)
+	fn expand_position_collateral() -> Weight {
+		(197_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: CdpEngine CollateralParams (r:1 w:0)
+	// Storage: Loans Positions (r:1 w:1)
+	// Storage: CdpEngine DebitExchangeRate (r:1 w:0)
+	// Storage: Dex TradingPairStatuses (r:2 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	// Storage: Loans TotalPositions (r:1 w:1)
+	// Storage: Prices LockedPrice (r:2 w:0)
+	// Storage: AcalaOracle Values (r:1 w:0)
+	// Storage: System Account (r:1 w:1)
+	// Storage: Rewards PoolInfos (r:1 w:1)
+	// Storage: Rewards SharesAndWithdrawnRewards (r:1 w:1)
+	fn shrink_position_debit() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: Honzon Authorization (r:1 w:1)
+	// Storage: Balances Reserves (r:1 w:1)
+	// Storage: Honzon AuthorizationScopes (r:0 w:1)
+	fn authorize_with_scope() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: Honzon Authorization (r:1 w:0)
+	// Storage: Honzon AuthorizationScopes (r:1 w:0)
+	// Storage: CdpEngine CollateralParams (r:1 w:0)
+	// Storage: Loans Positions (r:1 w:1)
+	// Storage: Loans TotalPositions (r:1 w:1)
+	// Storage: Tokens Accounts (r:3 w:3)
+	// Storage: Tokens TotalIssuance (r:1 w:1)
+	fn adjust_loan_by_authorizee() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(27 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: Honzon Authorization (r:1 w:0)
+	// Storage: Honzon AuthorizationScopes (r:1 w:0)
+	// Storage: Loans Positions (r:1 w:1)
+	// Storage: Dex TradingPairStatuses (r:2 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Tokens Accounts (r:5 w:5)
+	fn close_loan_has_debit_by_dex_by_authorizee() -> Weight {
+		(375_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(34 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	// Storage: Loans CrossCollateral (r:1 w:1)
+	// Storage: Loans TotalPositions (r:1 w:0)
+	// Storage: Loans Positions (r:1 w:0)
+	// Storage: CdpEngine CollateralParams (r:1 w:0)
+	fn set_cross_collateral(c: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
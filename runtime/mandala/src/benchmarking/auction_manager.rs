@@ -18,12 +18,13 @@
 
 use crate::{
 	dollar, AccountId, AuctionId, AuctionManager, CdpTreasury, Currencies, CurrencyId, EmergencyShutdown,
-	GetStableCurrencyId, GetStakingCurrencyId, Price, Runtime,
+	GetStableCurrencyId, GetStakingCurrencyId, Price, Rate, Ratio, Runtime, System,
 };
 
 use super::utils::feed_price;
 use frame_benchmarking::account;
 use frame_system::RawOrigin;
+use module_auction_manager::DutchAuctionParams;
 use module_support::{AuctionManager as AuctionManagerTrait, CDPTreasury};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::MultiCurrency;
@@ -62,6 +63,79 @@ runtime_benchmarks! {
 		// shutdown
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 	}: cancel(RawOrigin::None, auction_id)
+
+	set_dutch_auction_params {
+	}: _(
+		RawOrigin::Root,
+		STAKING,
+		Some(DutchAuctionParams {
+			start_price_ratio: Ratio::saturating_from_rational(120, 100),
+			price_decay_per_block: Rate::saturating_from_rational(1, 1000),
+			minimum_price_ratio: Ratio::saturating_from_rational(80, 100),
+			reset_blocks: 1_000,
+			reset_price_ratio: Ratio::saturating_from_rational(50, 100),
+		})
+	)
+
+	// `take_dutch_auction`, worst case:
+	// the auction is finished and the remain collateral is refunded
+	take_dutch_auction {
+		let buyer: AccountId = account("buyer", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+
+		// set balance
+		Currencies::deposit(STABLECOIN, &buyer, 200 * dollar(STABLECOIN))?;
+		Currencies::deposit(STAKING, &funder, 2 * dollar(STAKING))?;
+		CdpTreasury::deposit_collateral(&funder, STAKING, 2 * dollar(STAKING))?;
+
+		// feed price
+		feed_price(vec![(STAKING, Price::saturating_from_integer(120))])?;
+
+		// create Dutch collateral auction
+		AuctionManager::set_dutch_auction_params(
+			RawOrigin::Root.into(),
+			STAKING,
+			Some(DutchAuctionParams {
+				start_price_ratio: Ratio::saturating_from_rational(120, 100),
+				price_decay_per_block: Rate::saturating_from_rational(1, 1000),
+				minimum_price_ratio: Ratio::saturating_from_rational(80, 100),
+			reset_blocks: 1_000,
+			reset_price_ratio: Ratio::saturating_from_rational(50, 100),
+			}),
+		)?;
+		AuctionManager::new_collateral_auction(&funder, STAKING, 2 * dollar(STAKING), 100 * dollar(STABLECOIN))?;
+		let auction_id: AuctionId = Default::default();
+	}: _(RawOrigin::Signed(buyer), auction_id, 2 * dollar(STAKING), Price::saturating_from_integer(200))
+
+	// `reset_dutch_auction`, worst case:
+	// the auction has run too long and is anchored to the latest oracle price
+	reset_dutch_auction {
+		let caller: AccountId = account("caller", 0, SEED);
+		let funder: AccountId = account("funder", 0, SEED);
+
+		// set balance
+		Currencies::deposit(STAKING, &funder, 2 * dollar(STAKING))?;
+		CdpTreasury::deposit_collateral(&funder, STAKING, 2 * dollar(STAKING))?;
+
+		// feed price
+		feed_price(vec![(STAKING, Price::saturating_from_integer(120))])?;
+
+		// create Dutch collateral auction
+		AuctionManager::set_dutch_auction_params(
+			RawOrigin::Root.into(),
+			STAKING,
+			Some(DutchAuctionParams {
+				start_price_ratio: Ratio::saturating_from_rational(120, 100),
+				price_decay_per_block: Rate::saturating_from_rational(1, 1000),
+				minimum_price_ratio: Ratio::saturating_from_rational(80, 100),
+				reset_blocks: 1_000,
+				reset_price_ratio: Ratio::saturating_from_rational(50, 100),
+			}),
+		)?;
+		AuctionManager::new_collateral_auction(&funder, STAKING, 2 * dollar(STAKING), 100 * dollar(STABLECOIN))?;
+		let auction_id: AuctionId = Default::default();
+		System::set_block_number(System::block_number() + 1_000);
+	}: _(RawOrigin::Signed(caller), auction_id)
 }
 
 #[cfg(test)]
@@ -27,7 +27,8 @@ use super::utils::{feed_price, set_balance};
 use frame_benchmarking::account;
 use frame_support::traits::OnInitialize;
 use frame_system::RawOrigin;
-use module_support::DEXManager;
+use module_cdp_engine::InterestRateModel;
+use module_support::{CDPTreasury, DEXManager};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::{Change, GetByKey};
 use sp_runtime::{
@@ -68,6 +69,17 @@ fn inject_liquidity(
 	Ok(())
 }
 
+fn interest_rate_model() -> InterestRateModel {
+	InterestRateModel {
+		base_rate: Rate::saturating_from_rational(1, 100000000),
+		kink: Ratio::saturating_from_rational(80, 100),
+		multiplier: Rate::saturating_from_rational(1, 10000000),
+		jump_multiplier: Rate::saturating_from_rational(1, 1000000),
+		min_rate: Rate::saturating_from_rational(1, 100000000),
+		max_rate: Rate::saturating_from_rational(1, 100000),
+	}
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_cdp_engine }
 
@@ -113,6 +125,7 @@ runtime_benchmarks! {
 				Change::NewValue(Some(Ratio::saturating_from_rational(0, 100))),
 				Change::NewValue(min_debit_value * 100),
 			)?;
+			CdpEngine::set_interest_rate_model(RawOrigin::Root.into(), currency_id, Some(interest_rate_model()))?;
 
 			// adjust position
 			CdpEngine::adjust_position(&owner, currency_id, collateral_amount.try_into().unwrap(), min_debit_amount)?;
@@ -139,6 +152,27 @@ runtime_benchmarks! {
 	set_global_params {
 	}: _(RawOrigin::Root, Rate::saturating_from_rational(1, 1000000))
 
+	set_liquidation_close_factor {
+	}: _(RawOrigin::Root, STAKING, Some(Ratio::saturating_from_rational(50, 100)))
+
+	set_keeper_reward_ratio {
+	}: _(RawOrigin::Root, Ratio::saturating_from_rational(10, 100))
+
+	set_interest_rate_model {
+	}: _(
+		RawOrigin::Root,
+		STAKING,
+		Some(interest_rate_model())
+	)
+
+	// pay the keeper reward from the surplus in `keeper_liquidate`
+	keeper_reward {
+		let keeper: AccountId = account("keeper", 0, SEED);
+		CdpTreasury::on_system_surplus(10 * dollar(STABLECOIN))?;
+	}: {
+		CdpTreasury::withdraw_surplus(&keeper, 10 * dollar(STABLECOIN))?;
+	}
+
 	// `liquidate` by_auction
 	liquidate_by_auction {
 		let b in 1 .. MaxAuctionsCount::get();
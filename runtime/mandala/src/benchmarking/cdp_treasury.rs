@@ -17,17 +17,19 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, CdpTreasury, Currencies, CurrencyId, Dex, GetStableCurrencyId, GetStakingCurrencyId,
-	MaxAuctionsCount, Runtime,
+	dollar, AccountId, CdpTreasury, Currencies, CurrencyId, Dex, GetNativeCurrencyId, GetStableCurrencyId,
+	GetStakingCurrencyId, MaxAuctionsCount, Runtime,
 };
 
 use super::utils::set_balance;
 use frame_benchmarking::whitelisted_caller;
+use frame_support::traits::OnFinalize;
 use frame_system::RawOrigin;
 use module_support::{CDPTreasury, SwapLimit};
 use orml_benchmarking::runtime_benchmarks;
-use orml_traits::MultiCurrency;
+use orml_traits::{Change, MultiCurrency};
 
+const NATIVE: CurrencyId = GetNativeCurrencyId::get();
 const STABLECOIN: CurrencyId = GetStableCurrencyId::get();
 const STAKING: CurrencyId = GetStakingCurrencyId::get();
 
@@ -66,6 +68,30 @@ runtime_benchmarks! {
 	extract_surplus_to_treasury {
 		CdpTreasury::on_system_surplus(1_000 * dollar(STABLECOIN))?;
 	}: _(RawOrigin::Root, 200 * dollar(STABLECOIN))
+
+	set_surplus_and_debit_auction_params {
+	}: _(
+		RawOrigin::Root,
+		Change::NewValue(100 * dollar(STABLECOIN)),
+		Change::NewValue(1_000 * dollar(STABLECOIN)),
+		Change::NewValue(1_000 * dollar(NATIVE)),
+		Change::NewValue(100 * dollar(STABLECOIN))
+	)
+
+	// create surplus auction after offset surplus and debit
+	on_finalize {
+		CdpTreasury::set_surplus_and_debit_auction_params(
+			RawOrigin::Root.into(),
+			Change::NewValue(100 * dollar(STABLECOIN)),
+			Change::NewValue(1_000 * dollar(STABLECOIN)),
+			Change::NewValue(1_000 * dollar(NATIVE)),
+			Change::NewValue(100 * dollar(STABLECOIN)),
+		)?;
+		CdpTreasury::on_system_surplus(2_000 * dollar(STABLECOIN))?;
+		CdpTreasury::on_system_debit(100 * dollar(STABLECOIN))?;
+	}: {
+		CdpTreasury::on_finalize(1);
+	}
 }
 
 #[cfg(test)]
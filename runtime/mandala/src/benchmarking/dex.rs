@@ -18,17 +18,19 @@
 
 use crate::{
 	dollar, AccountId, Balance, Currencies, CurrencyId, Dex, Event, GetLiquidCurrencyId, GetNativeCurrencyId,
-	GetStableCurrencyId, GetStakingCurrencyId, Runtime, System, TradingPathLimit,
+	GetStableCurrencyId, GetStakingCurrencyId, Ratio, Runtime, System, TradingPathLimit,
 };
 
+use super::utils::lookup_of_account;
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_system::RawOrigin;
-use module_dex::TradingPairStatus;
+use module_dex::{Curve, SwapDeadline, TradingPairStatus};
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::{MultiCurrency, MultiCurrencyExtended};
 use primitives::TradingPair;
 use runtime_common::{BNC, RENBTC, VSKSM};
-use sp_runtime::traits::UniqueSaturatedInto;
+use sp_core::H160;
+use sp_runtime::{traits::UniqueSaturatedInto, FixedPointNumber};
 use sp_std::prelude::*;
 
 const SEED: u32 = 0;
@@ -104,6 +106,65 @@ runtime_benchmarks! {
 		assert_last_event(module_dex::Event::DisableTradingPair{trading_pair}.into());
 	}
 
+	// set exchange fee rate of a trading pair
+	set_exchange_fee {
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+	}: _(RawOrigin::Root, trading_pair.first(), trading_pair.second(), Some((3, 1000)))
+	verify {
+		assert_last_event(module_dex::Event::ExchangeFeeUpdated{trading_pair, exchange_fee: Some((3, 1000))}.into());
+	}
+
+	set_curve {
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+		let curve = Curve::stable_swap(100);
+	}: _(RawOrigin::Root, trading_pair.first(), trading_pair.second(), curve)
+	verify {
+		assert_last_event(module_dex::Event::CurveUpdated{trading_pair, curve}.into());
+	}
+
+	set_route_tokens {
+		let route_tokens = CURRENCY_LIST.to_vec();
+	}: _(RawOrigin::Root, route_tokens.clone())
+	verify {
+		assert_last_event(module_dex::Event::RouteTokensUpdated{route_tokens}.into());
+	}
+
+	// set the share of exchange fee charged as protocol fee
+	set_protocol_fee_share {
+		let share = Ratio::saturating_from_rational(1, 6);
+	}: _(RawOrigin::Root, share)
+	verify {
+		assert_last_event(module_dex::Event::ProtocolFeeShareUpdated{share}.into());
+	}
+
+	// worst: withdraw both sides of the charged protocol fee
+	withdraw_protocol_fee {
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+		Dex::set_protocol_fee_share(RawOrigin::Root.into(), Ratio::saturating_from_rational(1, 6))?;
+		inject_liquidity(maker, trading_pair.first(), trading_pair.second(), 10_000 * dollar(trading_pair.first()), 10_000 * dollar(trading_pair.second()), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.first(), &taker, (1_000 * dollar(trading_pair.first())).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.second(), &taker, (1_000 * dollar(trading_pair.second())).unique_saturated_into())?;
+		Dex::swap_with_exact_supply(RawOrigin::Signed(taker.clone()).into(), vec![trading_pair.first(), trading_pair.second()], 100 * dollar(trading_pair.first()), 0)?;
+		Dex::swap_with_exact_supply(RawOrigin::Signed(taker.clone()).into(), vec![trading_pair.second(), trading_pair.first()], 100 * dollar(trading_pair.second()), 0)?;
+	}: _(RawOrigin::Signed(taker), trading_pair.first(), trading_pair.second())
+	verify {
+		assert_eq!(Dex::protocol_fee_pool(trading_pair), (0, 0));
+	}
+
+	// flash swap without callback contract
+	flash_swap {
+		let maker: AccountId = account("maker", 0, SEED);
+		let borrower: AccountId = whitelisted_caller();
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+		inject_liquidity(maker, trading_pair.first(), trading_pair.second(), 10_000 * dollar(trading_pair.first()), 10_000 * dollar(trading_pair.second()), false)?;
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.first(), &borrower, (1_000 * dollar(trading_pair.first())).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.second(), &borrower, (1_000 * dollar(trading_pair.second())).unique_saturated_into())?;
+	}: _(RawOrigin::Signed(borrower), trading_pair.first(), trading_pair.second(), 100 * dollar(trading_pair.first()), 100 * dollar(trading_pair.second()), H160::default(), vec![])
+
 	// list a Provisioning trading pair
 	list_provisioning {
 		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
@@ -231,6 +292,73 @@ runtime_benchmarks! {
 		assert_eq!(Currencies::free_balance(trading_pair.dex_share_currency_id(), &founder), 2_000_000_000_000);
 	}
 
+	abort_provisioning {
+		let founder: AccountId = whitelisted_caller();
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+		if let TradingPairStatus::Enabled = Dex::trading_pair_statuses(trading_pair) {
+			Dex::disable_trading_pair(RawOrigin::Root.into(), trading_pair.first(), trading_pair.second())?;
+		}
+		Dex::list_provisioning(
+			RawOrigin::Root.into(),
+			trading_pair.first(),
+			trading_pair.second(),
+			dollar(trading_pair.first()),
+			dollar(trading_pair.second()),
+			100 * dollar(trading_pair.first()),
+			100 * dollar(trading_pair.second()),
+			0
+		)?;
+
+		// set balance
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.first(), &founder, (10 * dollar(trading_pair.first())).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.second(), &founder, (10 * dollar(trading_pair.second())).unique_saturated_into())?;
+
+		Dex::add_provision(
+			RawOrigin::Signed(founder.clone()).into(),
+			trading_pair.first(),
+			trading_pair.second(),
+			dollar(trading_pair.first()),
+			dollar(trading_pair.second()),
+		)?;
+	}: _(RawOrigin::Root, trading_pair.first(), trading_pair.second())
+	verify {
+		assert_last_event(module_dex::Event::ProvisioningAborted{trading_pair, accumulated_provision_0: dollar(trading_pair.first()), accumulated_provision_1: dollar(trading_pair.second())}.into());
+	}
+
+	refund_provision {
+		let founder: AccountId = whitelisted_caller();
+		let trading_pair = TradingPair::from_currency_ids(STABLECOIN, NATIVE).unwrap();
+		if let TradingPairStatus::Enabled = Dex::trading_pair_statuses(trading_pair) {
+			Dex::disable_trading_pair(RawOrigin::Root.into(), trading_pair.first(), trading_pair.second())?;
+		}
+		Dex::list_provisioning(
+			RawOrigin::Root.into(),
+			trading_pair.first(),
+			trading_pair.second(),
+			dollar(trading_pair.first()),
+			dollar(trading_pair.second()),
+			100 * dollar(trading_pair.first()),
+			100 * dollar(trading_pair.second()),
+			0
+		)?;
+
+		// set balance
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.first(), &founder, (10 * dollar(trading_pair.first())).unique_saturated_into())?;
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(trading_pair.second(), &founder, (10 * dollar(trading_pair.second())).unique_saturated_into())?;
+
+		Dex::add_provision(
+			RawOrigin::Signed(founder.clone()).into(),
+			trading_pair.first(),
+			trading_pair.second(),
+			dollar(trading_pair.first()),
+			dollar(trading_pair.second()),
+		)?;
+		Dex::abort_provisioning(RawOrigin::Root.into(), trading_pair.first(), trading_pair.second())?;
+	}: _(RawOrigin::Signed(whitelisted_caller()), founder.clone(), trading_pair.first(), trading_pair.second())
+	verify {
+		assert_last_event(module_dex::Event::RefundProvision{who: founder, currency_0: trading_pair.first(), contribution_0: dollar(trading_pair.first()), currency_1: trading_pair.second(), contribution_1: dollar(trading_pair.second())}.into());
+	}
+
 	// add liquidity but don't staking lp
 	add_liquidity {
 		let first_maker: AccountId = account("first_maker", 0, SEED);
@@ -330,6 +458,62 @@ runtime_benchmarks! {
 		// would panic the benchmark anyways, must add new currencies to CURRENCY_LIST for benchmarking to work
 		assert!(TradingPathLimit::get() < CURRENCY_LIST.len() as u32);
 	}
+
+	swap_with_exact_supply_to {
+		let u in 2 .. TradingPathLimit::get() as u32;
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		let dest: AccountId = account("dest", 0, SEED);
+
+		let mut path: Vec<CurrencyId> = vec![];
+		for i in 1 .. u {
+			if i == 1 {
+				let cur0 = CURRENCY_LIST[0];
+				let cur1 = CURRENCY_LIST[1];
+				path.push(cur0);
+				path.push(cur1);
+				inject_liquidity(maker.clone(), cur0, cur1, 10_000 * dollar(cur0), 10_000 * dollar(cur1), false)?;
+			} else {
+				path.push(CURRENCY_LIST[i as usize]);
+				inject_liquidity(maker.clone(), CURRENCY_LIST[i as usize - 1], CURRENCY_LIST[i as usize], 10_000 * dollar(CURRENCY_LIST[i as usize - 1]), 10_000 * dollar(CURRENCY_LIST[i as usize]), false)?;
+			}
+		}
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, (10_000 * dollar(path[0])).unique_saturated_into())?;
+	}: swap_with_exact_supply_to(RawOrigin::Signed(taker.clone()), path.clone(), 100 * dollar(path[0]), 0, lookup_of_account(dest), SwapDeadline::Timestamp(u64::MAX))
+	verify {
+		// would panic the benchmark anyways, must add new currencies to CURRENCY_LIST for benchmarking to work
+		assert!(TradingPathLimit::get() < CURRENCY_LIST.len() as u32);
+	}
+
+	swap_with_exact_target_to {
+		let u in 2 .. TradingPathLimit::get() as u32;
+
+		let maker: AccountId = account("maker", 0, SEED);
+		let taker: AccountId = whitelisted_caller();
+		let dest: AccountId = account("dest", 0, SEED);
+
+		let mut path: Vec<CurrencyId> = vec![];
+		for i in 1 .. u {
+			if i == 1 {
+				let cur0 = CURRENCY_LIST[0];
+				let cur1 = CURRENCY_LIST[1];
+				path.push(cur0);
+				path.push(cur1);
+				inject_liquidity(maker.clone(), cur0, cur1, 10_000 * dollar(cur0), 10_000 * dollar(cur1), false)?;
+			} else {
+				path.push(CURRENCY_LIST[i as usize]);
+				inject_liquidity(maker.clone(), CURRENCY_LIST[i as usize - 1], CURRENCY_LIST[i as usize], 10_000 * dollar(CURRENCY_LIST[i as usize - 1]), 10_000 * dollar(CURRENCY_LIST[i as usize]), false)?;
+			}
+		}
+
+		<Currencies as MultiCurrencyExtended<_>>::update_balance(path[0], &taker, (10_000 * dollar(path[0])).unique_saturated_into())?;
+	}: swap_with_exact_target_to(RawOrigin::Signed(taker.clone()), path.clone(), 10 * dollar(path[path.len() - 1]), 100 * dollar(path[0]), lookup_of_account(dest), SwapDeadline::Timestamp(u64::MAX))
+	verify {
+		// would panic the benchmark anyways, must add new currencies to CURRENCY_LIST for benchmarking to work
+		assert!(TradingPathLimit::get() < CURRENCY_LIST.len() as u32);
+	}
 }
 
 #[cfg(test)]
@@ -0,0 +1,90 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+use crate::{
+	dollar, AccountId, Balance, Currencies, CurrencyId, Dex, DexLimitOrder, GetStableCurrencyId, GetStakingCurrencyId,
+	Runtime,
+};
+
+use frame_benchmarking::{account, whitelisted_caller};
+use frame_system::RawOrigin;
+use orml_benchmarking::runtime_benchmarks;
+use orml_traits::MultiCurrencyExtended;
+use sp_runtime::traits::UniqueSaturatedInto;
+use sp_std::prelude::*;
+
+const SEED: u32 = 0;
+
+const STABLECOIN: CurrencyId = GetStableCurrencyId::get();
+const STAKING: CurrencyId = GetStakingCurrencyId::get();
+
+fn set_balance(currency_id: CurrencyId, who: &AccountId, amount: Balance) -> Result<(), &'static str> {
+	<Currencies as MultiCurrencyExtended<_>>::update_balance(currency_id, who, amount.unique_saturated_into())?;
+	Ok(())
+}
+
+fn inject_liquidity(maker: AccountId, max_amount_a: Balance, max_amount_b: Balance) -> Result<(), &'static str> {
+	set_balance(STAKING, &maker, max_amount_a)?;
+	set_balance(STABLECOIN, &maker, max_amount_b)?;
+
+	Dex::enable_trading_pair(RawOrigin::Root.into(), STAKING, STABLECOIN)?;
+
+	Dex::add_liquidity(
+		RawOrigin::Signed(maker).into(),
+		STAKING,
+		STABLECOIN,
+		max_amount_a,
+		max_amount_b,
+		Default::default(),
+		false,
+	)?;
+
+	Ok(())
+}
+
+runtime_benchmarks! {
+	{ Runtime, module_dex_limit_order }
+
+	place_order {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(STAKING, &caller, dollar(STAKING) * 10)?;
+	}: _(RawOrigin::Signed(caller), STAKING, STABLECOIN, dollar(STAKING), dollar(STABLECOIN) * 10)
+
+	cancel_order {
+		let caller: AccountId = whitelisted_caller();
+		set_balance(STAKING, &caller, dollar(STAKING) * 10)?;
+		let order_id = DexLimitOrder::do_place_order(&caller, STAKING, STABLECOIN, dollar(STAKING), dollar(STABLECOIN) * 10)?;
+	}: _(RawOrigin::Signed(caller), order_id)
+
+	execute_order {
+		let maker: AccountId = account("maker", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+		inject_liquidity(maker.clone(), dollar(STAKING) * 100, dollar(STABLECOIN) * 1000)?;
+		set_balance(STAKING, &maker, dollar(STAKING) * 10)?;
+		let order_id = DexLimitOrder::do_place_order(&maker, STAKING, STABLECOIN, dollar(STAKING), dollar(STABLECOIN))?;
+	}: _(RawOrigin::Signed(caller), order_id)
+}
+
+#[cfg(test)]
+mod tests {
+	use super::*;
+	use crate::benchmarking::utils::tests::new_test_ext;
+	use orml_benchmarking::impl_benchmark_test_suite;
+
+	impl_benchmark_test_suite!(new_test_ext(),);
+}
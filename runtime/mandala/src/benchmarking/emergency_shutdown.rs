@@ -24,7 +24,7 @@ use crate::{
 use super::utils::{feed_price, set_balance};
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_system::RawOrigin;
-use module_support::CDPTreasury;
+use module_support::{CDPTreasury, EmergencyShutdown as EmergencyShutdownT};
 use orml_benchmarking::runtime_benchmarks;
 use sp_runtime::traits::One;
 use sp_std::vec;
@@ -72,6 +72,40 @@ runtime_benchmarks! {
 		EmergencyShutdown::emergency_shutdown(RawOrigin::Root.into())?;
 		EmergencyShutdown::open_collateral_refund(RawOrigin::Root.into())?;
 	}: _(RawOrigin::Signed(caller),  1_000 * dollar(STABLECOIN))
+
+	emergency_shutdown_collateral {
+		let currency_id = CollateralCurrencyIds::get()[0];
+		feed_price(vec![(currency_id, Price::one())])?;
+	}: _(RawOrigin::Root, currency_id)
+
+	open_collateral_specific_refund {
+		let currency_id = CollateralCurrencyIds::get()[0];
+		feed_price(vec![(currency_id, Price::one())])?;
+		EmergencyShutdown::emergency_shutdown_collateral(RawOrigin::Root.into(), currency_id)?;
+	}: _(RawOrigin::Root, currency_id)
+
+	refund_collateral {
+		let currency_id = CollateralCurrencyIds::get()[0];
+		let funder: AccountId = account("funder", 0, SEED);
+		let caller: AccountId = whitelisted_caller();
+
+		set_balance(currency_id, &funder, 100 * dollar(currency_id));
+		CdpTreasury::deposit_collateral(&funder, currency_id, 100 * dollar(currency_id))?;
+		feed_price(vec![(currency_id, Price::one())])?;
+		CdpTreasury::issue_debit(&caller, 10 * dollar(STABLECOIN), true)?;
+
+		EmergencyShutdown::emergency_shutdown_collateral(RawOrigin::Root.into(), currency_id)?;
+		<EmergencyShutdown as EmergencyShutdownT>::on_collateral_settled(currency_id, 100 * dollar(currency_id));
+		EmergencyShutdown::open_collateral_specific_refund(RawOrigin::Root.into(), currency_id)?;
+	}: _(RawOrigin::Signed(caller), currency_id, 10 * dollar(STABLECOIN))
+
+	close_collateral_shutdown {
+		let currency_id = CollateralCurrencyIds::get()[0];
+		feed_price(vec![(currency_id, Price::one())])?;
+		EmergencyShutdown::emergency_shutdown_collateral(RawOrigin::Root.into(), currency_id)?;
+		<EmergencyShutdown as EmergencyShutdownT>::on_collateral_settled(currency_id, 100 * dollar(currency_id));
+		EmergencyShutdown::open_collateral_specific_refund(RawOrigin::Root.into(), currency_id)?;
+	}: _(RawOrigin::Root, currency_id)
 }
 
 #[cfg(test)]
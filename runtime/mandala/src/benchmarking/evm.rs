@@ -22,7 +22,10 @@ use crate::{
 };
 
 use super::utils::set_balance;
-use frame_support::dispatch::{DispatchError, DispatchResult};
+use frame_support::{
+	dispatch::{DispatchError, DispatchResult},
+	traits::OnInitialize,
+};
 use frame_system::RawOrigin;
 use module_evm::MaxCodeSize;
 use module_support::AddressMapping;
@@ -138,6 +141,12 @@ const FACTORY_CONTRACT: [u8; 399] = hex_literal::hex!("6080604052348015610010576
 runtime_benchmarks! {
 	{ Runtime, module_evm }
 
+	on_initialize {
+		module_evm::TransactionGasUsed::<Runtime>::put(vec![(1, 21_000)]);
+	}: {
+		EVM::on_initialize(1);
+	}
+
 	create {
 		let alice_account = alice_account_id();
 		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
@@ -217,6 +226,21 @@ runtime_benchmarks! {
 		assert_eq!(module_evm::AccountStorages::<Runtime>::get(&contract_address, H256::zero()), H256::from_low_u64_be(1));
 	}
 
+	schedule_call {
+		// Storage.store(1)
+		let input = hex_literal::hex!("6057361d0000000000000000000000000000000000000000000000000000000000000001").to_vec();
+		let alice_account = alice_account_id();
+		set_balance(NATIVE, &alice_account, 1_000_000 * dollar(NATIVE));
+
+		// contract address when it gets deployed
+		let contract_address = H160::from(hex_literal::hex!("5e0b4bfa0b55932a3587e648c3552a6515ba56b1"));
+
+		frame_support::assert_ok!(EVM::create(Origin::signed(alice_account.clone()), STORAGE_CONTRACT.to_vec(), 0, 21_000_000, 100_000));
+	}: _(RawOrigin::Signed(alice_account), contract_address, input, 0, 21_000_000, 100_000)
+	verify {
+		assert_eq!(module_idle_scheduler::Tasks::<Runtime>::iter().count(), 1);
+	}
+
 	transfer_maintainer {
 		let alice_account = alice_account_id();
 
@@ -307,6 +331,7 @@ mod tests {
 				0,
 				1_000_000,
 				100_000,
+				vec![],
 				config,
 			)
 			.unwrap();
@@ -332,6 +357,7 @@ mod tests {
 				0,
 				1_000_000,
 				100_000,
+				vec![],
 				config,
 			)
 			.unwrap();
@@ -342,7 +368,15 @@ mod tests {
 			let input =
 				hex_literal::hex!("6057361d0000000000000000000000000000000000000000000000000000000000000001").to_vec();
 			let result = <Runtime as module_evm::Config>::Runner::call(
-				caller, caller, address, input, 0, 1_000_000, 100_000, config,
+				caller,
+				caller,
+				address,
+				input,
+				0,
+				1_000_000,
+				100_000,
+				vec![],
+				config,
 			)
 			.unwrap();
 			assert!(result.exit_reason.is_succeed());
@@ -17,7 +17,7 @@
 // along with this program. If not, see <https://www.gnu.org/licenses/>.
 
 use crate::{
-	dollar, AccountId, Amount, Balance, CdpEngine, CollateralCurrencyIds, Currencies, CurrencyId,
+	dollar, AccountId, Amount, Balance, BlockNumber, CdpEngine, CollateralCurrencyIds, Currencies, CurrencyId,
 	DepositPerAuthorization, Dex, ExistentialDeposits, GetLiquidCurrencyId, GetNativeCurrencyId, GetStableCurrencyId,
 	GetStakingCurrencyId, Honzon, Price, Rate, Ratio, Runtime,
 };
@@ -25,6 +25,7 @@ use crate::{
 use super::utils::{feed_price, set_balance};
 use frame_benchmarking::{account, whitelisted_caller};
 use frame_system::RawOrigin;
+use module_honzon::AuthorizationScope;
 use orml_benchmarking::runtime_benchmarks;
 use orml_traits::{Change, GetByKey, MultiCurrencyExtended};
 use sp_runtime::{
@@ -75,6 +76,17 @@ fn inject_liquidity(
 	Ok(())
 }
 
+fn authorization_scope() -> AuthorizationScope<BlockNumber> {
+	AuthorizationScope {
+		expiry: Some(1_000),
+		max_collateral: None,
+		max_debit: None,
+		can_adjust: true,
+		can_transfer: false,
+		can_close_by_dex: true,
+	}
+}
+
 runtime_benchmarks! {
 	{ Runtime, module_honzon }
 
@@ -120,6 +132,15 @@ runtime_benchmarks! {
 		}
 	}: _(RawOrigin::Signed(caller))
 
+	authorize_with_scope {
+		let caller: AccountId = whitelisted_caller();
+		let to: AccountId = account("to", 0, SEED);
+		let to_lookup = AccountIdLookup::unlookup(to);
+
+		// set balance
+		set_balance(NATIVE, &caller, DepositPerAuthorization::get());
+	}: _(RawOrigin::Signed(caller), STAKING, to_lookup, authorization_scope())
+
 	// `adjust_loan`, best case:
 	// adjust both collateral and debit
 	adjust_loan {
@@ -237,6 +258,197 @@ runtime_benchmarks! {
 		)?;
 
 	}: _(RawOrigin::Signed(sender), LIQUID, collateral_amount)
+
+	expand_position_collateral {
+		let currency_id: CurrencyId = STAKING;
+		let sender: AccountId = whitelisted_caller();
+		let maker: AccountId = account("maker", 0, SEED);
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(currency_id), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+
+		// set balance and inject liquidity
+		set_balance(currency_id, &sender, collateral_amount + ExistentialDeposits::get(&currency_id));
+		inject_liquidity(maker, currency_id, STABLECOIN, 10_000 * dollar(currency_id), 10_000 * dollar(STABLECOIN), false)?;
+
+		feed_price(vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// initialize sender's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(sender.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			debit_amount,
+		)?;
+	}: _(RawOrigin::Signed(sender), currency_id, debit_value, 0)
+
+	shrink_position_debit {
+		let currency_id: CurrencyId = STAKING;
+		let sender: AccountId = whitelisted_caller();
+		let maker: AccountId = account("maker", 0, SEED);
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(currency_id), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+
+		// set balance and inject liquidity
+		set_balance(currency_id, &sender, collateral_amount + ExistentialDeposits::get(&currency_id));
+		inject_liquidity(maker, currency_id, STABLECOIN, 10_000 * dollar(currency_id), 10_000 * dollar(STABLECOIN), false)?;
+
+		feed_price(vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// initialize sender's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(sender.clone()).into(),
+			currency_id,
+			collateral_amount.try_into().unwrap(),
+			debit_amount,
+		)?;
+	}: _(RawOrigin::Signed(sender), currency_id, collateral_amount / 5, 0)
+
+	adjust_loan_by_authorizee {
+		let currency_id: CurrencyId = CollateralCurrencyIds::get()[0];
+		let sender: AccountId = account("sender", 0, SEED);
+		let sender_lookup = AccountIdLookup::unlookup(sender.clone());
+		let receiver: AccountId = whitelisted_caller();
+		let receiver_lookup = AccountIdLookup::unlookup(receiver.clone());
+
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(currency_id);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(currency_id), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+
+		// set balance
+		set_balance(currency_id, &sender, collateral_amount + ExistentialDeposits::get(&currency_id));
+		set_balance(NATIVE, &sender, DepositPerAuthorization::get());
+
+		// feed price
+		feed_price(vec![(currency_id, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			currency_id,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// authorize receiver
+		Honzon::authorize_with_scope(
+			RawOrigin::Signed(sender.clone()).into(),
+			currency_id,
+			receiver_lookup,
+			authorization_scope(),
+		)?;
+	}: _(RawOrigin::Signed(receiver), currency_id, sender_lookup, collateral_amount.try_into().unwrap(), debit_amount)
+
+	close_loan_has_debit_by_dex_by_authorizee {
+		let currency_id: CurrencyId = LIQUID;
+		let sender: AccountId = account("sender", 0, SEED);
+		let sender_lookup = AccountIdLookup::unlookup(sender.clone());
+		let receiver: AccountId = whitelisted_caller();
+		let receiver_lookup = AccountIdLookup::unlookup(receiver.clone());
+		let maker: AccountId = account("maker", 0, SEED);
+		let debit_value = 100 * dollar(STABLECOIN);
+		let debit_exchange_rate = CdpEngine::get_debit_exchange_rate(LIQUID);
+		let debit_amount = debit_exchange_rate.reciprocal().unwrap().saturating_mul_int(debit_value);
+		let debit_amount: Amount = debit_amount.unique_saturated_into();
+		let collateral_value = 10 * debit_value;
+		let collateral_amount = Price::saturating_from_rational(dollar(LIQUID), dollar(STABLECOIN)).saturating_mul_int(collateral_value);
+
+		// set balance and inject liquidity
+		set_balance(LIQUID, &sender, (10 * collateral_amount) + ExistentialDeposits::get(&LIQUID));
+		set_balance(NATIVE, &sender, DepositPerAuthorization::get());
+		inject_liquidity(maker.clone(), LIQUID, STAKING, 10_000 * dollar(LIQUID), 10_000 * dollar(STAKING), false)?;
+		inject_liquidity(maker, STAKING, STABLECOIN, 10_000 * dollar(STAKING), 10_000 * dollar(STABLECOIN), false)?;
+
+		feed_price(vec![(STAKING, Price::one())])?;
+
+		// set risk params
+		CdpEngine::set_collateral_params(
+			RawOrigin::Root.into(),
+			LIQUID,
+			Change::NoChange,
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(Some(Rate::saturating_from_rational(10, 100))),
+			Change::NewValue(Some(Ratio::saturating_from_rational(150, 100))),
+			Change::NewValue(debit_value * 100),
+		)?;
+
+		// initialize sender's loan
+		Honzon::adjust_loan(
+			RawOrigin::Signed(sender.clone()).into(),
+			LIQUID,
+			(10 * collateral_amount).try_into().unwrap(),
+			debit_amount,
+		)?;
+
+		// authorize receiver
+		Honzon::authorize_with_scope(
+			RawOrigin::Signed(sender.clone()).into(),
+			LIQUID,
+			receiver_lookup,
+			authorization_scope(),
+		)?;
+	}: _(RawOrigin::Signed(receiver), LIQUID, sender_lookup, collateral_amount)
+
+	// `set_cross_collateral`, worst case:
+	// disable the cross-collateral mode which has positions of `c` collateral types
+	set_cross_collateral {
+		let c in 0 .. CollateralCurrencyIds::get().len() as u32;
+
+		let caller: AccountId = whitelisted_caller();
+		let currency_ids = CollateralCurrencyIds::get();
+
+		for i in 0 .. c {
+			let currency_id = currency_ids[i as usize];
+			let collateral_amount = 100 * dollar(currency_id);
+
+			// set balance
+			set_balance(currency_id, &caller, collateral_amount + ExistentialDeposits::get(&currency_id));
+
+			Honzon::adjust_loan(
+				RawOrigin::Signed(caller.clone()).into(),
+				currency_id,
+				collateral_amount.try_into().unwrap(),
+				0,
+			)?;
+		}
+
+		Honzon::set_cross_collateral(RawOrigin::Signed(caller.clone()).into(), true)?;
+	}: _(RawOrigin::Signed(caller), false)
 }
 
 #[cfg(test)]
@@ -30,6 +30,7 @@ pub mod cdp_treasury;
 pub mod collator_selection;
 pub mod currencies;
 pub mod dex;
+pub mod dex_limit_order;
 pub mod dex_oracle;
 pub mod emergency_shutdown;
 pub mod evm;
@@ -49,10 +49,10 @@ use frame_system::{EnsureRoot, RawOrigin};
 use hex_literal::hex;
 use module_asset_registry::{AssetIdMaps, EvmErc20InfoMapping, FixedRateOfForeignAsset};
 use module_currencies::{BasicCurrencyAdapter, Currency};
-use module_evm::{CallInfo, CreateInfo, EvmTask, Runner};
+use module_evm::{AccessListItem, CallInfo, CreateInfo, EvmTask, Runner};
 use module_evm_accounts::EvmAddressMapping;
 use module_relaychain::RelayChainCallBuilder;
-use module_support::{AssetIdMapping, DispatchableTask, ExchangeRateProvider};
+use module_support::{AssetIdMapping, DEXManager, DispatchableTask, ExchangeRateProvider, SwapLimit};
 use module_transaction_payment::{Multiplier, TargetedFeeAdjustment, TransactionFeePoolTrader};
 use scale_info::TypeInfo;
 
@@ -118,7 +118,7 @@ pub use runtime_common::{
 	EnsureRootOrAllTechnicalCommittee, EnsureRootOrHalfFinancialCouncil, EnsureRootOrHalfGeneralCouncil,
 	EnsureRootOrHalfHomaCouncil, EnsureRootOrOneGeneralCouncil, EnsureRootOrOneThirdsTechnicalCommittee,
 	EnsureRootOrThreeFourthsGeneralCouncil, EnsureRootOrTwoThirdsGeneralCouncil,
-	EnsureRootOrTwoThirdsTechnicalCommittee, ExchangeRate, FinancialCouncilInstance,
+	EnsureRootOrTwoThirdsTechnicalCommittee, EvmFlashSwapCallback, ExchangeRate, FinancialCouncilInstance,
 	FinancialCouncilMembershipInstance, GasToWeight, GeneralCouncilInstance, GeneralCouncilMembershipInstance,
 	HomaCouncilInstance, HomaCouncilMembershipInstance, MaxTipsOfPriority, OffchainSolutionWeightLimit,
 	OperationalFeeMultiplier, OperatorMembershipInstanceAcala, Price, ProxyType, Rate, Ratio,
@@ -168,6 +168,7 @@ parameter_types! {
 	pub const TreasuryPalletId: PalletId = PalletId(*b"aca/trsy");
 	pub const LoansPalletId: PalletId = PalletId(*b"aca/loan");
 	pub const DEXPalletId: PalletId = PalletId(*b"aca/dexm");
+	pub const DexLimitOrderPalletId: PalletId = PalletId(*b"aca/lmto");
 	pub const CDPTreasuryPalletId: PalletId = PalletId(*b"aca/cdpt");
 	pub const HonzonTreasuryPalletId: PalletId = PalletId(*b"aca/hztr");
 	pub const HomaPalletId: PalletId = PalletId(*b"aca/homa");
@@ -193,6 +194,7 @@ pub fn get_all_module_accounts() -> Vec<AccountId> {
 		TreasuryPalletId::get().into_account(),
 		LoansPalletId::get().into_account(),
 		DEXPalletId::get().into_account(),
+		DexLimitOrderPalletId::get().into_account(),
 		CDPTreasuryPalletId::get().into_account(),
 		HonzonTreasuryPalletId::get().into_account(),
 		HomaTreasuryPalletId::get().into_account(),
@@ -979,10 +981,12 @@ impl module_auction_manager::Config for Runtime {
 	type AuctionTimeToClose = AuctionTimeToClose;
 	type AuctionDurationSoftCap = AuctionDurationSoftCap;
 	type GetStableCurrencyId = GetStableCurrencyId;
+	type GetNativeCurrencyId = GetNativeCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
 	type UnsignedPriority = runtime_common::AuctionManagerUnsignedPriority;
 	type EmergencyShutdown = EmergencyShutdown;
+	type UpdateOrigin = EnsureRootOrHalfFinancialCouncil;
 	type WeightInfo = weights::module_auction_manager::WeightInfo<Runtime>;
 }
 
@@ -1068,6 +1072,8 @@ parameter_types! {
 impl module_cdp_engine::Config for Runtime {
 	type Event = Event;
 	type PriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
+	type DEX = Dex;
+	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type DefaultLiquidationRatio = DefaultLiquidationRatio;
 	type DefaultDebitExchangeRate = DefaultDebitExchangeRate;
@@ -1098,6 +1104,8 @@ impl module_emergency_shutdown::Config for Runtime {
 	type Event = Event;
 	type CollateralCurrencyIds = CollateralCurrencyIds;
 	type PriceSource = Prices;
+	type LockedPriceSource = module_prices::PriorityLockedPriceProvider<Runtime>;
+	type GetStableCurrencyId = GetStableCurrencyId;
 	type CDPTreasury = CdpTreasury;
 	type AuctionManagerHandler = AuctionManager;
 	type ShutdownOrigin = EnsureRootOrHalfGeneralCouncil;
@@ -1107,6 +1115,8 @@ impl module_emergency_shutdown::Config for Runtime {
 parameter_types! {
 	pub const GetExchangeFee: (u32, u32) = (1, 1000);	// 0.1%
 	pub const TradingPathLimit: u32 = 4;
+	pub const MaxRouteTokens: u32 = 8;
+	pub const ExtendedProvisioningBlocks: BlockNumber = 2 * DAYS;
 	pub EnabledTradingPairs: Vec<TradingPair> = vec![
 		TradingPair::from_currency_ids(AUSD, ACA).unwrap(),
 		TradingPair::from_currency_ids(AUSD, DOT).unwrap(),
@@ -1121,21 +1131,48 @@ impl module_dex::Config for Runtime {
 	type Currency = Currencies;
 	type GetExchangeFee = GetExchangeFee;
 	type TradingPathLimit = TradingPathLimit;
+	type MaxRouteTokens = MaxRouteTokens;
 	type PalletId = DEXPalletId;
 	type Erc20InfoMapping = EvmErc20InfoMapping<Runtime>;
 	type DEXIncentives = Incentives;
 	type WeightInfo = weights::module_dex::WeightInfo<Runtime>;
 	type ListingOrigin = EnsureRootOrHalfGeneralCouncil;
 	type OnLiquidityPoolUpdated = ();
+	type ExtendedProvisioningBlocks = ExtendedProvisioningBlocks;
+	type TreasuryAccount = TreasuryAccount;
+	type FlashSwapCallback = EvmFlashSwapCallback<Runtime>;
+	type UnixTime = Timestamp;
+}
+
+parameter_types! {
+	pub const DexOracleObservationPeriod: Moment = 1000 * 60 * 10; // 10 mins
+	pub const DexOracleMaxObservations: u32 = 24 * 60 / 10 + 1; // 24 hours of observations
 }
 
 impl module_dex_oracle::Config for Runtime {
 	type DEX = Dex;
 	type Time = Timestamp;
 	type UpdateOrigin = EnsureRootOrHalfGeneralCouncil;
+	type ObservationPeriod = DexOracleObservationPeriod;
+	type MaxObservations = DexOracleMaxObservations;
 	type WeightInfo = weights::module_dex_oracle::WeightInfo<Runtime>;
 }
 
+parameter_types! {
+	pub const MaxOrdersPerAccount: u32 = 20;
+	pub const MaxCheckedOrdersPerBlock: u32 = 20;
+}
+
+impl module_dex_limit_order::Config for Runtime {
+	type Event = Event;
+	type Currency = Currencies;
+	type DEX = Dex;
+	type MaxOrdersPerAccount = MaxOrdersPerAccount;
+	type MaxCheckedOrdersPerBlock = MaxCheckedOrdersPerBlock;
+	type PalletId = DexLimitOrderPalletId;
+	type WeightInfo = weights::module_dex_limit_order::WeightInfo<Runtime>;
+}
+
 parameter_types! {
 	pub const MaxAuctionsCount: u32 = 50;
 	pub HonzonTreasuryAccount: AccountId = HonzonTreasuryPalletId::get().into_account();
@@ -1153,6 +1190,7 @@ impl module_cdp_treasury::Config for Runtime {
 	type PalletId = CDPTreasuryPalletId;
 	type TreasuryAccount = HonzonTreasuryAccount;
 	type AlternativeSwapPathJointList = AlternativeSwapPathJointList;
+	type EmergencyShutdown = EmergencyShutdown;
 	type WeightInfo = weights::module_cdp_treasury::WeightInfo<Runtime>;
 }
 
@@ -1489,6 +1527,7 @@ impl ecosystem_compound_cash::Config for Runtime {
 
 parameter_types! {
 	pub const ChainId: u64 = 595;
+	pub const EnableLondon: bool = true;
 	pub NetworkContractSource: H160 = H160::from_low_u64_be(0);
 }
 
@@ -1530,6 +1569,12 @@ impl<I: From<Balance>> frame_support::traits::Get<I> for TxFeePerGas {
 
 #[cfg(feature = "with-ethereum-compatibility")]
 static ISTANBUL_CONFIG: module_evm_utiltity::evm::Config = module_evm_utiltity::evm::Config::istanbul();
+#[cfg(feature = "with-ethereum-compatibility")]
+static LONDON_CONFIG: module_evm_utiltity::evm::Config = module_evm_utiltity::evm::Config {
+	// EIP-3529
+	refund_sstore_clears: 4800,
+	..module_evm_utiltity::evm::Config::berlin()
+};
 
 impl module_evm::Config for Runtime {
 	type AddressMapping = EvmAddressMapping<Runtime>;
@@ -1543,6 +1588,7 @@ impl module_evm::Config for Runtime {
 	type ChainId = ChainId;
 	type GasToWeight = GasToWeight;
 	type ChargeTransactionPayment = module_transaction_payment::ChargeTransactionPayment<Runtime>;
+	type EnableLondon = EnableLondon;
 	type NetworkContractOrigin = EnsureRootOrTwoThirdsTechnicalCommittee;
 	type NetworkContractSource = NetworkContractSource;
 	type DeveloperDeposit = DeveloperDeposit;
@@ -1557,7 +1603,11 @@ impl module_evm::Config for Runtime {
 
 	#[cfg(feature = "with-ethereum-compatibility")]
 	fn config() -> &'static module_evm_utiltity::evm::Config {
-		&ISTANBUL_CONFIG
+		if EnableLondon::get() {
+			&LONDON_CONFIG
+		} else {
+			&ISTANBUL_CONFIG
+		}
 	}
 }
 
@@ -2004,7 +2054,7 @@ impl Convert<(Call, SignedExtra), Result<(EthereumTransactionMessage, SignedExtr
 	fn convert(
 		(call, mut extra): (Call, SignedExtra),
 	) -> Result<(EthereumTransactionMessage, SignedExtra), InvalidTransaction> {
-		match call {
+		let (action, input, value, gas_limit, storage_limit, valid_until, access_list) = match call {
 			Call::EVM(module_evm::Call::eth_call {
 				action,
 				input,
@@ -2012,41 +2062,51 @@ impl Convert<(Call, SignedExtra), Result<(EthereumTransactionMessage, SignedExtr
 				gas_limit,
 				storage_limit,
 				valid_until,
-			}) => {
-				if System::block_number() > valid_until {
-					return Err(InvalidTransaction::Stale);
-				}
+			}) => (action, input, value, gas_limit, storage_limit, valid_until, vec![]),
+			Call::EVM(module_evm::Call::eth_call_v2 {
+				action,
+				input,
+				value,
+				gas_limit,
+				storage_limit,
+				valid_until,
+				access_list,
+			}) => (action, input, value, gas_limit, storage_limit, valid_until, access_list),
+			_ => return Err(InvalidTransaction::BadProof),
+		};
 
-				let (_, _, _, _, mortality, check_nonce, _, charge, ..) = extra.clone();
+		if System::block_number() > valid_until {
+			return Err(InvalidTransaction::Stale);
+		}
 
-				if mortality != frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal) {
-					// require immortal
-					return Err(InvalidTransaction::BadProof);
-				}
+		let (_, _, _, _, mortality, check_nonce, _, charge, ..) = extra.clone();
 
-				let nonce = check_nonce.nonce;
-				let tip = charge.0;
+		if mortality != frame_system::CheckEra::from(sp_runtime::generic::Era::Immortal) {
+			// require immortal
+			return Err(InvalidTransaction::BadProof);
+		}
 
-				extra.5.mark_as_ethereum_tx(valid_until);
+		let nonce = check_nonce.nonce;
+		let tip = charge.0;
 
-				Ok((
-					EthereumTransactionMessage {
-						chain_id: ChainId::get(),
-						genesis: System::block_hash(0),
-						nonce,
-						tip,
-						gas_limit,
-						storage_limit,
-						action,
-						value,
-						input,
-						valid_until,
-					},
-					extra,
-				))
-			}
-			_ => Err(InvalidTransaction::BadProof),
-		}
+		extra.5.mark_as_ethereum_tx(valid_until);
+
+		Ok((
+			EthereumTransactionMessage {
+				chain_id: ChainId::get(),
+				genesis: System::block_hash(0),
+				nonce,
+				tip,
+				gas_limit,
+				storage_limit,
+				action,
+				value,
+				input,
+				valid_until,
+				access_list,
+			},
+			extra,
+		))
 	}
 }
 
@@ -2144,6 +2204,7 @@ construct_runtime! {
 		Prices: module_prices::{Pallet, Storage, Call, Event<T>} = 110,
 		Dex: module_dex::{Pallet, Storage, Call, Event<T>, Config<T>} = 111,
 		DexOracle: module_dex_oracle::{Pallet, Storage, Call} = 112,
+		DexLimitOrder: module_dex_limit_order::{Pallet, Storage, Call, Event<T>} = 113,
 
 		// Honzon
 		AuctionManager: module_auction_manager::{Pallet, Storage, Call, Event<T>, ValidateUnsigned} = 120,
@@ -2213,6 +2274,7 @@ mod benches {
 	define_benchmarks!(
 		[module_dex, benchmarking::dex]
 		[module_dex_oracle, benchmarking::dex_oracle]
+		[module_dex_limit_order, benchmarking::dex_limit_order]
 		[module_asset_registry, benchmarking::asset_registry]
 		[module_auction_manager, benchmarking::auction_manager]
 		[module_cdp_engine, benchmarking::cdp_engine]
@@ -2373,6 +2435,79 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_dex_oracle_rpc_runtime_api::DexOracleRuntimeApi<Block, Moment> for Runtime {
+		fn get_current_price(base: CurrencyId, quote: CurrencyId) -> Option<ExchangeRate> {
+			DexOracle::get_relative_current_price(base, quote)
+		}
+
+		fn get_twap(base: CurrencyId, quote: CurrencyId, window: Moment) -> Option<ExchangeRate> {
+			DexOracle::get_twap(base, quote, window)
+		}
+	}
+
+	impl module_cdp_engine_rpc_runtime_api::CdpEngineRuntimeApi<Block, AccountId> for Runtime {
+		fn get_position_risk(who: AccountId, currency_id: CurrencyId) -> Option<module_cdp_engine::RiskInfo> {
+			CdpEngine::get_position_risk(&who, currency_id)
+		}
+
+		fn get_collateral_risk(currency_id: CurrencyId) -> Option<module_cdp_engine::RiskInfo> {
+			CdpEngine::get_collateral_risk(currency_id)
+		}
+	}
+
+	impl module_dex_rpc_runtime_api::DexRuntimeApi<Block, Balance, BlockNumber> for Runtime {
+		fn get_liquidity_pool(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_liquidity_pool(currency_id_a, currency_id_b)
+		}
+
+		fn get_swap_amount(path: Vec<CurrencyId>, limit: SwapLimit<Balance>) -> Option<(Balance, Balance)> {
+			Dex::get_swap_amount(&path, limit)
+		}
+
+		fn get_trading_pair_status(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+		) -> Option<module_dex::TradingPairStatus<Balance, BlockNumber>> {
+			Dex::get_trading_pair_status(currency_id_a, currency_id_b)
+		}
+
+		fn get_liquidity_share_value(
+			currency_id_a: CurrencyId,
+			currency_id_b: CurrencyId,
+			share_amount: Balance,
+		) -> Option<(Balance, Balance)> {
+			Dex::get_liquidity_share_value(currency_id_a, currency_id_b, share_amount)
+		}
+
+		fn get_collected_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_collected_protocol_fees(currency_id_a, currency_id_b)
+		}
+
+		fn get_pending_protocol_fees(currency_id_a: CurrencyId, currency_id_b: CurrencyId) -> (Balance, Balance) {
+			Dex::get_pending_protocol_fees(currency_id_a, currency_id_b)
+		}
+
+		fn get_best_swap_path_with_exact_supply(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			supply_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, Balance)> {
+			Dex::get_best_route(supply_currency_id, target_currency_id, SwapLimit::ExactSupply(supply_amount, Balance::MIN))
+				.0
+				.map(|(path, _, target_amount)| (path, target_amount))
+		}
+
+		fn get_best_swap_path_with_exact_target(
+			supply_currency_id: CurrencyId,
+			target_currency_id: CurrencyId,
+			target_amount: Balance,
+		) -> Option<(Vec<CurrencyId>, Balance)> {
+			Dex::get_best_route(supply_currency_id, target_currency_id, SwapLimit::ExactTarget(Balance::MAX, target_amount))
+				.0
+				.map(|(path, supply_amount, _)| (path, supply_amount))
+		}
+	}
+
 	impl module_evm_rpc_runtime_api::EVMRuntimeRPCApi<Block, Balance> for Runtime {
 		fn call(
 			from: H160,
@@ -2381,6 +2516,7 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CallInfo, sp_runtime::DispatchError> {
 			let config = if estimate {
@@ -2399,6 +2535,7 @@ impl_runtime_apis! {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list.unwrap_or_default(),
 				config.as_ref().unwrap_or(<Runtime as module_evm::Config>::config()),
 			)
 		}
@@ -2409,6 +2546,7 @@ impl_runtime_apis! {
 			value: Balance,
 			gas_limit: u64,
 			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
 			estimate: bool,
 		) -> Result<CreateInfo, sp_runtime::DispatchError> {
 			let config = if estimate {
@@ -2425,6 +2563,7 @@ impl_runtime_apis! {
 				value,
 				gas_limit,
 				storage_limit,
+				access_list.unwrap_or_default(),
 				config.as_ref().unwrap_or(<Runtime as module_evm::Config>::config()),
 			)
 		}
@@ -2467,6 +2606,170 @@ impl_runtime_apis! {
 		}
 	}
 
+	impl module_evm_rpc_runtime_api::EVMTraceApi<Block, Balance> for Runtime {
+		fn trace_call(
+			from: H160,
+			to: Option<H160>,
+			data: Vec<u8>,
+			value: Balance,
+			gas_limit: u64,
+			storage_limit: u32,
+			access_list: Option<Vec<AccessListItem>>,
+			tracer_config: module_evm::runner::TracerConfig,
+		) -> Result<module_evm::runner::TraceOutcome, sp_runtime::DispatchError> {
+			let config = <Runtime as module_evm::Config>::config();
+			let (result, trace) = module_evm::runner::trace(tracer_config, || match to {
+				Some(to) => module_evm::runner::stack::Runner::<Runtime>::call(
+					from,
+					from,
+					to,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default(),
+					config,
+				)
+				.map(|_| ()),
+				None => module_evm::runner::stack::Runner::<Runtime>::create(
+					from,
+					data,
+					value,
+					gas_limit,
+					storage_limit,
+					access_list.unwrap_or_default(),
+					config,
+				)
+				.map(|_| ()),
+			})?;
+			result?;
+
+			Ok(trace)
+		}
+
+		fn trace_extrinsic(
+			extrinsics: Vec<<Block as BlockT>::Extrinsic>,
+			traced: <Block as BlockT>::Extrinsic,
+			tracer_config: module_evm::runner::TracerConfig,
+		) -> Result<module_evm::runner::TraceOutcome, sp_runtime::DispatchError> {
+			for extrinsic in extrinsics {
+				let _ = Executive::apply_extrinsic(extrinsic);
+			}
+
+			let (result, trace) = module_evm::runner::trace(tracer_config, || Executive::apply_extrinsic(traced))?;
+			result.map_err(|_| sp_runtime::DispatchError::Other("Invalid parameter traced, apply failed"))?;
+
+			Ok(trace)
+		}
+	}
+
+	impl module_evm_rpc_runtime_api::EthRuntimeRPCApi<Block> for Runtime {
+		fn chain_id() -> u64 {
+			ChainId::get()
+		}
+
+		fn balance(address: H160) -> sp_core::U256 {
+			EVM::account_basic(&address).balance
+		}
+
+		fn nonce(address: H160) -> sp_core::U256 {
+			EVM::account_basic(&address).nonce
+		}
+
+		fn code(address: H160) -> Vec<u8> {
+			EVM::code_at_address(&address).into_inner()
+		}
+
+		fn storage_at(address: H160, index: sp_core::H256) -> sp_core::H256 {
+			EVM::account_storages(address, index)
+		}
+
+		fn timestamp() -> u64 {
+			Timestamp::now()
+		}
+
+		fn transaction(extrinsic: <Block as BlockT>::Extrinsic) -> Option<primitives::evm::EthereumTransaction> {
+			use module_support::AddressMapping;
+
+			let (address, _, extra) = extrinsic.0.signature?;
+			let who = <<Runtime as frame_system::Config>::Lookup as StaticLookup>::lookup(address).ok()?;
+			let from = EvmAddressMapping::<Runtime>::get_evm_address(&who)
+				.unwrap_or_else(|| EvmAddressMapping::<Runtime>::get_default_evm_address(&who));
+
+			let (action, input, value, gas_limit, storage_limit) = match extrinsic.0.function {
+				Call::EVM(module_evm::Call::eth_call { action, input, value, gas_limit, storage_limit, .. }) => {
+					(action, input, value, gas_limit, storage_limit)
+				}
+				Call::EVM(module_evm::Call::eth_call_v2 { action, input, value, gas_limit, storage_limit, .. }) => {
+					(action, input, value, gas_limit, storage_limit)
+				}
+				Call::EVM(module_evm::Call::call { target, input, value, gas_limit, storage_limit }) => {
+					(module_evm::TransactionAction::Call(target), input, value, gas_limit, storage_limit)
+				}
+				Call::EVM(module_evm::Call::create { init, value, gas_limit, storage_limit }) => {
+					(module_evm::TransactionAction::Create, init, value, gas_limit, storage_limit)
+				}
+				_ => return None,
+			};
+
+			Some(primitives::evm::EthereumTransaction {
+				from,
+				nonce: extra.5.nonce,
+				action,
+				value: module_evm::convert_decimals_to_evm(value).into(),
+				gas_limit,
+				storage_limit,
+				input,
+			})
+		}
+
+		fn receipts() -> Vec<primitives::evm::EthereumReceipt> {
+			EVM::receipts(System::events().into_iter().filter_map(|record| match (record.phase, record.event) {
+				(frame_system::Phase::ApplyExtrinsic(index), Event::EVM(event)) => Some((index, event)),
+				_ => None,
+			}))
+		}
+
+		fn convert_transaction(transaction: Vec<u8>) -> Result<<Block as BlockT>::Extrinsic, sp_runtime::DispatchError> {
+			let (signer, signature, msg) = primitives::unchecked_extrinsic::decode_ethereum_transaction(
+				&transaction,
+				TxFeePerGas::get(),
+				StorageDepositPerByte::get(),
+			)
+			.ok_or(sp_runtime::DispatchError::Other("Invalid parameter transaction, decode failed"))?;
+
+			if msg.chain_id != ChainId::get() {
+				return Err(sp_runtime::DispatchError::Other("Invalid parameter transaction, wrong chain id"));
+			}
+
+			let call = Call::EVM(module_evm::Call::eth_call {
+				action: msg.action,
+				input: msg.input,
+				value: msg.value,
+				gas_limit: msg.gas_limit,
+				storage_limit: msg.storage_limit,
+				valid_until: msg.valid_until,
+			});
+			let extra: SignedExtra = (
+				frame_system::CheckNonZeroSender::<Runtime>::new(),
+				frame_system::CheckSpecVersion::<Runtime>::new(),
+				frame_system::CheckTxVersion::<Runtime>::new(),
+				frame_system::CheckGenesis::<Runtime>::new(),
+				frame_system::CheckEra::<Runtime>::from(generic::Era::Immortal),
+				runtime_common::CheckNonce::<Runtime>::from(msg.nonce),
+				frame_system::CheckWeight::<Runtime>::new(),
+				module_transaction_payment::ChargeTransactionPayment::<Runtime>::from(msg.tip),
+				module_evm::SetEvmOrigin::<Runtime>::new(),
+			);
+
+			<UncheckedExtrinsic as sp_runtime::traits::Extrinsic>::new(
+				call,
+				Some((Address::Address20(signer.into()), signature, extra)),
+			)
+			.ok_or(sp_runtime::DispatchError::Other("Invalid parameter transaction, build extrinsic failed"))
+		}
+	}
+
 	impl cumulus_primitives_core::CollectCollationInfo<Block> for Runtime {
 		fn collect_collation_info(header: &<Block as BlockT>::Header) -> cumulus_primitives_core::CollationInfo {
 			ParachainSystem::collect_collation_info(header)
@@ -2682,7 +2985,44 @@ mod tests {
 						input: vec![0x01],
 						chain_id: 595,
 						genesis: sp_core::H256::default(),
-						valid_until: 30
+						valid_until: 30,
+						access_list: vec![],
+					},
+					expected_extra.clone()
+				)
+			);
+
+			let access_list = vec![AccessListItem {
+				address: H160::from_low_u64_be(1),
+				slots: vec![],
+			}];
+			assert_eq!(
+				ConvertEthereumTx::convert((
+					Call::EVM(module_evm::Call::eth_call_v2 {
+						action: module_evm::TransactionAction::Create,
+						input: vec![0x01],
+						value: 0,
+						gas_limit: 21_000,
+						storage_limit: 1_000,
+						valid_until: 30,
+						access_list: access_list.clone(),
+					}),
+					extra.clone()
+				))
+				.unwrap(),
+				(
+					EthereumTransactionMessage {
+						nonce: 3, // evm::account.nonce
+						tip: 0,
+						gas_limit: 21_000,
+						storage_limit: 1_000,
+						action: module_evm::TransactionAction::Create,
+						value: 0,
+						input: vec![0x01],
+						chain_id: 595,
+						genesis: sp_core::H256::default(),
+						valid_until: 30,
+						access_list,
 					},
 					expected_extra.clone()
 				)
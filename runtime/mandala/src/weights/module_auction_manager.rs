@@ -52,4 +52,22 @@ impl<T: frame_system::Config> module_auction_manager::WeightInfo for WeightInfo<
 			.saturating_add(T::DbWeight::get().reads(10 as Weight))
 			.saturating_add(T::DbWeight::get().writes(7 as Weight))
 	}
+	fn set_dutch_auction_params() -> Weight {
+		(15_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
+	fn take_dutch_auction() -> Weight {
+		(95_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(9 as Weight))
+			.saturating_add(T::DbWeight::get().writes(8 as Weight))
+	}
+	// Storage: EmergencyShutdown IsShutdown (r:1 w:0)
+	// Storage: AuctionManager DutchCollateralAuctions (r:1 w:1)
+	// Storage: AuctionManager CollateralDutchAuctionParams (r:1 w:0)
+	// Storage: Prices LockedPrice (r:2 w:0)
+	fn reset_dutch_auction() -> Weight {
+		(30_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(5 as Weight))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }
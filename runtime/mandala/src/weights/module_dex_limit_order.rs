@@ -0,0 +1,81 @@
+// This file is part of Acala.
+
+// Copyright (C) 2020-2022 Acala Foundation.
+// SPDX-License-Identifier: GPL-3.0-or-later WITH Classpath-exception-2.0
+
+// This program is free software: you can redistribute it and/or modify
+// it under the terms of the GNU General Public License as published by
+// the Free Software Foundation, either version 3 of the License, or
+// (at your option) any later version.
+
+// This program is distributed in the hope that it will be useful,
+// but WITHOUT ANY WARRANTY; without even the implied warranty of
+// MERCHANTABILITY or FITNESS FOR A PARTICULAR PURPOSE. See the
+// GNU General Public License for more details.
+
+// You should have received a copy of the GNU General Public License
+// along with this program. If not, see <https://www.gnu.org/licenses/>.
+
+//! Autogenerated weights for module_dex_limit_order
+//!
+//! THIS FILE WAS AUTO-GENERATED USING THE SUBSTRATE BENCHMARK CLI VERSION 4.0.0-dev
+//! DATE: 2022-01-21, STEPS: `50`, REPEAT: 20, LOW RANGE: `[]`, HIGH RANGE: `[]`
+//! EXECUTION: Some(Wasm), WASM-EXECUTION: Compiled, CHAIN: Some("dev"), DB CACHE: 128
+
+// Executed Command:
+// target/release/acala
+// benchmark
+// --chain=dev
+// --steps=50
+// --repeat=20
+// --pallet=module_dex_limit_order
+// --extrinsic=*
+// --execution=wasm
+// --wasm-execution=compiled
+// --heap-pages=4096
+// --template=./templates/runtime-weight-template.hbs
+// --output=./runtime/mandala/src/weights/
+
+#![cfg_attr(rustfmt, rustfmt_skip)]
+#![allow(unused_parens)]
+#![allow(unused_imports)]
+
+use frame_support::{traits::Get, weights::Weight};
+use sp_std::marker::PhantomData;
+
+/// Weight functions for module_dex_limit_order.
+pub struct WeightInfo<T>(PhantomData<T>);
+impl<T: frame_system::Config> module_dex_limit_order::WeightInfo for WeightInfo<T> {
+	// Storage: DexLimitOrder OrdersCount (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	// Storage: DexLimitOrder NextOrderId (r:1 w:1)
+	// Storage: DexLimitOrder Orders (r:0 w:1)
+	fn place_order() -> Weight {
+		(52_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(5 as Weight))
+	}
+	// Storage: DexLimitOrder Orders (r:1 w:1)
+	// Storage: Tokens Accounts (r:2 w:2)
+	// Storage: System Account (r:1 w:0)
+	// Storage: DexLimitOrder OrdersCount (r:1 w:1)
+	fn cancel_order() -> Weight {
+		(45_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(4 as Weight))
+			.saturating_add(T::DbWeight::get().writes(4 as Weight))
+	}
+	// Storage: DexLimitOrder Orders (r:1 w:1)
+	// Storage: Dex TradingPairStatuses (r:1 w:0)
+	// Storage: Dex LiquidityPool (r:1 w:1)
+	// Storage: Dex ExchangeFees (r:1 w:0)
+	// Storage: Dex ProtocolFeeShare (r:1 w:0)
+	// Storage: Tokens Accounts (r:4 w:4)
+	// Storage: System Account (r:1 w:0)
+	// Storage: DexLimitOrder OrdersCount (r:1 w:1)
+	fn execute_order() -> Weight {
+		(156_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(11 as Weight))
+			.saturating_add(T::DbWeight::get().writes(9 as Weight))
+	}
+}
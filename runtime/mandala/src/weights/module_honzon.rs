@@ -54,14 +54,14 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 	fn unauthorize() -> Weight {
 		(54_414_000 as Weight)
 			.saturating_add(T::DbWeight::get().reads(2 as Weight))
-			.saturating_add(T::DbWeight::get().writes(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
 	}
 	fn unauthorize_all(c: u32, ) -> Weight {
 		(31_904_000 as Weight)
 			// Standard Error: 1_582_000
 			.saturating_add((16_667_000 as Weight).saturating_mul(c as Weight))
 			.saturating_add(T::DbWeight::get().reads(1 as Weight))
-			.saturating_add(T::DbWeight::get().writes((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes((3 as Weight).saturating_mul(c as Weight)))
 	}
 	fn adjust_loan() -> Weight {
 		(220_676_000 as Weight)
@@ -78,4 +78,36 @@ impl<T: frame_system::Config> module_honzon::WeightInfo for WeightInfo<T> {
 			.saturating_add(T::DbWeight::get().reads(30 as Weight))
 			.saturating_add(T::DbWeight::get().writes(15 as Weight))
 	}
+	fn expand_position_collateral() -> Weight {
+		(197_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn shrink_position_debit() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(19 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn authorize_with_scope() -> Weight {
+		(55_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(2 as Weight))
+			.saturating_add(T::DbWeight::get().writes(3 as Weight))
+	}
+	fn adjust_loan_by_authorizee() -> Weight {
+		(205_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(27 as Weight))
+			.saturating_add(T::DbWeight::get().writes(11 as Weight))
+	}
+	fn close_loan_has_debit_by_dex_by_authorizee() -> Weight {
+		(375_000_000 as Weight)
+			.saturating_add(T::DbWeight::get().reads(34 as Weight))
+			.saturating_add(T::DbWeight::get().writes(14 as Weight))
+	}
+	fn set_cross_collateral(c: u32, ) -> Weight {
+		(21_000_000 as Weight)
+			.saturating_add((9_000_000 as Weight).saturating_mul(c as Weight))
+			.saturating_add(T::DbWeight::get().reads(1 as Weight))
+			.saturating_add(T::DbWeight::get().reads((2 as Weight).saturating_mul(c as Weight)))
+			.saturating_add(T::DbWeight::get().writes(1 as Weight))
+	}
 }